@@ -32,10 +32,47 @@ pub enum Error {
     #[error("Invalid seed: got {0} bytes, expected {1} bytes.")]
     InvalidSeedSize(usize, usize),
 
-    /// Indicates a default error
-    /// TODO: To delete when transition is over
-    #[error("{0}")]
-    Default(String),
+    /// Indicates that an input which must be non-empty is empty.
+    #[error("Empty input.")]
+    EmptyInput,
+
+    /// Indicates that the moduli are not coprime.
+    #[error("The moduli are not coprime.")]
+    NonCoprimeModuli,
+
+    /// Indicates an invalid polynomial degree.
+    #[error("Invalid degree: {0}.")]
+    InvalidDegree(usize),
+
+    /// Indicates that a modulus does not support the NTT of the requested
+    /// size.
+    #[error("Invalid NTT modulus: {0}.")]
+    InvalidNttModulus(u64),
+
+    /// Indicates that there is no context at the requested level.
+    #[error("Invalid level: {0}.")]
+    InvalidLevel(usize),
+
+    /// Indicates that a substitution exponent is even modulo 2 * degree.
+    #[error("Invalid substitution exponent: {0}.")]
+    InvalidSubstitutionExponent(usize),
+
+    /// Indicates that a variance is not between 1 and 16.
+    #[error("Invalid variance: {0} should be between 1 and 16.")]
+    InvalidVariance(usize),
+
+    /// Indicates that the representation must be specified.
+    #[error("The representation must be specified.")]
+    MissingRepresentation,
+
+    /// Indicates an incorrect number of coefficients.
+    #[error("Invalid number of coefficients: got {found}, expected {expected}.")]
+    InvalidCoefficientCount {
+        /// The expected number of coefficients.
+        expected: usize,
+        /// The number of coefficients provided.
+        found: usize,
+    },
 }
 
 #[cfg(test)]
@@ -66,5 +103,36 @@ mod tests {
             Error::InvalidSeedSize(0, 1).to_string(),
             "Invalid seed: got 0 bytes, expected 1 bytes."
         );
+        assert_eq!(Error::EmptyInput.to_string(), "Empty input.");
+        assert_eq!(
+            Error::NonCoprimeModuli.to_string(),
+            "The moduli are not coprime."
+        );
+        assert_eq!(Error::InvalidDegree(7).to_string(), "Invalid degree: 7.");
+        assert_eq!(
+            Error::InvalidNttModulus(3).to_string(),
+            "Invalid NTT modulus: 3."
+        );
+        assert_eq!(Error::InvalidLevel(2).to_string(), "Invalid level: 2.");
+        assert_eq!(
+            Error::InvalidSubstitutionExponent(2).to_string(),
+            "Invalid substitution exponent: 2."
+        );
+        assert_eq!(
+            Error::InvalidVariance(0).to_string(),
+            "Invalid variance: 0 should be between 1 and 16."
+        );
+        assert_eq!(
+            Error::MissingRepresentation.to_string(),
+            "The representation must be specified."
+        );
+        assert_eq!(
+            Error::InvalidCoefficientCount {
+                expected: 8,
+                found: 9
+            }
+            .to_string(),
+            "Invalid number of coefficients: got 9, expected 8."
+        );
     }
 }
@@ -4,7 +4,11 @@
 
 //! Mathematical utilities for the fhe.rs library.
 
+#[macro_use]
+mod parallel;
+
 mod errors;
+#[allow(renamed_and_removed_lints)]
 mod proto;
 
 pub mod rns;
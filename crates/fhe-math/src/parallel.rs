@@ -0,0 +1,33 @@
+//! Parallel iteration over the RNS moduli and the coefficients, when the
+//! `rayon` feature is enabled.
+
+/// Zips several iterators like [`itertools::izip`]. With the `rayon` feature,
+/// the arguments are converted into indexed parallel iterators, so that the
+/// iteration runs in parallel; the arguments must then be slices or axis
+/// iterators of `ndarray` arrays, and the consuming closures must be `Sync`.
+#[cfg(not(feature = "rayon"))]
+macro_rules! par_izip {
+    ($($args:tt)*) => {
+        itertools::izip!($($args)*)
+    };
+}
+
+#[cfg(feature = "rayon")]
+macro_rules! par_izip {
+    (@closure $p:pat => $tup:expr) => {
+        |$p| $tup
+    };
+    (@closure $p:pat => ($($tup:tt)*), $_iter:expr $(, $tail:expr)*) => {
+        par_izip!(@closure ($p, b) => ($($tup)*, b) $(, $tail)*)
+    };
+    ($first:expr $(,)?) => {
+        rayon::iter::IntoParallelIterator::into_par_iter($first)
+    };
+    ($first:expr, $second:expr $(,)?) => {
+        rayon::iter::IndexedParallelIterator::zip(par_izip!($first), $second)
+    };
+    ($first:expr $(, $rest:expr)* $(,)?) => {{
+        use rayon::iter::{IndexedParallelIterator as _, ParallelIterator as _};
+        par_izip!($first) $(.zip($rest))* .map(par_izip!(@closure a => (a) $(, $rest)*))
+    }};
+}
@@ -46,7 +46,7 @@ impl RnsContext {
     /// Returns an error if the list is empty, or if the moduli are no coprime.
     pub fn new(moduli_u64: &[u64]) -> Result<Self> {
         if moduli_u64.is_empty() {
-            Err(Error::Default("The list of moduli is empty".to_string()))
+            Err(Error::EmptyInput)
         } else {
             let mut product = BigUint::one();
             let mut product_dig = BigUintDig::one();
@@ -58,7 +58,7 @@ impl RnsContext {
                         let (d, _, _) = BigUintDig::from(moduli_u64[i])
                             .extended_gcd(&BigUintDig::from(moduli_u64[j]));
                         if d.cmp(&BigIntDig::from(1)) != Ordering::Equal {
-                            return Err(Error::Default("The moduli are not coprime".to_string()));
+                            return Err(Error::NonCoprimeModuli);
                         }
                     }
                 }
@@ -156,13 +156,13 @@ mod tests {
 
         let e = RnsContext::new(&[]);
         assert!(e.is_err());
-        assert_eq!(e.unwrap_err().to_string(), "The list of moduli is empty");
+        assert_eq!(e.unwrap_err(), crate::Error::EmptyInput);
         let e = RnsContext::new(&[2, 4]);
         assert!(e.is_err());
-        assert_eq!(e.unwrap_err().to_string(), "The moduli are not coprime");
+        assert_eq!(e.unwrap_err(), crate::Error::NonCoprimeModuli);
         let e = RnsContext::new(&[2, 3, 5, 30]);
         assert!(e.is_err());
-        assert_eq!(e.unwrap_err().to_string(), "The moduli are not coprime");
+        assert_eq!(e.unwrap_err(), crate::Error::NonCoprimeModuli);
     }
 
     #[test]
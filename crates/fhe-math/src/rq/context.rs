@@ -45,9 +45,7 @@ impl Context {
     /// supports the NTT of size `degree`.
     pub fn new(moduli: &[u64], degree: usize) -> Result<Self> {
         if !degree.is_power_of_two() || degree < 8 {
-            Err(Error::Default(
-                "The degree is not a power of two larger or equal to 8".to_string(),
-            ))
+            Err(Error::InvalidDegree(degree))
         } else {
             let mut q = Vec::with_capacity(moduli.len());
             let rns = Arc::new(RnsContext::new(moduli)?);
@@ -58,9 +56,7 @@ impl Context {
                     q.push(qi);
                     ops.push(op);
                 } else {
-                    return Err(Error::Default(
-                        "Impossible to construct a Ntt operator".to_string(),
-                    ));
+                    return Err(Error::InvalidNttModulus(*modulus));
                 }
             }
             let bitrev = (0..degree)
@@ -144,9 +140,7 @@ impl Context {
     /// Returns the context after `i` iterations.
     pub fn context_at_level(&self, i: usize) -> Result<Arc<Self>> {
         if i >= self.moduli.len() {
-            Err(Error::Default(
-                "No context at the specified level".to_string(),
-            ))
+            Err(Error::InvalidLevel(i))
         } else {
             let mut current_ctx = Arc::new(self.clone());
             for _ in 0..i {
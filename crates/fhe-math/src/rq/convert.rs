@@ -55,6 +55,7 @@ impl TryConvertFrom<Vec<u64>> for Poly {
         R: Into<Option<Representation>>,
     {
         let repr = representation.into();
+        let len = v.len();
         match repr {
             Some(Representation::Ntt) => {
                 if let Ok(coefficients) = Array2::from_shape_vec((ctx.q.len(), ctx.degree), v) {
@@ -67,9 +68,10 @@ impl TryConvertFrom<Vec<u64>> for Poly {
                         has_lazy_coefficients: false,
                     })
                 } else {
-                    Err(Error::Default(
-                        "In Ntt representation, all coefficients must be specified".to_string(),
-                    ))
+                    Err(Error::InvalidCoefficientCount {
+                        expected: ctx.q.len() * ctx.degree,
+                        found: len,
+                    })
                 }
             }
             Some(Representation::NttShoup) => {
@@ -85,10 +87,10 @@ impl TryConvertFrom<Vec<u64>> for Poly {
                     p.compute_coefficients_shoup();
                     Ok(p)
                 } else {
-                    Err(Error::Default(
-                        "In NttShoup representation, all coefficients must be specified"
-                            .to_string(),
-                    ))
+                    Err(Error::InvalidCoefficientCount {
+                        expected: ctx.q.len() * ctx.degree,
+                        found: len,
+                    })
                 }
             }
             Some(Representation::PowerBasis) => {
@@ -128,13 +130,13 @@ impl TryConvertFrom<Vec<u64>> for Poly {
                     }
                     Ok(out)
                 } else {
-                    Err(Error::Default("In PowerBasis representation, either all coefficients must be specified, or only coefficients up to the degree".to_string()))
+                    Err(Error::InvalidCoefficientCount {
+                        expected: ctx.q.len() * ctx.degree,
+                        found: v.len(),
+                    })
                 }
             }
-            None => Err(Error::Default(
-                "When converting from a vector, the representation needs to be specified"
-                    .to_string(),
-            )),
+            None => Err(Error::MissingRepresentation),
         }
     }
 }
@@ -154,20 +156,20 @@ impl TryConvertFrom<&Rq> for Poly {
             rq::Representation::POWERBASIS => Representation::PowerBasis,
             rq::Representation::NTT => Representation::Ntt,
             rq::Representation::NTTSHOUP => Representation::NttShoup,
-            _ => return Err(Error::Default("Unknown representation".to_string())),
+            _ => return Err(Error::Serialization("Unknown representation".to_string())),
         };
 
         let variable_time = variable_time || value.allow_variable_time;
 
         if let Some(r) = representation.into() as Option<Representation> {
             if r != representation_from_proto {
-                return Err(Error::Default("The representation asked for does not match the representation in the serialization".to_string()));
+                return Err(Error::IncorrectRepresentation(r, representation_from_proto));
             }
         }
 
         let degree = value.degree as usize;
-        if degree % 8 != 0 || degree < 8 {
-            return Err(Error::Default("Invalid degree".to_string()));
+        if !degree.is_multiple_of(8) || degree < 8 {
+            return Err(Error::InvalidDegree(degree));
         }
 
         let mut expected_nbytes = 0;
@@ -175,7 +177,7 @@ impl TryConvertFrom<&Rq> for Poly {
             .iter()
             .for_each(|qi| expected_nbytes += qi.serialization_length(degree));
         if value.coefficients.len() != expected_nbytes {
-            return Err(Error::Default("Invalid coefficients".to_string()));
+            return Err(Error::Serialization("Invalid coefficients".to_string()));
         }
 
         let mut coefficients = Vec::with_capacity(ctx.q.len() * ctx.degree);
@@ -203,9 +205,10 @@ impl TryConvertFrom<Array2<u64>> for Poly {
         R: Into<Option<Representation>>,
     {
         if a.shape() != [ctx.q.len(), ctx.degree] {
-            Err(Error::Default(
-                "The array of coefficient does not have the correct shape".to_string(),
-            ))
+            Err(Error::InvalidCoefficientCount {
+                expected: ctx.q.len() * ctx.degree,
+                found: a.len(),
+            })
         } else if let Some(repr) = representation.into() {
             let mut p = Self {
                 ctx: ctx.clone(),
@@ -220,7 +223,7 @@ impl TryConvertFrom<Array2<u64>> for Poly {
             }
             Ok(p)
         } else {
-            Err(Error::Default("When converting from a 2-dimensional array, the representation needs to be specified".to_string()))
+            Err(Error::MissingRepresentation)
         }
     }
 }
@@ -249,12 +252,17 @@ impl<'a> TryConvertFrom<&'a [i64]> for Poly {
     where
         R: Into<Option<Representation>>,
     {
-        if representation.into() != Some(Representation::PowerBasis) {
-            Err(Error::Default(
-                "Converting signed integer require to import in PowerBasis representation"
-                    .to_string(),
-            ))
-        } else if v.len() <= ctx.degree {
+        match representation.into() {
+            None => return Err(Error::MissingRepresentation),
+            Some(Representation::PowerBasis) => {}
+            Some(repr) => {
+                return Err(Error::IncorrectRepresentation(
+                    repr,
+                    Representation::PowerBasis,
+                ))
+            }
+        }
+        if v.len() <= ctx.degree {
             let mut out = Self::zero(ctx, Representation::PowerBasis);
             if variable_time {
                 unsafe { out.allow_variable_time_computations() }
@@ -269,7 +277,10 @@ impl<'a> TryConvertFrom<&'a [i64]> for Poly {
             });
             Ok(out)
         } else {
-            Err(Error::Default("In PowerBasis representation with signed integers, only `degree` coefficients can be specified".to_string()))
+            Err(Error::InvalidCoefficientCount {
+                expected: ctx.degree,
+                found: v.len(),
+            })
         }
     }
 }
@@ -301,10 +312,10 @@ impl<'a> TryConvertFrom<&'a [BigUint]> for Poly {
         let repr = representation.into();
 
         if v.len() > ctx.degree {
-            Err(Error::Default(
-                "The slice contains too many big integers compared to the polynomial degree"
-                    .to_string(),
-            ))
+            Err(Error::InvalidCoefficientCount {
+                expected: ctx.degree,
+                found: v.len(),
+            })
         } else if repr.is_some() {
             let mut coefficients = Array2::zeros((ctx.q.len(), ctx.degree));
 
@@ -330,10 +341,7 @@ impl<'a> TryConvertFrom<&'a [BigUint]> for Poly {
                 }
             }
         } else {
-            Err(Error::Default(
-                "When converting from a vector, the representation needs to be specified"
-                    .to_string(),
-            ))
+            Err(Error::MissingRepresentation)
         }
     }
 }
@@ -434,15 +442,21 @@ mod tests {
                 p
             );
             assert_eq!(
-				Poly::try_convert_from(&proto, &ctx, false, Representation::Ntt)
-					.expect_err("Should fail because of mismatched representations"),
-					CrateError::Default("The representation asked for does not match the representation in the serialization".to_string())
-			);
+                Poly::try_convert_from(&proto, &ctx, false, Representation::Ntt)
+                    .expect_err("Should fail because of mismatched representations"),
+                CrateError::IncorrectRepresentation(
+                    Representation::Ntt,
+                    Representation::PowerBasis
+                )
+            );
             assert_eq!(
-				Poly::try_convert_from(&proto, &ctx, false, Representation::NttShoup)
-					.expect_err("Should fail because of mismatched representations"),
-					CrateError::Default("The representation asked for does not match the representation in the serialization".to_string())
-			);
+                Poly::try_convert_from(&proto, &ctx, false, Representation::NttShoup)
+                    .expect_err("Should fail because of mismatched representations"),
+                CrateError::IncorrectRepresentation(
+                    Representation::NttShoup,
+                    Representation::PowerBasis
+                )
+            );
         }
 
         let ctx = Arc::new(Context::new(MODULI, 8)?);
@@ -454,21 +468,24 @@ mod tests {
             p
         );
         assert_eq!(
-			Poly::try_convert_from(&proto, &ctx, false, Representation::Ntt)
-				.expect_err("Should fail because of mismatched representations"),
-				CrateError::Default("The representation asked for does not match the representation in the serialization".to_string())
-		);
+            Poly::try_convert_from(&proto, &ctx, false, Representation::Ntt)
+                .expect_err("Should fail because of mismatched representations"),
+            CrateError::IncorrectRepresentation(Representation::Ntt, Representation::PowerBasis)
+        );
         assert_eq!(
-			Poly::try_convert_from(&proto, &ctx, false, Representation::NttShoup)
-				.expect_err("Should fail because of mismatched representations"),
-				CrateError::Default("The representation asked for does not match the representation in the serialization".to_string())
-		);
+            Poly::try_convert_from(&proto, &ctx, false, Representation::NttShoup)
+                .expect_err("Should fail because of mismatched representations"),
+            CrateError::IncorrectRepresentation(
+                Representation::NttShoup,
+                Representation::PowerBasis
+            )
+        );
 
         let ctx = Arc::new(Context::new(&MODULI[0..1], 8)?);
         assert_eq!(
             Poly::try_convert_from(&proto, &ctx, false, None)
                 .expect_err("Should fail because of incorrect context"),
-            CrateError::Default("Invalid coefficients".to_string())
+            CrateError::Serialization("Invalid coefficients".to_string())
         );
 
         Ok(())
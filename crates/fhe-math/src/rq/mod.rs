@@ -22,6 +22,8 @@ use itertools::{izip, Itertools};
 use ndarray::{s, Array2, ArrayView2, Axis};
 use rand::{CryptoRng, RngCore, SeedableRng};
 use rand_chacha::ChaCha8Rng;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 use std::sync::Arc;
 use zeroize::{Zeroize, Zeroizing};
 
@@ -55,9 +57,7 @@ impl SubstitutionExponent {
     pub fn new(ctx: &Arc<Context>, exponent: usize) -> Result<Self> {
         let exponent = exponent % (2 * ctx.degree);
         if exponent & 1 == 0 {
-            return Err(Error::Default(
-                "The exponent should be odd modulo 2 * degree".to_string(),
-            ));
+            return Err(Error::InvalidSubstitutionExponent(exponent));
         }
         let mut power = (exponent - 1) / 2;
         let mask = ctx.degree - 1;
@@ -272,13 +272,11 @@ impl Poly {
         rng: &mut T,
     ) -> Result<Self> {
         if !(1..=16).contains(&variance) {
-            Err(Error::Default(
-                "The variance should be an integer between 1 and 16".to_string(),
-            ))
+            Err(Error::InvalidVariance(variance))
         } else {
             let coeffs = Zeroizing::new(
                 sample_vec_cbd(ctx.degree, variance, rng)
-                    .map_err(|e| Error::Default(e.to_string()))?,
+                    .map_err(|_| Error::InvalidVariance(variance))?,
             );
             let mut p = Poly::try_convert_from(
                 coeffs.as_ref() as &[i64],
@@ -294,17 +292,17 @@ impl Poly {
     }
 
     /// Access the polynomial coefficients in RNS representation.
-    pub fn coefficients(&self) -> ArrayView2<u64> {
+    pub fn coefficients(&self) -> ArrayView2<'_, u64> {
         self.coefficients.view()
     }
 
     /// Computes the forward Ntt on the coefficients
     fn ntt_forward(&mut self) {
         if self.allow_variable_time_computations {
-            izip!(self.coefficients.outer_iter_mut(), self.ctx.ops.iter())
+            par_izip!(self.coefficients.outer_iter_mut(), &self.ctx.ops[..])
                 .for_each(|(mut v, op)| unsafe { op.forward_vt(v.as_mut_ptr()) });
         } else {
-            izip!(self.coefficients.outer_iter_mut(), self.ctx.ops.iter())
+            par_izip!(self.coefficients.outer_iter_mut(), &self.ctx.ops[..])
                 .for_each(|(mut v, op)| op.forward(v.as_slice_mut().unwrap()));
         }
     }
@@ -312,10 +310,10 @@ impl Poly {
     /// Computes the backward Ntt on the coefficients
     fn ntt_backward(&mut self) {
         if self.allow_variable_time_computations {
-            izip!(self.coefficients.outer_iter_mut(), self.ctx.ops.iter())
+            par_izip!(self.coefficients.outer_iter_mut(), &self.ctx.ops[..])
                 .for_each(|(mut v, op)| unsafe { op.backward_vt(v.as_mut_ptr()) });
         } else {
-            izip!(self.coefficients.outer_iter_mut(), self.ctx.ops.iter())
+            par_izip!(self.coefficients.outer_iter_mut(), &self.ctx.ops[..])
                 .for_each(|(mut v, op)| op.backward(v.as_slice_mut().unwrap()));
         }
     }
@@ -848,16 +846,10 @@ mod tests {
 
             let e = Poly::small(&ctx, Representation::PowerBasis, 0, &mut rng);
             assert!(e.is_err());
-            assert_eq!(
-                e.unwrap_err().to_string(),
-                "The variance should be an integer between 1 and 16"
-            );
+            assert_eq!(e.unwrap_err(), crate::Error::InvalidVariance(0));
             let e = Poly::small(&ctx, Representation::PowerBasis, 17, &mut rng);
             assert!(e.is_err());
-            assert_eq!(
-                e.unwrap_err().to_string(),
-                "The variance should be an integer between 1 and 16"
-            );
+            assert_eq!(e.unwrap_err(), crate::Error::InvalidVariance(17));
 
             for i in 1..=16 {
                 let p = Poly::small(&ctx, Representation::PowerBasis, i, &mut rng)?;
@@ -5,6 +5,8 @@ use crate::{Error, Result};
 use itertools::{izip, Itertools};
 use ndarray::Array2;
 use num_bigint::BigUint;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 use std::{
     cmp::min,
     ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
@@ -136,20 +138,20 @@ impl MulAssign<&Poly> for Poly {
             Representation::Ntt => {
                 if self.allow_variable_time_computations {
                     unsafe {
-                        izip!(
+                        par_izip!(
                             self.coefficients.outer_iter_mut(),
                             p.coefficients.outer_iter(),
-                            self.ctx.q.iter()
+                            &self.ctx.q[..]
                         )
                         .for_each(|(mut v1, v2, qi)| {
                             qi.mul_vec_vt(v1.as_slice_mut().unwrap(), v2.as_slice().unwrap());
                         });
                     }
                 } else {
-                    izip!(
+                    par_izip!(
                         self.coefficients.outer_iter_mut(),
                         p.coefficients.outer_iter(),
-                        self.ctx.q.iter()
+                        &self.ctx.q[..]
                     )
                     .for_each(|(mut v1, v2, qi)| {
                         qi.mul_vec(v1.as_slice_mut().unwrap(), v2.as_slice().unwrap())
@@ -158,11 +160,11 @@ impl MulAssign<&Poly> for Poly {
             }
             Representation::NttShoup => {
                 if self.allow_variable_time_computations {
-                    izip!(
+                    par_izip!(
                         self.coefficients.outer_iter_mut(),
                         p.coefficients.outer_iter(),
                         p.coefficients_shoup.as_ref().unwrap().outer_iter(),
-                        self.ctx.q.iter()
+                        &self.ctx.q[..]
                     )
                     .for_each(|(mut v1, v2, v2_shoup, qi)| unsafe {
                         qi.mul_shoup_vec_vt(
@@ -172,11 +174,11 @@ impl MulAssign<&Poly> for Poly {
                         )
                     });
                 } else {
-                    izip!(
+                    par_izip!(
                         self.coefficients.outer_iter_mut(),
                         p.coefficients.outer_iter(),
                         p.coefficients_shoup.as_ref().unwrap().outer_iter(),
-                        self.ctx.q.iter()
+                        &self.ctx.q[..]
                     )
                     .for_each(|(mut v1, v2, v2_shoup, qi)| {
                         qi.mul_shoup_vec(
@@ -197,12 +199,15 @@ impl MulAssign<&Poly> for Poly {
 
 impl MulAssign<&BigUint> for Poly {
     fn mul_assign(&mut self, p: &BigUint) {
+        // The Ntt representation of a constant polynomial is the constant
+        // repeated, so it can be multiplied coefficient-wise in any
+        // representation.
         let v: Vec<BigUint> = vec![p.clone()];
         let mut q = Poly::try_convert_from(
             v.as_ref() as &[BigUint],
             &self.ctx,
             self.allow_variable_time_computations,
-            self.representation.clone(),
+            Representation::PowerBasis,
         )
         .unwrap();
         q.change_representation(Representation::Ntt);
@@ -362,7 +367,7 @@ where
 
     let count = min(p.clone().count(), q.clone().count());
     if count == 0 {
-        return Err(Error::Default("At least one iterator is empty".to_string()));
+        return Err(Error::EmptyInput);
     }
 
     let p_first = p.clone().next().unwrap();
@@ -458,11 +463,12 @@ where
 #[cfg(test)]
 mod tests {
     use itertools::{izip, Itertools};
-    use rand::thread_rng;
+    use num_bigint::BigUint;
+    use rand::{thread_rng, RngCore};
 
     use super::dot_product;
     use crate::{
-        rq::{Context, Poly, Representation},
+        rq::{traits::TryConvertFrom, Context, Poly, Representation},
         zq::Modulus,
     };
     use std::{error::Error, sync::Arc};
@@ -584,6 +590,36 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn mul_biguint() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let ctx = Arc::new(Context::new(MODULI, 8)?);
+        for _ in 0..100 {
+            let b = BigUint::from(rng.next_u64());
+            let mut b_ntt = Poly::try_convert_from(
+                std::slice::from_ref(&b),
+                &ctx,
+                false,
+                Representation::PowerBasis,
+            )?;
+            b_ntt.change_representation(Representation::Ntt);
+
+            let mut p = Poly::random(&ctx, Representation::Ntt, &mut rng);
+            let r = &p * &b;
+            assert_eq!(r.representation, Representation::Ntt);
+            assert_eq!(r, &p * &b_ntt);
+            assert_eq!(&b * &p, r);
+
+            p.change_representation(Representation::PowerBasis);
+            let mut r = &p * &b;
+            assert_eq!(r.representation, Representation::PowerBasis);
+            r.change_representation(Representation::Ntt);
+            p.change_representation(Representation::Ntt);
+            assert_eq!(r, &p * &b_ntt);
+        }
+        Ok(())
+    }
+
     #[test]
     fn mul_shoup() -> Result<(), Box<dyn Error>> {
         let mut rng = thread_rng();
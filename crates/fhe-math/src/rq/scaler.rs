@@ -9,6 +9,8 @@ use crate::{
 };
 use itertools::izip;
 use ndarray::{s, Array2, Axis};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 use std::sync::Arc;
 
 /// Context extender.
@@ -24,7 +26,7 @@ impl Scaler {
     /// Create a scaler from a context `from` to a context `to`.
     pub fn new(from: &Arc<Context>, to: &Arc<Context>, factor: ScalingFactor) -> Result<Self> {
         if from.degree != to.degree {
-            return Err(Error::Default("Incompatible degrees".to_string()));
+            return Err(Error::InvalidContext);
         }
 
         let mut number_common_moduli = 0;
@@ -51,9 +53,7 @@ impl Scaler {
     /// Scale a polynomial
     pub(crate) fn scale(&self, p: &Poly) -> Result<Poly> {
         if p.ctx.as_ref() != self.from.as_ref() {
-            Err(Error::Default(
-                "The input polynomial does not have the correct context".to_string(),
-            ))
+            Err(Error::InvalidContext)
         } else {
             let mut representation = p.representation.clone();
             if representation == Representation::NttShoup {
@@ -70,7 +70,7 @@ impl Scaler {
 
             if self.number_common_moduli < self.to.q.len() {
                 if p.representation == Representation::PowerBasis {
-                    izip!(
+                    par_izip!(
                         new_coefficients
                             .slice_mut(s![self.number_common_moduli.., ..])
                             .axis_iter_mut(Axis(1)),
@@ -84,14 +84,14 @@ impl Scaler {
                     let mut p_coefficients_powerbasis = p.coefficients.clone();
                     // Backward NTT
                     if p.allow_variable_time_computations {
-                        izip!(p_coefficients_powerbasis.outer_iter_mut(), p.ctx.ops.iter())
+                        par_izip!(p_coefficients_powerbasis.outer_iter_mut(), &p.ctx.ops[..])
                             .for_each(|(mut v, op)| unsafe { op.backward_vt(v.as_mut_ptr()) });
                     } else {
-                        izip!(p_coefficients_powerbasis.outer_iter_mut(), p.ctx.ops.iter())
+                        par_izip!(p_coefficients_powerbasis.outer_iter_mut(), &p.ctx.ops[..])
                             .for_each(|(mut v, op)| op.backward(v.as_slice_mut().unwrap()));
                     }
                     // Conversion
-                    izip!(
+                    par_izip!(
                         new_coefficients
                             .slice_mut(s![self.number_common_moduli.., ..])
                             .axis_iter_mut(Axis(1)),
@@ -103,7 +103,7 @@ impl Scaler {
                     });
                     // Forward NTT on the second half
                     if p.allow_variable_time_computations {
-                        izip!(
+                        par_izip!(
                             new_coefficients
                                 .slice_mut(s![self.number_common_moduli.., ..])
                                 .outer_iter_mut(),
@@ -111,7 +111,7 @@ impl Scaler {
                         )
                         .for_each(|(mut v, op)| unsafe { op.forward_vt(v.as_mut_ptr()) });
                     } else {
-                        izip!(
+                        par_izip!(
                             new_coefficients
                                 .slice_mut(s![self.number_common_moduli.., ..])
                                 .outer_iter_mut(),
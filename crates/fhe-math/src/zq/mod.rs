@@ -4,6 +4,7 @@
 
 pub mod ntt;
 pub mod primes;
+mod simd;
 
 use crate::errors::{Error, Result};
 use fhe_util::{is_prime, transcode_from_bytes, transcode_to_bytes};
@@ -196,6 +197,9 @@ impl Modulus {
     pub fn add_vec(&self, a: &mut [u64], b: &[u64]) {
         debug_assert_eq!(a.len(), b.len());
 
+        if unsafe { simd::add_vec(simd::backend(), self, a, b) } {
+            return;
+        }
         izip!(a.iter_mut(), b.iter()).for_each(|(ai, bi)| *ai = self.add(*ai, *bi));
     }
 
@@ -210,6 +214,10 @@ impl Modulus {
         let n = a.len();
         debug_assert_eq!(n, b.len());
 
+        if simd::add_vec(simd::backend(), self, a, b) {
+            return;
+        }
+
         let p = self.p;
         macro_rules! add_at {
             ($idx:expr) => {
@@ -218,7 +226,7 @@ impl Modulus {
             };
         }
 
-        if n % 16 == 0 {
+        if n.is_multiple_of(16) {
             for i in 0..n / 16 {
                 add_at!(16 * i);
                 add_at!(16 * i + 1);
@@ -271,7 +279,7 @@ impl Modulus {
             };
         }
 
-        if n % 16 == 0 {
+        if n.is_multiple_of(16) {
             for i in 0..n / 16 {
                 sub_at!(16 * i);
                 sub_at!(16 * i + 1);
@@ -302,6 +310,10 @@ impl Modulus {
     pub fn mul_vec(&self, a: &mut [u64], b: &[u64]) {
         debug_assert_eq!(a.len(), b.len());
 
+        if unsafe { simd::mul_vec(simd::backend(), self, a, b) } {
+            return;
+        }
+
         if self.supports_opt {
             izip!(a.iter_mut(), b.iter()).for_each(|(ai, bi)| *ai = self.mul_opt(*ai, *bi));
         } else {
@@ -340,6 +352,10 @@ impl Modulus {
     pub unsafe fn mul_vec_vt(&self, a: &mut [u64], b: &[u64]) {
         debug_assert_eq!(a.len(), b.len());
 
+        if simd::mul_vec(simd::backend(), self, a, b) {
+            return;
+        }
+
         if self.supports_opt {
             izip!(a.iter_mut(), b.iter()).for_each(|(ai, bi)| *ai = self.mul_opt_vt(*ai, *bi));
         } else {
@@ -363,6 +379,9 @@ impl Modulus {
         debug_assert_eq!(a.len(), b_shoup.len());
         debug_assert_eq!(&b_shoup, &self.shoup_vec(b));
 
+        if unsafe { simd::mul_shoup_vec(simd::backend(), self, a, b, b_shoup) } {
+            return;
+        }
         izip!(a.iter_mut(), b.iter(), b_shoup.iter())
             .for_each(|(ai, bi, bi_shoup)| *ai = self.mul_shoup(*ai, *bi, *bi_shoup));
     }
@@ -379,12 +398,18 @@ impl Modulus {
         debug_assert_eq!(a.len(), b_shoup.len());
         debug_assert_eq!(&b_shoup, &self.shoup_vec(b));
 
+        if simd::mul_shoup_vec(simd::backend(), self, a, b, b_shoup) {
+            return;
+        }
         izip!(a.iter_mut(), b.iter(), b_shoup.iter())
             .for_each(|(ai, bi, bi_shoup)| *ai = self.mul_shoup_vt(*ai, *bi, *bi_shoup));
     }
 
     /// Reduce a vector in place in constant time.
     pub fn reduce_vec(&self, a: &mut [u64]) {
+        if unsafe { simd::reduce_vec(simd::backend(), self, a) } {
+            return;
+        }
         a.iter_mut().for_each(|ai| *ai = self.reduce(*ai));
     }
 
@@ -419,6 +444,9 @@ impl Modulus {
     /// This function is not constant time and its timing may reveal information
     /// about the values being reduced.
     pub unsafe fn reduce_vec_vt(&self, a: &mut [u64]) {
+        if simd::reduce_vec(simd::backend(), self, a) {
+            return;
+        }
         a.iter_mut().for_each(|ai| *ai = self.reduce_vt(*ai));
     }
 
@@ -694,7 +722,7 @@ impl Modulus {
     ///
     /// Panics if the size is not a multiple of 8.
     pub const fn serialization_length(&self, size: usize) -> usize {
-        assert!(size % 8 == 0);
+        assert!(size.is_multiple_of(8));
         let p_nbits = 64 - (self.p - 1).leading_zeros() as usize;
         p_nbits * size / 8
     }
@@ -1,6 +1,6 @@
 //! Number-Theoretic Transform in ZZ_q.
 
-use super::Modulus;
+use super::{simd, Modulus};
 use fhe_util::is_prime;
 use itertools::Itertools;
 use rand::{Rng, SeedableRng};
@@ -20,15 +20,15 @@ pub fn supports_ntt(p: u64, n: usize) -> bool {
 /// Number-Theoretic Transform operator.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct NttOperator {
-    p: Modulus,
-    p_twice: u64,
-    size: usize,
-    omegas: Box<[u64]>,
-    omegas_shoup: Box<[u64]>,
-    zetas_inv: Box<[u64]>,
-    zetas_inv_shoup: Box<[u64]>,
-    size_inv: u64,
-    size_inv_shoup: u64,
+    pub(super) p: Modulus,
+    pub(super) p_twice: u64,
+    pub(super) size: usize,
+    pub(super) omegas: Box<[u64]>,
+    pub(super) omegas_shoup: Box<[u64]>,
+    pub(super) zetas_inv: Box<[u64]>,
+    pub(super) zetas_inv_shoup: Box<[u64]>,
+    pub(super) size_inv: u64,
+    pub(super) size_inv_shoup: u64,
 }
 
 impl NttOperator {
@@ -83,6 +83,10 @@ impl NttOperator {
     pub fn forward(&self, a: &mut [u64]) {
         debug_assert_eq!(a.len(), self.size);
 
+        if unsafe { simd::forward(simd::backend(), self, a) } {
+            return;
+        }
+
         let n = self.size;
         let a_ptr = a.as_mut_ptr();
 
@@ -129,6 +133,10 @@ impl NttOperator {
     pub fn backward(&self, a: &mut [u64]) {
         debug_assert_eq!(a.len(), self.size);
 
+        if unsafe { simd::backward(simd::backend(), self, a) } {
+            return;
+        }
+
         let a_ptr = a.as_mut_ptr();
 
         let mut k = 0;
@@ -223,6 +231,11 @@ impl NttOperator {
     /// This function is not constant time and its timing may reveal information
     /// about the value being reduced.
     pub unsafe fn forward_vt(&self, a_ptr: *mut u64) {
+        let a = std::slice::from_raw_parts_mut(a_ptr, self.size);
+        if simd::forward(simd::backend(), self, a) {
+            return;
+        }
+
         self.forward_vt_lazy(a_ptr);
         for i in 0..self.size {
             *a_ptr.add(i) = self.reduce3_vt(*a_ptr.add(i))
@@ -236,6 +249,11 @@ impl NttOperator {
     /// This function is not constant time and its timing may reveal information
     /// about the value being reduced.
     pub unsafe fn backward_vt(&self, a_ptr: *mut u64) {
+        let a = std::slice::from_raw_parts_mut(a_ptr, self.size);
+        if simd::backward(simd::backend(), self, a) {
+            return;
+        }
+
         let mut k = 0;
         let mut m = self.size >> 1;
         let mut l = 1;
@@ -280,7 +298,7 @@ impl NttOperator {
     /// Reduce a modulo p.
     ///
     /// Aborts if a >= 4 * p.
-    const fn reduce3(&self, a: u64) -> u64 {
+    pub(super) const fn reduce3(&self, a: u64) -> u64 {
         debug_assert!(a < 4 * self.p.p);
 
         let y = Modulus::reduce1(a, 2 * self.p.p);
@@ -298,7 +316,7 @@ impl NttOperator {
     }
 
     /// NTT Butterfly.
-    fn butterfly(&self, x: &mut u64, y: &mut u64, w: u64, w_shoup: u64) {
+    pub(super) fn butterfly(&self, x: &mut u64, y: &mut u64, w: u64, w_shoup: u64) {
         debug_assert!(*x < 4 * self.p.p);
         debug_assert!(*y < 4 * self.p.p);
         debug_assert!(w < self.p.p);
@@ -330,7 +348,7 @@ impl NttOperator {
     }
 
     /// Inverse NTT butterfly.
-    fn inv_butterfly(&self, x: &mut u64, y: &mut u64, z: u64, z_shoup: u64) {
+    pub(super) fn inv_butterfly(&self, x: &mut u64, y: &mut u64, z: u64, z_shoup: u64) {
         debug_assert!(*x < self.p_twice);
         debug_assert!(*y < self.p_twice);
         debug_assert!(z < self.p.p);
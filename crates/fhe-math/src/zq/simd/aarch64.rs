@@ -0,0 +1,143 @@
+//! Vector type for the NEON instruction set.
+//!
+//! NEON has no 64 x 64 -> 128 bits multiplication, which is emulated using
+//! four 32 x 32 -> 64 bits multiplications.
+
+use core::arch::aarch64::*;
+
+/// Two 64-bit lanes, using NEON.
+pub(super) struct Neon;
+
+impl Neon {
+    pub(super) const LANES: usize = 2;
+
+    #[inline]
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn load(ptr: *const u64) -> uint64x2_t {
+        vld1q_u64(ptr)
+    }
+
+    #[inline]
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn store(ptr: *mut u64, a: uint64x2_t) {
+        vst1q_u64(ptr, a)
+    }
+
+    #[inline]
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn splat(a: u64) -> uint64x2_t {
+        vdupq_n_u64(a)
+    }
+
+    #[inline]
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn add(a: uint64x2_t, b: uint64x2_t) -> uint64x2_t {
+        vaddq_u64(a, b)
+    }
+
+    #[inline]
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn sub(a: uint64x2_t, b: uint64x2_t) -> uint64x2_t {
+        vsubq_u64(a, b)
+    }
+
+    #[inline]
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn or(a: uint64x2_t, b: uint64x2_t) -> uint64x2_t {
+        vorrq_u64(a, b)
+    }
+
+    #[inline]
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn shl(a: uint64x2_t, n: u32) -> uint64x2_t {
+        vshlq_u64(a, vdupq_n_s64(n as i64))
+    }
+
+    #[inline]
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn shr(a: uint64x2_t, n: u32) -> uint64x2_t {
+        // The shifts by a register are to the left, and to the right when the
+        // shift is negative.
+        vshlq_u64(a, vdupq_n_s64(-(n as i64)))
+    }
+
+    /// Returns all ones in the lanes where a < b, and zero elsewhere.
+    #[inline]
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn lt_mask(a: uint64x2_t, b: uint64x2_t) -> uint64x2_t {
+        vcltq_u64(a, b)
+    }
+
+    /// Subtracts m in the lanes where a >= m, in constant time.
+    #[inline]
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn reduce1(a: uint64x2_t, m: uint64x2_t) -> uint64x2_t {
+        vsubq_u64(a, vbicq_u64(m, vcltq_u64(a, m)))
+    }
+
+    /// Returns the high and low 64 bits of the products.
+    #[inline]
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn mul_wide(a: uint64x2_t, b: uint64x2_t) -> (uint64x2_t, uint64x2_t) {
+        let mask = vdupq_n_u64(0xffffffff);
+        let (a_lo, a_hi) = (vmovn_u64(a), vshrn_n_u64::<32>(a));
+        let (b_lo, b_hi) = (vmovn_u64(b), vshrn_n_u64::<32>(b));
+        let lo_lo = vmull_u32(a_lo, b_lo);
+        let lo_hi = vmull_u32(a_lo, b_hi);
+        let hi_lo = vmull_u32(a_hi, b_lo);
+        let hi_hi = vmull_u32(a_hi, b_hi);
+        let mid = vaddq_u64(
+            vaddq_u64(vshrq_n_u64::<32>(lo_lo), vandq_u64(lo_hi, mask)),
+            vandq_u64(hi_lo, mask),
+        );
+        let lo = vorrq_u64(vshlq_n_u64::<32>(mid), vandq_u64(lo_lo, mask));
+        let hi = vaddq_u64(
+            vaddq_u64(hi_hi, vshrq_n_u64::<32>(lo_hi)),
+            vaddq_u64(vshrq_n_u64::<32>(hi_lo), vshrq_n_u64::<32>(mid)),
+        );
+        (hi, lo)
+    }
+
+    /// Returns the high 64 bits of the products.
+    #[inline]
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn mul_hi(a: uint64x2_t, b: uint64x2_t) -> uint64x2_t {
+        Self::mul_wide(a, b).0
+    }
+
+    /// Returns the low 64 bits of the products.
+    #[inline]
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn mul_lo(a: uint64x2_t, b: uint64x2_t) -> uint64x2_t {
+        let (a_lo, a_hi) = (vmovn_u64(a), vshrn_n_u64::<32>(a));
+        let (b_lo, b_hi) = (vmovn_u64(b), vshrn_n_u64::<32>(b));
+        let cross = vmlal_u32(vmull_u32(a_lo, b_hi), a_hi, b_lo);
+        vaddq_u64(vmull_u32(a_lo, b_lo), vshlq_n_u64::<32>(cross))
+    }
+
+    /// Returns the Shoup constant used by [`Self::lazy_mul_shoup`].
+    #[inline]
+    pub(super) const fn shoup(b_shoup: u64) -> u64 {
+        b_shoup
+    }
+
+    /// Lazy Shoup multiplication, with output in [0, 2 * p).
+    #[inline]
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn lazy_mul_shoup(
+        a: uint64x2_t,
+        b: uint64x2_t,
+        b_shoup: uint64x2_t,
+        p: uint64x2_t,
+    ) -> uint64x2_t {
+        let q = Self::mul_hi(a, b_shoup);
+        vsubq_u64(Self::mul_lo(a, b), Self::mul_lo(q, p))
+    }
+}
+
+pub(super) mod neon {
+    use super::Neon;
+
+    vector_kernels!("neon", Neon);
+    ntt_kernels!("neon", Neon, Neon);
+}
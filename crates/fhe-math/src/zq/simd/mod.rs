@@ -0,0 +1,492 @@
+//! Vectorized implementations of the vector operations and of the NTT.
+//!
+//! The implementation is selected at runtime, by detecting the features of the
+//! CPU: AVX2 and AVX-512 (with IFMA when available) on x86_64, and NEON on
+//! aarch64. The vectorized implementations run in constant time and compute
+//! the same (fully reduced) outputs as the scalar implementations, which are
+//! used as a fallback.
+
+use super::{ntt::NttOperator, Modulus};
+use std::sync::OnceLock;
+
+/// Defines the vectorized vector operations over Z_p, using the vector type
+/// `$S`, in functions compiled with the target features `$features`.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+macro_rules! vector_kernels {
+    ($features:literal, $S:ident) => {
+        use crate::zq::Modulus;
+
+        /// Modular addition of vectors in place.
+        #[target_feature(enable = $features)]
+        pub(in crate::zq) unsafe fn add_vec(p: &Modulus, a: &mut [u64], b: &[u64]) {
+            let pv = $S::splat(p.p);
+            let mut a_chunks = a.chunks_exact_mut($S::LANES);
+            let mut b_chunks = b.chunks_exact($S::LANES);
+            for (ai, bi) in (&mut a_chunks).zip(&mut b_chunks) {
+                let s = $S::add($S::load(ai.as_ptr()), $S::load(bi.as_ptr()));
+                $S::store(ai.as_mut_ptr(), $S::reduce1(s, pv));
+            }
+            for (ai, bi) in a_chunks
+                .into_remainder()
+                .iter_mut()
+                .zip(b_chunks.remainder())
+            {
+                *ai = p.add(*ai, *bi);
+            }
+        }
+
+        /// Modular multiplication of vectors in place, using a Barrett
+        /// reduction of the products.
+        #[target_feature(enable = $features)]
+        pub(in crate::zq) unsafe fn mul_vec(p: &Modulus, a: &mut [u64], b: &[u64]) {
+            let k = p.nbits as u32;
+            let pv = $S::splat(p.p);
+            let mu = $S::splat(((1u128 << (2 * k)) / (p.p as u128)) as u64);
+            let mut a_chunks = a.chunks_exact_mut($S::LANES);
+            let mut b_chunks = b.chunks_exact($S::LANES);
+            for (ai, bi) in (&mut a_chunks).zip(&mut b_chunks) {
+                // The product is smaller than 2^(2k), so the quotient is estimated
+                // from floor(product / 2^(k - 1)) * floor(2^(2k) / p) / 2^(k + 1), up
+                // to 2.
+                let (hi, lo) = $S::mul_wide($S::load(ai.as_ptr()), $S::load(bi.as_ptr()));
+                let c1 = $S::or($S::shl(hi, 65 - k), $S::shr(lo, k - 1));
+                let (c2_hi, c2_lo) = $S::mul_wide(c1, mu);
+                let c3 = $S::or($S::shl(c2_hi, 63 - k), $S::shr(c2_lo, k + 1));
+                let r = $S::sub(lo, $S::mul_lo(c3, pv));
+                $S::store(ai.as_mut_ptr(), $S::reduce1($S::reduce1(r, pv), pv));
+            }
+            for (ai, bi) in a_chunks
+                .into_remainder()
+                .iter_mut()
+                .zip(b_chunks.remainder())
+            {
+                *ai = p.mul(*ai, *bi);
+            }
+        }
+
+        /// Shoup modular multiplication of vectors in place.
+        #[target_feature(enable = $features)]
+        pub(in crate::zq) unsafe fn mul_shoup_vec(
+            p: &Modulus,
+            a: &mut [u64],
+            b: &[u64],
+            b_shoup: &[u64],
+        ) {
+            let pv = $S::splat(p.p);
+            let mut a_chunks = a.chunks_exact_mut($S::LANES);
+            let mut b_chunks = b.chunks_exact($S::LANES);
+            let mut b_shoup_chunks = b_shoup.chunks_exact($S::LANES);
+            for ((ai, bi), bi_shoup) in (&mut a_chunks).zip(&mut b_chunks).zip(&mut b_shoup_chunks)
+            {
+                let r = $S::lazy_mul_shoup(
+                    $S::load(ai.as_ptr()),
+                    $S::load(bi.as_ptr()),
+                    $S::load(bi_shoup.as_ptr()),
+                    pv,
+                );
+                $S::store(ai.as_mut_ptr(), $S::reduce1(r, pv));
+            }
+            for ((ai, bi), bi_shoup) in a_chunks
+                .into_remainder()
+                .iter_mut()
+                .zip(b_chunks.remainder())
+                .zip(b_shoup_chunks.remainder())
+            {
+                *ai = p.mul_shoup(*ai, *bi, *bi_shoup);
+            }
+        }
+
+        /// Reduction of a vector in place, using the same Barrett reduction as
+        /// [`Modulus::reduce`].
+        #[target_feature(enable = $features)]
+        pub(in crate::zq) unsafe fn reduce_vec(p: &Modulus, a: &mut [u64]) {
+            let pv = $S::splat(p.p);
+            let barrett_hi = $S::splat(p.barrett_hi);
+            let barrett_lo = $S::splat(p.barrett_lo);
+            let mut a_chunks = a.chunks_exact_mut($S::LANES);
+            for ai in &mut a_chunks {
+                let x = $S::load(ai.as_ptr());
+                let t = $S::mul_hi(x, barrett_lo);
+                let (hi, lo) = $S::mul_wide(x, barrett_hi);
+                // Add the carry of lo + t, by subtracting the all-ones mask.
+                let q = $S::sub(hi, $S::lt_mask($S::add(lo, t), lo));
+                let r = $S::sub(x, $S::mul_lo(q, pv));
+                $S::store(ai.as_mut_ptr(), $S::reduce1(r, pv));
+            }
+            for ai in a_chunks.into_remainder() {
+                *ai = p.reduce(*ai);
+            }
+        }
+    };
+}
+
+/// Defines the vectorized forward and backward NTT, using the vector type
+/// `$S` and the Shoup multiplications of `$M`, in functions compiled with the
+/// target features `$features`. The levels of the NTT where the butterflies
+/// are on fewer than `$S::LANES` coefficients use the scalar butterflies.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+macro_rules! ntt_kernels {
+    ($features:literal, $S:ident, $M:ident) => {
+        use crate::zq::ntt::NttOperator;
+
+        /// Compute the forward NTT in place.
+        #[target_feature(enable = $features)]
+        pub(in crate::zq) unsafe fn forward(op: &NttOperator, a: &mut [u64]) {
+            let a_ptr = a.as_mut_ptr();
+            let p = $S::splat(op.p.p);
+            let p_twice = $S::splat(op.p_twice);
+
+            let mut l = op.size >> 1;
+            let mut m = 1;
+            let mut k = 1;
+            while l > 0 {
+                for i in 0..m {
+                    let omega = *op.omegas.get_unchecked(k);
+                    let omega_shoup = *op.omegas_shoup.get_unchecked(k);
+                    k += 1;
+
+                    let s = 2 * i * l;
+                    if l >= $S::LANES {
+                        let w = $S::splat(omega);
+                        let w_shoup = $S::splat($M::shoup(omega_shoup));
+                        for j in (s..(s + l)).step_by($S::LANES) {
+                            let x = $S::reduce1($S::load(a_ptr.add(j)), p_twice);
+                            let t = $M::lazy_mul_shoup($S::load(a_ptr.add(j + l)), w, w_shoup, p);
+                            $S::store(a_ptr.add(j + l), $S::sub($S::add(x, p_twice), t));
+                            $S::store(a_ptr.add(j), $S::add(x, t));
+                        }
+                    } else {
+                        for j in s..(s + l) {
+                            op.butterfly(
+                                &mut *a_ptr.add(j),
+                                &mut *a_ptr.add(j + l),
+                                omega,
+                                omega_shoup,
+                            );
+                        }
+                        if l == 1 {
+                            // The last level should reduce the output
+                            *a_ptr.add(s) = op.reduce3(*a_ptr.add(s));
+                            *a_ptr.add(s + 1) = op.reduce3(*a_ptr.add(s + 1));
+                        }
+                    }
+                }
+                l >>= 1;
+                m <<= 1;
+            }
+        }
+
+        /// Compute the backward NTT in place.
+        #[target_feature(enable = $features)]
+        pub(in crate::zq) unsafe fn backward(op: &NttOperator, a: &mut [u64]) {
+            let a_ptr = a.as_mut_ptr();
+            let p = $S::splat(op.p.p);
+            let p_twice = $S::splat(op.p_twice);
+
+            let mut k = 0;
+            let mut m = op.size >> 1;
+            let mut l = 1;
+            while m > 0 {
+                for i in 0..m {
+                    let zeta_inv = *op.zetas_inv.get_unchecked(k);
+                    let zeta_inv_shoup = *op.zetas_inv_shoup.get_unchecked(k);
+                    k += 1;
+
+                    let s = 2 * i * l;
+                    if l >= $S::LANES {
+                        let z = $S::splat(zeta_inv);
+                        let z_shoup = $S::splat($M::shoup(zeta_inv_shoup));
+                        for j in (s..(s + l)).step_by($S::LANES) {
+                            let x = $S::load(a_ptr.add(j));
+                            let y = $S::load(a_ptr.add(j + l));
+                            $S::store(a_ptr.add(j), $S::reduce1($S::add(x, y), p_twice));
+                            $S::store(
+                                a_ptr.add(j + l),
+                                $M::lazy_mul_shoup($S::sub($S::add(x, p_twice), y), z, z_shoup, p),
+                            );
+                        }
+                    } else {
+                        for j in s..(s + l) {
+                            op.inv_butterfly(
+                                &mut *a_ptr.add(j),
+                                &mut *a_ptr.add(j + l),
+                                zeta_inv,
+                                zeta_inv_shoup,
+                            );
+                        }
+                    }
+                }
+                l <<= 1;
+                m >>= 1;
+            }
+
+            let size_inv = $S::splat(op.size_inv);
+            let size_inv_shoup = $S::splat($M::shoup(op.size_inv_shoup));
+            for j in (0..op.size).step_by($S::LANES) {
+                let x = $M::lazy_mul_shoup($S::load(a_ptr.add(j)), size_inv, size_inv_shoup, p);
+                $S::store(a_ptr.add(j), $S::reduce1(x, p));
+            }
+        }
+    };
+}
+
+#[cfg(target_arch = "aarch64")]
+mod aarch64;
+#[cfg(target_arch = "x86_64")]
+mod x86;
+
+/// Instruction sets of the vectorized implementations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Backend {
+    /// No vectorization.
+    Scalar,
+    /// NEON, on aarch64.
+    #[cfg(target_arch = "aarch64")]
+    Neon,
+    /// AVX2, on x86_64.
+    #[cfg(target_arch = "x86_64")]
+    Avx2,
+    /// AVX-512F and AVX-512DQ, on x86_64.
+    #[cfg(target_arch = "x86_64")]
+    Avx512,
+    /// AVX-512F, AVX-512DQ and AVX-512 IFMA, on x86_64.
+    #[cfg(target_arch = "x86_64")]
+    Avx512Ifma,
+}
+
+/// Returns the backends supported by the CPU, from the slowest to the fastest.
+pub(super) fn available_backends() -> Vec<Backend> {
+    let mut backends = vec![Backend::Scalar];
+    #[cfg(target_arch = "x86_64")]
+    if is_x86_feature_detected!("avx2") {
+        backends.push(Backend::Avx2);
+        if is_x86_feature_detected!("avx512f") && is_x86_feature_detected!("avx512dq") {
+            backends.push(Backend::Avx512);
+            if is_x86_feature_detected!("avx512ifma") {
+                backends.push(Backend::Avx512Ifma);
+            }
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    if std::arch::is_aarch64_feature_detected!("neon") {
+        backends.push(Backend::Neon);
+    }
+    backends
+}
+
+/// Returns the fastest backend supported by the CPU, which is detected once.
+pub(super) fn backend() -> Backend {
+    static BACKEND: OnceLock<Backend> = OnceLock::new();
+    *BACKEND.get_or_init(|| *available_backends().last().unwrap())
+}
+
+/// Modular addition of vectors in place. Returns false, without modifying the
+/// vectors, if the backend has no vectorized implementation.
+///
+/// # Safety
+/// The backend must be supported by the CPU, and the vectors must be of the
+/// same length.
+pub(super) unsafe fn add_vec(backend: Backend, p: &Modulus, a: &mut [u64], b: &[u64]) -> bool {
+    match backend {
+        #[cfg(target_arch = "x86_64")]
+        Backend::Avx2 => x86::avx2::add_vec(p, a, b),
+        #[cfg(target_arch = "x86_64")]
+        Backend::Avx512 | Backend::Avx512Ifma => x86::avx512::add_vec(p, a, b),
+        #[cfg(target_arch = "aarch64")]
+        Backend::Neon => aarch64::neon::add_vec(p, a, b),
+        Backend::Scalar => return false,
+    }
+    true
+}
+
+/// Modular multiplication of vectors in place. Returns false, without
+/// modifying the vectors, if the backend has no vectorized implementation.
+///
+/// # Safety
+/// The backend must be supported by the CPU, and the vectors must be of the
+/// same length.
+pub(super) unsafe fn mul_vec(backend: Backend, p: &Modulus, a: &mut [u64], b: &[u64]) -> bool {
+    match backend {
+        #[cfg(target_arch = "x86_64")]
+        Backend::Avx2 => x86::avx2::mul_vec(p, a, b),
+        #[cfg(target_arch = "x86_64")]
+        Backend::Avx512 | Backend::Avx512Ifma => x86::avx512::mul_vec(p, a, b),
+        #[cfg(target_arch = "aarch64")]
+        Backend::Neon => aarch64::neon::mul_vec(p, a, b),
+        _ => return false,
+    }
+    true
+}
+
+/// Shoup modular multiplication of vectors in place. Returns false, without
+/// modifying the vectors, if the backend has no vectorized implementation.
+///
+/// # Safety
+/// The backend must be supported by the CPU, and the vectors must be of the
+/// same length.
+pub(super) unsafe fn mul_shoup_vec(
+    backend: Backend,
+    p: &Modulus,
+    a: &mut [u64],
+    b: &[u64],
+    b_shoup: &[u64],
+) -> bool {
+    match backend {
+        #[cfg(target_arch = "x86_64")]
+        Backend::Avx2 => x86::avx2::mul_shoup_vec(p, a, b, b_shoup),
+        #[cfg(target_arch = "x86_64")]
+        Backend::Avx512 | Backend::Avx512Ifma => x86::avx512::mul_shoup_vec(p, a, b, b_shoup),
+        #[cfg(target_arch = "aarch64")]
+        Backend::Neon => aarch64::neon::mul_shoup_vec(p, a, b, b_shoup),
+        _ => return false,
+    }
+    true
+}
+
+/// Reduction of a vector in place. Returns false, without modifying the
+/// vector, if the backend has no vectorized implementation.
+///
+/// # Safety
+/// The backend must be supported by the CPU.
+pub(super) unsafe fn reduce_vec(backend: Backend, p: &Modulus, a: &mut [u64]) -> bool {
+    match backend {
+        #[cfg(target_arch = "x86_64")]
+        Backend::Avx2 => x86::avx2::reduce_vec(p, a),
+        #[cfg(target_arch = "x86_64")]
+        Backend::Avx512 | Backend::Avx512Ifma => x86::avx512::reduce_vec(p, a),
+        #[cfg(target_arch = "aarch64")]
+        Backend::Neon => aarch64::neon::reduce_vec(p, a),
+        _ => return false,
+    }
+    true
+}
+
+/// Forward NTT in place. Returns false, without modifying the vector, if the
+/// backend has no vectorized implementation.
+///
+/// # Safety
+/// The backend must be supported by the CPU, and the vector must be of the
+/// size of the operator.
+pub(super) unsafe fn forward(backend: Backend, op: &NttOperator, a: &mut [u64]) -> bool {
+    match backend {
+        #[cfg(target_arch = "x86_64")]
+        Backend::Avx2 => x86::avx2::forward(op, a),
+        #[cfg(target_arch = "x86_64")]
+        Backend::Avx512Ifma if op.p.nbits <= 50 => x86::avx512_ifma::forward(op, a),
+        #[cfg(target_arch = "x86_64")]
+        Backend::Avx512 | Backend::Avx512Ifma => x86::avx512::forward(op, a),
+        #[cfg(target_arch = "aarch64")]
+        Backend::Neon => aarch64::neon::forward(op, a),
+        _ => return false,
+    }
+    true
+}
+
+/// Backward NTT in place. Returns false, without modifying the vector, if the
+/// backend has no vectorized implementation.
+///
+/// # Safety
+/// The backend must be supported by the CPU, and the vector must be of the
+/// size of the operator.
+pub(super) unsafe fn backward(backend: Backend, op: &NttOperator, a: &mut [u64]) -> bool {
+    match backend {
+        #[cfg(target_arch = "x86_64")]
+        Backend::Avx2 => x86::avx2::backward(op, a),
+        #[cfg(target_arch = "x86_64")]
+        Backend::Avx512Ifma if op.p.nbits <= 50 => x86::avx512_ifma::backward(op, a),
+        #[cfg(target_arch = "x86_64")]
+        Backend::Avx512 | Backend::Avx512Ifma => x86::avx512::backward(op, a),
+        #[cfg(target_arch = "aarch64")]
+        Backend::Neon => aarch64::neon::backward(op, a),
+        _ => return false,
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{available_backends, Backend};
+    use crate::zq::{ntt::NttOperator, primes::generate_prime, Modulus};
+    use itertools::{izip, Itertools};
+    use proptest::collection::vec as prop_vec;
+    use proptest::prelude::{any, BoxedStrategy, Just, Strategy};
+    use rand::thread_rng;
+
+    fn valid_moduli() -> impl Strategy<Value = Modulus> {
+        any::<u64>().prop_filter_map("filter invalid moduli", |p| Modulus::new(p).ok())
+    }
+
+    fn vecs() -> BoxedStrategy<(Vec<u64>, Vec<u64>)> {
+        prop_vec(any::<u64>(), 1..100)
+            .prop_flat_map(|vec| {
+                let len = vec.len();
+                (Just(vec), prop_vec(any::<u64>(), len))
+            })
+            .boxed()
+    }
+
+    proptest! {
+        #[test]
+        fn vector_operations(p in valid_moduli(), (a, mut b) in vecs()) {
+            b.iter_mut().for_each(|bi| *bi = p.reduce(*bi));
+            let a_reduced = a.iter().map(|ai| p.reduce(*ai)).collect_vec();
+            let b_shoup = b.iter().map(|bi| p.shoup(*bi)).collect_vec();
+
+            for backend in available_backends() {
+                let vectorized = backend != Backend::Scalar;
+
+                let mut c = a_reduced.clone();
+                prop_assert_eq!(unsafe { super::add_vec(backend, &p, &mut c, &b) }, vectorized);
+                if vectorized {
+                    prop_assert_eq!(&c, &izip!(&a_reduced, &b).map(|(ai, bi)| p.add(*ai, *bi)).collect_vec());
+                }
+
+                let mut c = a_reduced.clone();
+                if unsafe { super::mul_vec(backend, &p, &mut c, &b) } {
+                    prop_assert_eq!(&c, &izip!(&a_reduced, &b).map(|(ai, bi)| p.mul(*ai, *bi)).collect_vec());
+                }
+
+                // The Shoup multiplication also applies to unreduced inputs.
+                let mut c = a.clone();
+                if unsafe { super::mul_shoup_vec(backend, &p, &mut c, &b, &b_shoup) } {
+                    prop_assert_eq!(&c, &izip!(&a, &b, &b_shoup).map(|(ai, bi, bi_shoup)| p.mul_shoup(*ai, *bi, *bi_shoup)).collect_vec());
+                }
+
+                let mut c = a.clone();
+                if unsafe { super::reduce_vec(backend, &p, &mut c) } {
+                    prop_assert_eq!(&c, &a_reduced);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn ntt() {
+        let mut rng = thread_rng();
+        for size in [8, 16, 1024] {
+            // The moduli of at most 50 bits use AVX-512 IFMA when available.
+            for bits in [20, 50, 51, 62] {
+                let p = generate_prime(bits, 2 * size as u64, 1 << bits).unwrap();
+                let q = Modulus::new(p).unwrap();
+                let op = NttOperator::new(&q, size).unwrap();
+
+                for _ in 0..20 {
+                    let a = q.random_vec(size, &mut rng);
+                    // The scalar lazy forward NTT is not vectorized.
+                    let mut expected = a.clone();
+                    unsafe { op.forward_vt_lazy(expected.as_mut_ptr()) };
+                    expected.iter_mut().for_each(|ei| *ei = q.reduce(*ei));
+
+                    for backend in available_backends() {
+                        let mut b = a.clone();
+                        if unsafe { super::forward(backend, &op, &mut b) } {
+                            assert_eq!(b, expected);
+                            assert!(unsafe { super::backward(backend, &op, &mut b) });
+                            assert_eq!(b, a);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,325 @@
+//! Vector types for the AVX2 and AVX-512 instruction sets.
+//!
+//! Neither instruction set has a 64 x 64 -> 128 bits multiplication, which is
+//! emulated using four 32 x 32 -> 64 bits multiplications. With AVX-512 IFMA,
+//! the Shoup multiplications of the NTT use the 52-bit multiplications
+//! instead, when the modulus has at most 50 bits.
+
+use core::arch::x86_64::*;
+
+/// Four 64-bit lanes, using AVX2.
+pub(super) struct Avx2;
+
+impl Avx2 {
+    pub(super) const LANES: usize = 4;
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn load(ptr: *const u64) -> __m256i {
+        _mm256_loadu_si256(ptr.cast())
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn store(ptr: *mut u64, a: __m256i) {
+        _mm256_storeu_si256(ptr.cast(), a)
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn splat(a: u64) -> __m256i {
+        _mm256_set1_epi64x(a as i64)
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn add(a: __m256i, b: __m256i) -> __m256i {
+        _mm256_add_epi64(a, b)
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn sub(a: __m256i, b: __m256i) -> __m256i {
+        _mm256_sub_epi64(a, b)
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn or(a: __m256i, b: __m256i) -> __m256i {
+        _mm256_or_si256(a, b)
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn shl(a: __m256i, n: u32) -> __m256i {
+        _mm256_sll_epi64(a, _mm_cvtsi32_si128(n as i32))
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn shr(a: __m256i, n: u32) -> __m256i {
+        _mm256_srl_epi64(a, _mm_cvtsi32_si128(n as i32))
+    }
+
+    /// Returns all ones in the lanes where a < b, and zero elsewhere.
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn lt_mask(a: __m256i, b: __m256i) -> __m256i {
+        // There is no unsigned comparison, so we flip the sign bits.
+        let sign = _mm256_set1_epi64x(i64::MIN);
+        _mm256_cmpgt_epi64(_mm256_xor_si256(b, sign), _mm256_xor_si256(a, sign))
+    }
+
+    /// Subtracts m in the lanes where a >= m, in constant time.
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn reduce1(a: __m256i, m: __m256i) -> __m256i {
+        _mm256_sub_epi64(a, _mm256_andnot_si256(Self::lt_mask(a, m), m))
+    }
+
+    /// Returns the high and low 64 bits of the products.
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn mul_wide(a: __m256i, b: __m256i) -> (__m256i, __m256i) {
+        let mask = _mm256_set1_epi64x(0xffffffff);
+        let a_hi = _mm256_srli_epi64::<32>(a);
+        let b_hi = _mm256_srli_epi64::<32>(b);
+        let lo_lo = _mm256_mul_epu32(a, b);
+        let lo_hi = _mm256_mul_epu32(a, b_hi);
+        let hi_lo = _mm256_mul_epu32(a_hi, b);
+        let hi_hi = _mm256_mul_epu32(a_hi, b_hi);
+        let mid = _mm256_add_epi64(
+            _mm256_add_epi64(
+                _mm256_srli_epi64::<32>(lo_lo),
+                _mm256_and_si256(lo_hi, mask),
+            ),
+            _mm256_and_si256(hi_lo, mask),
+        );
+        let lo = _mm256_or_si256(_mm256_slli_epi64::<32>(mid), _mm256_and_si256(lo_lo, mask));
+        let hi = _mm256_add_epi64(
+            _mm256_add_epi64(hi_hi, _mm256_srli_epi64::<32>(lo_hi)),
+            _mm256_add_epi64(_mm256_srli_epi64::<32>(hi_lo), _mm256_srli_epi64::<32>(mid)),
+        );
+        (hi, lo)
+    }
+
+    /// Returns the high 64 bits of the products.
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn mul_hi(a: __m256i, b: __m256i) -> __m256i {
+        Self::mul_wide(a, b).0
+    }
+
+    /// Returns the low 64 bits of the products.
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn mul_lo(a: __m256i, b: __m256i) -> __m256i {
+        let lo_lo = _mm256_mul_epu32(a, b);
+        let lo_hi = _mm256_mul_epu32(a, _mm256_srli_epi64::<32>(b));
+        let hi_lo = _mm256_mul_epu32(_mm256_srli_epi64::<32>(a), b);
+        _mm256_add_epi64(
+            lo_lo,
+            _mm256_slli_epi64::<32>(_mm256_add_epi64(lo_hi, hi_lo)),
+        )
+    }
+
+    /// Returns the Shoup constant used by [`Self::lazy_mul_shoup`].
+    #[inline]
+    pub(super) const fn shoup(b_shoup: u64) -> u64 {
+        b_shoup
+    }
+
+    /// Lazy Shoup multiplication, with output in [0, 2 * p).
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn lazy_mul_shoup(
+        a: __m256i,
+        b: __m256i,
+        b_shoup: __m256i,
+        p: __m256i,
+    ) -> __m256i {
+        let q = Self::mul_hi(a, b_shoup);
+        _mm256_sub_epi64(Self::mul_lo(a, b), Self::mul_lo(q, p))
+    }
+}
+
+/// Eight 64-bit lanes, using AVX-512F and AVX-512DQ.
+pub(super) struct Avx512;
+
+impl Avx512 {
+    pub(super) const LANES: usize = 8;
+
+    #[inline]
+    #[target_feature(enable = "avx512f,avx512dq")]
+    pub(super) unsafe fn load(ptr: *const u64) -> __m512i {
+        _mm512_loadu_si512(ptr.cast())
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx512f,avx512dq")]
+    pub(super) unsafe fn store(ptr: *mut u64, a: __m512i) {
+        _mm512_storeu_si512(ptr.cast(), a)
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx512f,avx512dq")]
+    pub(super) unsafe fn splat(a: u64) -> __m512i {
+        _mm512_set1_epi64(a as i64)
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx512f,avx512dq")]
+    pub(super) unsafe fn add(a: __m512i, b: __m512i) -> __m512i {
+        _mm512_add_epi64(a, b)
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx512f,avx512dq")]
+    pub(super) unsafe fn sub(a: __m512i, b: __m512i) -> __m512i {
+        _mm512_sub_epi64(a, b)
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx512f,avx512dq")]
+    pub(super) unsafe fn or(a: __m512i, b: __m512i) -> __m512i {
+        _mm512_or_si512(a, b)
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx512f,avx512dq")]
+    pub(super) unsafe fn shl(a: __m512i, n: u32) -> __m512i {
+        _mm512_sll_epi64(a, _mm_cvtsi32_si128(n as i32))
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx512f,avx512dq")]
+    pub(super) unsafe fn shr(a: __m512i, n: u32) -> __m512i {
+        _mm512_srl_epi64(a, _mm_cvtsi32_si128(n as i32))
+    }
+
+    /// Returns all ones in the lanes where a < b, and zero elsewhere.
+    #[inline]
+    #[target_feature(enable = "avx512f,avx512dq")]
+    pub(super) unsafe fn lt_mask(a: __m512i, b: __m512i) -> __m512i {
+        _mm512_movm_epi64(_mm512_cmplt_epu64_mask(a, b))
+    }
+
+    /// Subtracts m in the lanes where a >= m, in constant time.
+    #[inline]
+    #[target_feature(enable = "avx512f,avx512dq")]
+    pub(super) unsafe fn reduce1(a: __m512i, m: __m512i) -> __m512i {
+        // When a < m, a - m wraps around and is larger than a.
+        _mm512_min_epu64(a, _mm512_sub_epi64(a, m))
+    }
+
+    /// Returns the high and low 64 bits of the products.
+    #[inline]
+    #[target_feature(enable = "avx512f,avx512dq")]
+    pub(super) unsafe fn mul_wide(a: __m512i, b: __m512i) -> (__m512i, __m512i) {
+        let mask = _mm512_set1_epi64(0xffffffff);
+        let a_hi = _mm512_srli_epi64::<32>(a);
+        let b_hi = _mm512_srli_epi64::<32>(b);
+        let lo_lo = _mm512_mul_epu32(a, b);
+        let lo_hi = _mm512_mul_epu32(a, b_hi);
+        let hi_lo = _mm512_mul_epu32(a_hi, b);
+        let hi_hi = _mm512_mul_epu32(a_hi, b_hi);
+        let mid = _mm512_add_epi64(
+            _mm512_add_epi64(
+                _mm512_srli_epi64::<32>(lo_lo),
+                _mm512_and_si512(lo_hi, mask),
+            ),
+            _mm512_and_si512(hi_lo, mask),
+        );
+        let lo = _mm512_mullo_epi64(a, b);
+        let hi = _mm512_add_epi64(
+            _mm512_add_epi64(hi_hi, _mm512_srli_epi64::<32>(lo_hi)),
+            _mm512_add_epi64(_mm512_srli_epi64::<32>(hi_lo), _mm512_srli_epi64::<32>(mid)),
+        );
+        (hi, lo)
+    }
+
+    /// Returns the high 64 bits of the products.
+    #[inline]
+    #[target_feature(enable = "avx512f,avx512dq")]
+    pub(super) unsafe fn mul_hi(a: __m512i, b: __m512i) -> __m512i {
+        Self::mul_wide(a, b).0
+    }
+
+    /// Returns the low 64 bits of the products.
+    #[inline]
+    #[target_feature(enable = "avx512f,avx512dq")]
+    pub(super) unsafe fn mul_lo(a: __m512i, b: __m512i) -> __m512i {
+        _mm512_mullo_epi64(a, b)
+    }
+
+    /// Returns the Shoup constant used by [`Self::lazy_mul_shoup`].
+    #[inline]
+    pub(super) const fn shoup(b_shoup: u64) -> u64 {
+        b_shoup
+    }
+
+    /// Lazy Shoup multiplication, with output in [0, 2 * p).
+    #[inline]
+    #[target_feature(enable = "avx512f,avx512dq")]
+    pub(super) unsafe fn lazy_mul_shoup(
+        a: __m512i,
+        b: __m512i,
+        b_shoup: __m512i,
+        p: __m512i,
+    ) -> __m512i {
+        let q = Self::mul_hi(a, b_shoup);
+        _mm512_sub_epi64(_mm512_mullo_epi64(a, b), _mm512_mullo_epi64(q, p))
+    }
+}
+
+/// Shoup multiplications of [`Avx512`] vectors using AVX-512 IFMA, which
+/// require a modulus of at most 50 bits and inputs smaller than 2^52.
+pub(super) struct Avx512Ifma;
+
+impl Avx512Ifma {
+    /// Returns the Shoup constant used by [`Self::lazy_mul_shoup`], i.e.,
+    /// `floor(b * 2^52 / p)` instead of `floor(b * 2^64 / p)`.
+    #[inline]
+    pub(super) const fn shoup(b_shoup: u64) -> u64 {
+        b_shoup >> 12
+    }
+
+    /// Lazy Shoup multiplication, with output in [0, 2 * p).
+    #[inline]
+    #[target_feature(enable = "avx512f,avx512dq,avx512ifma")]
+    pub(super) unsafe fn lazy_mul_shoup(
+        a: __m512i,
+        b: __m512i,
+        b_shoup: __m512i,
+        p: __m512i,
+    ) -> __m512i {
+        let zero = _mm512_setzero_si512();
+        let q = _mm512_madd52hi_epu64(zero, a, b_shoup);
+        let r = _mm512_sub_epi64(
+            _mm512_madd52lo_epu64(zero, a, b),
+            _mm512_madd52lo_epu64(zero, q, p),
+        );
+        // The result is smaller than 2^52, so it is determined by its low 52 bits.
+        _mm512_and_si512(r, _mm512_set1_epi64((1 << 52) - 1))
+    }
+}
+
+pub(super) mod avx2 {
+    use super::Avx2;
+
+    vector_kernels!("avx2", Avx2);
+    ntt_kernels!("avx2", Avx2, Avx2);
+}
+
+pub(super) mod avx512 {
+    use super::Avx512;
+
+    vector_kernels!("avx512f,avx512dq", Avx512);
+    ntt_kernels!("avx512f,avx512dq", Avx512, Avx512);
+}
+
+pub(super) mod avx512_ifma {
+    use super::{Avx512, Avx512Ifma};
+
+    ntt_kernels!("avx512f,avx512dq,avx512ifma", Avx512, Avx512Ifma);
+}
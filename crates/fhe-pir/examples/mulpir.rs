@@ -0,0 +1,197 @@
+// Implementation of MulPIR using the `fhe-pir` crate.
+//
+// MulPIR is a Private Information Retrieval scheme that enables a client to
+// retrieve a row from a database without revealing the index to the server.
+// MulPIR is described in <https://eprint.iacr.org/2019/1483>.
+// We use the same parameters as in the paper to enable an apple-to-apple
+// comparison.
+
+mod util;
+
+use console::style;
+use fhe_pir::{
+    Client, EncodedDatabase, PirParametersBuilder, Protocol, Query, Response, Server, ServerKeys,
+};
+use fhe_traits::{DeserializeParametrized, Serialize};
+use indicatif::HumanBytes;
+use rand::{rngs::OsRng, thread_rng, RngCore};
+use std::{env, error::Error, process::exit};
+use util::{
+    generate_database,
+    timeit::{timeit, timeit_n},
+};
+
+fn print_notice_and_exit(max_element_size: usize, error: Option<String>) {
+    println!(
+        "{} MulPIR with fhe.rs",
+        style("  overview:").magenta().bold()
+    );
+    println!(
+        "{} mulpir [-h] [--help] [--database_size=<value>] [--element_size=<value>]",
+        style("     usage:").magenta().bold()
+    );
+    println!(
+        "{} {} must be at least 1, and {} must be between 1 and {}",
+        style("constraints:").magenta().bold(),
+        style("database_size").blue(),
+        style("element_size").blue(),
+        max_element_size
+    );
+    if let Some(error) = error {
+        println!("{} {}", style("     error:").red().bold(), error);
+    }
+    exit(0);
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let protocol = Protocol::MulPir;
+    let bfv_parameters = protocol.default_bfv_parameters()?;
+
+    // Compute what is the maximum byte-length of an element to fit within one
+    // ciphertext. Each coefficient of the ciphertext polynomial can contain
+    // floor(log2(plaintext_modulus)) bits.
+    let max_element_size =
+        ((bfv_parameters.plaintext().ilog2() as usize) * bfv_parameters.degree()) / 8;
+
+    // This executable is a command line tool which enables to specify different
+    // database and element sizes.
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    // Print the help if requested.
+    if args.contains(&"-h".to_string()) || args.contains(&"--help".to_string()) {
+        print_notice_and_exit(max_element_size, None)
+    }
+
+    // Use the default values from <https://eprint.iacr.org/2019/1483.pdf>.
+    let mut database_size = 1 << 20;
+    let mut elements_size = 288;
+
+    // Update the database size and/or element size depending on the arguments
+    // provided.
+    for arg in &args {
+        if arg.starts_with("--database_size") {
+            let a: Vec<&str> = arg.rsplit('=').collect();
+            if a.len() != 2 || a[0].parse::<usize>().is_err() {
+                print_notice_and_exit(
+                    max_element_size,
+                    Some("Invalid `--database_size` command".to_string()),
+                )
+            } else {
+                database_size = a[0].parse::<usize>()?
+            }
+        } else if arg.starts_with("--element_size") {
+            let a: Vec<&str> = arg.rsplit('=').collect();
+            if a.len() != 2 || a[0].parse::<usize>().is_err() {
+                print_notice_and_exit(
+                    max_element_size,
+                    Some("Invalid `--element_size` command".to_string()),
+                )
+            } else {
+                elements_size = a[0].parse::<usize>()?
+            }
+        } else {
+            print_notice_and_exit(
+                max_element_size,
+                Some(format!("Unrecognized command: {arg}")),
+            )
+        }
+    }
+
+    if elements_size > max_element_size || elements_size == 0 || database_size == 0 {
+        print_notice_and_exit(
+            max_element_size,
+            Some("Element or database sizes out of bound".to_string()),
+        )
+    }
+
+    // The parameters are within bound, let's go! Let's first display some
+    // information about the database.
+    println!("# MulPIR with fhe.rs");
+    println!(
+        "database of {}",
+        HumanBytes((database_size * elements_size) as u64)
+    );
+    println!("\tdatabase_size = {database_size}");
+    println!("\telements_size = {elements_size}");
+
+    // Generation of a random database.
+    let database = timeit!("Database generation", {
+        generate_database(database_size, elements_size)
+    });
+
+    // Let's generate the PIR parameters structure.
+    let par = timeit!(
+        "Parameters generation",
+        PirParametersBuilder::new(protocol)
+            .set_bfv_parameters(&bfv_parameters)
+            .set_database_size(database_size)
+            .set_element_size(elements_size)
+            .build_arc()?
+    );
+    let (dim1, dim2) = par.dimensions();
+    println!("number_rows = {}", par.number_rows());
+    println!(
+        "number_elements_per_plaintext = {}",
+        par.elements_per_plaintext()
+    );
+    println!("dimensions = {dim1} {dim2}");
+
+    // Proprocess the database on the server side: the database is reshaped so
+    // as to pack as many values as possible in every plaintext, and each
+    // plaintext is encoded as a polynomial in Ntt representation.
+    let server = timeit!("Database preprocessing", {
+        Server::new(EncodedDatabase::new(&database, &par)?)
+    });
+
+    // Client setup: the client generates a secret key, and the keys needed by
+    // the server: an evaluation key which enables to obliviously expand the
+    // query into (dim1 + dim2) ciphertexts, and a relinearization key.
+    let (client, keys_serialized) = timeit!("Client setup", {
+        let client = Client::new(&par, &mut OsRng);
+        let keys_serialized = client.server_keys(&mut thread_rng())?.to_bytes();
+        (client, keys_serialized)
+    });
+    println!(
+        "📄 Server keys: {}",
+        HumanBytes(keys_serialized.len() as u64)
+    );
+
+    // Server setup: the server receives the keys and deserializes them.
+    let keys = timeit!(
+        "Server setup",
+        ServerKeys::from_bytes(&keys_serialized, &par)?
+    );
+
+    // Client query: the client encrypts a selection vector with non-zero values
+    // at the row and at the column of the plaintext which contains the
+    // `index`-th element of the database.
+    let index = (thread_rng().next_u64() as usize) % database_size;
+    let query = timeit!("Client query", {
+        client.query(index, &mut thread_rng())?.to_bytes()
+    });
+    println!("📄 Query: {}", HumanBytes(query.len() as u64));
+
+    // Server response: The server receives the query, and after deserializing it,
+    // expands it into `dim1 + dim2` ciphertexts, computes the inner product of
+    // the first `dim1` ciphertexts with the columns of the database viewed as a
+    // dim1 * dim2 matrix, and multiplies the results with the last `dim2`
+    // ciphertexts. The response is relinearized and switched to the last level
+    // to optimize communication.
+    // The operation is done `5` times to compute an average response time.
+    let response = timeit_n!("Server response", 5, {
+        let query = Query::from_bytes(&query, &par)?;
+        server.respond(&keys, &query)?.to_bytes()
+    });
+    println!("📄 Response: {}", HumanBytes(response.len() as u64));
+
+    // Client processing: Upon reception of the response, the client decrypts
+    // it, and extracts the bytes of the element from the decrypted plaintext.
+    let answer = timeit!("Client answer", {
+        let response = Response::from_bytes(&response, &par)?;
+        client.decode_response(index, &response)?
+    });
+
+    assert_eq!(&database[index], &answer);
+
+    Ok(())
+}
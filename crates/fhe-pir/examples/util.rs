@@ -1,9 +1,6 @@
 //! Utility functions for the examples
 
-use fhe::bfv;
-use fhe_traits::FheEncoder;
-use fhe_util::transcode_from_bytes;
-use std::{cmp::min, fmt, sync::Arc, time::Duration};
+use std::{cmp::min, fmt, time::Duration};
 
 /// Macros to time code and display a human-readable duration.
 pub mod timeit {
@@ -74,54 +71,5 @@ pub fn generate_database(database_size: usize, elements_size: usize) -> Vec<Vec<
     database
 }
 
-#[allow(dead_code)]
-pub fn number_elements_per_plaintext(
-    degree: usize,
-    plaintext_nbits: usize,
-    elements_size: usize,
-) -> usize {
-    (plaintext_nbits * degree) / (elements_size * 8)
-}
-
-#[allow(dead_code)]
-pub fn encode_database(
-    database: &Vec<Vec<u8>>,
-    par: Arc<bfv::BfvParameters>,
-    level: usize,
-) -> (Vec<bfv::Plaintext>, (usize, usize)) {
-    assert!(!database.is_empty());
-
-    let elements_size = database[0].len();
-    let plaintext_nbits = par.plaintext().ilog2() as usize;
-    let number_elements_per_plaintext =
-        number_elements_per_plaintext(par.degree(), plaintext_nbits, elements_size);
-    let number_rows =
-        (database.len() + number_elements_per_plaintext - 1) / number_elements_per_plaintext;
-    println!("number_rows = {number_rows}");
-    println!("number_elements_per_plaintext = {number_elements_per_plaintext}");
-    let dimension_1 = (number_rows as f64).sqrt().ceil() as usize;
-    let dimension_2 = (number_rows + dimension_1 - 1) / dimension_1;
-    println!("dimensions = {dimension_1} {dimension_2}");
-    println!("dimension = {}", dimension_1 * dimension_2);
-    let mut preprocessed_database =
-        vec![
-            bfv::Plaintext::zero(bfv::Encoding::poly_at_level(level), &par).unwrap();
-            dimension_1 * dimension_2
-        ];
-    (0..number_rows).for_each(|i| {
-        let mut serialized_plaintext = vec![0u8; number_elements_per_plaintext * elements_size];
-        for j in 0..number_elements_per_plaintext {
-            if let Some(pt) = database.get(j + i * number_elements_per_plaintext) {
-                serialized_plaintext[j * elements_size..(j + 1) * elements_size].copy_from_slice(pt)
-            }
-        }
-        let pt_values = transcode_from_bytes(&serialized_plaintext, plaintext_nbits);
-        preprocessed_database[i] =
-            bfv::Plaintext::try_encode(&pt_values, bfv::Encoding::poly_at_level(level), &par)
-                .unwrap();
-    });
-    (preprocessed_database, (dimension_1, dimension_2))
-}
-
 #[allow(dead_code)]
 fn main() {}
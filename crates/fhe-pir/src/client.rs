@@ -0,0 +1,177 @@
+//! Client of the Private Information Retrieval protocols.
+
+use crate::{Error, PirParameters, Protocol, Query, Response, Result, ServerKeys};
+use fhe::bfv::{
+    Ciphertext, Encoding, EvaluationKeyBuilder, Plaintext, RelinearizationKey, SecretKey,
+};
+use fhe_math::rq::{traits::TryConvertFrom, Context, Poly, Representation};
+use fhe_traits::{FheDecoder, FheDecrypter, FheEncoder, FheEncrypter};
+use fhe_util::{inverse, transcode_bidirectional, transcode_to_bytes};
+use itertools::Itertools;
+use rand::{CryptoRng, RngCore};
+use std::sync::Arc;
+
+/// A client retrieving elements of a database without revealing their
+/// indices to the server.
+pub struct Client {
+    par: Arc<PirParameters>,
+    sk: SecretKey,
+}
+
+impl Client {
+    /// Generate a new client with a fresh secret key.
+    pub fn new<R: RngCore + CryptoRng>(par: &Arc<PirParameters>, rng: &mut R) -> Self {
+        Self {
+            par: par.clone(),
+            sk: SecretKey::random(&par.bfv, rng),
+        }
+    }
+
+    /// Returns the parameters of the client.
+    pub fn parameters(&self) -> &Arc<PirParameters> {
+        &self.par
+    }
+
+    /// Generate the keys which the server needs to answer the queries of this
+    /// client: an evaluation key enabling to expand the queries into
+    /// `dimension_1 + dimension_2` ciphertexts and, for MulPIR, a
+    /// relinearization key.
+    pub fn server_keys<R: RngCore + CryptoRng>(&self, rng: &mut R) -> Result<ServerKeys> {
+        let ek = EvaluationKeyBuilder::new_leveled(&self.sk, 1, 0)?
+            .enable_expansion(self.par.expansion_level())?
+            .build(rng)?;
+        let rk = match self.par.protocol {
+            Protocol::SealPir => None,
+            Protocol::MulPir => Some(RelinearizationKey::new_leveled(&self.sk, 1, 1, rng)?),
+        };
+        Ok(ServerKeys { ek, rk })
+    }
+
+    /// Generate a query for the element at `index` in the database.
+    ///
+    /// The query encrypts, at level 1, a selection vector with zeros
+    /// everywhere, except at the indices `i` and `dimension_1 + j` where the
+    /// element is stored in the `i * dimension_2 + j`-th plaintext, which are
+    /// set to the inverse of `2^level` modulo the plaintext modulus to
+    /// compensate for the expansion.
+    pub fn query<R: RngCore + CryptoRng>(&self, index: usize, rng: &mut R) -> Result<Query> {
+        if index >= self.par.database_size {
+            return Err(Error::IndexOutOfBounds(index, self.par.database_size));
+        }
+
+        let (dimension_1, dimension_2) = self.par.dimensions;
+        let row = index / self.par.elements_per_plaintext();
+        let inv = inverse(1 << self.par.expansion_level(), self.par.bfv.plaintext()).unwrap();
+        let mut pt = vec![0u64; dimension_1 + dimension_2];
+        pt[row / dimension_2] = inv;
+        pt[dimension_1 + (row % dimension_2)] = inv;
+
+        let query_pt = Plaintext::try_encode(&pt, Encoding::poly_at_level(1), &self.par.bfv)?;
+        Ok(Query(self.sk.try_encrypt(&query_pt, rng)?))
+    }
+
+    /// Decode the element at `index` from the response of the server to the
+    /// query for this index.
+    pub fn decode_response(&self, index: usize, response: &Response) -> Result<Vec<u8>> {
+        if index >= self.par.database_size {
+            return Err(Error::IndexOutOfBounds(index, self.par.database_size));
+        }
+        if response.0.len() != self.par.response_size() {
+            return Err(Error::ResponseSizeMismatch {
+                expected: self.par.response_size(),
+                found: response.0.len(),
+            });
+        }
+
+        let ct = match self.par.protocol {
+            Protocol::SealPir => self.decrypt_folded_ciphertext(&response.0)?,
+            Protocol::MulPir => response.0[0].clone(),
+        };
+        let pt = self.sk.try_decrypt(&ct)?;
+        let pt = Vec::<u64>::try_decode(&pt, Encoding::poly_at_level(self.par.bfv.max_level()))?;
+        let plaintext = transcode_to_bytes(&pt, self.par.plaintext_nbits());
+
+        let element_size = self.par.element_size;
+        let offset = index % self.par.elements_per_plaintext();
+        Ok(plaintext[offset * element_size..(offset + 1) * element_size].to_vec())
+    }
+
+    /// In SealPIR, the response encrypts the coefficients of a ciphertext at
+    /// the last level, which this function decrypts and reassembles.
+    fn decrypt_folded_ciphertext(&self, cts: &[Ciphertext]) -> Result<Ciphertext> {
+        let encoding = Encoding::poly_at_level(self.par.bfv.max_level());
+        let decrypted = cts
+            .iter()
+            .map(|ct| {
+                let pt = self.sk.try_decrypt(ct)?;
+                Vec::<u64>::try_decode(&pt, encoding.clone())
+            })
+            .flatten_ok()
+            .collect::<fhe::Result<Vec<u64>>>()?;
+
+        let degree = self.par.bfv.degree();
+        let ncoefficients = self.par.coefficients_per_ciphertext_part();
+        let ctx = Arc::new(Context::new(&self.par.bfv.moduli()[..1], degree)?);
+        let c = decrypted
+            .chunks_exact(ncoefficients)
+            .take(2)
+            .map(|coefficients| {
+                let mut poly = transcode_bidirectional(
+                    coefficients,
+                    self.par.plaintext_nbits(),
+                    self.par.last_modulus_nbits(),
+                );
+                poly.truncate(degree);
+                Poly::try_convert_from(poly, &ctx, true, Representation::Ntt)
+            })
+            .collect::<fhe_math::Result<Vec<Poly>>>()?;
+        Ok(Ciphertext::new(c, &self.par.bfv)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Client;
+    use crate::{test_utils::test_parameters, Error, Protocol, Response};
+    use rand::thread_rng;
+    use std::error::Error as StdError;
+
+    #[test]
+    fn server_keys() -> Result<(), Box<dyn StdError>> {
+        let mut rng = thread_rng();
+        let par = test_parameters(Protocol::SealPir, 100, 100)?;
+        let keys = Client::new(&par, &mut rng).server_keys(&mut rng)?;
+        assert!(keys.ek.supports_expansion(par.expansion_level()));
+        assert!(keys.rk.is_none());
+
+        let par = test_parameters(Protocol::MulPir, 100, 100)?;
+        let keys = Client::new(&par, &mut rng).server_keys(&mut rng)?;
+        assert!(keys.ek.supports_expansion(par.expansion_level()));
+        assert!(keys.rk.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn errors() -> Result<(), Box<dyn StdError>> {
+        let mut rng = thread_rng();
+        let par = test_parameters(Protocol::SealPir, 100, 100)?;
+        let client = Client::new(&par, &mut rng);
+
+        assert_eq!(
+            client.query(100, &mut rng).err(),
+            Some(Error::IndexOutOfBounds(100, 100))
+        );
+        assert_eq!(
+            client.decode_response(100, &Response(vec![])).err(),
+            Some(Error::IndexOutOfBounds(100, 100))
+        );
+        assert_eq!(
+            client.decode_response(0, &Response(vec![])).err(),
+            Some(Error::ResponseSizeMismatch {
+                expected: par.response_size(),
+                found: 0
+            })
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,160 @@
+//! Database encoded as BFV plaintexts.
+
+use crate::{Error, PirParameters, Result};
+use fhe::bfv::{Encoding, Plaintext, PlaintextVec};
+use fhe_traits::FheEncoder;
+use fhe_util::transcode_from_bytes;
+use std::{
+    io::{Read, Write},
+    sync::Arc,
+};
+
+/// A database preprocessed by the server.
+///
+/// The database is reshaped so as to pack as many elements as possible in
+/// every plaintext, and the `dimension_1 * dimension_2` plaintexts are encoded
+/// at level 1, with their polynomials in Ntt representation. The encoded
+/// database can be written once using [`EncodedDatabase::write_to`], and read
+/// back without re-encoding it using [`EncodedDatabase::read_from`].
+pub struct EncodedDatabase {
+    pub(crate) par: Arc<PirParameters>,
+    pub(crate) plaintexts: PlaintextVec,
+}
+
+impl EncodedDatabase {
+    /// Encode a database of `par.database_size()` elements of
+    /// `par.element_size()` bytes.
+    pub fn new<T: AsRef<[u8]>>(database: &[T], par: &Arc<PirParameters>) -> Result<Self> {
+        if database.len() != par.database_size() {
+            return Err(Error::DatabaseSizeMismatch {
+                expected: par.database_size(),
+                found: database.len(),
+            });
+        }
+        if let Some(element) = database
+            .iter()
+            .find(|element| element.as_ref().len() != par.element_size())
+        {
+            return Err(Error::ElementSizeMismatch {
+                expected: par.element_size(),
+                found: element.as_ref().len(),
+            });
+        }
+
+        let (dimension_1, dimension_2) = par.dimensions();
+        let element_size = par.element_size();
+        let elements_per_plaintext = par.elements_per_plaintext();
+        let encoding = Encoding::poly_at_level(1);
+
+        let mut plaintexts = Vec::with_capacity(dimension_1 * dimension_2);
+        for row in database.chunks(elements_per_plaintext) {
+            let mut serialized_plaintext = vec![0u8; elements_per_plaintext * element_size];
+            for (chunk, element) in serialized_plaintext.chunks_mut(element_size).zip(row) {
+                chunk.copy_from_slice(element.as_ref())
+            }
+            let pt_values = transcode_from_bytes(&serialized_plaintext, par.plaintext_nbits());
+            plaintexts.push(Plaintext::try_encode(
+                &pt_values,
+                encoding.clone(),
+                &par.bfv,
+            )?);
+        }
+        plaintexts.resize(
+            dimension_1 * dimension_2,
+            Plaintext::zero(encoding, &par.bfv)?,
+        );
+
+        Ok(Self {
+            par: par.clone(),
+            plaintexts: PlaintextVec(plaintexts),
+        })
+    }
+
+    /// Returns the parameters of the database.
+    pub fn parameters(&self) -> &Arc<PirParameters> {
+        &self.par
+    }
+
+    /// Write the encoded database to a writer, in the format of
+    /// [`PlaintextVec::write_to`].
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<()> {
+        Ok(self.plaintexts.write_to(writer)?)
+    }
+
+    /// Read an encoded database written by [`EncodedDatabase::write_to`] with
+    /// the same parameters. The polynomials in Ntt representation are read as
+    /// written, without computing any Ntt.
+    pub fn read_from<R: Read>(reader: R, par: &Arc<PirParameters>) -> Result<Self> {
+        let plaintexts = PlaintextVec::read_from(reader, &par.bfv)?;
+        let (dimension_1, dimension_2) = par.dimensions();
+        if plaintexts.0.len() != dimension_1 * dimension_2 {
+            return Err(Error::SerializationError);
+        }
+        if plaintexts.0.iter().any(|pt| pt.level() != 1) {
+            return Err(Error::SerializationError);
+        }
+        Ok(Self {
+            par: par.clone(),
+            plaintexts,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EncodedDatabase;
+    use crate::{
+        test_utils::{generate_database, test_parameters},
+        Error, Protocol,
+    };
+    use std::error::Error as StdError;
+
+    #[test]
+    fn encode() -> Result<(), Box<dyn StdError>> {
+        let par = test_parameters(Protocol::SealPir, 100, 100)?;
+        let database = generate_database(100, 100);
+
+        let encoded = EncodedDatabase::new(&database, &par)?;
+        assert_eq!(encoded.plaintexts.0.len(), 20);
+        assert!(encoded.plaintexts.0.iter().all(|pt| pt.level() == 1));
+
+        assert_eq!(
+            EncodedDatabase::new(&database[1..], &par).err(),
+            Some(Error::DatabaseSizeMismatch {
+                expected: 100,
+                found: 99
+            })
+        );
+        let mut database = database;
+        database[3].push(0);
+        assert_eq!(
+            EncodedDatabase::new(&database, &par).err(),
+            Some(Error::ElementSizeMismatch {
+                expected: 100,
+                found: 101
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn write_read() -> Result<(), Box<dyn StdError>> {
+        let par = test_parameters(Protocol::SealPir, 100, 100)?;
+        let encoded = EncodedDatabase::new(&generate_database(100, 100), &par)?;
+
+        let mut bytes = vec![];
+        encoded.write_to(&mut bytes)?;
+        let read = EncodedDatabase::read_from(bytes.as_slice(), &par)?;
+        assert_eq!(read.plaintexts.0, encoded.plaintexts.0);
+
+        // The database must have been encoded with the same dimensions.
+        let other_par = test_parameters(Protocol::SealPir, 200, 100)?;
+        assert_eq!(
+            EncodedDatabase::read_from(bytes.as_slice(), &other_par).err(),
+            Some(Error::SerializationError)
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,162 @@
+use thiserror::Error;
+
+/// The Result type for this library.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Enum encapsulating all the possible errors from this library.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    /// Indicates that an error from the underlying homomorphic encryption
+    /// library was encountered.
+    #[error("{0}")]
+    FheError(fhe::Error),
+
+    /// Indicates a serialization error.
+    #[error("Serialization error")]
+    SerializationError,
+
+    /// Indicates that the parameters are invalid.
+    #[error("Invalid parameters: {0}")]
+    InvalidParameters(String),
+
+    /// Indicates that the database does not have the expected number of
+    /// elements.
+    #[error("Database size mismatch: found {found}, expected {expected}")]
+    DatabaseSizeMismatch {
+        /// The expected number of elements.
+        expected: usize,
+        /// The number of elements of the database.
+        found: usize,
+    },
+
+    /// Indicates that an element does not have the expected size.
+    #[error("Element size mismatch: found {found}, expected {expected}")]
+    ElementSizeMismatch {
+        /// The expected size, in bytes.
+        expected: usize,
+        /// The size of the element, in bytes.
+        found: usize,
+    },
+
+    /// Indicates that a response does not have the expected number of
+    /// ciphertexts.
+    #[error("Response size mismatch: found {found}, expected {expected}")]
+    ResponseSizeMismatch {
+        /// The expected number of ciphertexts.
+        expected: usize,
+        /// The number of ciphertexts of the response.
+        found: usize,
+    },
+
+    /// Indicates that a query does not have the expected number of
+    /// ciphertexts.
+    #[error("Query size mismatch: found {found}, expected {expected}")]
+    QuerySizeMismatch {
+        /// The expected number of ciphertexts.
+        expected: usize,
+        /// The number of ciphertexts of the query.
+        found: usize,
+    },
+
+    /// Indicates that an index is out of the bounds of the database.
+    #[error("Index out of bounds: {0} is not smaller than {1}")]
+    IndexOutOfBounds(usize, usize),
+
+    /// Indicates that there are more key-value pairs than the capacity of the
+    /// keyword database.
+    #[error("Too many key-value pairs: found {found}, capacity {capacity}")]
+    TooManyPairs {
+        /// The capacity of the keyword database.
+        capacity: usize,
+        /// The number of key-value pairs.
+        found: usize,
+    },
+
+    /// Indicates that a key appears several times in a keyword database.
+    #[error("Duplicate key")]
+    DuplicateKey,
+
+    /// Indicates that the key-value pairs could not be inserted in a cuckoo
+    /// hash table.
+    #[error("Cuckoo hashing failed")]
+    CuckooHashingFailed,
+}
+
+impl From<fhe::Error> for Error {
+    fn from(e: fhe::Error) -> Self {
+        Error::FheError(e)
+    }
+}
+
+impl From<fhe_math::Error> for Error {
+    fn from(e: fhe_math::Error) -> Self {
+        Error::FheError(fhe::Error::MathError(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Error;
+
+    #[test]
+    fn error_strings() {
+        assert_eq!(
+            Error::FheError(fhe::Error::SerializationError).to_string(),
+            fhe::Error::SerializationError.to_string()
+        );
+        assert_eq!(Error::SerializationError.to_string(), "Serialization error");
+        assert_eq!(
+            Error::InvalidParameters("test".to_string()).to_string(),
+            "Invalid parameters: test"
+        );
+        assert_eq!(
+            Error::DatabaseSizeMismatch {
+                expected: 2,
+                found: 1
+            }
+            .to_string(),
+            "Database size mismatch: found 1, expected 2"
+        );
+        assert_eq!(
+            Error::ElementSizeMismatch {
+                expected: 2,
+                found: 1
+            }
+            .to_string(),
+            "Element size mismatch: found 1, expected 2"
+        );
+        assert_eq!(
+            Error::ResponseSizeMismatch {
+                expected: 2,
+                found: 1
+            }
+            .to_string(),
+            "Response size mismatch: found 1, expected 2"
+        );
+        assert_eq!(
+            Error::QuerySizeMismatch {
+                expected: 2,
+                found: 1
+            }
+            .to_string(),
+            "Query size mismatch: found 1, expected 2"
+        );
+        assert_eq!(
+            Error::IndexOutOfBounds(3, 2).to_string(),
+            "Index out of bounds: 3 is not smaller than 2"
+        );
+        assert_eq!(
+            Error::TooManyPairs {
+                capacity: 2,
+                found: 3
+            }
+            .to_string(),
+            "Too many key-value pairs: found 3, capacity 2"
+        );
+        assert_eq!(Error::DuplicateKey.to_string(), "Duplicate key");
+        assert_eq!(
+            Error::CuckooHashingFailed.to_string(),
+            "Cuckoo hashing failed"
+        );
+    }
+}
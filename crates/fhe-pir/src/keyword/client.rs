@@ -0,0 +1,102 @@
+//! Client of the keyword Private Information Retrieval protocol.
+
+use super::parameters::TAG_SIZE;
+use crate::{
+    Client, Error, KeywordPirParameters, KeywordQuery, KeywordResponse, Result, ServerKeys,
+};
+use rand::{CryptoRng, RngCore};
+use std::sync::Arc;
+
+/// A client retrieving the values associated with keys in a key-value
+/// database, without revealing the keys to the server.
+pub struct KeywordClient {
+    par: Arc<KeywordPirParameters>,
+    client: Client,
+}
+
+impl KeywordClient {
+    /// Generate a new client with a fresh secret key.
+    ///
+    /// The parameters must be the ones of the database, as returned by
+    /// [`KeywordDatabase::parameters`](crate::KeywordDatabase::parameters).
+    pub fn new<R: RngCore + CryptoRng>(par: &Arc<KeywordPirParameters>, rng: &mut R) -> Self {
+        Self {
+            par: par.clone(),
+            client: Client::new(&par.pir, rng),
+        }
+    }
+
+    /// Returns the parameters of the client.
+    pub fn parameters(&self) -> &Arc<KeywordPirParameters> {
+        &self.par
+    }
+
+    /// Generate the keys which the server needs to answer the queries of this
+    /// client, see [`Client::server_keys`].
+    pub fn server_keys<R: RngCore + CryptoRng>(&self, rng: &mut R) -> Result<ServerKeys> {
+        self.client.server_keys(rng)
+    }
+
+    /// Generate a query for the value associated with `key`, made of an index
+    /// PIR query for every bucket where the pair may be stored.
+    pub fn query<R: RngCore + CryptoRng>(&self, key: &[u8], rng: &mut R) -> Result<KeywordQuery> {
+        let queries = self
+            .par
+            .buckets(key)
+            .into_iter()
+            .map(|bucket| self.client.query(bucket, rng))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(KeywordQuery(queries))
+    }
+
+    /// Decode the value associated with `key` from the response of the server
+    /// to the query for this key, or `None` if the key is not in the database.
+    pub fn decode_response(
+        &self,
+        key: &[u8],
+        response: &KeywordResponse,
+    ) -> Result<Option<Vec<u8>>> {
+        if response.0.len() != self.par.number_hash_functions {
+            return Err(Error::ResponseSizeMismatch {
+                expected: self.par.number_hash_functions,
+                found: response.0.len(),
+            });
+        }
+
+        let tag = self.par.tag(key);
+        for (bucket, response) in self.par.buckets(key).into_iter().zip(&response.0) {
+            let element = self.client.decode_response(bucket, response)?;
+            if element[..TAG_SIZE] == tag {
+                return Ok(Some(element[TAG_SIZE..].to_vec()));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KeywordClient;
+    use crate::{test_utils::test_keyword_parameters, Error, KeywordResponse, Protocol};
+    use rand::thread_rng;
+    use std::error::Error as StdError;
+
+    #[test]
+    fn query() -> Result<(), Box<dyn StdError>> {
+        let mut rng = thread_rng();
+        let par = test_keyword_parameters(Protocol::SealPir, 100, 20)?;
+        let client = KeywordClient::new(&par, &mut rng);
+
+        assert_eq!(client.query(b"key", &mut rng)?.0.len(), 3);
+        assert_eq!(
+            client
+                .decode_response(b"key", &KeywordResponse(vec![]))
+                .err(),
+            Some(Error::ResponseSizeMismatch {
+                expected: 3,
+                found: 0
+            })
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,245 @@
+//! Key-value database stored in a cuckoo hash table and encoded as BFV
+//! plaintexts.
+
+use super::parameters::TAG_SIZE;
+use crate::{EncodedDatabase, Error, KeywordPirParameters, Result};
+use rand::{CryptoRng, Rng, RngCore};
+use std::{
+    collections::HashSet,
+    io::{Read, Write},
+    sync::Arc,
+};
+
+/// Maximum number of evictions when inserting a pair in the cuckoo hash table.
+const MAX_EVICTIONS: usize = 500;
+
+/// Maximum number of hash seeds tried when building the cuckoo hash table.
+const MAX_ATTEMPTS: usize = 8;
+
+/// A key-value database preprocessed by the server.
+///
+/// The pairs are inserted in a cuckoo hash table, whose buckets are then
+/// encoded as an [`EncodedDatabase`] of the index PIR protocol.
+pub struct KeywordDatabase {
+    pub(crate) par: Arc<KeywordPirParameters>,
+    pub(crate) database: EncodedDatabase,
+}
+
+impl KeywordDatabase {
+    /// Encode a database of at most `par.capacity()` key-value pairs with
+    /// values of `par.value_size()` bytes.
+    ///
+    /// The pairs are first inserted using the hash seed of `par`. Cuckoo
+    /// hashing fails with small probability, in which case new seeds are
+    /// drawn from `rng`: the parameters of the database, which the clients
+    /// need, are returned by [`KeywordDatabase::parameters`].
+    pub fn new<K: AsRef<[u8]>, V: AsRef<[u8]>, R: RngCore + CryptoRng>(
+        pairs: &[(K, V)],
+        par: &Arc<KeywordPirParameters>,
+        rng: &mut R,
+    ) -> Result<Self> {
+        let capacity = par.capacity();
+        if pairs.len() > capacity {
+            return Err(Error::TooManyPairs {
+                capacity,
+                found: pairs.len(),
+            });
+        }
+        if let Some((_, value)) = pairs
+            .iter()
+            .find(|(_, value)| value.as_ref().len() != par.value_size)
+        {
+            return Err(Error::ElementSizeMismatch {
+                expected: par.value_size,
+                found: value.as_ref().len(),
+            });
+        }
+        let mut keys = HashSet::with_capacity(pairs.len());
+        if !pairs.iter().all(|(key, _)| keys.insert(key.as_ref())) {
+            return Err(Error::DuplicateKey);
+        }
+
+        let mut par = par.clone();
+        let mut table = None;
+        for _ in 0..MAX_ATTEMPTS {
+            table = cuckoo_hash(&par, pairs, rng);
+            if table.is_some() {
+                break;
+            }
+            par = Arc::new(par.with_hash_seed(rng.gen()));
+        }
+        let table = table.ok_or(Error::CuckooHashingFailed)?;
+
+        let element_size = TAG_SIZE + par.value_size;
+        let buckets = table
+            .iter()
+            .map(|bucket| {
+                let mut element = vec![0u8; element_size];
+                if let Some(i) = bucket {
+                    let (key, value) = &pairs[*i];
+                    element[..TAG_SIZE].copy_from_slice(&par.tag(key.as_ref()));
+                    element[TAG_SIZE..].copy_from_slice(value.as_ref());
+                }
+                element
+            })
+            .collect::<Vec<Vec<u8>>>();
+        let database = EncodedDatabase::new(&buckets, &par.pir)?;
+
+        Ok(Self { par, database })
+    }
+
+    /// Returns the parameters of the database.
+    pub fn parameters(&self) -> &Arc<KeywordPirParameters> {
+        &self.par
+    }
+
+    /// Write the encoded database to a writer, in the format of
+    /// [`EncodedDatabase::write_to`].
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<()> {
+        self.database.write_to(writer)
+    }
+
+    /// Read an encoded database written by [`KeywordDatabase::write_to`], whose
+    /// parameters are `par`.
+    pub fn read_from<R: Read>(reader: R, par: &Arc<KeywordPirParameters>) -> Result<Self> {
+        Ok(Self {
+            par: par.clone(),
+            database: EncodedDatabase::read_from(reader, &par.pir)?,
+        })
+    }
+}
+
+/// Insert the pairs in a cuckoo hash table, and returns for every bucket the
+/// index of the pair it stores, if any. Returns `None` if a pair cannot be
+/// inserted after `MAX_EVICTIONS` evictions.
+fn cuckoo_hash<K: AsRef<[u8]>, V, R: RngCore>(
+    par: &KeywordPirParameters,
+    pairs: &[(K, V)],
+    rng: &mut R,
+) -> Option<Vec<Option<usize>>> {
+    let candidates = pairs
+        .iter()
+        .map(|(key, _)| par.buckets(key.as_ref()))
+        .collect::<Vec<Vec<usize>>>();
+    let mut table = vec![None; par.number_buckets()];
+
+    for i in 0..pairs.len() {
+        let mut current = i;
+        let mut inserted = false;
+        for _ in 0..MAX_EVICTIONS {
+            let buckets = &candidates[current];
+            if let Some(bucket) = buckets.iter().find(|bucket| table[**bucket].is_none()) {
+                table[*bucket] = Some(current);
+                inserted = true;
+                break;
+            }
+            // Evict the pair of a random candidate bucket, which is then
+            // inserted in turn.
+            let bucket = buckets[rng.gen_range(0..buckets.len())];
+            current = table[bucket].replace(current).unwrap();
+        }
+        if !inserted {
+            return None;
+        }
+    }
+
+    Some(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cuckoo_hash, KeywordDatabase};
+    use crate::{
+        test_utils::{generate_pairs, test_bfv_parameters, test_keyword_parameters},
+        Error, KeywordPirParametersBuilder, Protocol,
+    };
+    use rand::thread_rng;
+    use std::error::Error as StdError;
+
+    #[test]
+    fn cuckoo() -> Result<(), Box<dyn StdError>> {
+        let mut rng = thread_rng();
+        let par = test_keyword_parameters(Protocol::SealPir, 100, 20)?;
+        let pairs = generate_pairs(100, 20);
+
+        let table = cuckoo_hash(&par, &pairs, &mut rng).unwrap();
+        assert_eq!(table.len(), par.number_buckets());
+        assert_eq!(table.iter().flatten().count(), 100);
+        for (bucket, i) in table.iter().enumerate() {
+            if let Some(i) = i {
+                assert!(par.buckets(&pairs[*i].0).contains(&bucket));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn encode() -> Result<(), Box<dyn StdError>> {
+        let mut rng = thread_rng();
+        let par = test_keyword_parameters(Protocol::SealPir, 100, 20)?;
+        let pairs = generate_pairs(100, 20);
+
+        let database = KeywordDatabase::new(&pairs, &par, &mut rng)?;
+        assert_eq!(database.parameters().pir_parameters(), par.pir_parameters());
+        assert!(KeywordDatabase::new(&pairs[..10], &par, &mut rng).is_ok());
+
+        assert_eq!(
+            KeywordDatabase::new(&generate_pairs(101, 20), &par, &mut rng).err(),
+            Some(Error::TooManyPairs {
+                capacity: 100,
+                found: 101
+            })
+        );
+        let mut duplicate_pairs = pairs.clone();
+        duplicate_pairs[1].0 = duplicate_pairs[0].0.clone();
+        assert_eq!(
+            KeywordDatabase::new(&duplicate_pairs, &par, &mut rng).err(),
+            Some(Error::DuplicateKey)
+        );
+        let mut invalid_pairs = pairs;
+        invalid_pairs[3].1.push(0);
+        assert_eq!(
+            KeywordDatabase::new(&invalid_pairs, &par, &mut rng).err(),
+            Some(Error::ElementSizeMismatch {
+                expected: 20,
+                found: 21
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn full_capacity() -> Result<(), Box<dyn StdError>> {
+        let mut rng = thread_rng();
+        for number_hash_functions in 2..=8 {
+            for capacity in [1, 10, 1000] {
+                let par = KeywordPirParametersBuilder::new(Protocol::SealPir)
+                    .set_bfv_parameters(&test_bfv_parameters())
+                    .set_capacity(capacity)
+                    .set_value_size(20)
+                    .set_number_hash_functions(number_hash_functions)
+                    .build_arc()?;
+                KeywordDatabase::new(&generate_pairs(capacity, 20), &par, &mut rng)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn write_read() -> Result<(), Box<dyn StdError>> {
+        let mut rng = thread_rng();
+        let par = test_keyword_parameters(Protocol::MulPir, 100, 20)?;
+        let database = KeywordDatabase::new(&generate_pairs(100, 20), &par, &mut rng)?;
+
+        let mut bytes = vec![];
+        database.write_to(&mut bytes)?;
+        let read = KeywordDatabase::read_from(bytes.as_slice(), database.parameters())?;
+        assert_eq!(read.par, database.par);
+        assert_eq!(read.database.plaintexts.0, database.database.plaintexts.0);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+//! Messages exchanged between the client and the server of the keyword
+//! Private Information Retrieval protocol.
+
+use crate::{
+    proto::pir::{KeywordQuery as KeywordQueryProto, KeywordResponse as KeywordResponseProto},
+    Error, KeywordPirParameters, Query, Response, Result,
+};
+use fhe_traits::{DeserializeParametrized, FheParametrized, Serialize};
+use protobuf::Message;
+use std::sync::Arc;
+
+/// A keyword query, made of one index PIR [`Query`] for each hash function.
+#[derive(Debug, PartialEq, Eq)]
+pub struct KeywordQuery(pub(crate) Vec<Query>);
+
+/// A response of the server to a [`KeywordQuery`], made of one index PIR
+/// [`Response`] for each hash function.
+#[derive(Debug, PartialEq, Eq)]
+pub struct KeywordResponse(pub(crate) Vec<Response>);
+
+impl FheParametrized for KeywordQuery {
+    type Parameters = KeywordPirParameters;
+}
+
+impl FheParametrized for KeywordResponse {
+    type Parameters = KeywordPirParameters;
+}
+
+impl Serialize for KeywordQuery {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut proto = KeywordQueryProto::new();
+        proto.queries = self.0.iter().map(|query| query.to_bytes()).collect();
+        proto.write_to_bytes().unwrap()
+    }
+}
+
+impl DeserializeParametrized for KeywordQuery {
+    type Error = Error;
+
+    fn from_bytes(bytes: &[u8], par: &Arc<KeywordPirParameters>) -> Result<Self> {
+        let proto =
+            KeywordQueryProto::parse_from_bytes(bytes).map_err(|_| Error::SerializationError)?;
+        Ok(Self(
+            proto
+                .queries
+                .iter()
+                .map(|query| Query::from_bytes(query, &par.pir))
+                .collect::<Result<Vec<Query>>>()?,
+        ))
+    }
+}
+
+impl Serialize for KeywordResponse {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut proto = KeywordResponseProto::new();
+        proto.responses = self.0.iter().map(|response| response.to_bytes()).collect();
+        proto.write_to_bytes().unwrap()
+    }
+}
+
+impl DeserializeParametrized for KeywordResponse {
+    type Error = Error;
+
+    fn from_bytes(bytes: &[u8], par: &Arc<KeywordPirParameters>) -> Result<Self> {
+        let proto =
+            KeywordResponseProto::parse_from_bytes(bytes).map_err(|_| Error::SerializationError)?;
+        Ok(Self(
+            proto
+                .responses
+                .iter()
+                .map(|response| Response::from_bytes(response, &par.pir))
+                .collect::<Result<Vec<Response>>>()?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{KeywordQuery, KeywordResponse};
+    use crate::{
+        test_utils::{generate_pairs, test_keyword_parameters},
+        Error, KeywordClient, KeywordDatabase, KeywordServer, Protocol,
+    };
+    use fhe_traits::{DeserializeParametrized, Serialize};
+    use rand::thread_rng;
+    use std::error::Error as StdError;
+
+    #[test]
+    fn serialize() -> Result<(), Box<dyn StdError>> {
+        let mut rng = thread_rng();
+        let par = test_keyword_parameters(Protocol::SealPir, 100, 20)?;
+        let pairs = generate_pairs(100, 20);
+        let database = KeywordDatabase::new(&pairs, &par, &mut rng)?;
+        let par = database.parameters().clone();
+        let server = KeywordServer::new(database);
+        let client = KeywordClient::new(&par, &mut rng);
+        let keys = client.server_keys(&mut rng)?;
+
+        let query = client.query(&pairs[7].0, &mut rng)?;
+        let query_bytes = query.to_bytes();
+        assert_eq!(query, KeywordQuery::from_bytes(&query_bytes, &par)?);
+
+        let response = server.respond(&keys, &KeywordQuery::from_bytes(&query_bytes, &par)?)?;
+        let response_bytes = response.to_bytes();
+        assert_eq!(
+            response,
+            KeywordResponse::from_bytes(&response_bytes, &par)?
+        );
+        assert_eq!(
+            client.decode_response(
+                &pairs[7].0,
+                &KeywordResponse::from_bytes(&response_bytes, &par)?
+            )?,
+            Some(pairs[7].1.clone())
+        );
+
+        assert_eq!(
+            KeywordQuery::from_bytes(&[1, 2, 3], &par).err(),
+            Some(Error::SerializationError)
+        );
+        assert_eq!(
+            KeywordResponse::from_bytes(&[1, 2, 3], &par).err(),
+            Some(Error::SerializationError)
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,18 @@
+//! Keyword Private Information Retrieval.
+//!
+//! The key-value pairs are inserted by the server in a cuckoo hash table,
+//! which is then used as the database of an index PIR protocol. Since a pair
+//! is stored in one of the buckets its key is hashed to, the client retrieves
+//! all these buckets, and finds the value next to the tag of its key.
+
+mod client;
+mod database;
+mod messages;
+mod parameters;
+mod server;
+
+pub use client::KeywordClient;
+pub use database::KeywordDatabase;
+pub use messages::{KeywordQuery, KeywordResponse};
+pub use parameters::{KeywordPirParameters, KeywordPirParametersBuilder};
+pub use server::KeywordServer;
@@ -0,0 +1,349 @@
+//! Parameters of the keyword Private Information Retrieval protocol.
+
+use crate::{
+    proto::pir::KeywordParameters as KeywordParametersProto, Error, PirParameters,
+    PirParametersBuilder, Protocol, Result,
+};
+use fhe::bfv::BfvParameters;
+use fhe_traits::{Deserialize, FheParameters, Serialize};
+use fhe_util::div_ceil;
+use protobuf::Message;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+/// Size, in bytes, of the tags identifying the keys in the buckets.
+pub(crate) const TAG_SIZE: usize = 16;
+
+/// The ratio, as a fraction, between the number of buckets of the cuckoo hash
+/// table and its capacity. Cuckoo hashing with 2 hash functions fails with
+/// high probability when more than half of the buckets are occupied, and
+/// still fails often close to this threshold, whereas 3 hash functions and
+/// more support loads above 90%.
+const fn buckets_per_pair(number_hash_functions: usize) -> (usize, usize) {
+    if number_hash_functions == 2 {
+        (3, 1)
+    } else {
+        (3, 2)
+    }
+}
+
+/// Parameters of a keyword Private Information Retrieval protocol, over a
+/// database of at most `capacity` key-value pairs with values of `value_size`
+/// bytes.
+///
+/// The pairs are stored in a cuckoo hash table of `pir.database_size()`
+/// buckets: each pair is stored in one of the `number_hash_functions` buckets
+/// which the hash functions, seeded by `hash_seed`, map its key to. Each
+/// bucket is an element of an index PIR database, made of a tag of the key
+/// followed by the value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeywordPirParameters {
+    pub(crate) pir: Arc<PirParameters>,
+    pub(crate) value_size: usize,
+    pub(crate) number_hash_functions: usize,
+    pub(crate) hash_seed: [u8; 32],
+}
+
+impl FheParameters for KeywordPirParameters {}
+
+impl KeywordPirParameters {
+    /// Returns the parameters of the underlying index PIR protocol.
+    pub fn pir_parameters(&self) -> &Arc<PirParameters> {
+        &self.pir
+    }
+
+    /// Returns the maximum number of key-value pairs of the database.
+    pub fn capacity(&self) -> usize {
+        let (buckets, pairs) = buckets_per_pair(self.number_hash_functions);
+        (pairs * self.number_buckets()) / buckets
+    }
+
+    /// Returns the size of the values, in bytes.
+    pub const fn value_size(&self) -> usize {
+        self.value_size
+    }
+
+    /// Returns the number of buckets of the cuckoo hash table.
+    pub fn number_buckets(&self) -> usize {
+        self.pir.database_size()
+    }
+
+    /// Returns the number of hash functions, which is also the number of index
+    /// PIR queries issued for every key.
+    pub const fn number_hash_functions(&self) -> usize {
+        self.number_hash_functions
+    }
+
+    /// Returns the seed of the hash functions.
+    pub const fn hash_seed(&self) -> &[u8; 32] {
+        &self.hash_seed
+    }
+
+    /// The buckets where the pair with this key may be stored, one for each
+    /// hash function.
+    pub(crate) fn buckets(&self, key: &[u8]) -> Vec<usize> {
+        (0..self.number_hash_functions as u32)
+            .map(|i| {
+                let digest = Sha256::new()
+                    .chain_update(b"fhe-pir bucket")
+                    .chain_update(self.hash_seed)
+                    .chain_update(i.to_le_bytes())
+                    .chain_update(key)
+                    .finalize();
+                let h = u64::from_le_bytes(digest[..8].try_into().unwrap());
+                (h % self.number_buckets() as u64) as usize
+            })
+            .collect()
+    }
+
+    /// The tag identifying this key in its bucket.
+    pub(crate) fn tag(&self, key: &[u8]) -> [u8; TAG_SIZE] {
+        let digest = Sha256::new()
+            .chain_update(b"fhe-pir tag")
+            .chain_update(self.hash_seed)
+            .chain_update(key)
+            .finalize();
+        digest[..TAG_SIZE].try_into().unwrap()
+    }
+
+    /// Returns a copy of these parameters with another seed.
+    pub(crate) fn with_hash_seed(&self, hash_seed: [u8; 32]) -> Self {
+        Self {
+            hash_seed,
+            ..self.clone()
+        }
+    }
+}
+
+/// Builder for the parameters of a keyword Private Information Retrieval
+/// protocol.
+#[derive(Debug)]
+pub struct KeywordPirParametersBuilder {
+    protocol: Protocol,
+    bfv: Option<Arc<BfvParameters>>,
+    capacity: usize,
+    value_size: usize,
+    number_hash_functions: usize,
+    hash_seed: [u8; 32],
+}
+
+impl KeywordPirParametersBuilder {
+    /// Creates a new instance of the builder for the specified index PIR
+    /// protocol.
+    pub fn new(protocol: Protocol) -> Self {
+        Self {
+            protocol,
+            bfv: None,
+            capacity: 0,
+            value_size: 0,
+            number_hash_functions: 3,
+            hash_seed: [0u8; 32],
+        }
+    }
+
+    /// Sets the BFV parameters. When not specified, the default parameters of
+    /// the protocol are used, see [`Protocol::default_bfv_parameters`].
+    pub fn set_bfv_parameters(&mut self, par: &Arc<BfvParameters>) -> &mut Self {
+        self.bfv = Some(par.clone());
+        self
+    }
+
+    /// Sets the maximum number of key-value pairs of the database.
+    pub fn set_capacity(&mut self, capacity: usize) -> &mut Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Sets the size of the values, in bytes.
+    pub fn set_value_size(&mut self, value_size: usize) -> &mut Self {
+        self.value_size = value_size;
+        self
+    }
+
+    /// Sets the number of hash functions of the cuckoo hash table. When not
+    /// specified, 3 hash functions are used.
+    pub fn set_number_hash_functions(&mut self, number_hash_functions: usize) -> &mut Self {
+        self.number_hash_functions = number_hash_functions;
+        self
+    }
+
+    /// Sets the seed of the hash functions. When not specified, the seed is
+    /// zero; the server may pick another seed when building the database, see
+    /// [`KeywordDatabase::new`](crate::KeywordDatabase::new).
+    pub fn set_hash_seed(&mut self, hash_seed: &[u8; 32]) -> &mut Self {
+        self.hash_seed = *hash_seed;
+        self
+    }
+
+    /// Build a new [`KeywordPirParameters`].
+    ///
+    /// The cuckoo hash table has `3 * capacity` buckets when using 2 hash
+    /// functions, and `1.5 * capacity` buckets otherwise, which enables to
+    /// insert `capacity` pairs with high probability.
+    pub fn build(&self) -> Result<KeywordPirParameters> {
+        if self.capacity == 0 {
+            return Err(Error::InvalidParameters(
+                "the capacity must be at least 1".to_string(),
+            ));
+        }
+        if !(2..=8).contains(&self.number_hash_functions) {
+            return Err(Error::InvalidParameters(
+                "the number of hash functions must be between 2 and 8".to_string(),
+            ));
+        }
+
+        let (buckets, pairs) = buckets_per_pair(self.number_hash_functions);
+        let mut builder = PirParametersBuilder::new(self.protocol);
+        if let Some(bfv) = &self.bfv {
+            builder.set_bfv_parameters(bfv);
+        }
+        let pir = builder
+            .set_database_size(div_ceil(buckets * self.capacity, pairs).max(2))
+            .set_element_size(TAG_SIZE + self.value_size)
+            .build_arc()?;
+
+        Ok(KeywordPirParameters {
+            pir,
+            value_size: self.value_size,
+            number_hash_functions: self.number_hash_functions,
+            hash_seed: self.hash_seed,
+        })
+    }
+
+    /// Build a new [`KeywordPirParameters`] inside an [`Arc`].
+    pub fn build_arc(&self) -> Result<Arc<KeywordPirParameters>> {
+        self.build().map(Arc::new)
+    }
+}
+
+impl Serialize for KeywordPirParameters {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut proto = KeywordParametersProto::new();
+        proto.pir_parameters = self.pir.to_bytes();
+        proto.value_size = self.value_size as u64;
+        proto.number_hash_functions = self.number_hash_functions as u32;
+        proto.hash_seed = self.hash_seed.to_vec();
+        proto.write_to_bytes().unwrap()
+    }
+}
+
+/// The deserialized BFV parameters must provide at least 128 bits of security.
+impl Deserialize for KeywordPirParameters {
+    type Error = Error;
+
+    fn try_deserialize(bytes: &[u8]) -> Result<Self> {
+        let proto = KeywordParametersProto::parse_from_bytes(bytes)
+            .map_err(|_| Error::SerializationError)?;
+        let pir = Arc::new(PirParameters::try_deserialize(&proto.pir_parameters)?);
+        let hash_seed = proto
+            .hash_seed
+            .try_into()
+            .map_err(|_| Error::SerializationError)?;
+        if pir.element_size() != TAG_SIZE + proto.value_size as usize
+            || !(2..=8).contains(&proto.number_hash_functions)
+        {
+            return Err(Error::SerializationError);
+        }
+        Ok(Self {
+            pir,
+            value_size: proto.value_size as usize,
+            number_hash_functions: proto.number_hash_functions as usize,
+            hash_seed,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{KeywordPirParametersBuilder, TAG_SIZE};
+    use crate::{test_utils::test_bfv_parameters, Error, KeywordPirParameters, Protocol};
+    use fhe_traits::{Deserialize, Serialize};
+    use std::error::Error as StdError;
+
+    #[test]
+    fn builder() -> Result<(), Box<dyn StdError>> {
+        let par = KeywordPirParametersBuilder::new(Protocol::SealPir)
+            .set_bfv_parameters(&test_bfv_parameters())
+            .set_capacity(100)
+            .set_value_size(20)
+            .build()?;
+        assert_eq!(par.capacity(), 100);
+        assert_eq!(par.value_size(), 20);
+        assert_eq!(par.number_buckets(), 150);
+        assert_eq!(par.number_hash_functions(), 3);
+        assert_eq!(par.hash_seed(), &[0u8; 32]);
+        assert_eq!(par.pir_parameters().element_size(), TAG_SIZE + 20);
+
+        for capacity in [1, 2, 10, 99, 100, 1000] {
+            for number_hash_functions in 2..=8 {
+                let par = KeywordPirParametersBuilder::new(Protocol::SealPir)
+                    .set_bfv_parameters(&test_bfv_parameters())
+                    .set_capacity(capacity)
+                    .set_value_size(20)
+                    .set_number_hash_functions(number_hash_functions)
+                    .build()?;
+                assert_eq!(par.capacity(), capacity);
+            }
+        }
+        let par = KeywordPirParametersBuilder::new(Protocol::SealPir)
+            .set_bfv_parameters(&test_bfv_parameters())
+            .set_capacity(100)
+            .set_value_size(20)
+            .set_number_hash_functions(2)
+            .build()?;
+        assert_eq!(par.number_buckets(), 300);
+
+        let mut builder = KeywordPirParametersBuilder::new(Protocol::MulPir);
+        builder
+            .set_bfv_parameters(&test_bfv_parameters())
+            .set_value_size(20);
+        assert!(matches!(builder.build(), Err(Error::InvalidParameters(_))));
+        builder.set_capacity(100).set_number_hash_functions(1);
+        assert!(matches!(builder.build(), Err(Error::InvalidParameters(_))));
+        builder.set_number_hash_functions(9);
+        assert!(matches!(builder.build(), Err(Error::InvalidParameters(_))));
+        builder.set_number_hash_functions(2).set_value_size(500);
+        assert!(matches!(builder.build(), Err(Error::InvalidParameters(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn hash_functions() -> Result<(), Box<dyn StdError>> {
+        let par = KeywordPirParametersBuilder::new(Protocol::SealPir)
+            .set_bfv_parameters(&test_bfv_parameters())
+            .set_capacity(100)
+            .set_value_size(20)
+            .build()?;
+        let buckets = par.buckets(b"key");
+        assert_eq!(buckets.len(), 3);
+        assert!(buckets.iter().all(|b| *b < par.number_buckets()));
+        assert_eq!(buckets, par.buckets(b"key"));
+        assert_eq!(par.tag(b"key"), par.tag(b"key"));
+        assert_ne!(par.tag(b"key"), par.tag(b"other key"));
+
+        // The hash functions depend on the seed.
+        let other_par = par.with_hash_seed([1u8; 32]);
+        assert_ne!(par.tag(b"key"), other_par.tag(b"key"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn serialize() -> Result<(), Box<dyn StdError>> {
+        for protocol in [Protocol::SealPir, Protocol::MulPir] {
+            let par = KeywordPirParametersBuilder::new(protocol)
+                .set_capacity(1000)
+                .set_value_size(64)
+                .set_number_hash_functions(2)
+                .set_hash_seed(&[42u8; 32])
+                .build()?;
+            assert_eq!(par, KeywordPirParameters::try_deserialize(&par.to_bytes())?);
+        }
+        assert_eq!(
+            KeywordPirParameters::try_deserialize(&[1, 2, 3]).err(),
+            Some(Error::SerializationError)
+        );
+        Ok(())
+    }
+}
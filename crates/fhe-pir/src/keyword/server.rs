@@ -0,0 +1,113 @@
+//! Server of the keyword Private Information Retrieval protocol.
+
+use crate::{
+    Error, KeywordDatabase, KeywordPirParameters, KeywordQuery, KeywordResponse, Result, Server,
+    ServerKeys,
+};
+use std::sync::Arc;
+
+/// A server answering keyword queries over a key-value database.
+pub struct KeywordServer {
+    par: Arc<KeywordPirParameters>,
+    server: Server,
+}
+
+impl KeywordServer {
+    /// Create a server for a key-value database.
+    pub fn new(database: KeywordDatabase) -> Self {
+        Self {
+            par: database.par,
+            server: Server::new(database.database),
+        }
+    }
+
+    /// Returns the parameters of the server.
+    pub fn parameters(&self) -> &Arc<KeywordPirParameters> {
+        &self.par
+    }
+
+    /// Answer a keyword query, using the keys of the client which generated
+    /// it, by answering each of its index PIR queries.
+    pub fn respond(&self, keys: &ServerKeys, query: &KeywordQuery) -> Result<KeywordResponse> {
+        if query.0.len() != self.par.number_hash_functions {
+            return Err(Error::QuerySizeMismatch {
+                expected: self.par.number_hash_functions,
+                found: query.0.len(),
+            });
+        }
+        let responses = query
+            .0
+            .iter()
+            .map(|query| self.server.respond(keys, query))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(KeywordResponse(responses))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KeywordServer;
+    use crate::{
+        test_utils::{generate_pairs, test_keyword_parameters},
+        Error, KeywordClient, KeywordDatabase, KeywordQuery, Protocol,
+    };
+    use rand::{thread_rng, Rng};
+    use std::error::Error as StdError;
+
+    #[test]
+    fn respond() -> Result<(), Box<dyn StdError>> {
+        let mut rng = thread_rng();
+        for protocol in [Protocol::SealPir, Protocol::MulPir] {
+            for (number_pairs, value_size) in [(1, 1), (100, 20), (300, 0)] {
+                let par = test_keyword_parameters(protocol, number_pairs, value_size)?;
+                let pairs = generate_pairs(number_pairs, value_size);
+                let database = KeywordDatabase::new(&pairs, &par, &mut rng)?;
+
+                // The client uses the parameters of the database.
+                let par = database.parameters().clone();
+                let server = KeywordServer::new(database);
+                let client = KeywordClient::new(&par, &mut rng);
+                let keys = client.server_keys(&mut rng)?;
+
+                for _ in 0..3 {
+                    let (key, value) = &pairs[rng.gen_range(0..number_pairs)];
+                    let response = server.respond(&keys, &client.query(key, &mut rng)?)?;
+                    assert_eq!(client.decode_response(key, &response)?, Some(value.clone()));
+                }
+
+                let key = b"not in the database";
+                let response = server.respond(&keys, &client.query(key, &mut rng)?)?;
+                assert_eq!(client.decode_response(key, &response)?, None);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn query_size_mismatch() -> Result<(), Box<dyn StdError>> {
+        let mut rng = thread_rng();
+        let par = test_keyword_parameters(Protocol::SealPir, 10, 20)?;
+        let database = KeywordDatabase::new(&generate_pairs(10, 20), &par, &mut rng)?;
+        let server = KeywordServer::new(database);
+        let client = KeywordClient::new(&par, &mut rng);
+        let keys = client.server_keys(&mut rng)?;
+
+        let mut query = client.query(b"key", &mut rng)?;
+        query.0.pop();
+        assert_eq!(
+            server.respond(&keys, &query).err(),
+            Some(Error::QuerySizeMismatch {
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            server.respond(&keys, &KeywordQuery(vec![])).err(),
+            Some(Error::QuerySizeMismatch {
+                expected: 3,
+                found: 0
+            })
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,132 @@
+#![crate_name = "fhe_pir"]
+#![crate_type = "lib"]
+#![warn(missing_docs, unused_imports)]
+
+//! Private Information Retrieval (PIR) using the BFV scheme of the fhe.rs
+//! library.
+//!
+//! A PIR protocol enables a [`Client`] to retrieve an element of a database
+//! held by a [`Server`] without revealing which element is retrieved. This
+//! crate implements SealPIR (<https://eprint.iacr.org/2017/1142>) and MulPIR
+//! (<https://eprint.iacr.org/2019/1483>), in which the database is viewed as a
+//! two-dimensional matrix of plaintexts.
+//!
+//! On top of these index PIR protocols, a [`KeywordClient`] can retrieve the
+//! value associated with a key in a key-value database held by a
+//! [`KeywordServer`], which stores the pairs in a cuckoo hash table.
+//!
+//! ```
+//! # use fhe_pir::{Client, EncodedDatabase, PirParametersBuilder, Protocol, Server};
+//! # use rand::thread_rng;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut rng = thread_rng();
+//! let database = (0..100u8).map(|i| vec![i; 32]).collect::<Vec<_>>();
+//! let par = PirParametersBuilder::new(Protocol::MulPir)
+//!     .set_database_size(database.len())
+//!     .set_element_size(32)
+//!     .build_arc()?;
+//!
+//! // The server encodes the database once.
+//! let server = Server::new(EncodedDatabase::new(&database, &par)?);
+//!
+//! // The client generates its keys, which it sends to the server, and a query.
+//! let client = Client::new(&par, &mut rng);
+//! let keys = client.server_keys(&mut rng)?;
+//! let query = client.query(42, &mut rng)?;
+//!
+//! let response = server.respond(&keys, &query)?;
+//! assert_eq!(client.decode_response(42, &response)?, database[42]);
+//! # Ok(())
+//! # }
+//! ```
+
+mod client;
+mod database;
+mod errors;
+mod keyword;
+mod messages;
+mod parameters;
+#[allow(renamed_and_removed_lints)]
+mod proto;
+mod server;
+
+pub use client::Client;
+pub use database::EncodedDatabase;
+pub use errors::{Error, Result};
+pub use keyword::{
+    KeywordClient, KeywordDatabase, KeywordPirParameters, KeywordPirParametersBuilder,
+    KeywordQuery, KeywordResponse, KeywordServer,
+};
+pub use messages::{Query, Response, ServerKeys};
+pub use parameters::{PirParameters, PirParametersBuilder, Protocol};
+pub use server::Server;
+
+#[cfg(test)]
+mod test_utils {
+    use crate::{
+        KeywordPirParameters, KeywordPirParametersBuilder, PirParameters, PirParametersBuilder,
+        Protocol, Result,
+    };
+    use fhe::bfv::{BfvParameters, BfvParametersBuilder};
+    use std::sync::Arc;
+
+    /// Insecure BFV parameters of degree 256, which are fast enough for tests.
+    pub(crate) fn test_bfv_parameters() -> Arc<BfvParameters> {
+        BfvParametersBuilder::new()
+            .set_degree(256)
+            .set_plaintext_modulus(65537)
+            .set_moduli_sizes(&[62, 62, 62])
+            .allow_insecure()
+            .build_arc()
+            .unwrap()
+    }
+
+    pub(crate) fn test_parameters(
+        protocol: Protocol,
+        database_size: usize,
+        element_size: usize,
+    ) -> Result<Arc<PirParameters>> {
+        PirParametersBuilder::new(protocol)
+            .set_bfv_parameters(&test_bfv_parameters())
+            .set_database_size(database_size)
+            .set_element_size(element_size)
+            .build_arc()
+    }
+
+    /// Generate a database of elements of the form [i || 0...0] where i is the
+    /// 4B little endian encoding of the index.
+    pub(crate) fn generate_database(database_size: usize, element_size: usize) -> Vec<Vec<u8>> {
+        (0..database_size)
+            .map(|i| {
+                let mut element = vec![0u8; element_size];
+                let n = element_size.min(4);
+                element[..n].copy_from_slice(&(i as u32).to_le_bytes()[..n]);
+                element
+            })
+            .collect()
+    }
+
+    pub(crate) fn test_keyword_parameters(
+        protocol: Protocol,
+        capacity: usize,
+        value_size: usize,
+    ) -> Result<Arc<KeywordPirParameters>> {
+        KeywordPirParametersBuilder::new(protocol)
+            .set_bfv_parameters(&test_bfv_parameters())
+            .set_capacity(capacity)
+            .set_value_size(value_size)
+            .build_arc()
+    }
+
+    /// Generate key-value pairs whose keys are of the form "key i", and whose
+    /// values are of the form [i || 0...0].
+    pub(crate) fn generate_pairs(
+        number_pairs: usize,
+        value_size: usize,
+    ) -> Vec<(Vec<u8>, Vec<u8>)> {
+        (0..number_pairs)
+            .map(|i| format!("key {i}").into_bytes())
+            .zip(generate_database(number_pairs, value_size))
+            .collect()
+    }
+}
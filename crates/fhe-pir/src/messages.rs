@@ -0,0 +1,165 @@
+//! Messages exchanged between the client and the server.
+
+use crate::{
+    proto::pir::{Query as QueryProto, Response as ResponseProto, ServerKeys as ServerKeysProto},
+    Error, PirParameters, Result,
+};
+use fhe::bfv::{Ciphertext, EvaluationKey, RelinearizationKey};
+use fhe_traits::{DeserializeParametrized, FheParametrized, Serialize};
+use protobuf::Message;
+use std::sync::Arc;
+
+/// The keys generated by the client, which the server needs to answer the
+/// queries of this client.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ServerKeys {
+    /// The evaluation key enabling to expand the queries.
+    pub(crate) ek: EvaluationKey,
+    /// The relinearization key, for MulPIR.
+    pub(crate) rk: Option<RelinearizationKey>,
+}
+
+/// A query, encrypting the index of a row of the database.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Query(pub(crate) Ciphertext);
+
+/// A response of the server to a [`Query`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct Response(pub(crate) Vec<Ciphertext>);
+
+impl FheParametrized for ServerKeys {
+    type Parameters = PirParameters;
+}
+
+impl FheParametrized for Query {
+    type Parameters = PirParameters;
+}
+
+impl FheParametrized for Response {
+    type Parameters = PirParameters;
+}
+
+impl Serialize for ServerKeys {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut proto = ServerKeysProto::new();
+        proto.evaluation_key = self.ek.to_bytes();
+        if let Some(rk) = &self.rk {
+            proto.relinearization_key = rk.to_bytes();
+        }
+        proto.write_to_bytes().unwrap()
+    }
+}
+
+impl DeserializeParametrized for ServerKeys {
+    type Error = Error;
+
+    fn from_bytes(bytes: &[u8], par: &Arc<PirParameters>) -> Result<Self> {
+        let proto =
+            ServerKeysProto::parse_from_bytes(bytes).map_err(|_| Error::SerializationError)?;
+        let ek = EvaluationKey::from_bytes(&proto.evaluation_key, &par.bfv)?;
+        let rk = if proto.relinearization_key.is_empty() {
+            None
+        } else {
+            Some(RelinearizationKey::from_bytes(
+                &proto.relinearization_key,
+                &par.bfv,
+            )?)
+        };
+        Ok(Self { ek, rk })
+    }
+}
+
+impl Serialize for Query {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut proto = QueryProto::new();
+        proto.ciphertext = self.0.to_bytes();
+        proto.write_to_bytes().unwrap()
+    }
+}
+
+impl DeserializeParametrized for Query {
+    type Error = Error;
+
+    fn from_bytes(bytes: &[u8], par: &Arc<PirParameters>) -> Result<Self> {
+        let proto = QueryProto::parse_from_bytes(bytes).map_err(|_| Error::SerializationError)?;
+        Ok(Self(Ciphertext::from_bytes(&proto.ciphertext, &par.bfv)?))
+    }
+}
+
+impl Serialize for Response {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut proto = ResponseProto::new();
+        proto.ciphertexts = self.0.iter().map(|ct| ct.to_bytes()).collect();
+        proto.write_to_bytes().unwrap()
+    }
+}
+
+impl DeserializeParametrized for Response {
+    type Error = Error;
+
+    fn from_bytes(bytes: &[u8], par: &Arc<PirParameters>) -> Result<Self> {
+        let proto =
+            ResponseProto::parse_from_bytes(bytes).map_err(|_| Error::SerializationError)?;
+        Ok(Self(
+            proto
+                .ciphertexts
+                .iter()
+                .map(|ct| Ciphertext::from_bytes(ct, &par.bfv))
+                .collect::<fhe::Result<Vec<Ciphertext>>>()?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Query, Response, ServerKeys};
+    use crate::{
+        test_utils::{generate_database, test_parameters},
+        Client, EncodedDatabase, Error, Protocol, Server,
+    };
+    use fhe_traits::{DeserializeParametrized, Serialize};
+    use rand::thread_rng;
+    use std::error::Error as StdError;
+
+    #[test]
+    fn serialize() -> Result<(), Box<dyn StdError>> {
+        let mut rng = thread_rng();
+        for protocol in [Protocol::SealPir, Protocol::MulPir] {
+            let par = test_parameters(protocol, 100, 100)?;
+            let database = generate_database(100, 100);
+            let server = Server::new(EncodedDatabase::new(&database, &par)?);
+            let client = Client::new(&par, &mut rng);
+
+            let keys = client.server_keys(&mut rng)?;
+            let keys_bytes = keys.to_bytes();
+            assert_eq!(keys, ServerKeys::from_bytes(&keys_bytes, &par)?);
+
+            let query = client.query(33, &mut rng)?;
+            let query_bytes = query.to_bytes();
+            assert_eq!(query, Query::from_bytes(&query_bytes, &par)?);
+
+            // The server and the client only exchange bytes.
+            let response = server.respond(
+                &ServerKeys::from_bytes(&keys_bytes, &par)?,
+                &Query::from_bytes(&query_bytes, &par)?,
+            )?;
+            let response = Response::from_bytes(&response.to_bytes(), &par)?;
+            assert_eq!(client.decode_response(33, &response)?, database[33]);
+        }
+
+        let par = test_parameters(Protocol::SealPir, 100, 100)?;
+        assert_eq!(
+            Query::from_bytes(&[1, 2, 3], &par).err(),
+            Some(Error::SerializationError)
+        );
+        assert_eq!(
+            Response::from_bytes(&[1, 2, 3], &par).err(),
+            Some(Error::SerializationError)
+        );
+        assert_eq!(
+            ServerKeys::from_bytes(&[1, 2, 3], &par).err(),
+            Some(Error::SerializationError)
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,385 @@
+//! Parameters of the Private Information Retrieval protocols.
+
+use crate::{proto::pir::Parameters as ParametersProto, Error, Result};
+use fhe::bfv::{BfvParameters, BfvParametersBuilder};
+use fhe_traits::{Deserialize, FheParameters, Serialize};
+use fhe_util::{div_ceil, inverse};
+use protobuf::Message;
+use std::sync::Arc;
+
+/// The Private Information Retrieval protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    /// SealPIR, described in <https://eprint.iacr.org/2017/1142>. The response
+    /// consists of several ciphertexts, obtained by decomposing the ciphertexts
+    /// of the first dimension into plaintexts.
+    SealPir,
+    /// MulPIR, described in <https://eprint.iacr.org/2019/1483>. The response
+    /// consists of a single ciphertext, obtained by multiplying the ciphertexts
+    /// of the two dimensions; the server needs a relinearization key.
+    MulPir,
+}
+
+impl Protocol {
+    /// The default BFV parameters for this protocol: the parameters of
+    /// Microsoft's SealPIR implementation for SealPIR, and the parameters of
+    /// Table 1 of <https://eprint.iacr.org/2019/1483> for MulPIR.
+    pub fn default_bfv_parameters(&self) -> Result<Arc<BfvParameters>> {
+        let (degree, plaintext_modulus, moduli_sizes): (usize, u64, [usize; 3]) = match self {
+            Protocol::SealPir => (4096, 2056193, [36, 36, 37]),
+            Protocol::MulPir => (
+                8192,
+                (1 << 20) + (1 << 19) + (1 << 17) + (1 << 16) + (1 << 14) + 1,
+                [50, 55, 55],
+            ),
+        };
+        Ok(BfvParametersBuilder::new()
+            .set_degree(degree)
+            .set_plaintext_modulus(plaintext_modulus)
+            .set_moduli_sizes(&moduli_sizes)
+            .build_arc()?)
+    }
+}
+
+/// Parameters of a Private Information Retrieval protocol over a database of
+/// `database_size` elements of `element_size` bytes.
+///
+/// The elements are packed into plaintexts, which are viewed as a matrix of
+/// `dimension_1` rows and `dimension_2` columns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PirParameters {
+    pub(crate) bfv: Arc<BfvParameters>,
+    pub(crate) protocol: Protocol,
+    pub(crate) database_size: usize,
+    pub(crate) element_size: usize,
+    pub(crate) dimensions: (usize, usize),
+}
+
+impl FheParameters for PirParameters {}
+
+impl PirParameters {
+    /// Returns the underlying BFV parameters.
+    pub fn bfv_parameters(&self) -> &Arc<BfvParameters> {
+        &self.bfv
+    }
+
+    /// Returns the protocol.
+    pub const fn protocol(&self) -> Protocol {
+        self.protocol
+    }
+
+    /// Returns the number of elements of the database.
+    pub const fn database_size(&self) -> usize {
+        self.database_size
+    }
+
+    /// Returns the size of the elements, in bytes.
+    pub const fn element_size(&self) -> usize {
+        self.element_size
+    }
+
+    /// Returns the dimensions of the matrix of plaintexts.
+    pub const fn dimensions(&self) -> (usize, usize) {
+        self.dimensions
+    }
+
+    /// Returns the number of elements packed in each plaintext.
+    pub fn elements_per_plaintext(&self) -> usize {
+        Self::elements_per_plaintext_internal(&self.bfv, self.element_size)
+    }
+
+    /// Returns the number of plaintexts storing the elements of the database.
+    pub fn number_rows(&self) -> usize {
+        div_ceil(self.database_size, self.elements_per_plaintext())
+    }
+
+    /// Returns the number of ciphertexts in the responses.
+    pub fn response_size(&self) -> usize {
+        match self.protocol {
+            Protocol::SealPir => div_ceil(
+                2 * self.coefficients_per_ciphertext_part(),
+                self.bfv.degree(),
+            ),
+            Protocol::MulPir => 1,
+        }
+    }
+
+    /// The expansion level, such that the query expands into `2^level` >=
+    /// `dimension_1 + dimension_2` ciphertexts.
+    pub(crate) fn expansion_level(&self) -> usize {
+        (self.dimensions.0 + self.dimensions.1)
+            .next_power_of_two()
+            .ilog2() as usize
+    }
+
+    /// Number of bits of data in each plaintext coefficient.
+    pub(crate) fn plaintext_nbits(&self) -> usize {
+        self.bfv.plaintext().ilog2() as usize
+    }
+
+    /// Number of bits of each coefficient of the ciphertexts at the last level.
+    pub(crate) fn last_modulus_nbits(&self) -> usize {
+        64 - self.bfv.moduli()[0].leading_zeros() as usize
+    }
+
+    /// Number of plaintext coefficients needed to store one polynomial of a
+    /// ciphertext at the last level, for SealPIR.
+    pub(crate) fn coefficients_per_ciphertext_part(&self) -> usize {
+        div_ceil(
+            self.bfv.degree() * self.last_modulus_nbits(),
+            self.plaintext_nbits(),
+        )
+    }
+
+    fn elements_per_plaintext_internal(bfv: &BfvParameters, element_size: usize) -> usize {
+        (bfv.plaintext().ilog2() as usize * bfv.degree()) / (element_size * 8)
+    }
+}
+
+/// Builder for the parameters of a Private Information Retrieval protocol.
+#[derive(Debug)]
+pub struct PirParametersBuilder {
+    protocol: Protocol,
+    bfv: Option<Arc<BfvParameters>>,
+    database_size: usize,
+    element_size: usize,
+    dimensions: Option<(usize, usize)>,
+}
+
+impl PirParametersBuilder {
+    /// Creates a new instance of the builder for the specified protocol.
+    pub fn new(protocol: Protocol) -> Self {
+        Self {
+            protocol,
+            bfv: None,
+            database_size: 0,
+            element_size: 0,
+            dimensions: None,
+        }
+    }
+
+    /// Sets the BFV parameters. When not specified, the default parameters of
+    /// the protocol are used, see [`Protocol::default_bfv_parameters`].
+    pub fn set_bfv_parameters(&mut self, par: &Arc<BfvParameters>) -> &mut Self {
+        self.bfv = Some(par.clone());
+        self
+    }
+
+    /// Sets the number of elements of the database.
+    pub fn set_database_size(&mut self, database_size: usize) -> &mut Self {
+        self.database_size = database_size;
+        self
+    }
+
+    /// Sets the size of the elements, in bytes.
+    pub fn set_element_size(&mut self, element_size: usize) -> &mut Self {
+        self.element_size = element_size;
+        self
+    }
+
+    /// Sets the dimensions of the matrix of plaintexts. When not specified, the
+    /// matrix is as square as possible.
+    pub fn set_dimensions(&mut self, dimension_1: usize, dimension_2: usize) -> &mut Self {
+        self.dimensions = Some((dimension_1, dimension_2));
+        self
+    }
+
+    /// Build a new [`PirParameters`].
+    pub fn build(&self) -> Result<PirParameters> {
+        let bfv = match &self.bfv {
+            Some(bfv) => bfv.clone(),
+            None => self.protocol.default_bfv_parameters()?,
+        };
+
+        if bfv.moduli().len() < 2 {
+            return Err(Error::InvalidParameters(
+                "the BFV parameters must have at least two moduli".to_string(),
+            ));
+        }
+        let level = bfv.degree().ilog2();
+        if inverse(1 << level, bfv.plaintext()).is_none() {
+            return Err(Error::InvalidParameters(
+                "the plaintext modulus must be odd".to_string(),
+            ));
+        }
+
+        if self.database_size == 0 {
+            return Err(Error::InvalidParameters(
+                "the database size must be at least 1".to_string(),
+            ));
+        }
+        let max_element_size = (bfv.plaintext().ilog2() as usize * bfv.degree()) / 8;
+        if self.element_size == 0 || self.element_size > max_element_size {
+            return Err(Error::InvalidParameters(format!(
+                "the element size must be between 1 and {max_element_size}"
+            )));
+        }
+
+        let number_rows = div_ceil(
+            self.database_size,
+            PirParameters::elements_per_plaintext_internal(&bfv, self.element_size),
+        );
+        let dimensions = self.dimensions.unwrap_or_else(|| {
+            let dimension_1 = (number_rows as f64).sqrt().ceil() as usize;
+            (dimension_1, div_ceil(number_rows, dimension_1))
+        });
+        if dimensions.0 * dimensions.1 < number_rows {
+            return Err(Error::InvalidParameters(format!(
+                "the dimensions {} x {} are too small for {number_rows} rows",
+                dimensions.0, dimensions.1
+            )));
+        }
+        if dimensions.0 == 0 || dimensions.1 == 0 || dimensions.0 + dimensions.1 > bfv.degree() {
+            return Err(Error::InvalidParameters(format!(
+                "the sum of the dimensions must be between 2 and {}",
+                bfv.degree()
+            )));
+        }
+
+        Ok(PirParameters {
+            bfv,
+            protocol: self.protocol,
+            database_size: self.database_size,
+            element_size: self.element_size,
+            dimensions,
+        })
+    }
+
+    /// Build a new [`PirParameters`] inside an [`Arc`].
+    pub fn build_arc(&self) -> Result<Arc<PirParameters>> {
+        self.build().map(Arc::new)
+    }
+}
+
+impl Serialize for PirParameters {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut proto = ParametersProto::new();
+        proto.bfv_parameters = self.bfv.to_bytes();
+        proto.protocol = match self.protocol {
+            Protocol::SealPir => 0,
+            Protocol::MulPir => 1,
+        };
+        proto.database_size = self.database_size as u64;
+        proto.element_size = self.element_size as u64;
+        proto.dimension_1 = self.dimensions.0 as u64;
+        proto.dimension_2 = self.dimensions.1 as u64;
+        proto.write_to_bytes().unwrap()
+    }
+}
+
+/// The deserialized BFV parameters must provide at least 128 bits of security.
+impl Deserialize for PirParameters {
+    type Error = Error;
+
+    fn try_deserialize(bytes: &[u8]) -> Result<Self> {
+        let proto =
+            ParametersProto::parse_from_bytes(bytes).map_err(|_| Error::SerializationError)?;
+        let protocol = match proto.protocol {
+            0 => Protocol::SealPir,
+            1 => Protocol::MulPir,
+            _ => return Err(Error::SerializationError),
+        };
+        let bfv = Arc::new(BfvParameters::try_deserialize(&proto.bfv_parameters)?);
+        PirParametersBuilder::new(protocol)
+            .set_bfv_parameters(&bfv)
+            .set_database_size(proto.database_size as usize)
+            .set_element_size(proto.element_size as usize)
+            .set_dimensions(proto.dimension_1 as usize, proto.dimension_2 as usize)
+            .build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PirParametersBuilder, Protocol};
+    use crate::{test_utils::test_bfv_parameters, Error, PirParameters};
+    use fhe::bfv::BfvParametersBuilder;
+    use fhe_traits::{Deserialize, Serialize};
+    use std::error::Error as StdError;
+
+    #[test]
+    fn builder() -> Result<(), Box<dyn StdError>> {
+        let bfv = test_bfv_parameters();
+
+        // 100 elements of 100 bytes: 5 elements per plaintext and 20 rows.
+        let par = PirParametersBuilder::new(Protocol::SealPir)
+            .set_bfv_parameters(&bfv)
+            .set_database_size(100)
+            .set_element_size(100)
+            .build()?;
+        assert_eq!(par.protocol(), Protocol::SealPir);
+        assert_eq!(par.database_size(), 100);
+        assert_eq!(par.element_size(), 100);
+        assert_eq!(par.elements_per_plaintext(), 5);
+        assert_eq!(par.number_rows(), 20);
+        assert_eq!(par.dimensions(), (5, 4));
+        assert_eq!(par.expansion_level(), 4);
+
+        let par = PirParametersBuilder::new(Protocol::MulPir)
+            .set_bfv_parameters(&bfv)
+            .set_database_size(100)
+            .set_element_size(100)
+            .set_dimensions(2, 10)
+            .build()?;
+        assert_eq!(par.dimensions(), (2, 10));
+        assert_eq!(par.response_size(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn builder_errors() -> Result<(), Box<dyn StdError>> {
+        let bfv = test_bfv_parameters();
+        let mut builder = PirParametersBuilder::new(Protocol::SealPir);
+        builder.set_bfv_parameters(&bfv).set_element_size(100);
+        assert!(matches!(builder.build(), Err(Error::InvalidParameters(_))));
+
+        builder.set_database_size(100).set_element_size(0);
+        assert!(matches!(builder.build(), Err(Error::InvalidParameters(_))));
+        builder.set_element_size(513);
+        assert!(matches!(builder.build(), Err(Error::InvalidParameters(_))));
+
+        builder.set_element_size(100).set_dimensions(4, 4);
+        assert!(matches!(builder.build(), Err(Error::InvalidParameters(_))));
+        builder.set_dimensions(20, 250);
+        assert!(matches!(builder.build(), Err(Error::InvalidParameters(_))));
+        builder.set_dimensions(20, 1);
+        assert!(builder.build().is_ok());
+
+        let even_plaintext = BfvParametersBuilder::new()
+            .set_degree(256)
+            .set_plaintext_modulus(1 << 16)
+            .set_moduli_sizes(&[62, 62, 62])
+            .allow_insecure()
+            .build_arc()?;
+        builder.set_bfv_parameters(&even_plaintext);
+        assert!(matches!(builder.build(), Err(Error::InvalidParameters(_))));
+
+        let single_modulus = BfvParametersBuilder::new()
+            .set_degree(256)
+            .set_plaintext_modulus(65537)
+            .set_moduli_sizes(&[62])
+            .allow_insecure()
+            .build_arc()?;
+        builder.set_bfv_parameters(&single_modulus);
+        assert!(matches!(builder.build(), Err(Error::InvalidParameters(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn serialize() -> Result<(), Box<dyn StdError>> {
+        for protocol in [Protocol::SealPir, Protocol::MulPir] {
+            let par = PirParametersBuilder::new(protocol)
+                .set_database_size(1000)
+                .set_element_size(288)
+                .build()?;
+            assert_eq!(par, PirParameters::try_deserialize(&par.to_bytes())?);
+        }
+        assert_eq!(
+            PirParameters::try_deserialize(&[1, 2, 3]).err(),
+            Some(Error::SerializationError)
+        );
+        Ok(())
+    }
+}
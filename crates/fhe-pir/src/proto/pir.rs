@@ -0,0 +1,1097 @@
+// This file is generated by rust-protobuf 3.2.0. Do not edit
+// .proto file is parsed by protoc --rust-out=...
+// @generated
+
+// https://github.com/rust-lang/rust-clippy/issues/702
+#![allow(unknown_lints)]
+#![allow(clippy::all)]
+
+#![allow(unused_attributes)]
+#![cfg_attr(rustfmt, rustfmt::skip)]
+
+#![allow(box_pointers)]
+#![allow(dead_code)]
+#![allow(missing_docs)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(trivial_casts)]
+#![allow(unused_results)]
+#![allow(unused_mut)]
+
+//! Generated file from `pir.proto`
+
+/// Generated files are compatible only with the same version
+/// of protobuf runtime.
+const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_3_2_0;
+
+#[derive(PartialEq,Clone,Default,Debug)]
+// @@protoc_insertion_point(message:fhers.pir.Parameters)
+pub struct Parameters {
+    // message fields
+    // @@protoc_insertion_point(field:fhers.pir.Parameters.bfv_parameters)
+    pub bfv_parameters: ::std::vec::Vec<u8>,
+    // @@protoc_insertion_point(field:fhers.pir.Parameters.protocol)
+    pub protocol: u32,
+    // @@protoc_insertion_point(field:fhers.pir.Parameters.database_size)
+    pub database_size: u64,
+    // @@protoc_insertion_point(field:fhers.pir.Parameters.element_size)
+    pub element_size: u64,
+    // @@protoc_insertion_point(field:fhers.pir.Parameters.dimension_1)
+    pub dimension_1: u64,
+    // @@protoc_insertion_point(field:fhers.pir.Parameters.dimension_2)
+    pub dimension_2: u64,
+    // special fields
+    // @@protoc_insertion_point(special_field:fhers.pir.Parameters.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a Parameters {
+    fn default() -> &'a Parameters {
+        <Parameters as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Parameters {
+    pub fn new() -> Parameters {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(6);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "bfv_parameters",
+            |m: &Parameters| { &m.bfv_parameters },
+            |m: &mut Parameters| { &mut m.bfv_parameters },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "protocol",
+            |m: &Parameters| { &m.protocol },
+            |m: &mut Parameters| { &mut m.protocol },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "database_size",
+            |m: &Parameters| { &m.database_size },
+            |m: &mut Parameters| { &mut m.database_size },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "element_size",
+            |m: &Parameters| { &m.element_size },
+            |m: &mut Parameters| { &mut m.element_size },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "dimension_1",
+            |m: &Parameters| { &m.dimension_1 },
+            |m: &mut Parameters| { &mut m.dimension_1 },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "dimension_2",
+            |m: &Parameters| { &m.dimension_2 },
+            |m: &mut Parameters| { &mut m.dimension_2 },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<Parameters>(
+            "Parameters",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for Parameters {
+    const NAME: &'static str = "Parameters";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.bfv_parameters = is.read_bytes()?;
+                },
+                16 => {
+                    self.protocol = is.read_uint32()?;
+                },
+                24 => {
+                    self.database_size = is.read_uint64()?;
+                },
+                32 => {
+                    self.element_size = is.read_uint64()?;
+                },
+                40 => {
+                    self.dimension_1 = is.read_uint64()?;
+                },
+                48 => {
+                    self.dimension_2 = is.read_uint64()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.bfv_parameters.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(1, &self.bfv_parameters);
+        }
+        if self.protocol != 0 {
+            my_size += ::protobuf::rt::uint32_size(2, self.protocol);
+        }
+        if self.database_size != 0 {
+            my_size += ::protobuf::rt::uint64_size(3, self.database_size);
+        }
+        if self.element_size != 0 {
+            my_size += ::protobuf::rt::uint64_size(4, self.element_size);
+        }
+        if self.dimension_1 != 0 {
+            my_size += ::protobuf::rt::uint64_size(5, self.dimension_1);
+        }
+        if self.dimension_2 != 0 {
+            my_size += ::protobuf::rt::uint64_size(6, self.dimension_2);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.bfv_parameters.is_empty() {
+            os.write_bytes(1, &self.bfv_parameters)?;
+        }
+        if self.protocol != 0 {
+            os.write_uint32(2, self.protocol)?;
+        }
+        if self.database_size != 0 {
+            os.write_uint64(3, self.database_size)?;
+        }
+        if self.element_size != 0 {
+            os.write_uint64(4, self.element_size)?;
+        }
+        if self.dimension_1 != 0 {
+            os.write_uint64(5, self.dimension_1)?;
+        }
+        if self.dimension_2 != 0 {
+            os.write_uint64(6, self.dimension_2)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> Parameters {
+        Parameters::new()
+    }
+
+    fn clear(&mut self) {
+        self.bfv_parameters.clear();
+        self.protocol = 0;
+        self.database_size = 0;
+        self.element_size = 0;
+        self.dimension_1 = 0;
+        self.dimension_2 = 0;
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static Parameters {
+        static instance: Parameters = Parameters {
+            bfv_parameters: ::std::vec::Vec::new(),
+            protocol: 0,
+            database_size: 0,
+            element_size: 0,
+            dimension_1: 0,
+            dimension_2: 0,
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for Parameters {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("Parameters").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for Parameters {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Parameters {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+#[derive(PartialEq,Clone,Default,Debug)]
+// @@protoc_insertion_point(message:fhers.pir.ServerKeys)
+pub struct ServerKeys {
+    // message fields
+    // @@protoc_insertion_point(field:fhers.pir.ServerKeys.evaluation_key)
+    pub evaluation_key: ::std::vec::Vec<u8>,
+    // @@protoc_insertion_point(field:fhers.pir.ServerKeys.relinearization_key)
+    pub relinearization_key: ::std::vec::Vec<u8>,
+    // special fields
+    // @@protoc_insertion_point(special_field:fhers.pir.ServerKeys.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a ServerKeys {
+    fn default() -> &'a ServerKeys {
+        <ServerKeys as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl ServerKeys {
+    pub fn new() -> ServerKeys {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(2);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "evaluation_key",
+            |m: &ServerKeys| { &m.evaluation_key },
+            |m: &mut ServerKeys| { &mut m.evaluation_key },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "relinearization_key",
+            |m: &ServerKeys| { &m.relinearization_key },
+            |m: &mut ServerKeys| { &mut m.relinearization_key },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<ServerKeys>(
+            "ServerKeys",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for ServerKeys {
+    const NAME: &'static str = "ServerKeys";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.evaluation_key = is.read_bytes()?;
+                },
+                18 => {
+                    self.relinearization_key = is.read_bytes()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.evaluation_key.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(1, &self.evaluation_key);
+        }
+        if !self.relinearization_key.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(2, &self.relinearization_key);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.evaluation_key.is_empty() {
+            os.write_bytes(1, &self.evaluation_key)?;
+        }
+        if !self.relinearization_key.is_empty() {
+            os.write_bytes(2, &self.relinearization_key)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> ServerKeys {
+        ServerKeys::new()
+    }
+
+    fn clear(&mut self) {
+        self.evaluation_key.clear();
+        self.relinearization_key.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static ServerKeys {
+        static instance: ServerKeys = ServerKeys {
+            evaluation_key: ::std::vec::Vec::new(),
+            relinearization_key: ::std::vec::Vec::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for ServerKeys {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("ServerKeys").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for ServerKeys {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ServerKeys {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+#[derive(PartialEq,Clone,Default,Debug)]
+// @@protoc_insertion_point(message:fhers.pir.Query)
+pub struct Query {
+    // message fields
+    // @@protoc_insertion_point(field:fhers.pir.Query.ciphertext)
+    pub ciphertext: ::std::vec::Vec<u8>,
+    // special fields
+    // @@protoc_insertion_point(special_field:fhers.pir.Query.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a Query {
+    fn default() -> &'a Query {
+        <Query as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Query {
+    pub fn new() -> Query {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(1);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "ciphertext",
+            |m: &Query| { &m.ciphertext },
+            |m: &mut Query| { &mut m.ciphertext },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<Query>(
+            "Query",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for Query {
+    const NAME: &'static str = "Query";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.ciphertext = is.read_bytes()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.ciphertext.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(1, &self.ciphertext);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.ciphertext.is_empty() {
+            os.write_bytes(1, &self.ciphertext)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> Query {
+        Query::new()
+    }
+
+    fn clear(&mut self) {
+        self.ciphertext.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static Query {
+        static instance: Query = Query {
+            ciphertext: ::std::vec::Vec::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for Query {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("Query").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for Query {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Query {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+#[derive(PartialEq,Clone,Default,Debug)]
+// @@protoc_insertion_point(message:fhers.pir.Response)
+pub struct Response {
+    // message fields
+    // @@protoc_insertion_point(field:fhers.pir.Response.ciphertexts)
+    pub ciphertexts: ::std::vec::Vec<::std::vec::Vec<u8>>,
+    // special fields
+    // @@protoc_insertion_point(special_field:fhers.pir.Response.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a Response {
+    fn default() -> &'a Response {
+        <Response as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Response {
+    pub fn new() -> Response {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(1);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "ciphertexts",
+            |m: &Response| { &m.ciphertexts },
+            |m: &mut Response| { &mut m.ciphertexts },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<Response>(
+            "Response",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for Response {
+    const NAME: &'static str = "Response";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.ciphertexts.push(is.read_bytes()?);
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        for value in &self.ciphertexts {
+            my_size += ::protobuf::rt::bytes_size(1, &value);
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        for v in &self.ciphertexts {
+            os.write_bytes(1, &v)?;
+        };
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> Response {
+        Response::new()
+    }
+
+    fn clear(&mut self) {
+        self.ciphertexts.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static Response {
+        static instance: Response = Response {
+            ciphertexts: ::std::vec::Vec::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for Response {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("Response").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for Response {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Response {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+#[derive(PartialEq,Clone,Default,Debug)]
+// @@protoc_insertion_point(message:fhers.pir.KeywordParameters)
+pub struct KeywordParameters {
+    // message fields
+    // @@protoc_insertion_point(field:fhers.pir.KeywordParameters.pir_parameters)
+    pub pir_parameters: ::std::vec::Vec<u8>,
+    // @@protoc_insertion_point(field:fhers.pir.KeywordParameters.value_size)
+    pub value_size: u64,
+    // @@protoc_insertion_point(field:fhers.pir.KeywordParameters.number_hash_functions)
+    pub number_hash_functions: u32,
+    // @@protoc_insertion_point(field:fhers.pir.KeywordParameters.hash_seed)
+    pub hash_seed: ::std::vec::Vec<u8>,
+    // special fields
+    // @@protoc_insertion_point(special_field:fhers.pir.KeywordParameters.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a KeywordParameters {
+    fn default() -> &'a KeywordParameters {
+        <KeywordParameters as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl KeywordParameters {
+    pub fn new() -> KeywordParameters {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(4);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "pir_parameters",
+            |m: &KeywordParameters| { &m.pir_parameters },
+            |m: &mut KeywordParameters| { &mut m.pir_parameters },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "value_size",
+            |m: &KeywordParameters| { &m.value_size },
+            |m: &mut KeywordParameters| { &mut m.value_size },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "number_hash_functions",
+            |m: &KeywordParameters| { &m.number_hash_functions },
+            |m: &mut KeywordParameters| { &mut m.number_hash_functions },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "hash_seed",
+            |m: &KeywordParameters| { &m.hash_seed },
+            |m: &mut KeywordParameters| { &mut m.hash_seed },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<KeywordParameters>(
+            "KeywordParameters",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for KeywordParameters {
+    const NAME: &'static str = "KeywordParameters";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.pir_parameters = is.read_bytes()?;
+                },
+                16 => {
+                    self.value_size = is.read_uint64()?;
+                },
+                24 => {
+                    self.number_hash_functions = is.read_uint32()?;
+                },
+                34 => {
+                    self.hash_seed = is.read_bytes()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.pir_parameters.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(1, &self.pir_parameters);
+        }
+        if self.value_size != 0 {
+            my_size += ::protobuf::rt::uint64_size(2, self.value_size);
+        }
+        if self.number_hash_functions != 0 {
+            my_size += ::protobuf::rt::uint32_size(3, self.number_hash_functions);
+        }
+        if !self.hash_seed.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(4, &self.hash_seed);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.pir_parameters.is_empty() {
+            os.write_bytes(1, &self.pir_parameters)?;
+        }
+        if self.value_size != 0 {
+            os.write_uint64(2, self.value_size)?;
+        }
+        if self.number_hash_functions != 0 {
+            os.write_uint32(3, self.number_hash_functions)?;
+        }
+        if !self.hash_seed.is_empty() {
+            os.write_bytes(4, &self.hash_seed)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> KeywordParameters {
+        KeywordParameters::new()
+    }
+
+    fn clear(&mut self) {
+        self.pir_parameters.clear();
+        self.value_size = 0;
+        self.number_hash_functions = 0;
+        self.hash_seed.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static KeywordParameters {
+        static instance: KeywordParameters = KeywordParameters {
+            pir_parameters: ::std::vec::Vec::new(),
+            value_size: 0,
+            number_hash_functions: 0,
+            hash_seed: ::std::vec::Vec::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for KeywordParameters {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("KeywordParameters").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for KeywordParameters {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for KeywordParameters {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+#[derive(PartialEq,Clone,Default,Debug)]
+// @@protoc_insertion_point(message:fhers.pir.KeywordQuery)
+pub struct KeywordQuery {
+    // message fields
+    // @@protoc_insertion_point(field:fhers.pir.KeywordQuery.queries)
+    pub queries: ::std::vec::Vec<::std::vec::Vec<u8>>,
+    // special fields
+    // @@protoc_insertion_point(special_field:fhers.pir.KeywordQuery.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a KeywordQuery {
+    fn default() -> &'a KeywordQuery {
+        <KeywordQuery as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl KeywordQuery {
+    pub fn new() -> KeywordQuery {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(1);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "queries",
+            |m: &KeywordQuery| { &m.queries },
+            |m: &mut KeywordQuery| { &mut m.queries },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<KeywordQuery>(
+            "KeywordQuery",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for KeywordQuery {
+    const NAME: &'static str = "KeywordQuery";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.queries.push(is.read_bytes()?);
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        for value in &self.queries {
+            my_size += ::protobuf::rt::bytes_size(1, &value);
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        for v in &self.queries {
+            os.write_bytes(1, &v)?;
+        };
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> KeywordQuery {
+        KeywordQuery::new()
+    }
+
+    fn clear(&mut self) {
+        self.queries.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static KeywordQuery {
+        static instance: KeywordQuery = KeywordQuery {
+            queries: ::std::vec::Vec::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for KeywordQuery {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("KeywordQuery").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for KeywordQuery {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for KeywordQuery {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+#[derive(PartialEq,Clone,Default,Debug)]
+// @@protoc_insertion_point(message:fhers.pir.KeywordResponse)
+pub struct KeywordResponse {
+    // message fields
+    // @@protoc_insertion_point(field:fhers.pir.KeywordResponse.responses)
+    pub responses: ::std::vec::Vec<::std::vec::Vec<u8>>,
+    // special fields
+    // @@protoc_insertion_point(special_field:fhers.pir.KeywordResponse.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a KeywordResponse {
+    fn default() -> &'a KeywordResponse {
+        <KeywordResponse as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl KeywordResponse {
+    pub fn new() -> KeywordResponse {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(1);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "responses",
+            |m: &KeywordResponse| { &m.responses },
+            |m: &mut KeywordResponse| { &mut m.responses },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<KeywordResponse>(
+            "KeywordResponse",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for KeywordResponse {
+    const NAME: &'static str = "KeywordResponse";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.responses.push(is.read_bytes()?);
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        for value in &self.responses {
+            my_size += ::protobuf::rt::bytes_size(1, &value);
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        for v in &self.responses {
+            os.write_bytes(1, &v)?;
+        };
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> KeywordResponse {
+        KeywordResponse::new()
+    }
+
+    fn clear(&mut self) {
+        self.responses.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static KeywordResponse {
+        static instance: KeywordResponse = KeywordResponse {
+            responses: ::std::vec::Vec::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for KeywordResponse {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("KeywordResponse").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for KeywordResponse {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for KeywordResponse {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+static file_descriptor_proto_data: &'static [u8] = b"\
+    \n\tpir.proto\x12\tfhers.pir\"\xd9\x01\n\nParameters\x12%\n\x0ebfv_par\
+    ameters\x18\x01\x20\x01(\x0cR\rbfvParameters\x12\x1a\n\x08protocol\x18\
+    \x02\x20\x01(\rR\x08protocol\x12#\n\rdatabase_size\x18\x03\x20\x01(\x04\
+    R\x0cdatabaseSize\x12!\n\x0celement_size\x18\x04\x20\x01(\x04R\x0belem\
+    entSize\x12\x1f\n\x0bdimension_1\x18\x05\x20\x01(\x04R\ndimension1\x12\
+    \x1f\n\x0bdimension_2\x18\x06\x20\x01(\x04R\ndimension2\"d\n\nServerKe\
+    ys\x12%\n\x0eevaluation_key\x18\x01\x20\x01(\x0cR\revaluationKey\x12/\n\
+    \x13relinearization_key\x18\x02\x20\x01(\x0cR\x12relinearizationKey\"'\
+    \n\x05Query\x12\x1e\n\nciphertext\x18\x01\x20\x01(\x0cR\nciphertext\",\
+    \n\x08Response\x12\x20\n\x0bciphertexts\x18\x01\x20\x03(\x0cR\x0bciphe\
+    rtexts\"\xaa\x01\n\x11KeywordParameters\x12%\n\x0epir_parameters\x18\x01\
+    \x20\x01(\x0cR\rpirParameters\x12\x1d\n\nvalue_size\x18\x02\x20\x01(\x04\
+    R\tvalueSize\x122\n\x15number_hash_functions\x18\x03\x20\x01(\rR\x13nu\
+    mberHashFunctions\x12\x1b\n\thash_seed\x18\x04\x20\x01(\x0cR\x08hashSe\
+    ed\"(\n\x0cKeywordQuery\x12\x18\n\x07queries\x18\x01\x20\x03(\x0cR\x07\
+    queries\"/\n\x0fKeywordResponse\x12\x1c\n\tresponses\x18\x01\x20\x03(\x0c\
+    R\tresponsesb\x06proto3\
+";
+
+/// `FileDescriptorProto` object which was a source for this generated file
+fn file_descriptor_proto() -> &'static ::protobuf::descriptor::FileDescriptorProto {
+    static file_descriptor_proto_lazy: ::protobuf::rt::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::rt::Lazy::new();
+    file_descriptor_proto_lazy.get(|| {
+        ::protobuf::Message::parse_from_bytes(file_descriptor_proto_data).unwrap()
+    })
+}
+
+/// `FileDescriptor` object which allows dynamic access to files
+pub fn file_descriptor() -> &'static ::protobuf::reflect::FileDescriptor {
+    static generated_file_descriptor_lazy: ::protobuf::rt::Lazy<::protobuf::reflect::GeneratedFileDescriptor> = ::protobuf::rt::Lazy::new();
+    static file_descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::FileDescriptor> = ::protobuf::rt::Lazy::new();
+    file_descriptor.get(|| {
+        let generated_file_descriptor = generated_file_descriptor_lazy.get(|| {
+            let mut deps = ::std::vec::Vec::with_capacity(0);
+            let mut messages = ::std::vec::Vec::with_capacity(7);
+            messages.push(Parameters::generated_message_descriptor_data());
+            messages.push(ServerKeys::generated_message_descriptor_data());
+            messages.push(Query::generated_message_descriptor_data());
+            messages.push(Response::generated_message_descriptor_data());
+            messages.push(KeywordParameters::generated_message_descriptor_data());
+            messages.push(KeywordQuery::generated_message_descriptor_data());
+            messages.push(KeywordResponse::generated_message_descriptor_data());
+            let mut enums = ::std::vec::Vec::with_capacity(0);
+            ::protobuf::reflect::GeneratedFileDescriptor::new_generated(
+                file_descriptor_proto(),
+                deps,
+                messages,
+                enums,
+            )
+        });
+        ::protobuf::reflect::FileDescriptor::new_generated_2(generated_file_descriptor)
+    })
+}
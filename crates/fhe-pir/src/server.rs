@@ -0,0 +1,176 @@
+//! Server of the Private Information Retrieval protocols.
+
+use crate::{EncodedDatabase, PirParameters, Protocol, Query, Response, Result, ServerKeys};
+use fhe::bfv::{dot_product_scalar, Ciphertext, Encoding, PlaintextVec};
+use fhe_traits::FheEncoderVariableTime;
+use fhe_util::transcode_bidirectional;
+use std::sync::Arc;
+
+/// A server answering queries over an encoded database.
+pub struct Server {
+    par: Arc<PirParameters>,
+    database: EncodedDatabase,
+}
+
+impl Server {
+    /// Create a server for an encoded database.
+    pub fn new(database: EncodedDatabase) -> Self {
+        Self {
+            par: database.par.clone(),
+            database,
+        }
+    }
+
+    /// Returns the parameters of the server.
+    pub fn parameters(&self) -> &Arc<PirParameters> {
+        &self.par
+    }
+
+    /// Answer a query, using the keys of the client which generated it.
+    ///
+    /// The query is first expanded into `dimension_1 + dimension_2`
+    /// ciphertexts, which all encrypt zero except the ones selecting the row
+    /// and the column of the plaintext storing the element. The inner product
+    /// of the first `dimension_1` ciphertexts with each column of the database
+    /// selects the row; the selected row is then folded using the last
+    /// `dimension_2` ciphertexts, as specified by the protocol.
+    pub fn respond(&self, keys: &ServerKeys, query: &Query) -> Result<Response> {
+        let (dimension_1, dimension_2) = self.par.dimensions;
+        let expanded_query = keys.ek.expands(&query.0, dimension_1 + dimension_2)?;
+        let (row_selection, column_selection) = expanded_query.split_at(dimension_1);
+
+        let plaintexts = &self.database.plaintexts.0;
+        let columns = (0..dimension_2).map(|i| {
+            let column = plaintexts.iter().skip(i).step_by(dimension_2);
+            dot_product_scalar(row_selection.iter(), column)
+        });
+
+        match self.par.protocol {
+            Protocol::SealPir => {
+                // Decompose the ciphertexts at the last level into plaintexts,
+                // which are then selected by the last dimension.
+                let folded = columns
+                    .map(|c| {
+                        let mut c = c?;
+                        c.mod_switch_to_last_level()?;
+                        self.decompose(&c)
+                    })
+                    .collect::<Result<Vec<PlaintextVec>>>()?;
+                let response = (0..self.par.response_size())
+                    .map(|i| {
+                        let mut out = dot_product_scalar(
+                            column_selection.iter(),
+                            folded.iter().map(|pts| &pts.0[i]),
+                        )?;
+                        out.mod_switch_to_last_level()?;
+                        Ok(out)
+                    })
+                    .collect::<fhe::Result<Vec<Ciphertext>>>()?;
+                Ok(Response(response))
+            }
+            Protocol::MulPir => {
+                let rk = keys
+                    .rk
+                    .as_ref()
+                    .ok_or(fhe::Error::MissingRelinearizationKey(1))?;
+                let mut out = Ciphertext::zero(&self.par.bfv);
+                for (c, ci) in columns.zip(column_selection) {
+                    out.try_add_assign(&c?.try_mul(ci)?)?
+                }
+                rk.relinearizes(&mut out)?;
+                out.mod_switch_to_last_level()?;
+                Ok(Response(vec![out]))
+            }
+        }
+    }
+
+    /// Encode the coefficients of a ciphertext at the last level as plaintexts
+    /// at level 1.
+    fn decompose(&self, ct: &Ciphertext) -> Result<PlaintextVec> {
+        let mut pt_values = Vec::with_capacity(2 * self.par.coefficients_per_ciphertext_part());
+        for i in 0..2 {
+            pt_values.append(&mut transcode_bidirectional(
+                ct.get(i).unwrap().coefficients().as_slice().unwrap(),
+                self.par.last_modulus_nbits(),
+                self.par.plaintext_nbits(),
+            ));
+        }
+        // The coefficients of the ciphertext are public to the server.
+        Ok(unsafe {
+            PlaintextVec::try_encode_vt(&pt_values, Encoding::poly_at_level(1), &self.par.bfv)?
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Server;
+    use crate::{
+        test_utils::{generate_database, test_parameters},
+        Client, EncodedDatabase, Error, PirParametersBuilder, Protocol,
+    };
+    use rand::{thread_rng, Rng};
+    use std::error::Error as StdError;
+
+    #[test]
+    fn respond() -> Result<(), Box<dyn StdError>> {
+        let mut rng = thread_rng();
+        for protocol in [Protocol::SealPir, Protocol::MulPir] {
+            for (database_size, element_size) in [(1, 1), (100, 100), (500, 20), (10, 512)] {
+                let par = test_parameters(protocol, database_size, element_size)?;
+                let database = generate_database(database_size, element_size);
+                let server = Server::new(EncodedDatabase::new(&database, &par)?);
+
+                let client = Client::new(&par, &mut rng);
+                let keys = client.server_keys(&mut rng)?;
+                for _ in 0..3 {
+                    let index = rng.gen_range(0..database_size);
+                    let query = client.query(index, &mut rng)?;
+                    let response = server.respond(&keys, &query)?;
+                    assert_eq!(response.0.len(), par.response_size());
+                    assert_eq!(client.decode_response(index, &response)?, database[index]);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn respond_with_dimensions() -> Result<(), Box<dyn StdError>> {
+        let mut rng = thread_rng();
+        let database = generate_database(100, 100);
+        for dimensions in [(1, 20), (20, 1), (3, 7)] {
+            let par = PirParametersBuilder::new(Protocol::SealPir)
+                .set_bfv_parameters(&crate::test_utils::test_bfv_parameters())
+                .set_database_size(100)
+                .set_element_size(100)
+                .set_dimensions(dimensions.0, dimensions.1)
+                .build_arc()?;
+            let server = Server::new(EncodedDatabase::new(&database, &par)?);
+            let client = Client::new(&par, &mut rng);
+            let keys = client.server_keys(&mut rng)?;
+            for index in [0, 57, 99] {
+                let response = server.respond(&keys, &client.query(index, &mut rng)?)?;
+                assert_eq!(client.decode_response(index, &response)?, database[index]);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn missing_relinearization_key() -> Result<(), Box<dyn StdError>> {
+        let mut rng = thread_rng();
+        let database = generate_database(100, 100);
+        let sealpir_par = test_parameters(Protocol::SealPir, 100, 100)?;
+        let mulpir_par = test_parameters(Protocol::MulPir, 100, 100)?;
+
+        let server = Server::new(EncodedDatabase::new(&database, &mulpir_par)?);
+        let client = Client::new(&sealpir_par, &mut rng);
+        let keys = client.server_keys(&mut rng)?;
+        assert_eq!(
+            server.respond(&keys, &client.query(0, &mut rng)?).err(),
+            Some(Error::FheError(fhe::Error::MissingRelinearizationKey(1)))
+        );
+        Ok(())
+    }
+}
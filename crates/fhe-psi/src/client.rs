@@ -0,0 +1,258 @@
+//! Client of the Private Set Intersection protocol.
+
+use crate::{Error, PsiParameters, Query, Response, Result, ServerKeys};
+use fhe::bfv::{Encoding, Plaintext, PublicKey, RelinearizationKey, SecretKey};
+use fhe_math::zq::Modulus;
+use fhe_traits::{FheDecoder, FheDecrypter, FheEncoder, FheEncrypter};
+use rand::{CryptoRng, RngCore};
+use std::sync::Arc;
+
+/// Maximum number of evictions when inserting an item in the cuckoo hash
+/// table.
+const MAX_EVICTIONS: usize = 500;
+
+/// A client learning which of its items belong to the set of the server,
+/// without revealing its items to the server.
+pub struct Client {
+    par: Arc<PsiParameters>,
+    sk: SecretKey,
+}
+
+impl Client {
+    /// Generate a new client with a fresh secret key.
+    pub fn new<R: RngCore + CryptoRng>(par: &Arc<PsiParameters>, rng: &mut R) -> Self {
+        Self {
+            par: par.clone(),
+            sk: SecretKey::random(&par.bfv, rng),
+        }
+    }
+
+    /// Returns the parameters of the client.
+    pub fn parameters(&self) -> &Arc<PsiParameters> {
+        &self.par
+    }
+
+    /// Generate the keys which the server needs to answer the queries of this
+    /// client.
+    pub fn server_keys<R: RngCore + CryptoRng>(&self, rng: &mut R) -> Result<ServerKeys> {
+        Ok(ServerKeys {
+            rk: RelinearizationKey::new(&self.sk, rng)?,
+            pk: PublicKey::new(&self.sk, rng),
+        })
+    }
+
+    /// Generate a query for the items of the client, which must contain at
+    /// most `par.client_capacity()` items.
+    ///
+    /// The items are inserted in a cuckoo hash table, and the query encrypts
+    /// the powers `y^(2^i)` of the field elements `y` representing the items
+    /// in the slots of their bins, from which the server computes all the
+    /// powers up to the partition size.
+    pub fn query<T: AsRef<[u8]>, R: RngCore + CryptoRng>(
+        &self,
+        items: &[T],
+        rng: &mut R,
+    ) -> Result<Query> {
+        let table = self.cuckoo_hash(items)?;
+
+        let felts_per_item = self.par.felts_per_item;
+        let mut slots = vec![0u64; self.par.bfv.degree()];
+        for (bin, entry) in table.iter().enumerate() {
+            if let Some((i, hash_index)) = entry {
+                slots[bin * felts_per_item..(bin + 1) * felts_per_item]
+                    .copy_from_slice(&self.par.felts(items[*i].as_ref(), *hash_index));
+            }
+        }
+
+        let t = Modulus::new(self.par.bfv.plaintext())?;
+        let mut cts = Vec::with_capacity(self.par.number_query_powers());
+        for i in 0..self.par.number_query_powers() {
+            if i > 0 {
+                slots = slots.iter().map(|y| t.mul(*y, *y)).collect();
+            }
+            let pt = Plaintext::try_encode(&slots, Encoding::simd(), &self.par.bfv)?;
+            cts.push(self.sk.try_encrypt(&pt, rng)?);
+        }
+        Ok(Query(cts))
+    }
+
+    /// Decode the response of the server to the query for these items, and
+    /// returns for every item whether it belongs to the set of the server.
+    ///
+    /// An item belongs to the set of the server when, for some partition, the
+    /// slots of its bin all decrypt to zero; the server masks the decrypted
+    /// values of the other slots, which are uniformly random and non-zero.
+    pub fn decode_response<T: AsRef<[u8]>>(
+        &self,
+        items: &[T],
+        response: &Response,
+    ) -> Result<Vec<bool>> {
+        let table = self.cuckoo_hash(items)?;
+        let decrypted = response
+            .0
+            .iter()
+            .map(|ct| {
+                let pt = self.sk.try_decrypt(ct)?;
+                Vec::<u64>::try_decode(&pt, Encoding::simd())
+            })
+            .collect::<fhe::Result<Vec<Vec<u64>>>>()?;
+
+        let felts_per_item = self.par.felts_per_item;
+        let mut membership = vec![false; items.len()];
+        for (bin, entry) in table.iter().enumerate() {
+            if let Some((i, _)) = entry {
+                membership[*i] = decrypted.iter().any(|slots| {
+                    slots[bin * felts_per_item..(bin + 1) * felts_per_item]
+                        .iter()
+                        .all(|v| *v == 0)
+                });
+            }
+        }
+        Ok(membership)
+    }
+
+    /// Insert the items in a cuckoo hash table, and returns for every bin the
+    /// index of the item it stores along with the index of the hash function
+    /// mapping the item to this bin, if any.
+    ///
+    /// The evictions are deterministic, so that the query and the decoding of
+    /// the response compute the same table.
+    fn cuckoo_hash<T: AsRef<[u8]>>(&self, items: &[T]) -> Result<Vec<Option<(usize, usize)>>> {
+        let capacity = self.par.client_capacity();
+        if items.len() > capacity {
+            return Err(Error::TooManyItems {
+                capacity,
+                found: items.len(),
+            });
+        }
+
+        let candidates = items
+            .iter()
+            .map(|item| self.par.bins(item.as_ref()))
+            .collect::<Vec<Vec<usize>>>();
+        let number_hash_functions = self.par.number_hash_functions;
+        let mut table = vec![None; self.par.number_bins()];
+
+        'items: for i in 0..items.len() {
+            // The item to insert, and the hash function whose bin it evicts
+            // when all its bins are occupied.
+            let (mut current, mut next_hash_index) = (i, 0);
+            for _ in 0..MAX_EVICTIONS {
+                let bins = &candidates[current];
+                if let Some(hash_index) =
+                    (0..number_hash_functions).find(|hash_index| table[bins[*hash_index]].is_none())
+                {
+                    table[bins[hash_index]] = Some((current, hash_index));
+                    continue 'items;
+                }
+                let hash_index = next_hash_index % number_hash_functions;
+                let (evicted, evicted_hash_index) = table[bins[hash_index]]
+                    .replace((current, hash_index))
+                    .unwrap();
+                (current, next_hash_index) = (evicted, evicted_hash_index + 1);
+            }
+            return Err(Error::CuckooHashingFailed);
+        }
+
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Client;
+    use crate::{Error, PsiParametersBuilder};
+    use fhe::bfv::BfvParametersBuilder;
+    use rand::thread_rng;
+    use std::error::Error as StdError;
+
+    #[test]
+    fn cuckoo_hash() -> Result<(), Box<dyn StdError>> {
+        let mut rng = thread_rng();
+        // The client only needs the degree and the plaintext modulus to hash
+        // its items.
+        let bfv = BfvParametersBuilder::new()
+            .set_degree(256)
+            .set_plaintext_modulus(65537)
+            .set_moduli_sizes(&[62])
+            .allow_insecure()
+            .build_arc()?;
+        let par = PsiParametersBuilder::new()
+            .set_bfv_parameters(&bfv)
+            .set_flooding_bits(40)
+            .build_arc()?;
+        let client = Client::new(&par, &mut rng);
+        let items = (0..par.client_capacity() as u32)
+            .map(u32::to_le_bytes)
+            .collect::<Vec<_>>();
+
+        let table = client.cuckoo_hash(&items)?;
+        assert_eq!(table, client.cuckoo_hash(&items)?);
+        assert_eq!(table.len(), par.number_bins());
+        assert_eq!(table.iter().flatten().count(), items.len());
+        for (bin, entry) in table.iter().enumerate() {
+            if let Some((i, hash_index)) = entry {
+                assert_eq!(par.bins(&items[*i])[*hash_index], bin);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn full_capacity() -> Result<(), Box<dyn StdError>> {
+        let mut rng = thread_rng();
+        for degree in [256, 8192] {
+            let bfv = BfvParametersBuilder::new()
+                .set_degree(degree)
+                .set_plaintext_modulus(65537)
+                .set_moduli_sizes(&[62])
+                .allow_insecure()
+                .build_arc()?;
+            for number_hash_functions in 2..=8 {
+                let par = PsiParametersBuilder::new()
+                    .set_bfv_parameters(&bfv)
+                    .set_flooding_bits(40)
+                    .set_number_hash_functions(number_hash_functions)
+                    .build_arc()?;
+                let client = Client::new(&par, &mut rng);
+                let items = (0..par.client_capacity() as u32)
+                    .map(u32::to_le_bytes)
+                    .collect::<Vec<_>>();
+                let table = client.cuckoo_hash(&items)?;
+                assert_eq!(table.iter().flatten().count(), par.client_capacity());
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn query() -> Result<(), Box<dyn StdError>> {
+        let mut rng = thread_rng();
+        let bfv = BfvParametersBuilder::new()
+            .set_degree(256)
+            .set_plaintext_modulus(65537)
+            .set_moduli_sizes(&[62])
+            .allow_insecure()
+            .build_arc()?;
+        let par = PsiParametersBuilder::new()
+            .set_bfv_parameters(&bfv)
+            .set_flooding_bits(40)
+            .build_arc()?;
+        let client = Client::new(&par, &mut rng);
+
+        let query = client.query(&[b"item"], &mut rng)?;
+        assert_eq!(query.0.len(), par.number_query_powers());
+
+        let items = (0..=par.client_capacity() as u32)
+            .map(u32::to_le_bytes)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            client.query(&items, &mut rng).err(),
+            Some(Error::TooManyItems {
+                capacity: par.client_capacity(),
+                found: items.len()
+            })
+        );
+        Ok(())
+    }
+}
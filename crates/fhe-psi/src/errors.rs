@@ -0,0 +1,96 @@
+use thiserror::Error;
+
+/// The Result type for this library.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Enum encapsulating all the possible errors from this library.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    /// Indicates that an error from the underlying homomorphic encryption
+    /// library was encountered.
+    #[error("{0}")]
+    FheError(fhe::Error),
+
+    /// Indicates a serialization error.
+    #[error("Serialization error")]
+    SerializationError,
+
+    /// Indicates that the parameters are invalid.
+    #[error("Invalid parameters: {0}")]
+    InvalidParameters(String),
+
+    /// Indicates that the client set has more items than the capacity of the
+    /// parameters.
+    #[error("Too many items: found {found}, capacity {capacity}")]
+    TooManyItems {
+        /// The maximum number of items of the client set.
+        capacity: usize,
+        /// The number of items of the client set.
+        found: usize,
+    },
+
+    /// Indicates that the items of the client set could not be inserted in a
+    /// cuckoo hash table.
+    #[error("Cuckoo hashing failed")]
+    CuckooHashingFailed,
+
+    /// Indicates that a query does not have the expected number of
+    /// ciphertexts.
+    #[error("Query size mismatch: found {found}, expected {expected}")]
+    QuerySizeMismatch {
+        /// The expected number of ciphertexts.
+        expected: usize,
+        /// The number of ciphertexts of the query.
+        found: usize,
+    },
+}
+
+impl From<fhe::Error> for Error {
+    fn from(e: fhe::Error) -> Self {
+        Error::FheError(e)
+    }
+}
+
+impl From<fhe_math::Error> for Error {
+    fn from(e: fhe_math::Error) -> Self {
+        Error::FheError(fhe::Error::MathError(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Error;
+
+    #[test]
+    fn error_strings() {
+        assert_eq!(
+            Error::FheError(fhe::Error::SerializationError).to_string(),
+            fhe::Error::SerializationError.to_string()
+        );
+        assert_eq!(Error::SerializationError.to_string(), "Serialization error");
+        assert_eq!(
+            Error::InvalidParameters("test".to_string()).to_string(),
+            "Invalid parameters: test"
+        );
+        assert_eq!(
+            Error::TooManyItems {
+                capacity: 2,
+                found: 3
+            }
+            .to_string(),
+            "Too many items: found 3, capacity 2"
+        );
+        assert_eq!(
+            Error::CuckooHashingFailed.to_string(),
+            "Cuckoo hashing failed"
+        );
+        assert_eq!(
+            Error::QuerySizeMismatch {
+                expected: 2,
+                found: 1
+            }
+            .to_string(),
+            "Query size mismatch: found 1, expected 2"
+        );
+    }
+}
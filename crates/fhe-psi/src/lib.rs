@@ -0,0 +1,57 @@
+#![crate_name = "fhe_psi"]
+#![crate_type = "lib"]
+#![warn(missing_docs, unused_imports)]
+
+//! Unbalanced Private Set Intersection (PSI) using the BFV scheme of the
+//! fhe.rs library.
+//!
+//! A PSI protocol enables a [`Client`] holding a small set of items to learn
+//! which of its items belong to the large set held by a [`Server`], without
+//! revealing its items to the server. This crate follows the protocol of Chen,
+//! Laine and Rindal (<https://eprint.iacr.org/2017/299>): the items are hashed
+//! in the bins of the slots of SIMD encoded plaintexts, and the server
+//! evaluates, in each slot, a polynomial whose roots are its items in that bin.
+//!
+//! The server masks the decrypted values of the slots, which only reveal
+//! whether they are zero, and floods the noise of its responses with an
+//! encryption of zero carrying a large noise, so that the noise of the
+//! ciphertexts, which depends on the set of the server, is statistically
+//! hidden from the client.
+//!
+//! ```
+//! # use fhe_psi::{Client, PsiParametersBuilder, Server};
+//! # use rand::thread_rng;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut rng = thread_rng();
+//! let server_items = (0..1000u32).map(|i| i.to_le_bytes()).collect::<Vec<_>>();
+//! let client_items = (990..1010u32).map(|i| i.to_le_bytes()).collect::<Vec<_>>();
+//! let par = PsiParametersBuilder::new().build_arc()?;
+//!
+//! // The server preprocesses its set once.
+//! let server = Server::new(&server_items, &par)?;
+//!
+//! // The client generates its keys, which it sends to the server, and a query.
+//! let client = Client::new(&par, &mut rng);
+//! let keys = client.server_keys(&mut rng)?;
+//! let query = client.query(&client_items, &mut rng)?;
+//!
+//! let response = server.respond(&keys, &query, &mut rng)?;
+//! let membership = client.decode_response(&client_items, &response)?;
+//! assert_eq!(membership, (990..1010).map(|i| i < 1000).collect::<Vec<_>>());
+//! # Ok(())
+//! # }
+//! ```
+
+mod client;
+mod errors;
+mod messages;
+mod parameters;
+#[allow(renamed_and_removed_lints)]
+mod proto;
+mod server;
+
+pub use client::Client;
+pub use errors::{Error, Result};
+pub use messages::{Query, Response, ServerKeys};
+pub use parameters::{PsiParameters, PsiParametersBuilder};
+pub use server::Server;
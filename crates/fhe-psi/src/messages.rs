@@ -0,0 +1,178 @@
+//! Messages exchanged between the client and the server.
+
+use crate::{
+    proto::psi::{Query as QueryProto, Response as ResponseProto, ServerKeys as ServerKeysProto},
+    Error, PsiParameters, Result,
+};
+use fhe::bfv::{Ciphertext, PublicKey, RelinearizationKey};
+use fhe_traits::{DeserializeParametrized, FheParametrized, Serialize};
+use protobuf::Message;
+use std::sync::Arc;
+
+/// The keys generated by the client, which the server needs to answer the
+/// queries of this client.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ServerKeys {
+    /// The relinearization key enabling to compute the powers of the items.
+    pub(crate) rk: RelinearizationKey,
+    /// The public key enabling to flood the noise of the responses.
+    pub(crate) pk: PublicKey,
+}
+
+/// A query, encrypting powers of the items of the client in the slots of its
+/// bins.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Query(pub(crate) Vec<Ciphertext>);
+
+/// A response of the server to a [`Query`], with one ciphertext for each
+/// partition of the bins.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Response(pub(crate) Vec<Ciphertext>);
+
+impl FheParametrized for ServerKeys {
+    type Parameters = PsiParameters;
+}
+
+impl FheParametrized for Query {
+    type Parameters = PsiParameters;
+}
+
+impl FheParametrized for Response {
+    type Parameters = PsiParameters;
+}
+
+impl Serialize for ServerKeys {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut proto = ServerKeysProto::new();
+        proto.relinearization_key = self.rk.to_bytes();
+        proto.public_key = self.pk.to_bytes();
+        proto.write_to_bytes().unwrap()
+    }
+}
+
+impl DeserializeParametrized for ServerKeys {
+    type Error = Error;
+
+    fn from_bytes(bytes: &[u8], par: &Arc<PsiParameters>) -> Result<Self> {
+        let proto =
+            ServerKeysProto::parse_from_bytes(bytes).map_err(|_| Error::SerializationError)?;
+        Ok(Self {
+            rk: RelinearizationKey::from_bytes(&proto.relinearization_key, &par.bfv)?,
+            pk: PublicKey::from_bytes(&proto.public_key, &par.bfv)?,
+        })
+    }
+}
+
+impl Serialize for Query {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut proto = QueryProto::new();
+        proto.ciphertexts = self.0.iter().map(|ct| ct.to_bytes()).collect();
+        proto.write_to_bytes().unwrap()
+    }
+}
+
+impl DeserializeParametrized for Query {
+    type Error = Error;
+
+    fn from_bytes(bytes: &[u8], par: &Arc<PsiParameters>) -> Result<Self> {
+        let proto = QueryProto::parse_from_bytes(bytes).map_err(|_| Error::SerializationError)?;
+        Ok(Self(
+            proto
+                .ciphertexts
+                .iter()
+                .map(|ct| Ciphertext::from_bytes(ct, &par.bfv))
+                .collect::<fhe::Result<Vec<Ciphertext>>>()?,
+        ))
+    }
+}
+
+impl Serialize for Response {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut proto = ResponseProto::new();
+        proto.ciphertexts = self.0.iter().map(|ct| ct.to_bytes()).collect();
+        proto.write_to_bytes().unwrap()
+    }
+}
+
+impl DeserializeParametrized for Response {
+    type Error = Error;
+
+    fn from_bytes(bytes: &[u8], par: &Arc<PsiParameters>) -> Result<Self> {
+        let proto =
+            ResponseProto::parse_from_bytes(bytes).map_err(|_| Error::SerializationError)?;
+        Ok(Self(
+            proto
+                .ciphertexts
+                .iter()
+                .map(|ct| Ciphertext::from_bytes(ct, &par.bfv))
+                .collect::<fhe::Result<Vec<Ciphertext>>>()?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Query, Response, ServerKeys};
+    use crate::{Client, Error, PsiParametersBuilder, Server};
+    use fhe::bfv::BfvParametersBuilder;
+    use fhe_traits::{DeserializeParametrized, Serialize};
+    use rand::thread_rng;
+    use std::error::Error as StdError;
+
+    #[test]
+    fn serialize() -> Result<(), Box<dyn StdError>> {
+        let mut rng = thread_rng();
+        // With partitions of one item, the server does not multiply
+        // ciphertexts, and two moduli leave room for the flooding noise.
+        let bfv = BfvParametersBuilder::new()
+            .set_degree(256)
+            .set_plaintext_modulus(65537)
+            .set_moduli_sizes(&[62, 62])
+            .allow_insecure()
+            .build_arc()?;
+        let par = PsiParametersBuilder::new()
+            .set_bfv_parameters(&bfv)
+            .set_partition_size(1)
+            .set_flooding_bits(100)
+            .build_arc()?;
+        let server_items = (0..100u32).map(u32::to_le_bytes).collect::<Vec<_>>();
+        let client_items = (90..110u32).map(u32::to_le_bytes).collect::<Vec<_>>();
+        let server = Server::new(&server_items, &par)?;
+        let client = Client::new(&par, &mut rng);
+
+        let keys = client.server_keys(&mut rng)?;
+        let keys_bytes = keys.to_bytes();
+        assert_eq!(keys, ServerKeys::from_bytes(&keys_bytes, &par)?);
+
+        let query = client.query(&client_items, &mut rng)?;
+        let query_bytes = query.to_bytes();
+        assert_eq!(query, Query::from_bytes(&query_bytes, &par)?);
+
+        // The server and the client only exchange bytes.
+        let response = server.respond(
+            &ServerKeys::from_bytes(&keys_bytes, &par)?,
+            &Query::from_bytes(&query_bytes, &par)?,
+            &mut rng,
+        )?;
+        let response = Response::from_bytes(&response.to_bytes(), &par)?;
+        let membership = client.decode_response(&client_items, &response)?;
+        assert_eq!(
+            membership,
+            (90..110).map(|i| i < 100).collect::<Vec<bool>>()
+        );
+
+        assert_eq!(
+            Query::from_bytes(&[1, 2, 3], &par).err(),
+            Some(Error::SerializationError)
+        );
+        assert_eq!(
+            Response::from_bytes(&[1, 2, 3], &par).err(),
+            Some(Error::SerializationError)
+        );
+        assert_eq!(
+            ServerKeys::from_bytes(&[1, 2, 3], &par).err(),
+            Some(Error::SerializationError)
+        );
+        Ok(())
+    }
+}
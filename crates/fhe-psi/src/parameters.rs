@@ -0,0 +1,422 @@
+//! Parameters of the Private Set Intersection protocol.
+
+use crate::{proto::psi::Parameters as ParametersProto, Error, Result};
+use fhe::bfv::{BfvParameters, BfvParametersBuilder, Encoding, Plaintext};
+use fhe_math::rns::RnsContext;
+use fhe_traits::{Deserialize, FheEncoder, FheParameters, Serialize};
+use protobuf::Message;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+/// The ratio, as a fraction, between the number of bins and the capacity of the
+/// cuckoo hash table of the client. Cuckoo hashing with 2 hash functions fails
+/// with high probability when more than half of the bins are occupied, and
+/// still fails often close to this threshold, whereas 3 hash functions and
+/// more support loads above 90%.
+const fn bins_per_item(number_hash_functions: usize) -> (usize, usize) {
+    if number_hash_functions == 2 {
+        (3, 1)
+    } else {
+        (3, 2)
+    }
+}
+
+/// Parameters of the Private Set Intersection protocol.
+///
+/// The items are hashed into `felts_per_item` elements of the plaintext field,
+/// which occupy as many consecutive slots of a SIMD encoded plaintext; the
+/// `degree / felts_per_item` groups of slots are the bins of the hash tables.
+/// The client inserts its items in a cuckoo hash table using
+/// `number_hash_functions` hash functions, seeded by `hash_seed`, while the
+/// server inserts each of its items in the bins of all the hash functions. The
+/// items of the server in every bin are then split in partitions of at most
+/// `partition_size` items. The server floods the noise of its responses with a
+/// noise of `flooding_bits` bits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PsiParameters {
+    pub(crate) bfv: Arc<BfvParameters>,
+    pub(crate) felts_per_item: usize,
+    pub(crate) number_hash_functions: usize,
+    pub(crate) partition_size: usize,
+    pub(crate) hash_seed: [u8; 32],
+    pub(crate) flooding_bits: usize,
+}
+
+impl FheParameters for PsiParameters {}
+
+impl PsiParameters {
+    /// Returns the underlying BFV parameters.
+    pub fn bfv_parameters(&self) -> &Arc<BfvParameters> {
+        &self.bfv
+    }
+
+    /// Returns the number of plaintext field elements representing an item.
+    pub const fn felts_per_item(&self) -> usize {
+        self.felts_per_item
+    }
+
+    /// Returns the number of hash functions.
+    pub const fn number_hash_functions(&self) -> usize {
+        self.number_hash_functions
+    }
+
+    /// Returns the maximum number of items of the server in a partition, which
+    /// is the degree of the polynomials evaluated by the server.
+    pub const fn partition_size(&self) -> usize {
+        self.partition_size
+    }
+
+    /// Returns the seed of the hash functions.
+    pub const fn hash_seed(&self) -> &[u8; 32] {
+        &self.hash_seed
+    }
+
+    /// Returns the number of bits of the flooding noise of the responses.
+    pub const fn flooding_bits(&self) -> usize {
+        self.flooding_bits
+    }
+
+    /// Returns the number of bins of the hash tables.
+    pub fn number_bins(&self) -> usize {
+        self.bfv.degree() / self.felts_per_item
+    }
+
+    /// Returns the maximum number of items of the client set, which is a third
+    /// of the number of bins when using 2 hash functions, and two thirds
+    /// otherwise.
+    pub fn client_capacity(&self) -> usize {
+        let (bins, items) = bins_per_item(self.number_hash_functions);
+        (items * self.number_bins()) / bins
+    }
+
+    /// Returns the number of ciphertexts of a query, which encrypt the powers
+    /// `y^(2^i)` of the items `y` of the client for `2^i <= partition_size`.
+    pub fn number_query_powers(&self) -> usize {
+        self.partition_size.ilog2() as usize + 1
+    }
+
+    /// The bins where the item may be stored, one for each hash function.
+    pub(crate) fn bins(&self, item: &[u8]) -> Vec<usize> {
+        (0..self.number_hash_functions as u32)
+            .map(|i| {
+                let digest = Sha256::new()
+                    .chain_update(b"fhe-psi bin")
+                    .chain_update(self.hash_seed)
+                    .chain_update(i.to_le_bytes())
+                    .chain_update(item)
+                    .finalize();
+                let h = u64::from_le_bytes(digest[..8].try_into().unwrap());
+                (h % self.number_bins() as u64) as usize
+            })
+            .collect()
+    }
+
+    /// The plaintext field elements representing the item when it is stored
+    /// in the bin of the `hash_index`-th hash function. Including the index of
+    /// the hash function ensures that an item only matches itself when the
+    /// server and the client store it using the same hash function.
+    pub(crate) fn felts(&self, item: &[u8], hash_index: usize) -> Vec<u64> {
+        (0..self.felts_per_item as u32)
+            .map(|j| {
+                let digest = Sha256::new()
+                    .chain_update(b"fhe-psi felt")
+                    .chain_update(self.hash_seed)
+                    .chain_update((hash_index as u32).to_le_bytes())
+                    .chain_update(j.to_le_bytes())
+                    .chain_update(item)
+                    .finalize();
+                let h = u64::from_le_bytes(digest[..8].try_into().unwrap());
+                h % self.bfv.plaintext()
+            })
+            .collect()
+    }
+}
+
+/// Builder for the parameters of the Private Set Intersection protocol.
+#[derive(Debug)]
+pub struct PsiParametersBuilder {
+    bfv: Option<Arc<BfvParameters>>,
+    felts_per_item: usize,
+    number_hash_functions: usize,
+    partition_size: usize,
+    hash_seed: [u8; 32],
+    flooding_bits: usize,
+}
+
+impl PsiParametersBuilder {
+    /// Creates a new instance of the builder.
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            bfv: None,
+            felts_per_item: 4,
+            number_hash_functions: 3,
+            partition_size: 16,
+            hash_seed: [0u8; 32],
+            flooding_bits: 180,
+        }
+    }
+
+    /// Sets the BFV parameters, whose plaintext modulus must support the SIMD
+    /// encoding, and whose ciphertext modulus must leave room for the
+    /// flooding noise. When not specified, parameters of degree 8192 with a
+    /// plaintext modulus of 17 bits and a ciphertext modulus of 218 bits are
+    /// used.
+    pub fn set_bfv_parameters(&mut self, par: &Arc<BfvParameters>) -> &mut Self {
+        self.bfv = Some(par.clone());
+        self
+    }
+
+    /// Sets the number of plaintext field elements representing an item. When
+    /// not specified, the items are represented by 4 elements.
+    ///
+    /// An item of the client is wrongly reported in the intersection with a
+    /// probability of about `(partition_size / t)^felts_per_item` for every
+    /// partition of its bin, where `t` is the plaintext modulus.
+    pub fn set_felts_per_item(&mut self, felts_per_item: usize) -> &mut Self {
+        self.felts_per_item = felts_per_item;
+        self
+    }
+
+    /// Sets the number of hash functions. When not specified, 3 hash functions
+    /// are used.
+    pub fn set_number_hash_functions(&mut self, number_hash_functions: usize) -> &mut Self {
+        self.number_hash_functions = number_hash_functions;
+        self
+    }
+
+    /// Sets the maximum number of items of the server in a partition. When not
+    /// specified, the partitions have at most 16 items.
+    ///
+    /// Larger partitions reduce the size of the responses, but increase the
+    /// multiplicative depth of the evaluation of the polynomials.
+    pub fn set_partition_size(&mut self, partition_size: usize) -> &mut Self {
+        self.partition_size = partition_size;
+        self
+    }
+
+    /// Sets the seed of the hash functions. When not specified, the seed is
+    /// zero.
+    pub fn set_hash_seed(&mut self, hash_seed: &[u8; 32]) -> &mut Self {
+        self.hash_seed = *hash_seed;
+        self
+    }
+
+    /// Sets the number of bits of the flooding noise, which the server adds to
+    /// its responses before switching them to the last level. When not
+    /// specified, the flooding noise has 180 bits.
+    ///
+    /// The flooding noise hides the noise of the evaluation of the
+    /// polynomials, which depends on the set of the server, when it exceeds
+    /// this noise by a statistical security margin, e.g., 40 bits. The
+    /// ciphertext modulus must exceed the flooding noise by the number of bits
+    /// of the plaintext modulus plus one.
+    pub fn set_flooding_bits(&mut self, flooding_bits: usize) -> &mut Self {
+        self.flooding_bits = flooding_bits;
+        self
+    }
+
+    /// Build a new [`PsiParameters`].
+    pub fn build(&self) -> Result<PsiParameters> {
+        let bfv = match &self.bfv {
+            Some(bfv) => bfv.clone(),
+            None => BfvParametersBuilder::new()
+                .set_degree(8192)
+                .set_plaintext_modulus(65537)
+                .set_moduli_sizes(&[54, 54, 55, 55])
+                .build_arc()?,
+        };
+
+        if Plaintext::try_encode(&[0u64], Encoding::simd(), &bfv).is_err() {
+            return Err(Error::InvalidParameters(
+                "the plaintext modulus must support the SIMD encoding".to_string(),
+            ));
+        }
+        if self.felts_per_item == 0 || self.felts_per_item > bfv.degree() / 2 {
+            return Err(Error::InvalidParameters(format!(
+                "the number of field elements per item must be between 1 and {}",
+                bfv.degree() / 2
+            )));
+        }
+        if !(2..=8).contains(&self.number_hash_functions) {
+            return Err(Error::InvalidParameters(
+                "the number of hash functions must be between 2 and 8".to_string(),
+            ));
+        }
+        if self.partition_size == 0 {
+            return Err(Error::InvalidParameters(
+                "the partition size must be at least 1".to_string(),
+            ));
+        }
+        // Reserve the flooding noise in the noise budget of the ciphertexts.
+        let modulus_bits = RnsContext::new(bfv.moduli())?.modulus().bits() as usize;
+        let plaintext_bits = 64 - bfv.plaintext().leading_zeros() as usize;
+        if self.flooding_bits + plaintext_bits + 1 >= modulus_bits {
+            return Err(Error::InvalidParameters(format!(
+                "the flooding noise of {} bits exceeds the noise budget of the BFV parameters",
+                self.flooding_bits
+            )));
+        }
+
+        Ok(PsiParameters {
+            bfv,
+            felts_per_item: self.felts_per_item,
+            number_hash_functions: self.number_hash_functions,
+            partition_size: self.partition_size,
+            hash_seed: self.hash_seed,
+            flooding_bits: self.flooding_bits,
+        })
+    }
+
+    /// Build a new [`PsiParameters`] inside an [`Arc`].
+    pub fn build_arc(&self) -> Result<Arc<PsiParameters>> {
+        self.build().map(Arc::new)
+    }
+}
+
+impl Serialize for PsiParameters {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut proto = ParametersProto::new();
+        proto.bfv_parameters = self.bfv.to_bytes();
+        proto.felts_per_item = self.felts_per_item as u32;
+        proto.number_hash_functions = self.number_hash_functions as u32;
+        proto.partition_size = self.partition_size as u64;
+        proto.hash_seed = self.hash_seed.to_vec();
+        proto.flooding_bits = self.flooding_bits as u32;
+        proto.write_to_bytes().unwrap()
+    }
+}
+
+/// The deserialized BFV parameters must provide at least 128 bits of security.
+impl Deserialize for PsiParameters {
+    type Error = Error;
+
+    fn try_deserialize(bytes: &[u8]) -> Result<Self> {
+        let proto =
+            ParametersProto::parse_from_bytes(bytes).map_err(|_| Error::SerializationError)?;
+        let bfv = Arc::new(BfvParameters::try_deserialize(&proto.bfv_parameters)?);
+        let hash_seed: [u8; 32] = proto
+            .hash_seed
+            .try_into()
+            .map_err(|_| Error::SerializationError)?;
+        PsiParametersBuilder::new()
+            .set_bfv_parameters(&bfv)
+            .set_felts_per_item(proto.felts_per_item as usize)
+            .set_number_hash_functions(proto.number_hash_functions as usize)
+            .set_partition_size(proto.partition_size as usize)
+            .set_hash_seed(&hash_seed)
+            .set_flooding_bits(proto.flooding_bits as usize)
+            .build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PsiParametersBuilder;
+    use crate::{Error, PsiParameters};
+    use fhe::bfv::BfvParametersBuilder;
+    use fhe_traits::{Deserialize, Serialize};
+    use std::error::Error as StdError;
+
+    #[test]
+    fn builder() -> Result<(), Box<dyn StdError>> {
+        let par = PsiParametersBuilder::new().build()?;
+        assert_eq!(par.bfv_parameters().degree(), 8192);
+        assert_eq!(par.felts_per_item(), 4);
+        assert_eq!(par.number_hash_functions(), 3);
+        assert_eq!(par.partition_size(), 16);
+        assert_eq!(par.hash_seed(), &[0u8; 32]);
+        assert_eq!(par.number_bins(), 2048);
+        assert_eq!(par.client_capacity(), 1365);
+        assert_eq!(par.number_query_powers(), 5);
+        assert_eq!(par.flooding_bits(), 180);
+
+        let bfv = BfvParametersBuilder::new()
+            .set_degree(256)
+            .set_plaintext_modulus(65537)
+            .set_moduli_sizes(&[62, 62, 62])
+            .allow_insecure()
+            .build_arc()?;
+        let par = PsiParametersBuilder::new()
+            .set_bfv_parameters(&bfv)
+            .set_flooding_bits(160)
+            .set_felts_per_item(3)
+            .set_partition_size(15)
+            .build()?;
+        assert_eq!(par.number_bins(), 85);
+        assert_eq!(par.client_capacity(), 56);
+        assert_eq!(par.number_query_powers(), 4);
+
+        let par = PsiParametersBuilder::new()
+            .set_number_hash_functions(2)
+            .build()?;
+        assert_eq!(par.client_capacity(), 682);
+
+        let mut builder = PsiParametersBuilder::new();
+        builder
+            .set_bfv_parameters(&bfv)
+            .set_flooding_bits(160)
+            .set_felts_per_item(0);
+        assert!(matches!(builder.build(), Err(Error::InvalidParameters(_))));
+        builder.set_felts_per_item(129);
+        assert!(matches!(builder.build(), Err(Error::InvalidParameters(_))));
+        builder.set_felts_per_item(4).set_number_hash_functions(1);
+        assert!(matches!(builder.build(), Err(Error::InvalidParameters(_))));
+        builder.set_number_hash_functions(3).set_partition_size(0);
+        assert!(matches!(builder.build(), Err(Error::InvalidParameters(_))));
+
+        // The flooding noise and the plaintext modulus of 17 bits must fit in
+        // the ciphertext modulus of 186 bits.
+        builder.set_partition_size(16).set_flooding_bits(167);
+        assert_eq!(builder.build()?.flooding_bits(), 167);
+        builder.set_flooding_bits(168);
+        assert!(matches!(builder.build(), Err(Error::InvalidParameters(_))));
+        builder.set_flooding_bits(160);
+
+        let no_simd = BfvParametersBuilder::new()
+            .set_degree(256)
+            .set_plaintext_modulus(1153)
+            .set_moduli_sizes(&[62, 62, 62])
+            .allow_insecure()
+            .build_arc()?;
+        builder.set_bfv_parameters(&no_simd).set_partition_size(16);
+        assert!(matches!(builder.build(), Err(Error::InvalidParameters(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn hash_functions() -> Result<(), Box<dyn StdError>> {
+        let par = PsiParametersBuilder::new().build()?;
+        let bins = par.bins(b"item");
+        assert_eq!(bins.len(), 3);
+        assert!(bins.iter().all(|b| *b < par.number_bins()));
+        assert_eq!(bins, par.bins(b"item"));
+
+        let felts = par.felts(b"item", 0);
+        assert_eq!(felts.len(), 4);
+        assert!(felts.iter().all(|f| *f < par.bfv_parameters().plaintext()));
+        assert_eq!(felts, par.felts(b"item", 0));
+        assert_ne!(felts, par.felts(b"item", 1));
+        assert_ne!(felts, par.felts(b"other item", 0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn serialize() -> Result<(), Box<dyn StdError>> {
+        let par = PsiParametersBuilder::new()
+            .set_felts_per_item(5)
+            .set_number_hash_functions(2)
+            .set_partition_size(31)
+            .set_hash_seed(&[42u8; 32])
+            .set_flooding_bits(170)
+            .build()?;
+        assert_eq!(par, PsiParameters::try_deserialize(&par.to_bytes())?);
+        assert_eq!(
+            PsiParameters::try_deserialize(&[1, 2, 3]).err(),
+            Some(Error::SerializationError)
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,668 @@
+// This file is generated by rust-protobuf 3.2.0. Do not edit
+// .proto file is parsed by protoc --rust-out=...
+// @generated
+
+// https://github.com/rust-lang/rust-clippy/issues/702
+#![allow(unknown_lints)]
+#![allow(clippy::all)]
+
+#![allow(unused_attributes)]
+#![cfg_attr(rustfmt, rustfmt::skip)]
+
+#![allow(box_pointers)]
+#![allow(dead_code)]
+#![allow(missing_docs)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(trivial_casts)]
+#![allow(unused_results)]
+#![allow(unused_mut)]
+
+//! Generated file from `psi.proto`
+
+/// Generated files are compatible only with the same version
+/// of protobuf runtime.
+const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_3_2_0;
+
+#[derive(PartialEq,Clone,Default,Debug)]
+// @@protoc_insertion_point(message:fhers.psi.Parameters)
+pub struct Parameters {
+    // message fields
+    // @@protoc_insertion_point(field:fhers.psi.Parameters.bfv_parameters)
+    pub bfv_parameters: ::std::vec::Vec<u8>,
+    // @@protoc_insertion_point(field:fhers.psi.Parameters.felts_per_item)
+    pub felts_per_item: u32,
+    // @@protoc_insertion_point(field:fhers.psi.Parameters.number_hash_functions)
+    pub number_hash_functions: u32,
+    // @@protoc_insertion_point(field:fhers.psi.Parameters.partition_size)
+    pub partition_size: u64,
+    // @@protoc_insertion_point(field:fhers.psi.Parameters.hash_seed)
+    pub hash_seed: ::std::vec::Vec<u8>,
+    // @@protoc_insertion_point(field:fhers.psi.Parameters.flooding_bits)
+    pub flooding_bits: u32,
+    // special fields
+    // @@protoc_insertion_point(special_field:fhers.psi.Parameters.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a Parameters {
+    fn default() -> &'a Parameters {
+        <Parameters as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Parameters {
+    pub fn new() -> Parameters {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(6);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "bfv_parameters",
+            |m: &Parameters| { &m.bfv_parameters },
+            |m: &mut Parameters| { &mut m.bfv_parameters },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "felts_per_item",
+            |m: &Parameters| { &m.felts_per_item },
+            |m: &mut Parameters| { &mut m.felts_per_item },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "number_hash_functions",
+            |m: &Parameters| { &m.number_hash_functions },
+            |m: &mut Parameters| { &mut m.number_hash_functions },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "partition_size",
+            |m: &Parameters| { &m.partition_size },
+            |m: &mut Parameters| { &mut m.partition_size },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "hash_seed",
+            |m: &Parameters| { &m.hash_seed },
+            |m: &mut Parameters| { &mut m.hash_seed },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "flooding_bits",
+            |m: &Parameters| { &m.flooding_bits },
+            |m: &mut Parameters| { &mut m.flooding_bits },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<Parameters>(
+            "Parameters",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for Parameters {
+    const NAME: &'static str = "Parameters";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.bfv_parameters = is.read_bytes()?;
+                },
+                16 => {
+                    self.felts_per_item = is.read_uint32()?;
+                },
+                24 => {
+                    self.number_hash_functions = is.read_uint32()?;
+                },
+                32 => {
+                    self.partition_size = is.read_uint64()?;
+                },
+                42 => {
+                    self.hash_seed = is.read_bytes()?;
+                },
+                48 => {
+                    self.flooding_bits = is.read_uint32()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.bfv_parameters.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(1, &self.bfv_parameters);
+        }
+        if self.felts_per_item != 0 {
+            my_size += ::protobuf::rt::uint32_size(2, self.felts_per_item);
+        }
+        if self.number_hash_functions != 0 {
+            my_size += ::protobuf::rt::uint32_size(3, self.number_hash_functions);
+        }
+        if self.partition_size != 0 {
+            my_size += ::protobuf::rt::uint64_size(4, self.partition_size);
+        }
+        if !self.hash_seed.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(5, &self.hash_seed);
+        }
+        if self.flooding_bits != 0 {
+            my_size += ::protobuf::rt::uint32_size(6, self.flooding_bits);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.bfv_parameters.is_empty() {
+            os.write_bytes(1, &self.bfv_parameters)?;
+        }
+        if self.felts_per_item != 0 {
+            os.write_uint32(2, self.felts_per_item)?;
+        }
+        if self.number_hash_functions != 0 {
+            os.write_uint32(3, self.number_hash_functions)?;
+        }
+        if self.partition_size != 0 {
+            os.write_uint64(4, self.partition_size)?;
+        }
+        if !self.hash_seed.is_empty() {
+            os.write_bytes(5, &self.hash_seed)?;
+        }
+        if self.flooding_bits != 0 {
+            os.write_uint32(6, self.flooding_bits)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> Parameters {
+        Parameters::new()
+    }
+
+    fn clear(&mut self) {
+        self.bfv_parameters.clear();
+        self.felts_per_item = 0;
+        self.number_hash_functions = 0;
+        self.partition_size = 0;
+        self.hash_seed.clear();
+        self.flooding_bits = 0;
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static Parameters {
+        static instance: Parameters = Parameters {
+            bfv_parameters: ::std::vec::Vec::new(),
+            felts_per_item: 0,
+            number_hash_functions: 0,
+            partition_size: 0,
+            hash_seed: ::std::vec::Vec::new(),
+            flooding_bits: 0,
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for Parameters {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("Parameters").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for Parameters {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Parameters {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+#[derive(PartialEq,Clone,Default,Debug)]
+// @@protoc_insertion_point(message:fhers.psi.ServerKeys)
+pub struct ServerKeys {
+    // message fields
+    // @@protoc_insertion_point(field:fhers.psi.ServerKeys.relinearization_key)
+    pub relinearization_key: ::std::vec::Vec<u8>,
+    // @@protoc_insertion_point(field:fhers.psi.ServerKeys.public_key)
+    pub public_key: ::std::vec::Vec<u8>,
+    // special fields
+    // @@protoc_insertion_point(special_field:fhers.psi.ServerKeys.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a ServerKeys {
+    fn default() -> &'a ServerKeys {
+        <ServerKeys as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl ServerKeys {
+    pub fn new() -> ServerKeys {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(2);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "relinearization_key",
+            |m: &ServerKeys| { &m.relinearization_key },
+            |m: &mut ServerKeys| { &mut m.relinearization_key },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "public_key",
+            |m: &ServerKeys| { &m.public_key },
+            |m: &mut ServerKeys| { &mut m.public_key },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<ServerKeys>(
+            "ServerKeys",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for ServerKeys {
+    const NAME: &'static str = "ServerKeys";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.relinearization_key = is.read_bytes()?;
+                },
+                18 => {
+                    self.public_key = is.read_bytes()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.relinearization_key.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(1, &self.relinearization_key);
+        }
+        if !self.public_key.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(2, &self.public_key);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.relinearization_key.is_empty() {
+            os.write_bytes(1, &self.relinearization_key)?;
+        }
+        if !self.public_key.is_empty() {
+            os.write_bytes(2, &self.public_key)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> ServerKeys {
+        ServerKeys::new()
+    }
+
+    fn clear(&mut self) {
+        self.relinearization_key.clear();
+        self.public_key.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static ServerKeys {
+        static instance: ServerKeys = ServerKeys {
+            relinearization_key: ::std::vec::Vec::new(),
+            public_key: ::std::vec::Vec::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for ServerKeys {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("ServerKeys").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for ServerKeys {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ServerKeys {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+#[derive(PartialEq,Clone,Default,Debug)]
+// @@protoc_insertion_point(message:fhers.psi.Query)
+pub struct Query {
+    // message fields
+    // @@protoc_insertion_point(field:fhers.psi.Query.ciphertexts)
+    pub ciphertexts: ::std::vec::Vec<::std::vec::Vec<u8>>,
+    // special fields
+    // @@protoc_insertion_point(special_field:fhers.psi.Query.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a Query {
+    fn default() -> &'a Query {
+        <Query as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Query {
+    pub fn new() -> Query {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(1);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "ciphertexts",
+            |m: &Query| { &m.ciphertexts },
+            |m: &mut Query| { &mut m.ciphertexts },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<Query>(
+            "Query",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for Query {
+    const NAME: &'static str = "Query";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.ciphertexts.push(is.read_bytes()?);
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        for value in &self.ciphertexts {
+            my_size += ::protobuf::rt::bytes_size(1, &value);
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        for v in &self.ciphertexts {
+            os.write_bytes(1, &v)?;
+        };
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> Query {
+        Query::new()
+    }
+
+    fn clear(&mut self) {
+        self.ciphertexts.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static Query {
+        static instance: Query = Query {
+            ciphertexts: ::std::vec::Vec::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for Query {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("Query").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for Query {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Query {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+#[derive(PartialEq,Clone,Default,Debug)]
+// @@protoc_insertion_point(message:fhers.psi.Response)
+pub struct Response {
+    // message fields
+    // @@protoc_insertion_point(field:fhers.psi.Response.ciphertexts)
+    pub ciphertexts: ::std::vec::Vec<::std::vec::Vec<u8>>,
+    // special fields
+    // @@protoc_insertion_point(special_field:fhers.psi.Response.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a Response {
+    fn default() -> &'a Response {
+        <Response as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Response {
+    pub fn new() -> Response {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(1);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "ciphertexts",
+            |m: &Response| { &m.ciphertexts },
+            |m: &mut Response| { &mut m.ciphertexts },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<Response>(
+            "Response",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for Response {
+    const NAME: &'static str = "Response";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.ciphertexts.push(is.read_bytes()?);
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        for value in &self.ciphertexts {
+            my_size += ::protobuf::rt::bytes_size(1, &value);
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        for v in &self.ciphertexts {
+            os.write_bytes(1, &v)?;
+        };
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> Response {
+        Response::new()
+    }
+
+    fn clear(&mut self) {
+        self.ciphertexts.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static Response {
+        static instance: Response = Response {
+            ciphertexts: ::std::vec::Vec::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for Response {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("Response").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for Response {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Response {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+static file_descriptor_proto_data: &'static [u8] = b"\
+    \n\tpsi.proto\x12\tfhers.psi\"\xf6\x01\n\nParameters\x12%\n\x0ebfv_par\
+    ameters\x18\x01\x20\x01(\x0cR\rbfvParameters\x12$\n\x0efelts_per_item\x18\
+    \x02\x20\x01(\rR\x0cfeltsPerItem\x122\n\x15number_hash_functions\x18\x03\
+    \x20\x01(\rR\x13numberHashFunctions\x12%\n\x0epartition_size\x18\x04\x20\
+    \x01(\x04R\rpartitionSize\x12\x1b\n\thash_seed\x18\x05\x20\x01(\x0cR\x08\
+    hashSeed\x12#\n\rflooding_bits\x18\x06\x20\x01(\rR\x0cfloodingBits\"\\\
+    \n\nServerKeys\x12/\n\x13relinearization_key\x18\x01\x20\x01(\x0cR\x12\
+    relinearizationKey\x12\x1d\n\npublic_key\x18\x02\x20\x01(\x0cR\tpublic\
+    Key\")\n\x05Query\x12\x20\n\x0bciphertexts\x18\x01\x20\x03(\x0cR\x0bci\
+    phertexts\",\n\x08Response\x12\x20\n\x0bciphertexts\x18\x01\x20\x03(\x0c\
+    R\x0bciphertextsb\x06proto3\
+";
+
+/// `FileDescriptorProto` object which was a source for this generated file
+fn file_descriptor_proto() -> &'static ::protobuf::descriptor::FileDescriptorProto {
+    static file_descriptor_proto_lazy: ::protobuf::rt::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::rt::Lazy::new();
+    file_descriptor_proto_lazy.get(|| {
+        ::protobuf::Message::parse_from_bytes(file_descriptor_proto_data).unwrap()
+    })
+}
+
+/// `FileDescriptor` object which allows dynamic access to files
+pub fn file_descriptor() -> &'static ::protobuf::reflect::FileDescriptor {
+    static generated_file_descriptor_lazy: ::protobuf::rt::Lazy<::protobuf::reflect::GeneratedFileDescriptor> = ::protobuf::rt::Lazy::new();
+    static file_descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::FileDescriptor> = ::protobuf::rt::Lazy::new();
+    file_descriptor.get(|| {
+        let generated_file_descriptor = generated_file_descriptor_lazy.get(|| {
+            let mut deps = ::std::vec::Vec::with_capacity(0);
+            let mut messages = ::std::vec::Vec::with_capacity(4);
+            messages.push(Parameters::generated_message_descriptor_data());
+            messages.push(ServerKeys::generated_message_descriptor_data());
+            messages.push(Query::generated_message_descriptor_data());
+            messages.push(Response::generated_message_descriptor_data());
+            let mut enums = ::std::vec::Vec::with_capacity(0);
+            ::protobuf::reflect::GeneratedFileDescriptor::new_generated(
+                file_descriptor_proto(),
+                deps,
+                messages,
+                enums,
+            )
+        });
+        ::protobuf::reflect::FileDescriptor::new_generated_2(generated_file_descriptor)
+    })
+}
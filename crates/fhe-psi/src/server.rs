@@ -0,0 +1,360 @@
+//! Server of the Private Set Intersection protocol.
+
+use crate::{Error, PsiParameters, Query, Response, Result, ServerKeys};
+use fhe::bfv::{dot_product_scalar, Ciphertext, Encoding, Multiplicator, Plaintext};
+use fhe_math::zq::Modulus;
+use fhe_traits::FheEncoder;
+use fhe_util::div_ceil;
+use rand::{CryptoRng, Rng, RngCore};
+use std::sync::Arc;
+
+/// A server holding a set of items, against which the clients compute their
+/// intersections.
+pub struct Server {
+    par: Arc<PsiParameters>,
+    /// For every partition, the `partition_size + 1` coefficients of the
+    /// polynomials of the slots, encoded as SIMD plaintexts.
+    partitions: Vec<Vec<Plaintext>>,
+}
+
+impl Server {
+    /// Preprocess the set of the server.
+    ///
+    /// Every item is inserted in the bins of all the hash functions, and the
+    /// items of every bin are split in partitions of at most
+    /// `par.partition_size()` items. For each partition and each slot of a
+    /// bin, the server interpolates the polynomial whose roots are the field
+    /// elements representing the items of the partition in that slot.
+    pub fn new<T: AsRef<[u8]>>(items: &[T], par: &Arc<PsiParameters>) -> Result<Self> {
+        let felts_per_item = par.felts_per_item;
+        let mut bins = vec![vec![]; par.number_bins()];
+        for item in items {
+            for (hash_index, bin) in par.bins(item.as_ref()).into_iter().enumerate() {
+                bins[bin].push(par.felts(item.as_ref(), hash_index));
+            }
+        }
+
+        let partition_size = par.partition_size;
+        let max_load = bins.iter().map(|bin| bin.len()).max().unwrap_or_default();
+        let number_partitions = div_ceil(max_load, partition_size).max(1);
+
+        let t = Modulus::new(par.bfv.plaintext())?;
+        let mut partitions = Vec::with_capacity(number_partitions);
+        for p in 0..number_partitions {
+            // The slots which are not in a bin, or whose bin partition is
+            // empty, evaluate the constant polynomial 1.
+            let mut coefficients = vec![vec![0u64; par.bfv.degree()]; partition_size + 1];
+            coefficients[0].fill(1);
+            for (b, bin) in bins.iter().enumerate() {
+                let start = (p * partition_size).min(bin.len());
+                let end = ((p + 1) * partition_size).min(bin.len());
+                for j in 0..felts_per_item {
+                    let roots = bin[start..end].iter().map(|felts| felts[j]);
+                    let polynomial = interpolate(roots, &t);
+                    for (k, c) in polynomial.into_iter().enumerate() {
+                        coefficients[k][b * felts_per_item + j] = c;
+                    }
+                }
+            }
+            partitions.push(
+                coefficients
+                    .iter()
+                    .map(|c| Plaintext::try_encode(c, Encoding::simd(), &par.bfv))
+                    .collect::<fhe::Result<Vec<Plaintext>>>()?,
+            );
+        }
+
+        Ok(Self {
+            par: par.clone(),
+            partitions,
+        })
+    }
+
+    /// Returns the parameters of the server.
+    pub fn parameters(&self) -> &Arc<PsiParameters> {
+        &self.par
+    }
+
+    /// Returns the number of partitions, which is the number of ciphertexts in
+    /// the responses.
+    pub fn number_partitions(&self) -> usize {
+        self.partitions.len()
+    }
+
+    /// Answer a query, using the keys of the client which generated it.
+    ///
+    /// The server computes the powers of the query up to the partition size,
+    /// and evaluates the polynomials of every partition over the slots. Each
+    /// evaluation is multiplied by a fresh random non-zero mask, so that the
+    /// decrypted slots only reveal whether they are zero, and the ciphertexts
+    /// are switched to the last level to reduce the size of the response.
+    ///
+    /// The response is not noise flooded: its noise depends on the set of the
+    /// server, and is not hidden from the client.
+    pub fn respond<R: RngCore + CryptoRng>(
+        &self,
+        keys: &ServerKeys,
+        query: &Query,
+        rng: &mut R,
+    ) -> Result<Response> {
+        if query.0.len() != self.par.number_query_powers() {
+            return Err(Error::QuerySizeMismatch {
+                expected: self.par.number_query_powers(),
+                found: query.0.len(),
+            });
+        }
+
+        let multiplicator = Multiplicator::default(&keys.rk)?;
+        let powers = self.powers(&query.0, &multiplicator)?;
+
+        let t = self.par.bfv.plaintext();
+        let response = self
+            .partitions
+            .iter()
+            .map(|coefficients| {
+                let mut out = dot_product_scalar(powers[1..].iter(), coefficients[1..].iter())?;
+                out += &coefficients[0];
+                let mask = (0..self.par.bfv.degree())
+                    .map(|_| rng.gen_range(1..t))
+                    .collect::<Vec<u64>>();
+                out *= &Plaintext::try_encode(&mask, Encoding::simd(), &self.par.bfv)?;
+                // The query is encrypted at level 0, where the evaluation
+                // remains.
+                out.try_add_assign(&keys.pk.try_encrypt_zero_with_flooding(
+                    0,
+                    self.par.flooding_bits,
+                    rng,
+                )?)?;
+                out.mod_switch_to_last_level()?;
+                Ok(out)
+            })
+            .collect::<fhe::Result<Vec<Ciphertext>>>()?;
+        Ok(Response(response))
+    }
+
+    /// Compute the powers `y^k` for `k <= partition_size` from the powers
+    /// `y^(2^i)` of the query. The power `y^k` is the product of the powers of
+    /// two of its binary decomposition, multiplied with a balanced tree so
+    /// that its depth is `ceil(log2(popcount(k)))`. The first entry, standing
+    /// for `y^0`, is never used.
+    fn powers(
+        &self,
+        query: &[Ciphertext],
+        multiplicator: &Multiplicator,
+    ) -> Result<Vec<Ciphertext>> {
+        let partition_size = self.par.partition_size;
+        let mut powers = Vec::with_capacity(partition_size + 1);
+        powers.push(query[0].clone());
+        for k in 1..=partition_size {
+            if k.is_power_of_two() {
+                powers.push(query[k.ilog2() as usize].clone());
+            } else {
+                // Split the bits of k in two halves, whose powers have already
+                // been computed.
+                let bits = (0..usize::BITS)
+                    .filter(|i| (k >> i) & 1 == 1)
+                    .collect::<Vec<_>>();
+                let high = bits[bits.len() / 2..].iter().map(|i| 1 << i).sum::<usize>();
+                powers.push(multiplicator.multiply(&powers[high], &powers[k - high])?);
+            }
+        }
+        Ok(powers)
+    }
+}
+
+/// Returns the coefficients of the polynomial of degree `roots.len()` whose
+/// roots are `roots`, and whose leading coefficient is 1.
+fn interpolate<I: Iterator<Item = u64>>(roots: I, t: &Modulus) -> Vec<u64> {
+    let mut polynomial = vec![1u64];
+    for root in roots {
+        // Multiply the polynomial by (x - root).
+        polynomial.push(0);
+        for k in (0..polynomial.len()).rev() {
+            let shifted = if k > 0 { polynomial[k - 1] } else { 0 };
+            polynomial[k] = t.sub(shifted, t.mul(polynomial[k], root));
+        }
+    }
+    polynomial
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{interpolate, Server};
+    use crate::{Client, Error, PsiParametersBuilder};
+    use fhe::bfv::BfvParametersBuilder;
+    use fhe_math::zq::Modulus;
+    use rand::thread_rng;
+    use std::error::Error as StdError;
+
+    #[test]
+    fn interpolation() -> Result<(), Box<dyn StdError>> {
+        let t = Modulus::new(65537)?;
+        assert_eq!(interpolate([].into_iter(), &t), vec![1]);
+
+        let roots = [3, 0, 65536, 12345];
+        let polynomial = interpolate(roots.into_iter(), &t);
+        assert_eq!(polynomial.len(), 5);
+        assert_eq!(polynomial[4], 1);
+        for x in 0..100u64 {
+            let y = polynomial
+                .iter()
+                .rev()
+                .fold(0, |acc, c| t.add(t.mul(acc, x), *c));
+            assert_eq!(y == 0, roots.contains(&x));
+        }
+        for root in roots {
+            let y = polynomial
+                .iter()
+                .rev()
+                .fold(0, |acc, c| t.add(t.mul(acc, root), *c));
+            assert_eq!(y, 0);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn respond() -> Result<(), Box<dyn StdError>> {
+        let mut rng = thread_rng();
+        // Three moduli support the multiplications of partitions of up to 7
+        // items, and the flooding noise.
+        let bfv = BfvParametersBuilder::new()
+            .set_degree(256)
+            .set_plaintext_modulus(65537)
+            .set_moduli_sizes(&[62, 62, 62])
+            .allow_insecure()
+            .build_arc()?;
+        let server_items = (0..200u32).map(u32::to_le_bytes).collect::<Vec<_>>();
+        for partition_size in [1, 4, 7] {
+            let par = PsiParametersBuilder::new()
+                .set_bfv_parameters(&bfv)
+                .set_flooding_bits(160)
+                .set_partition_size(partition_size)
+                .build_arc()?;
+            let server = Server::new(&server_items, &par)?;
+            assert!(server.number_partitions() > 1);
+
+            let client = Client::new(&par, &mut rng);
+            let keys = client.server_keys(&mut rng)?;
+            let client_items = (180..180 + par.client_capacity() as u32)
+                .map(u32::to_le_bytes)
+                .collect::<Vec<_>>();
+            let query = client.query(&client_items, &mut rng)?;
+            let response = server.respond(&keys, &query, &mut rng)?;
+            assert_eq!(response.0.len(), server.number_partitions());
+
+            let membership = client.decode_response(&client_items, &response)?;
+            let expected = (180..180 + par.client_capacity())
+                .map(|i| i < 200)
+                .collect::<Vec<bool>>();
+            assert_eq!(membership, expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn respond_with_flooding() -> Result<(), Box<dyn StdError>> {
+        let mut rng = thread_rng();
+        // The largest flooding noise for a ciphertext modulus of 186 bits and a
+        // plaintext modulus of 17 bits.
+        let bfv = BfvParametersBuilder::new()
+            .set_degree(256)
+            .set_plaintext_modulus(65537)
+            .set_moduli_sizes(&[62, 62, 62])
+            .allow_insecure()
+            .build_arc()?;
+        let par = PsiParametersBuilder::new()
+            .set_bfv_parameters(&bfv)
+            .set_flooding_bits(167)
+            .set_partition_size(7)
+            .build_arc()?;
+        let server_items = (0..200u32).map(u32::to_le_bytes).collect::<Vec<_>>();
+        let server = Server::new(&server_items, &par)?;
+        let client = Client::new(&par, &mut rng);
+        let keys = client.server_keys(&mut rng)?;
+
+        // The flooding noise is fresh in every response, and decryption still
+        // succeeds.
+        let client_items = (150..150 + par.client_capacity() as u32)
+            .map(u32::to_le_bytes)
+            .collect::<Vec<_>>();
+        let query = client.query(&client_items, &mut rng)?;
+        let expected = (150..150 + par.client_capacity())
+            .map(|i| i < 200)
+            .collect::<Vec<bool>>();
+        let response = server.respond(&keys, &query, &mut rng)?;
+        assert_eq!(client.decode_response(&client_items, &response)?, expected);
+        let other_response = server.respond(&keys, &query, &mut rng)?;
+        assert_ne!(response, other_response);
+        assert_eq!(
+            client.decode_response(&client_items, &other_response)?,
+            expected
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn respond_empty_sets() -> Result<(), Box<dyn StdError>> {
+        let mut rng = thread_rng();
+        // With partitions of one item, the server does not multiply
+        // ciphertexts, and two moduli leave room for the flooding noise.
+        let bfv = BfvParametersBuilder::new()
+            .set_degree(256)
+            .set_plaintext_modulus(65537)
+            .set_moduli_sizes(&[62, 62])
+            .allow_insecure()
+            .build_arc()?;
+        let par = PsiParametersBuilder::new()
+            .set_bfv_parameters(&bfv)
+            .set_partition_size(1)
+            .set_flooding_bits(100)
+            .build_arc()?;
+        let client = Client::new(&par, &mut rng);
+        let keys = client.server_keys(&mut rng)?;
+        let no_items: [&[u8]; 0] = [];
+
+        let server = Server::new(&no_items, &par)?;
+        assert_eq!(server.number_partitions(), 1);
+        let client_items = (0..10u32).map(u32::to_le_bytes).collect::<Vec<_>>();
+        let response = server.respond(&keys, &client.query(&client_items, &mut rng)?, &mut rng)?;
+        assert_eq!(
+            client.decode_response(&client_items, &response)?,
+            vec![false; 10]
+        );
+
+        let server = Server::new(&client_items, &par)?;
+        let response = server.respond(&keys, &client.query(&no_items, &mut rng)?, &mut rng)?;
+        assert!(client.decode_response(&no_items, &response)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn query_size_mismatch() -> Result<(), Box<dyn StdError>> {
+        let mut rng = thread_rng();
+        let bfv = BfvParametersBuilder::new()
+            .set_degree(256)
+            .set_plaintext_modulus(65537)
+            .set_moduli_sizes(&[62, 62])
+            .allow_insecure()
+            .build_arc()?;
+        let par = PsiParametersBuilder::new()
+            .set_bfv_parameters(&bfv)
+            .set_partition_size(2)
+            .set_flooding_bits(100)
+            .build_arc()?;
+        let items = (0..10u32).map(u32::to_le_bytes).collect::<Vec<_>>();
+        let server = Server::new(&items, &par)?;
+        let client = Client::new(&par, &mut rng);
+        let keys = client.server_keys(&mut rng)?;
+
+        let mut query = client.query(&items, &mut rng)?;
+        query.0.pop();
+        assert_eq!(
+            server.respond(&keys, &query, &mut rng).err(),
+            Some(Error::QuerySizeMismatch {
+                expected: 2,
+                found: 1
+            })
+        );
+        Ok(())
+    }
+}
@@ -237,7 +237,7 @@ mod tests {
             let a = (thread_rng().next_u32() >> 1) as usize;
             assert_eq!(div_ceil(a, 1), a);
             assert_eq!(div_ceil(a, 2), (a >> 1) + (a & 1));
-            assert_eq!(div_ceil(a, 8), (a + 7) / 8);
+            assert_eq!(div_ceil(a, 8), a.div_ceil(8));
         }
     }
 
@@ -0,0 +1,147 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use fhe::bfv::BfvParameters;
+use fhe::bgv::{
+    BgvParametersBuilder, Ciphertext, Encoding, Plaintext, PublicKey, RelinearizationKey, SecretKey,
+};
+use fhe_traits::{FheDecrypter, FheEncoder, FheEncrypter};
+use itertools::Itertools;
+use rand::{rngs::OsRng, thread_rng};
+use std::time::Duration;
+
+pub fn bgv_benchmark(c: &mut Criterion) {
+    let mut rng = thread_rng();
+    let mut group = c.benchmark_group("bgv");
+    group.sample_size(10);
+    group.warm_up_time(Duration::from_millis(600));
+    group.measurement_time(Duration::from_millis(1000));
+
+    // Use the same parameters as the BFV benchmarks to compare the two schemes.
+    for bfv_par in BfvParameters::default_parameters_128(20) {
+        let par = BgvParametersBuilder::new()
+            .set_degree(bfv_par.degree())
+            .set_plaintext_modulus(bfv_par.plaintext())
+            .set_moduli(bfv_par.moduli())
+            .build_arc()
+            .unwrap();
+        let sk = SecretKey::random(&par, &mut OsRng);
+        let rk = if par.moduli().len() > 1 {
+            Some(RelinearizationKey::new(&sk, &mut rng).unwrap())
+        } else {
+            None
+        };
+
+        let pt1 = Plaintext::try_encode(&(1..16u64).collect_vec(), Encoding::simd(), &par).unwrap();
+        let pt2 = Plaintext::try_encode(&(3..39u64).collect_vec(), Encoding::simd(), &par).unwrap();
+        let mut c1: Ciphertext = sk.try_encrypt(&pt1, &mut rng).unwrap();
+        let c2: Ciphertext = sk.try_encrypt(&pt2, &mut rng).unwrap();
+
+        let q = par.moduli_sizes().iter().sum::<usize>();
+
+        group.bench_function(
+            BenchmarkId::new("keygen_pk", format!("n={}/log(q)={}", par.degree(), q)),
+            |b| {
+                b.iter(|| PublicKey::new(&sk, &mut rng));
+            },
+        );
+
+        group.bench_function(
+            BenchmarkId::new("keygen_rk", format!("n={}/log(q)={}", par.degree(), q)),
+            |b| {
+                b.iter(|| RelinearizationKey::new(&sk, &mut rng));
+            },
+        );
+
+        group.bench_function(
+            BenchmarkId::new("encrypt_sk", format!("n={}/log(q)={}", par.degree(), q)),
+            |b| {
+                b.iter(|| {
+                    let _: Ciphertext = sk.try_encrypt(&pt1, &mut rng).unwrap();
+                });
+            },
+        );
+
+        group.bench_function(
+            BenchmarkId::new("decrypt", format!("n={}/log(q)={}", par.degree(), q)),
+            |b| {
+                b.iter(|| sk.try_decrypt(&c1).unwrap());
+            },
+        );
+
+        group.bench_function(
+            BenchmarkId::new("add_ct", format!("n={}/log(q)={}", par.degree(), q)),
+            |b| {
+                b.iter(|| &c1 + &c2);
+            },
+        );
+
+        group.bench_function(
+            BenchmarkId::new("add_assign_ct", format!("n={}/log(q)={}", par.degree(), q)),
+            |b| {
+                b.iter(|| c1 += &c2);
+            },
+        );
+
+        group.bench_function(
+            BenchmarkId::new("add_pt", format!("n={}/log(q)={}", par.degree(), q)),
+            |b| {
+                b.iter(|| &c1 + &pt2);
+            },
+        );
+
+        group.bench_function(
+            BenchmarkId::new("mul_pt", format!("n={}/log(q)={}", par.degree(), q)),
+            |b| {
+                b.iter(|| &c1 * &pt2);
+            },
+        );
+
+        group.bench_function(
+            BenchmarkId::new("mul", format!("n={}/log(q)={}", par.degree(), q)),
+            |b| {
+                b.iter(|| &c1 * &c2);
+            },
+        );
+
+        if let Some(rk) = rk.as_ref() {
+            let c3 = &c1 * &c2;
+            group.bench_function(
+                BenchmarkId::new("relinearize", format!("n={}/log(q)={}", par.degree(), q)),
+                |b| {
+                    b.iter(|| {
+                        let mut c = c3.clone();
+                        rk.relinearizes(&mut c).unwrap()
+                    });
+                },
+            );
+
+            group.bench_function(
+                BenchmarkId::new("mod_switch", format!("n={}/log(q)={}", par.degree(), q)),
+                |b| {
+                    b.iter(|| {
+                        let mut c = c1.clone();
+                        c.mod_switch_to_next_level().unwrap()
+                    });
+                },
+            );
+
+            group.bench_function(
+                BenchmarkId::new(
+                    "mul_relin_mod_switch",
+                    format!("n={}/log(q)={}", par.degree(), q),
+                ),
+                |b| {
+                    b.iter(|| {
+                        let mut c = &c1 * &c2;
+                        rk.relinearizes(&mut c).unwrap();
+                        c.mod_switch_to_next_level().unwrap()
+                    });
+                },
+            );
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(bgv, bgv_benchmark);
+criterion_main!(bgv);
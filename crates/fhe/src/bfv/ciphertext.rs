@@ -1,7 +1,8 @@
 //! Ciphertext type in the BFV encryption scheme.
 
 use crate::bfv::{
-    parameters::BfvParameters, proto::bfv::Ciphertext as CiphertextProto, traits::TryConvertFrom,
+    noise, parameters::BfvParameters, proto::bfv::Ciphertext as CiphertextProto,
+    traits::TryConvertFrom,
 };
 use crate::{Error, Result};
 use fhe_math::rq::{Poly, Representation};
@@ -14,7 +15,7 @@ use rand_chacha::ChaCha8Rng;
 use std::sync::Arc;
 
 /// A ciphertext encrypting a plaintext.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub struct Ciphertext {
     /// The parameters of the underlying BFV encryption scheme.
     pub(crate) par: Arc<BfvParameters>,
@@ -27,11 +28,34 @@ pub struct Ciphertext {
 
     /// The ciphertext level
     pub(crate) level: usize,
+
+    /// The base-2 logarithm of a heuristic bound on the noise, if known.
+    pub(crate) noise: Option<f64>,
 }
 
+/// The noise estimate is not compared, so that a ciphertext equals its
+/// deserialization.
+impl PartialEq for Ciphertext {
+    fn eq(&self, other: &Self) -> bool {
+        self.par == other.par
+            && self.seed == other.seed
+            && self.c == other.c
+            && self.level == other.level
+    }
+}
+
+impl Eq for Ciphertext {}
+
 impl Ciphertext {
     /// Modulo switch the ciphertext to the last level.
     pub fn mod_switch_to_last_level(&mut self) -> Result<()> {
+        for level in self.level..self.par.max_level() {
+            let ctx = self.par.ctx_at_level(level)?;
+            let q = *ctx.moduli().last().unwrap();
+            self.noise = self
+                .noise
+                .map(|noise| noise::mod_switch(&self.par, noise, q, self.c.len()));
+        }
         self.level = self.par.max_level();
         let last_ctx = self.par.ctx_at_level(self.level)?;
         self.seed = None;
@@ -48,6 +72,11 @@ impl Ciphertext {
     /// Modulo switch the ciphertext to the next level.
     pub fn mod_switch_to_next_level(&mut self) -> Result<()> {
         if self.level < self.par.max_level() {
+            let ctx = self.par.ctx_at_level(self.level)?;
+            let q = *ctx.moduli().last().unwrap();
+            self.noise = self
+                .noise
+                .map(|noise| noise::mod_switch(&self.par, noise, q, self.c.len()));
             self.seed = None;
             for ci in self.c.iter_mut() {
                 ci.change_representation(Representation::PowerBasis);
@@ -59,6 +88,20 @@ impl Ciphertext {
         Ok(())
     }
 
+    /// Multiply the ciphertext by the monomial `x^power`, which rotates the
+    /// coefficients of a plaintext with [`Encoding::poly`](crate::bfv::Encoding::poly)
+    /// encoding, negating those which wrap around.
+    pub fn mul_by_monomial(&mut self, power: usize) -> Result<()> {
+        let two_degree = self.par.degree() << 1;
+        self.seed = None;
+        for ci in self.c.iter_mut() {
+            ci.change_representation(Representation::PowerBasis);
+            ci.multiply_inverse_power_of_x((two_degree - power % two_degree) % two_degree)?;
+            ci.change_representation(Representation::Ntt);
+        }
+        Ok(())
+    }
+
     /// Create a ciphertext from a vector of polynomials.
     /// A ciphertext must contain at least two polynomials, and all polynomials
     /// must be in Ntt representation and with the same context.
@@ -88,6 +131,7 @@ impl Ciphertext {
             seed: None,
             c,
             level,
+            noise: None,
         })
     }
 
@@ -95,6 +139,30 @@ impl Ciphertext {
     pub fn get(&self, i: usize) -> Option<&Poly> {
         self.c.get(i)
     }
+
+    /// Returns a heuristic estimate of the size in bits of the noise in the
+    /// ciphertext, tracked through the homomorphic operations without the
+    /// secret key. The estimate is a worst-case bound, so it is usually larger
+    /// than the noise measured by
+    /// [`SecretKey::measure_noise`](crate::bfv::SecretKey::measure_noise).
+    ///
+    /// Returns `None` if the noise is unknown, e.g., for a ciphertext created
+    /// by [`Ciphertext::new`] or deserialized.
+    pub fn estimated_noise_bits(&self) -> Option<usize> {
+        self.noise.map(|noise| noise.max(0.0).ceil() as usize)
+    }
+
+    /// Returns an estimate of the number of bits by which the noise can still
+    /// grow before the ciphertext fails to decrypt, based on
+    /// [`Ciphertext::estimated_noise_bits`]. Returns `None` if the noise is
+    /// unknown.
+    pub fn remaining_budget_bits(&self) -> Option<usize> {
+        let noise = self.noise?;
+        let ctx = self.par.ctx_at_level(self.level).ok()?;
+        let budget =
+            noise::log2_product(ctx.moduli()) - (self.par.plaintext() as f64).log2() - 1.0 - noise;
+        Some(budget.max(0.0).floor() as usize)
+    }
 }
 
 impl FheCiphertext for Ciphertext {}
@@ -129,6 +197,7 @@ impl Ciphertext {
             seed: None,
             c: Default::default(),
             level: 0,
+            noise: Some(f64::NEG_INFINITY),
         }
     }
 }
@@ -153,11 +222,11 @@ impl From<&Ciphertext> for CiphertextProto {
 impl TryConvertFrom<&CiphertextProto> for Ciphertext {
     fn try_convert_from(value: &CiphertextProto, par: &Arc<BfvParameters>) -> Result<Self> {
         if value.c.is_empty() || (value.c.len() == 1 && value.seed.is_empty()) {
-            return Err(Error::DefaultError("Not enough polynomials".to_string()));
+            return Err(Error::SerializationError);
         }
 
         if value.level as usize > par.max_level() {
-            return Err(Error::DefaultError("Invalid level".to_string()));
+            return Err(Error::InvalidLevel(value.level as usize));
         }
 
         let ctx = par.ctx_at_level(value.level as usize)?;
@@ -188,6 +257,7 @@ impl TryConvertFrom<&CiphertextProto> for Ciphertext {
             seed,
             c,
             level: value.level as usize,
+            noise: None,
         })
     }
 }
@@ -196,10 +266,11 @@ impl TryConvertFrom<&CiphertextProto> for Ciphertext {
 mod tests {
     use crate::bfv::{
         proto::bfv::Ciphertext as CiphertextProto, traits::TryConvertFrom, BfvParameters,
-        Ciphertext, Encoding, Plaintext, SecretKey,
+        Ciphertext, Encoding, EvaluationKeyBuilder, KeySwitchingMode, Multiplicator, Plaintext,
+        PublicKey, RelinearizationKey, SecretKey,
     };
     use fhe_traits::FheDecrypter;
-    use fhe_traits::{DeserializeParametrized, FheEncoder, FheEncrypter, Serialize};
+    use fhe_traits::{DeserializeParametrized, FheDecoder, FheEncoder, FheEncrypter, Serialize};
     use rand::thread_rng;
     use std::error::Error;
 
@@ -279,6 +350,41 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn mul_by_monomial() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        for params in [
+            BfvParameters::default_arc(1, 8),
+            BfvParameters::default_arc(6, 8),
+        ] {
+            let degree = params.degree();
+            let sk = SecretKey::random(&params, &mut rng);
+            let v = params.plaintext.random_vec(degree, &mut rng);
+            let pt = Plaintext::try_encode(&v, Encoding::poly(), &params)?;
+            let ct: Ciphertext = sk.try_encrypt(&pt, &mut rng)?;
+
+            for power in [0, 1, 3, degree - 1, degree, degree + 5, 2 * degree + 1] {
+                // Multiplication by x^power modulo x^degree + 1.
+                let mut expected = vec![0u64; degree];
+                for (i, vi) in v.iter().enumerate() {
+                    let j = (i + power) % (2 * degree);
+                    if j < degree {
+                        expected[j] = *vi
+                    } else {
+                        expected[j - degree] = params.plaintext.neg(*vi)
+                    }
+                }
+
+                let mut ct2 = ct.clone();
+                ct2.mul_by_monomial(power)?;
+                let pt2 = sk.try_decrypt(&ct2)?;
+                assert_eq!(Vec::<u64>::try_decode(&pt2, Encoding::poly())?, expected);
+            }
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn mod_switch_to_last_level() -> Result<(), Box<dyn Error>> {
         let mut rng = thread_rng();
@@ -301,4 +407,66 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn noise_estimate() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BfvParameters::default_arc(6, 16);
+        let sk = SecretKey::random(&params, &mut rng);
+        let pk = PublicKey::new(&sk, &mut rng);
+        let ek = EvaluationKeyBuilder::new(&sk)?
+            .enable_column_rotation(1)?
+            .build(&mut rng)?;
+        let v = params.plaintext.random_vec(params.degree(), &mut rng);
+        let pt = Plaintext::try_encode(&v, Encoding::simd(), &params)?;
+
+        // The estimate must bound the measured noise, without being too loose.
+        let check = |ct: &Ciphertext, slack: usize| -> Result<(), Box<dyn Error>> {
+            let measured = unsafe { sk.measure_noise(ct)? };
+            let estimated = ct.estimated_noise_bits().unwrap();
+            assert!(measured <= estimated);
+            assert!(estimated <= measured + slack);
+            assert!(ct.remaining_budget_bits().unwrap() > 0);
+            Ok(())
+        };
+
+        let ct1: Ciphertext = sk.try_encrypt(&pt, &mut rng)?;
+        let ct2: Ciphertext = pk.try_encrypt(&pt, &mut rng)?;
+        check(&ct1, 4)?;
+        check(&ct2, 8)?;
+        check(&(&ct1 + &ct2), 8)?;
+        check(&(&ct1 - &ct2), 8)?;
+        check(&(&ct1 * &pt), 10)?;
+        check(&(&ct1 * 1000u64), 6)?;
+        check(&ek.rotates_columns_by(&ct1, 1)?, 10)?;
+
+        for mode in [KeySwitchingMode::Rns, KeySwitchingMode::Hybrid { dnum: 2 }] {
+            let rk = RelinearizationKey::new_leveled_with_mode(&sk, 0, 0, mode, &mut rng)?;
+            let mut multiplicator = Multiplicator::default(&rk)?;
+            let ct3 = multiplicator.multiply(&ct1, &ct2)?;
+            check(&ct3, 24)?;
+            multiplicator.enable_mod_switching()?;
+            let mut ct4 = multiplicator.multiply(&ct1, &ct2)?;
+            check(&ct4, 10)?;
+            ct4.mod_switch_to_last_level()?;
+            check(&ct4, 10)?;
+        }
+
+        // The budget decreases with the multiplications and the modulus switching.
+        let ct5 = &ct1 * &ct1;
+        assert!(ct5.remaining_budget_bits() < ct1.remaining_budget_bits());
+        let mut ct6 = ct1.clone();
+        ct6.mod_switch_to_last_level()?;
+        assert!(ct6.remaining_budget_bits() < ct1.remaining_budget_bits());
+
+        // The noise of deserialized ciphertexts is unknown.
+        let ct7 = Ciphertext::from_bytes(&ct1.to_bytes(), &params)?;
+        assert_eq!(ct7, ct1);
+        assert!(ct7.estimated_noise_bits().is_none());
+        assert!(ct7.remaining_budget_bits().is_none());
+        assert!((&ct7 + &ct1).estimated_noise_bits().is_none());
+        assert_eq!(Ciphertext::zero(&params).estimated_noise_bits(), Some(0));
+
+        Ok(())
+    }
 }
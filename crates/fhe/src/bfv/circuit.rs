@@ -0,0 +1,641 @@
+//! Circuits of homomorphic operations in the BFV encryption scheme.
+
+use crate::{
+    bfv::{
+        noise, BfvParameters, Ciphertext, Encoding, EvaluationKey, EvaluationKeyBuilder, Plaintext,
+        RelinearizationKey, SecretKey,
+    },
+    Error, Result,
+};
+use fhe_math::zq::Modulus;
+use fhe_traits::FheEncoder;
+use rand::{CryptoRng, RngCore};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Arc;
+
+/// Identifier of a node of a [`Circuit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(usize);
+
+/// A node of a circuit; the operands always precede the node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    Input,
+    Add(usize, usize),
+    Sub(usize, usize),
+    Mul(usize, usize),
+    MulPlaintext(usize, Vec<u64>),
+    Galois(usize, usize),
+}
+
+/// The level, number of parts and estimated noise of the ciphertext computed
+/// by a node.
+#[derive(Debug, Clone, Copy)]
+struct Shape {
+    level: usize,
+    size: usize,
+    noise: f64,
+}
+
+/// The evaluation plan of a circuit.
+#[derive(Debug, Default)]
+struct Plan {
+    /// The shape of the ciphertext computed by each node.
+    shapes: Vec<Shape>,
+
+    /// The shape of the products after relinearization and modulus switching.
+    relinearized: HashMap<usize, Shape>,
+
+    relinearization_levels: BTreeSet<usize>,
+    galois_exponents: BTreeMap<usize, BTreeSet<usize>>,
+}
+
+/// A circuit of homomorphic operations over the slots of SIMD encoded
+/// ciphertexts, represented as a directed acyclic graph of operations.
+///
+/// The circuit decides where to relinearize and to switch modulus:
+/// - The relinearization of a product is delayed until it is used by an
+///   operation other than an addition, a subtraction or a multiplication by a
+///   plaintext, so that a sum of products is relinearized only once;
+/// - The ciphertexts are switched to the next level after a relinearization
+///   or a rotation, whenever the estimated noise is large enough for the
+///   switch to cost at most one bit of noise budget. A level always keeps two
+///   moduli, as required by key switching.
+///
+/// The keys needed by the evaluation are reported by
+/// [`Circuit::relinearization_levels`] and [`Circuit::galois_exponents`], and
+/// can be generated with [`Circuit::generate_keys`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Circuit {
+    par: Arc<BfvParameters>,
+    nodes: Vec<Node>,
+    num_inputs: usize,
+    outputs: Vec<usize>,
+}
+
+impl Circuit {
+    /// Creates an empty circuit.
+    pub fn new(par: &Arc<BfvParameters>) -> Self {
+        Self {
+            par: par.clone(),
+            nodes: vec![],
+            num_inputs: 0,
+            outputs: vec![],
+        }
+    }
+
+    /// Adds an input to the circuit; the inputs are provided to
+    /// [`Circuit::evaluate`] in the order they are added.
+    pub fn input(&mut self) -> NodeId {
+        self.num_inputs += 1;
+        self.push(Node::Input)
+    }
+
+    /// Adds the sum of two nodes to the circuit.
+    pub fn add(&mut self, lhs: NodeId, rhs: NodeId) -> Result<NodeId> {
+        let node = Node::Add(self.check(lhs)?, self.check(rhs)?);
+        Ok(self.push(node))
+    }
+
+    /// Adds the difference of two nodes to the circuit.
+    pub fn sub(&mut self, lhs: NodeId, rhs: NodeId) -> Result<NodeId> {
+        let node = Node::Sub(self.check(lhs)?, self.check(rhs)?);
+        Ok(self.push(node))
+    }
+
+    /// Adds the product of two nodes to the circuit.
+    pub fn mul(&mut self, lhs: NodeId, rhs: NodeId) -> Result<NodeId> {
+        let node = Node::Mul(self.check(lhs)?, self.check(rhs)?);
+        Ok(self.push(node))
+    }
+
+    /// Adds the product of a node with the plaintext encoding `values` using
+    /// [`Encoding::simd`] to the circuit.
+    pub fn mul_plaintext(&mut self, node: NodeId, values: &[u64]) -> Result<NodeId> {
+        if values.len() > self.par.degree() {
+            return Err(Error::TooManyValues(values.len(), self.par.degree()));
+        }
+        let node = Node::MulPlaintext(self.check(node)?, values.to_vec());
+        Ok(self.push(node))
+    }
+
+    /// Adds the rotation of the columns of a node by `i` to the circuit, where
+    /// `i` is between 1 and half the degree (excluded).
+    pub fn rotate_columns(&mut self, node: NodeId, i: usize) -> Result<NodeId> {
+        if i == 0 || i >= self.par.degree() / 2 {
+            return Err(Error::InvalidColumnIndex(i));
+        }
+        let exponent = Modulus::new(2 * self.par.degree() as u64)?.pow(3, i as u64) as usize;
+        let node = Node::Galois(self.check(node)?, exponent);
+        Ok(self.push(node))
+    }
+
+    /// Adds the rotation of the rows of a node to the circuit.
+    pub fn rotate_rows(&mut self, node: NodeId) -> Result<NodeId> {
+        let node = Node::Galois(self.check(node)?, 2 * self.par.degree() - 1);
+        Ok(self.push(node))
+    }
+
+    /// Marks a node as an output of the circuit; the outputs are returned by
+    /// [`Circuit::evaluate`] in the order they are marked.
+    pub fn output(&mut self, node: NodeId) -> Result<()> {
+        let node = self.check(node)?;
+        self.outputs.push(node);
+        Ok(())
+    }
+
+    /// Returns the multiplicative depth of the circuit.
+    pub fn depth(&self) -> usize {
+        let mut depths: Vec<usize> = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            let depth = match node {
+                Node::Input => 0,
+                Node::Add(a, b) | Node::Sub(a, b) => depths[*a].max(depths[*b]),
+                Node::Mul(a, b) => depths[*a].max(depths[*b]) + 1,
+                Node::MulPlaintext(a, _) | Node::Galois(a, _) => depths[*a],
+            };
+            depths.push(depth)
+        }
+        self.outputs.iter().map(|i| depths[*i]).max().unwrap_or(0)
+    }
+
+    /// Returns the (sorted) ciphertext levels at which relinearization keys
+    /// are needed to evaluate the circuit.
+    pub fn relinearization_levels(&self) -> Vec<usize> {
+        self.plan().relinearization_levels.into_iter().collect()
+    }
+
+    /// Returns the (sorted) exponents of the Galois keys needed to evaluate
+    /// the circuit, for each ciphertext level at which they are needed.
+    pub fn galois_exponents(&self) -> BTreeMap<usize, Vec<usize>> {
+        self.plan()
+            .galois_exponents
+            .into_iter()
+            .map(|(level, exponents)| (level, exponents.into_iter().collect()))
+            .collect()
+    }
+
+    /// Generates the keys needed to evaluate the circuit.
+    pub fn generate_keys<R: RngCore + CryptoRng>(
+        &self,
+        sk: &SecretKey,
+        rng: &mut R,
+    ) -> Result<CircuitKeys> {
+        if sk.par != self.par {
+            return Err(Error::ParametersMismatch);
+        }
+        let plan = self.plan();
+        let rks = plan
+            .relinearization_levels
+            .iter()
+            .map(|level| RelinearizationKey::new_leveled(sk, *level, *level, rng))
+            .collect::<Result<Vec<_>>>()?;
+        let eks = plan
+            .galois_exponents
+            .iter()
+            .map(|(level, exponents)| {
+                let mut builder = EvaluationKeyBuilder::new_leveled(sk, *level, *level)?;
+                for exponent in exponents {
+                    builder.enable_galois_element(*exponent)?;
+                }
+                builder.build(rng)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        CircuitKeys::new(rks, eks)
+    }
+
+    /// Homomorphically evaluate the circuit on ciphertexts at level 0, and
+    /// returns the relinearized outputs.
+    pub fn evaluate(&self, inputs: &[Ciphertext], keys: &CircuitKeys) -> Result<Vec<Ciphertext>> {
+        if inputs.len() < self.num_inputs {
+            return Err(Error::TooFewValues(inputs.len(), self.num_inputs));
+        } else if inputs.len() > self.num_inputs {
+            return Err(Error::TooManyValues(inputs.len(), self.num_inputs));
+        }
+        for ct in inputs {
+            if ct.par != self.par {
+                return Err(Error::ParametersMismatch);
+            } else if ct.level != 0 {
+                return Err(Error::LevelMismatch {
+                    expected: 0,
+                    found: ct.level,
+                });
+            } else if ct.c.len() != 2 {
+                return Err(Error::InvalidCiphertextSize {
+                    expected: 2,
+                    found: ct.c.len(),
+                });
+            }
+        }
+
+        let plan = self.plan();
+        let mut evaluation = Evaluation {
+            plan: &plan,
+            keys,
+            values: Vec::with_capacity(self.nodes.len()),
+            relinearized: HashMap::new(),
+        };
+        let mut inputs = inputs.iter();
+        for (i, node) in self.nodes.iter().enumerate() {
+            let level = plan.shapes[i].level;
+            let ct = match node {
+                Node::Input => inputs.next().unwrap().clone(),
+                Node::Add(a, b) | Node::Sub(a, b) => {
+                    let (ct_a, ct_b) = if plan.shapes[*a].size == plan.shapes[*b].size {
+                        (evaluation.values[*a].clone(), evaluation.values[*b].clone())
+                    } else {
+                        (evaluation.relinearized(*a)?, evaluation.relinearized(*b)?)
+                    };
+                    let ct_a = mod_switch_to(ct_a, level)?;
+                    let ct_b = mod_switch_to(ct_b, level)?;
+                    if matches!(node, Node::Add(..)) {
+                        ct_a.try_add(&ct_b)?
+                    } else {
+                        ct_a.try_sub(&ct_b)?
+                    }
+                }
+                Node::Mul(a, b) => {
+                    let ct_a = mod_switch_to(evaluation.relinearized(*a)?, level)?;
+                    let ct_b = mod_switch_to(evaluation.relinearized(*b)?, level)?;
+                    ct_a.try_mul(&ct_b)?
+                }
+                Node::MulPlaintext(a, values) => {
+                    let pt =
+                        Plaintext::try_encode(values, Encoding::simd_at_level(level), &self.par)?;
+                    evaluation.values[*a].try_mul_plaintext(&pt)?
+                }
+                Node::Galois(a, exponent) => {
+                    let ct_a = evaluation.relinearized(*a)?;
+                    let ek = keys
+                        .eks
+                        .get(&ct_a.level)
+                        .ok_or(Error::MissingGaloisKey(*exponent))?;
+                    mod_switch_to(ek.apply_galois(&ct_a, *exponent)?, level)?
+                }
+            };
+            evaluation.values.push(ct);
+        }
+
+        self.outputs
+            .iter()
+            .map(|i| evaluation.relinearized(*i))
+            .collect()
+    }
+
+    /// Checks that the node belongs to the circuit, and returns its index.
+    fn check(&self, node: NodeId) -> Result<usize> {
+        if node.0 < self.nodes.len() {
+            Ok(node.0)
+        } else {
+            Err(Error::InvalidNode(node.0))
+        }
+    }
+
+    fn push(&mut self, node: Node) -> NodeId {
+        self.nodes.push(node);
+        NodeId(self.nodes.len() - 1)
+    }
+
+    /// Computes the evaluation plan, by estimating the noise of the
+    /// ciphertexts as if the inputs were fresh encryptions.
+    fn plan(&self) -> Plan {
+        let mut plan = Plan::default();
+        for node in &self.nodes {
+            let shape = match node {
+                Node::Input => Shape {
+                    level: 0,
+                    size: 2,
+                    noise: noise::encryption(&self.par),
+                },
+                Node::Add(a, b) | Node::Sub(a, b) => {
+                    let (shape_a, shape_b) = if plan.shapes[*a].size == plan.shapes[*b].size {
+                        (plan.shapes[*a], plan.shapes[*b])
+                    } else {
+                        (
+                            self.relinearize(&mut plan, *a),
+                            self.relinearize(&mut plan, *b),
+                        )
+                    };
+                    let level = shape_a.level.max(shape_b.level);
+                    Shape {
+                        level,
+                        size: shape_a.size,
+                        noise: noise::addition(
+                            self.mod_switch(shape_a, level).noise,
+                            self.mod_switch(shape_b, level).noise,
+                        ),
+                    }
+                }
+                Node::Mul(a, b) => {
+                    let shape_a = self.relinearize(&mut plan, *a);
+                    let shape_b = self.relinearize(&mut plan, *b);
+                    let level = shape_a.level.max(shape_b.level);
+                    Shape {
+                        level,
+                        size: 3,
+                        noise: noise::tensor(
+                            &self.par,
+                            self.mod_switch(shape_a, level).noise,
+                            self.mod_switch(shape_b, level).noise,
+                        ),
+                    }
+                }
+                Node::MulPlaintext(a, _) => Shape {
+                    noise: noise::plaintext_multiplication(&self.par, plan.shapes[*a].noise),
+                    ..plan.shapes[*a]
+                },
+                Node::Galois(a, exponent) => {
+                    let shape = self.relinearize(&mut plan, *a);
+                    plan.galois_exponents
+                        .entry(shape.level)
+                        .or_default()
+                        .insert(*exponent);
+                    self.mod_switch_down(Shape {
+                        noise: noise::addition(
+                            noise::rounding(shape.noise),
+                            self.key_switching_noise(shape.level),
+                        ),
+                        ..shape
+                    })
+                }
+            };
+            plan.shapes.push(shape)
+        }
+        for i in &self.outputs {
+            self.relinearize(&mut plan, *i);
+        }
+        plan
+    }
+
+    /// Returns the shape of the ciphertext of a node once relinearized and
+    /// switched down, and records the relinearization in the plan.
+    fn relinearize(&self, plan: &mut Plan, i: usize) -> Shape {
+        let shape = plan.shapes[i];
+        if shape.size == 2 {
+            return shape;
+        }
+        if let Some(shape) = plan.relinearized.get(&i) {
+            return *shape;
+        }
+        plan.relinearization_levels.insert(shape.level);
+        let relinearized = self.mod_switch_down(Shape {
+            level: shape.level,
+            size: 2,
+            noise: noise::addition(shape.noise, self.key_switching_noise(shape.level)),
+        });
+        plan.relinearized.insert(i, relinearized);
+        relinearized
+    }
+
+    /// Switches a ciphertext down while it costs at most one bit of noise
+    /// budget, keeping at least two moduli.
+    fn mod_switch_down(&self, mut shape: Shape) -> Shape {
+        while shape.level + 1 < self.par.max_level() {
+            let q = self.last_modulus(shape.level);
+            let noise = noise::mod_switch(&self.par, shape.noise, q, shape.size);
+            if shape.noise - noise < (q as f64).log2() - 1.0 {
+                break;
+            }
+            shape.level += 1;
+            shape.noise = noise;
+        }
+        shape
+    }
+
+    /// Switches a ciphertext to `level`.
+    fn mod_switch(&self, mut shape: Shape, level: usize) -> Shape {
+        while shape.level < level {
+            let q = self.last_modulus(shape.level);
+            shape.noise = noise::mod_switch(&self.par, shape.noise, q, shape.size);
+            shape.level += 1;
+        }
+        shape
+    }
+
+    fn last_modulus(&self, level: usize) -> u64 {
+        self.par.moduli()[self.par.moduli().len() - 1 - level]
+    }
+
+    /// Estimated noise of a key switching at `level` with a key at the same
+    /// level.
+    fn key_switching_noise(&self, level: usize) -> f64 {
+        let moduli = &self.par.moduli()[..self.par.moduli().len() - level];
+        noise::key_switching(&self.par, moduli.iter().map(|qi| (*qi as f64).log2()), 0.0)
+    }
+}
+
+/// The keys used to evaluate a [`Circuit`]: relinearization keys and
+/// evaluation keys, at most one of each per ciphertext level.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CircuitKeys {
+    rks: HashMap<usize, RelinearizationKey>,
+    eks: HashMap<usize, EvaluationKey>,
+}
+
+impl CircuitKeys {
+    /// Gathers relinearization keys and evaluation keys, for instance after
+    /// their deserialization. Returns an error if several keys of the same
+    /// type are for the same ciphertext level.
+    pub fn new(rks: Vec<RelinearizationKey>, eks: Vec<EvaluationKey>) -> Result<Self> {
+        let mut keys = Self {
+            rks: HashMap::new(),
+            eks: HashMap::new(),
+        };
+        for rk in rks {
+            let level = rk.ksk.ciphertext_level;
+            if keys.rks.insert(level, rk).is_some() {
+                return Err(Error::DuplicateRelinearizationKey(level));
+            }
+        }
+        for ek in eks {
+            let level = ek.ciphertext_level();
+            if keys.eks.insert(level, ek).is_some() {
+                return Err(Error::DuplicateEvaluationKey(level));
+            }
+        }
+        Ok(keys)
+    }
+}
+
+/// The state of the evaluation of a circuit.
+struct Evaluation<'a> {
+    plan: &'a Plan,
+    keys: &'a CircuitKeys,
+    values: Vec<Ciphertext>,
+    relinearized: HashMap<usize, Ciphertext>,
+}
+
+impl Evaluation<'_> {
+    /// Returns the ciphertext of a node, relinearized and switched down as
+    /// planned.
+    fn relinearized(&mut self, i: usize) -> Result<Ciphertext> {
+        if self.values[i].c.len() == 2 {
+            return Ok(self.values[i].clone());
+        }
+        if let Some(ct) = self.relinearized.get(&i) {
+            return Ok(ct.clone());
+        }
+        let mut ct = self.values[i].clone();
+        let rk = self
+            .keys
+            .rks
+            .get(&ct.level)
+            .ok_or(Error::MissingRelinearizationKey(ct.level))?;
+        rk.relinearizes(&mut ct)?;
+        let ct = mod_switch_to(ct, self.plan.relinearized[&i].level)?;
+        self.relinearized.insert(i, ct.clone());
+        Ok(ct)
+    }
+}
+
+/// Switches a ciphertext down to `level`.
+fn mod_switch_to(mut ct: Ciphertext, level: usize) -> Result<Ciphertext> {
+    while ct.level < level {
+        ct.mod_switch_to_next_level()?;
+    }
+    Ok(ct)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Circuit, CircuitKeys};
+    use crate::bfv::{
+        BfvParameters, BfvParametersBuilder, Ciphertext, Encoding, Plaintext, SecretKey,
+    };
+    use fhe_traits::{FheDecoder, FheDecrypter, FheEncoder, FheEncrypter};
+    use itertools::{izip, Itertools};
+    use rand::thread_rng;
+    use std::error::Error;
+
+    #[test]
+    fn evaluate() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        for params in [
+            BfvParameters::default_arc(6, 16),
+            BfvParametersBuilder::new()
+                .set_degree(16)
+                .set_plaintext_modulus(1153)
+                .set_moduli_sizes(&[25; 6])
+                .allow_insecure()
+                .build_arc()?,
+        ] {
+            let degree = params.degree();
+            let row_size = degree / 2;
+            let t = params.plaintext.clone();
+            let sk = SecretKey::random(&params, &mut rng);
+
+            // The sum of products `x * y + z * x`, rotated by one column and
+            // multiplied by a plaintext, and the product `x * y * z * x` with its
+            // rows rotated.
+            let mut circuit = Circuit::new(&params);
+            let x = circuit.input();
+            let y = circuit.input();
+            let z = circuit.input();
+            let xy = circuit.mul(x, y)?;
+            let zx = circuit.mul(z, x)?;
+            let sum = circuit.add(xy, zx)?;
+            let rotated = circuit.rotate_columns(sum, 1)?;
+            let w = t.random_vec(degree, &mut rng);
+            let out1 = circuit.mul_plaintext(rotated, &w)?;
+            let xyz = circuit.mul(xy, z)?;
+            let xyzx = circuit.mul(xyz, x)?;
+            let out2 = circuit.rotate_rows(xyzx)?;
+            let diff = circuit.sub(out2, xy)?;
+            circuit.output(out1)?;
+            circuit.output(out2)?;
+            circuit.output(diff)?;
+            assert_eq!(circuit.depth(), 3);
+
+            // The key switching noise is large enough for the ciphertexts to be
+            // switched down after the relinearizations.
+            let levels = circuit.relinearization_levels();
+            assert!(levels.len() > 1 && levels[0] == 0);
+            assert_eq!(circuit.galois_exponents().values().flatten().count(), 2);
+
+            let keys = circuit.generate_keys(&sk, &mut rng)?;
+            let values = (0..3).map(|_| t.random_vec(degree, &mut rng)).collect_vec();
+            let inputs = values
+                .iter()
+                .map(|v| {
+                    let pt = Plaintext::try_encode(v, Encoding::simd(), &params)?;
+                    sk.try_encrypt(&pt, &mut rng)
+                })
+                .collect::<crate::Result<Vec<_>>>()?;
+            let outputs = circuit.evaluate(&inputs, &keys)?;
+            assert_eq!(outputs.len(), 3);
+
+            let (vx, vy, vz) = (&values[0], &values[1], &values[2]);
+            let vxy = izip!(vx, vy).map(|(a, b)| t.mul(*a, *b)).collect_vec();
+            let vsum = izip!(&vxy, vz, vx)
+                .map(|(a, b, c)| t.add(*a, t.mul(*b, *c)))
+                .collect_vec();
+            let mut expected1 = vec![0u64; degree];
+            for j in 0..degree {
+                let row = j / row_size;
+                expected1[j] = t.mul(vsum[row * row_size + (j + 1) % row_size], w[j]);
+            }
+            let vxyzx = izip!(&vxy, vz, vx)
+                .map(|(a, b, c)| t.mul(t.mul(*a, *b), *c))
+                .collect_vec();
+            let expected2 = [&vxyzx[row_size..], &vxyzx[..row_size]].concat();
+            let expected3 = izip!(&expected2, &vxy)
+                .map(|(a, b)| t.sub(*a, *b))
+                .collect_vec();
+
+            for (ct, expected) in izip!(&outputs, [expected1, expected2, expected3]) {
+                assert_eq!(ct.c.len(), 2);
+                assert!(ct.remaining_budget_bits().unwrap() > 0);
+                let pt = sk.try_decrypt(ct)?;
+                assert_eq!(Vec::<u64>::try_decode(&pt, Encoding::simd())?, expected);
+            }
+            assert!(outputs[1].level > 0);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn errors() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BfvParameters::default_arc(3, 16);
+        let sk = SecretKey::random(&params, &mut rng);
+
+        let mut circuit = Circuit::new(&params);
+        let x = circuit.input();
+        let mut other = Circuit::new(&params);
+        other.input();
+        let y = other.input();
+        assert_eq!(circuit.add(x, y).err(), Some(crate::Error::InvalidNode(1)));
+        assert_eq!(
+            circuit.rotate_columns(x, 0).err(),
+            Some(crate::Error::InvalidColumnIndex(0))
+        );
+        assert_eq!(
+            circuit.rotate_columns(x, 8).err(),
+            Some(crate::Error::InvalidColumnIndex(8))
+        );
+        assert!(circuit.mul_plaintext(x, &[1; 17]).is_err());
+
+        let xx = circuit.mul(x, x)?;
+        let rotated = circuit.rotate_columns(xx, 1)?;
+        circuit.output(rotated)?;
+
+        let pt = Plaintext::try_encode(&[1u64, 2, 3], Encoding::simd(), &params)?;
+        let ct: Ciphertext = sk.try_encrypt(&pt, &mut rng)?;
+        let keys = circuit.generate_keys(&sk, &mut rng)?;
+        assert!(circuit.evaluate(&[], &keys).is_err());
+        assert!(circuit.evaluate(&[ct.clone(), ct.clone()], &keys).is_err());
+        let mut ct1 = ct.clone();
+        ct1.mod_switch_to_next_level()?;
+        assert!(circuit.evaluate(&[ct1], &keys).is_err());
+
+        // The keys must be provided.
+        let keys = CircuitKeys::new(vec![], vec![])?;
+        assert!(matches!(
+            circuit.evaluate(&[ct], &keys),
+            Err(crate::Error::MissingRelinearizationKey(0))
+        ));
+
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-//! The encoding type for BFV.
+//! The encoding type for BFV, also used by BGV.
 
 use std::fmt::Display;
 
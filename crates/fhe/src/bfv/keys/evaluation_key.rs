@@ -1,21 +1,41 @@
 //! Leveled evaluation keys for the BFV encryption scheme.
 
 use crate::bfv::{
-    keys::GaloisKey,
+    keys::{GaloisKey, KeySwitchingMode},
     proto::bfv::{EvaluationKey as EvaluationKeyProto, GaloisKey as GaloisKeyProto},
     traits::TryConvertFrom,
-    BfvParameters, Ciphertext, SecretKey,
+    BfvParameters, Ciphertext, LinearTransform, SecretKey,
 };
 use crate::{Error, Result};
 use fhe_math::rq::{traits::TryConvertFrom as TryConvertFromPoly, Poly, Representation};
 use fhe_math::zq::Modulus;
 use fhe_traits::{DeserializeParametrized, FheParametrized, Serialize};
+use itertools::Itertools;
 use protobuf::Message;
 use rand::{CryptoRng, RngCore};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
+/// Strategy to select the Galois keys enabling column rotations by any amount,
+/// which trades the number of keys for the number of rotations performed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RotationKeyStrategy {
+    /// Generate keys for the rotations by the powers of two smaller than the
+    /// row size, i.e., `log2(degree / 2)` keys. A rotation by `i` is
+    /// decomposed in binary, and uses one key per bit set in `i`.
+    #[default]
+    MinimizeKeys,
+
+    /// Generate keys for the rotations by the positive and negative powers of
+    /// two smaller than the row size, i.e., about `2 * log2(degree / 2)` keys.
+    /// A rotation by `i` is decomposed in non-adjacent form, and uses at most
+    /// `log2(degree / 2) / 2 + 1` keys, and a third on average.
+    MinimizeLatency,
+}
+
 /// Evaluation key for the BFV encryption scheme.
 ///
 /// An evaluation key enables one or several of the following operations:
@@ -23,6 +43,7 @@ use zeroize::{Zeroize, ZeroizeOnDrop};
 /// - row rotation
 /// - oblivious expansion
 /// - inner sum
+/// - arbitrary Galois automorphisms
 #[derive(Debug, PartialEq, Eq)]
 pub struct EvaluationKey {
     par: Arc<BfvParameters>,
@@ -62,9 +83,13 @@ impl EvaluationKey {
     /// Computes the homomorphic inner sum.
     pub fn computes_inner_sum(&self, ct: &Ciphertext) -> Result<Ciphertext> {
         if !self.supports_inner_sum() {
-            Err(Error::DefaultError(
-                "This key does not support the inner sum functionality".to_string(),
-            ))
+            let mut exponents = vec![self.par.degree() * 2 - 1];
+            let mut i = 1;
+            while i < self.par.degree() / 2 {
+                exponents.push(self.rotation_exponent(i));
+                i *= 2
+            }
+            Err(self.missing_galois_key(&exponents))
         } else {
             let mut out = ct.clone();
 
@@ -98,9 +123,7 @@ impl EvaluationKey {
     /// Homomorphically rotate the rows of the plaintext
     pub fn rotates_rows(&self, ct: &Ciphertext) -> Result<Ciphertext> {
         if !self.supports_row_rotation() {
-            Err(Error::DefaultError(
-                "This key does not support the row rotation functionality".to_string(),
-            ))
+            Err(Error::MissingGaloisKey(self.par.degree() * 2 - 1))
         } else {
             let gk = self.gk.get(&(self.par.degree() * 2 - 1)).unwrap();
             gk.relinearize(ct)
@@ -122,9 +145,7 @@ impl EvaluationKey {
     /// Homomorphically rotate the columns of the plaintext
     pub fn rotates_columns_by(&self, ct: &Ciphertext, i: usize) -> Result<Ciphertext> {
         if !self.supports_column_rotation_by(i) {
-            Err(Error::DefaultError(
-                "This key does not support rotating the columns by this index".to_string(),
-            ))
+            Err(Error::MissingGaloisKey(self.rotation_exponent(i)))
         } else {
             let gk = self
                 .gk
@@ -134,6 +155,92 @@ impl EvaluationKey {
         }
     }
 
+    /// Reports whether the evaluation key enables to apply the Galois
+    /// automorphism `x -> x^exponent`.
+    pub fn supports_galois(&self, exponent: usize) -> bool {
+        if self.evaluation_key_level == self.par.moduli().len() {
+            false
+        } else {
+            self.gk.contains_key(&(exponent % (2 * self.par.degree())))
+        }
+    }
+
+    /// Homomorphically apply the Galois automorphism `x -> x^exponent` to the
+    /// plaintext polynomial.
+    pub fn apply_galois(&self, ct: &Ciphertext, exponent: usize) -> Result<Ciphertext> {
+        if !self.supports_galois(exponent) {
+            Err(Error::MissingGaloisKey(exponent % (2 * self.par.degree())))
+        } else {
+            let gk = self.gk.get(&(exponent % (2 * self.par.degree()))).unwrap();
+            gk.relinearize(ct)
+        }
+    }
+
+    /// Decompose a left rotation of the columns by `i` into rotations for which
+    /// this evaluation key has a Galois key, using the non-adjacent form of
+    /// `i` if possible, and its binary decomposition otherwise. Returns `None`
+    /// if no decomposition is supported.
+    fn column_rotation_decomposition(&self, i: isize) -> Option<Vec<usize>> {
+        let row_size = self.par.degree() / 2;
+        let i = i.rem_euclid(row_size as isize) as usize;
+        if i == 0 {
+            return Some(vec![]);
+        } else if self.supports_column_rotation_by(i) {
+            return Some(vec![i]);
+        }
+
+        // A rotation by a multiple of the row size is the identity.
+        let naf = non_adjacent_form(i)
+            .iter()
+            .enumerate()
+            .filter(|(k, d)| **d != 0 && (1 << k) < row_size)
+            .map(|(k, d)| if *d > 0 { 1 << k } else { row_size - (1 << k) })
+            .collect_vec();
+        let binary = (0..row_size.ilog2())
+            .filter(|k| (i >> k) & 1 == 1)
+            .map(|k| 1 << k)
+            .collect_vec();
+        [naf, binary].into_iter().find(|decomposition| {
+            decomposition
+                .iter()
+                .all(|j| self.supports_column_rotation_by(*j))
+        })
+    }
+
+    /// Reports whether the evaluation key enables to rotate the columns of the
+    /// plaintext by `i`, possibly using several rotations. Negative values of
+    /// `i` correspond to rotations to the right.
+    pub fn supports_arbitrary_column_rotation_by(&self, i: isize) -> bool {
+        if self.evaluation_key_level == self.par.moduli().len() {
+            false
+        } else {
+            self.column_rotation_decomposition(i).is_some()
+        }
+    }
+
+    /// Homomorphically rotate the columns of the plaintext to the left by `i`,
+    /// or to the right by `-i` when `i` is negative. Unlike
+    /// [`Self::rotates_columns_by`], the rotation is decomposed into rotations
+    /// for which this key has Galois keys, e.g., the keys generated by
+    /// [`EvaluationKeyBuilder::enable_arbitrary_column_rotation`].
+    pub fn rotates_columns_arbitrarily_by(&self, ct: &Ciphertext, i: isize) -> Result<Ciphertext> {
+        if !self.supports_arbitrary_column_rotation_by(i) {
+            let row_size = self.par.degree() / 2;
+            let i = i.rem_euclid(row_size as isize) as usize;
+            let exponents = (0..row_size.ilog2())
+                .filter(|k| (i >> k) & 1 == 1)
+                .map(|k| self.rotation_exponent(1 << k))
+                .collect_vec();
+            return Err(self.missing_galois_key(&exponents));
+        }
+
+        let mut out = ct.clone();
+        for j in self.column_rotation_decomposition(i).unwrap() {
+            out = self.rotates_columns_by(&out, j)?;
+        }
+        Ok(out)
+    }
+
     /// Reports whether the evaluation key supports oblivious expansion.
     pub fn supports_expansion(&self, level: usize) -> bool {
         if level == 0 {
@@ -156,9 +263,10 @@ impl EvaluationKey {
     pub fn expands(&self, ct: &Ciphertext, size: usize) -> Result<Vec<Ciphertext>> {
         let level = size.next_power_of_two().ilog2() as usize;
         if ct.c.len() != 2 {
-            Err(Error::DefaultError(
-                "The ciphertext is not of size 2".to_string(),
-            ))
+            Err(Error::InvalidCiphertextSize {
+                expected: 2,
+                found: ct.c.len(),
+            })
         } else if level == 0 {
             Ok(vec![ct.clone()])
         } else if self.supports_expansion(level) {
@@ -170,25 +278,68 @@ impl EvaluationKey {
             for l in 0..level {
                 let monomial = &self.monomials[l];
                 let gk = self.gk.get(&((self.par.degree() >> l) + 1)).unwrap();
-                for i in 0..(1 << l) {
-                    let sub = gk.relinearize(&out[i])?;
+                // The ciphertexts `out[i]` and `out[(1 << l) | i]` are computed
+                // from `out[i]` only, independently for each `i`.
+                let (low, high) = out.split_at_mut(1 << l);
+                let expand = |(i, (low_i, high_i)): (usize, (&mut Ciphertext, &mut Ciphertext))| {
+                    let sub = gk.relinearize(low_i)?;
                     if (1 << l) | i < size {
-                        out[(1 << l) | i] = &out[i] - &sub;
-                        out[(1 << l) | i].c[0] *= monomial;
-                        out[(1 << l) | i].c[1] *= monomial;
+                        *high_i = &*low_i - &sub;
+                        high_i.c[0] *= monomial;
+                        high_i.c[1] *= monomial;
                     }
-                    out[i] += &sub;
-                }
+                    *low_i += &sub;
+                    Ok::<_, Error>(())
+                };
+                #[cfg(not(feature = "rayon"))]
+                low.iter_mut()
+                    .zip(high.iter_mut())
+                    .enumerate()
+                    .try_for_each(expand)?;
+                #[cfg(feature = "rayon")]
+                low.par_iter_mut()
+                    .zip(high.par_iter_mut())
+                    .enumerate()
+                    .try_for_each(expand)?;
             }
             out.truncate(size);
             Ok(out)
         } else {
-            Err(Error::DefaultError(
-                "This key does not support expansion at this level".to_string(),
-            ))
+            let exponents = (0..level)
+                .map(|l| (self.par.degree() >> l) + 1)
+                .collect_vec();
+            Err(self.missing_galois_key(&exponents))
+        }
+    }
+
+    /// Returns the level of the ciphertexts this evaluation key applies to.
+    pub(crate) fn ciphertext_level(&self) -> usize {
+        self.ciphertext_level
+    }
+
+    /// Returns the Galois exponent corresponding to a column rotation by `i`.
+    fn rotation_exponent(&self, i: usize) -> usize {
+        match self.rot_to_gk_exponent.get(&i) {
+            Some(exponent) => *exponent,
+            None => Modulus::new(2 * self.par.degree() as u64)
+                .unwrap()
+                .pow(3, i as u64) as usize,
         }
     }
 
+    /// Returns an error reporting the first exponent in `exponents` for which
+    /// this key does not have a Galois key.
+    fn missing_galois_key(&self, exponents: &[usize]) -> Error {
+        let exponent = exponents
+            .iter()
+            .find(|exponent| {
+                self.evaluation_key_level == self.par.moduli().len()
+                    || !self.gk.contains_key(exponent)
+            })
+            .unwrap_or(&exponents[0]);
+        Error::MissingGaloisKey(*exponent)
+    }
+
     fn construct_rot_to_gk_exponent(par: &Arc<BfvParameters>) -> HashMap<usize, usize> {
         let mut m = HashMap::new();
         let q = Modulus::new(2 * par.degree() as u64).unwrap();
@@ -200,6 +351,28 @@ impl EvaluationKey {
     }
 }
 
+/// Returns the non-adjacent form of `i`, i.e., its signed binary
+/// decomposition with digits in {-1, 0, 1} such that no two consecutive
+/// digits are non-zero, least significant digit first.
+fn non_adjacent_form(mut i: usize) -> Vec<i8> {
+    let mut digits = vec![];
+    while i > 0 {
+        if i & 1 == 1 {
+            if i & 3 == 1 {
+                digits.push(1);
+                i -= 1;
+            } else {
+                digits.push(-1);
+                i += 1;
+            }
+        } else {
+            digits.push(0);
+        }
+        i >>= 1;
+    }
+    digits
+}
+
 impl FheParametrized for EvaluationKey {
     type Parameters = BfvParameters;
 }
@@ -219,7 +392,7 @@ impl DeserializeParametrized for EvaluationKey {
         if let Ok(gkp) = gkp {
             EvaluationKey::try_convert_from(&gkp, par)
         } else {
-            Err(Error::DefaultError("Invalid serialization".to_string()))
+            Err(Error::SerializationError)
         }
     }
 }
@@ -234,7 +407,9 @@ pub struct EvaluationKeyBuilder {
     row_rotation: bool,
     expansion_level: usize,
     column_rotation: HashSet<usize>,
+    galois_elements: HashSet<usize>,
     rot_to_gk_exponent: HashMap<usize, usize>,
+    key_switching_mode: KeySwitchingMode,
 }
 
 impl Zeroize for EvaluationKeyBuilder {
@@ -256,7 +431,9 @@ impl EvaluationKeyBuilder {
             row_rotation: false,
             expansion_level: 0,
             column_rotation: HashSet::new(),
+            galois_elements: HashSet::new(),
             rot_to_gk_exponent: EvaluationKey::construct_rot_to_gk_exponent(&sk.par),
+            key_switching_mode: KeySwitchingMode::default(),
         })
     }
 
@@ -270,8 +447,10 @@ impl EvaluationKeyBuilder {
         ciphertext_level: usize,
         evaluation_key_level: usize,
     ) -> Result<Self> {
-        if ciphertext_level < evaluation_key_level || ciphertext_level > sk.par.max_level() {
-            return Err(Error::DefaultError("Unexpected levels".to_string()));
+        if ciphertext_level > sk.par.max_level() {
+            return Err(Error::InvalidLevel(ciphertext_level));
+        } else if ciphertext_level < evaluation_key_level {
+            return Err(Error::InvalidLevel(evaluation_key_level));
         }
 
         Ok(Self {
@@ -282,7 +461,9 @@ impl EvaluationKeyBuilder {
             row_rotation: false,
             expansion_level: 0,
             column_rotation: HashSet::new(),
+            galois_elements: HashSet::new(),
             rot_to_gk_exponent: EvaluationKey::construct_rot_to_gk_exponent(&sk.par),
+            key_switching_mode: KeySwitchingMode::default(),
         })
     }
 
@@ -297,11 +478,9 @@ impl EvaluationKeyBuilder {
             .len()
             == 1
         {
-            Err(Error::DefaultError(
-                "Not enough moduli to enable expansion".to_string(),
-            ))
+            Err(Error::NotEnoughModuli)
         } else if level >= 64 - self.sk.par.degree().leading_zeros() as usize {
-            Err(Error::DefaultError("Invalid level 2".to_string()))
+            Err(Error::InvalidLevel(level))
         } else {
             self.expansion_level = level;
             Ok(self)
@@ -319,9 +498,7 @@ impl EvaluationKeyBuilder {
             .len()
             == 1
         {
-            Err(Error::DefaultError(
-                "Not enough moduli to enable relinearization".to_string(),
-            ))
+            Err(Error::NotEnoughModuli)
         } else {
             self.inner_sum = true;
             Ok(self)
@@ -339,9 +516,7 @@ impl EvaluationKeyBuilder {
             .len()
             == 1
         {
-            Err(Error::DefaultError(
-                "Not enough moduli to enable relinearization".to_string(),
-            ))
+            Err(Error::NotEnoughModuli)
         } else {
             self.row_rotation = true;
             Ok(self)
@@ -356,7 +531,86 @@ impl EvaluationKeyBuilder {
             self.column_rotation.insert(*exp);
             Ok(self)
         } else {
-            Err(Error::DefaultError("Invalid column index".to_string()))
+            Err(Error::InvalidColumnIndex(i))
+        }
+    }
+
+    /// Allow this evaluation key to homomorphically rotate the plaintext
+    /// columns by any amount, by generating the Galois keys selected by the
+    /// strategy. See [`EvaluationKey::rotates_columns_arbitrarily_by`].
+    #[allow(unused_must_use)]
+    pub fn enable_arbitrary_column_rotation(
+        &mut self,
+        strategy: RotationKeyStrategy,
+    ) -> Result<&mut Self> {
+        let row_size = self.sk.par.degree() / 2;
+        let mut k = 1;
+        while k < row_size {
+            self.column_rotation
+                .insert(*self.rot_to_gk_exponent.get(&k).unwrap());
+            if strategy == RotationKeyStrategy::MinimizeLatency {
+                self.column_rotation
+                    .insert(*self.rot_to_gk_exponent.get(&(row_size - k)).unwrap());
+            }
+            k *= 2;
+        }
+        Ok(self)
+    }
+
+    /// Allow this evaluation key to evaluate the [`LinearTransform`], by
+    /// enabling the column and row rotations it uses.
+    pub fn enable_linear_transform(&mut self, lt: &LinearTransform) -> Result<&mut Self> {
+        for i in lt.rotation_indices() {
+            self.enable_column_rotation(i)?;
+        }
+        if lt.requires_row_rotation() {
+            self.enable_row_rotation()?;
+        }
+        Ok(self)
+    }
+
+    /// Allow this evaluation key to homomorphically apply the Galois
+    /// automorphism `x -> x^exponent`, where the exponent must be odd modulo
+    /// twice the degree.
+    #[allow(unused_must_use)]
+    pub fn enable_galois_element(&mut self, exponent: usize) -> Result<&mut Self> {
+        let exponent = exponent % (2 * self.sk.par.degree());
+        if exponent & 1 == 0 {
+            Err(Error::MathError(
+                fhe_math::Error::InvalidSubstitutionExponent(exponent),
+            ))
+        } else if self
+            .sk
+            .par
+            .ctx_at_level(self.evaluation_key_level)?
+            .moduli()
+            .len()
+            == 1
+        {
+            Err(Error::NotEnoughModuli)
+        } else {
+            self.galois_elements.insert(exponent);
+            Ok(self)
+        }
+    }
+
+    /// Use hybrid key switching with `dnum` digits for the Galois keys of
+    /// this evaluation key. See [`KeySwitchingMode::Hybrid`].
+    pub fn enable_hybrid_key_switching(&mut self, dnum: usize) -> Result<&mut Self> {
+        let num_moduli = self
+            .sk
+            .par
+            .ctx_at_level(self.ciphertext_level)?
+            .moduli()
+            .len();
+        if dnum == 0 || dnum > num_moduli {
+            Err(Error::InvalidDnum {
+                dnum,
+                max: num_moduli,
+            })
+        } else {
+            self.key_switching_mode = KeySwitchingMode::Hybrid { dnum };
+            Ok(self)
         }
     }
 
@@ -372,6 +626,7 @@ impl EvaluationKeyBuilder {
         };
 
         let mut indices = self.column_rotation.clone();
+        indices.extend(&self.galois_elements);
 
         if self.row_rotation {
             indices.insert(self.sk.par.degree() * 2 - 1);
@@ -406,14 +661,17 @@ impl EvaluationKeyBuilder {
             ek.monomials.push(monomial);
         }
 
-        for index in indices {
+        // Generate the keys in a fixed order so that the output only depends on
+        // the randomness generator.
+        for index in indices.into_iter().sorted() {
             ek.gk.insert(
                 index,
-                GaloisKey::new(
+                GaloisKey::new_with_mode(
                     &self.sk,
                     index,
                     self.ciphertext_level,
                     self.evaluation_key_level,
+                    self.key_switching_mode,
                     rng,
                 )?,
             );
@@ -426,7 +684,7 @@ impl EvaluationKeyBuilder {
 impl From<&EvaluationKey> for EvaluationKeyProto {
     fn from(ek: &EvaluationKey) -> Self {
         let mut proto = EvaluationKeyProto::new();
-        for (_, gk) in ek.gk.iter() {
+        for (_, gk) in ek.gk.iter().sorted_by_key(|(index, _)| **index) {
             proto.gk.push(GaloisKeyProto::from(gk))
         }
         proto.ciphertext_level = ek.ciphertext_level as u32;
@@ -441,14 +699,10 @@ impl TryConvertFrom<&EvaluationKeyProto> for EvaluationKey {
         for gkp in &value.gk {
             let key = GaloisKey::try_convert_from(gkp, par)?;
             if key.ksk.ciphertext_level != value.ciphertext_level as usize {
-                return Err(Error::DefaultError(
-                    "Galois key has incorrect ciphertext level".to_string(),
-                ));
+                return Err(Error::SerializationError);
             }
             if key.ksk.ksk_level != value.evaluation_key_level as usize {
-                return Err(Error::DefaultError(
-                    "Galois key has incorrect evaluation key level".to_string(),
-                ));
+                return Err(Error::SerializationError);
             }
             gk.insert(key.element.exponent, key);
         }
@@ -482,7 +736,7 @@ impl TryConvertFrom<&EvaluationKeyProto> for EvaluationKey {
 
 #[cfg(test)]
 mod tests {
-    use super::{EvaluationKey, EvaluationKeyBuilder};
+    use super::{EvaluationKey, EvaluationKeyBuilder, RotationKeyStrategy};
     use crate::bfv::{
         proto::bfv::EvaluationKey as LeveledEvaluationKeyProto, traits::TryConvertFrom,
         BfvParameters, Encoding, Plaintext, SecretKey,
@@ -490,8 +744,9 @@ mod tests {
     use fhe_traits::{
         DeserializeParametrized, FheDecoder, FheDecrypter, FheEncoder, FheEncrypter, Serialize,
     };
-    use itertools::izip;
-    use rand::thread_rng;
+    use itertools::{izip, Itertools};
+    use rand::{thread_rng, RngCore, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
     use std::{cmp::min, error::Error};
 
     #[test]
@@ -561,16 +816,30 @@ mod tests {
             EvaluationKeyBuilder::new_leveled(&sk, params.max_level(), params.max_level())?;
         let e = builder.enable_inner_sum();
         assert!(e.is_err());
-        assert_eq!(
-            e.unwrap_err(),
-            crate::Error::DefaultError("Not enough moduli to enable relinearization".to_string())
-        );
+        assert_eq!(e.unwrap_err(), crate::Error::NotEnoughModuli);
 
         let e = EvaluationKeyBuilder::new_leveled(&sk, 0, 1);
         assert!(e.is_err());
+        assert_eq!(e.unwrap_err(), crate::Error::InvalidLevel(1));
+
+        // The missing Galois keys are reported.
+        let ek = EvaluationKeyBuilder::new(&sk)?
+            .enable_row_rotation()?
+            .build(&mut rng)?;
+        let pt = Plaintext::try_encode(&[1u64, 2, 3], Encoding::simd(), &params)?;
+        let ct = sk.try_encrypt(&pt, &mut rng)?;
+        assert!(ek.rotates_rows(&ct).is_ok());
+        assert_eq!(
+            ek.rotates_columns_by(&ct, 1),
+            Err(crate::Error::MissingGaloisKey(3))
+        );
+        assert_eq!(
+            ek.computes_inner_sum(&ct),
+            Err(crate::Error::MissingGaloisKey(3))
+        );
         assert_eq!(
-            e.unwrap_err(),
-            crate::Error::DefaultError("Unexpected levels".to_string())
+            ek.apply_galois(&ct, 5),
+            Err(crate::Error::MissingGaloisKey(5))
         );
 
         Ok(())
@@ -722,6 +991,114 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn arbitrary_column_rotation() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BfvParameters::default_arc(3, 32);
+        let row_size = params.degree() >> 1;
+        let sk = SecretKey::random(&params, &mut rng);
+        for (strategy, num_keys, max_rotations) in [
+            (RotationKeyStrategy::MinimizeKeys, 4, 4),
+            (RotationKeyStrategy::MinimizeLatency, 7, 3),
+        ] {
+            let ek = EvaluationKeyBuilder::new(&sk)?
+                .enable_arbitrary_column_rotation(strategy)?
+                .build(&mut rng)?;
+            assert_eq!(ek.gk.len(), num_keys);
+
+            let v = params.plaintext.random_vec(params.degree(), &mut rng);
+            let pt = Plaintext::try_encode(&v, Encoding::simd(), &params)?;
+            let ct = sk.try_encrypt(&pt, &mut rng)?;
+            for i in -(row_size as isize)..=(row_size as isize) {
+                assert!(ek.supports_arbitrary_column_rotation_by(i));
+                let decomposition = ek.column_rotation_decomposition(i).unwrap();
+                assert!(decomposition.len() <= max_rotations);
+
+                let shift = i.rem_euclid(row_size as isize) as usize;
+                let expected = (0..params.degree())
+                    .map(|j| v[(j / row_size) * row_size + (j + shift) % row_size])
+                    .collect_vec();
+
+                let ct2 = ek.rotates_columns_arbitrarily_by(&ct, i)?;
+                let pt = sk.try_decrypt(&ct2)?;
+                assert_eq!(Vec::<u64>::try_decode(&pt, Encoding::simd())?, expected)
+            }
+        }
+
+        let ek = EvaluationKeyBuilder::new(&sk)?
+            .enable_column_rotation(1)?
+            .build(&mut rng)?;
+        assert!(ek.supports_arbitrary_column_rotation_by(0));
+        assert!(ek.supports_arbitrary_column_rotation_by(1));
+        assert!(!ek.supports_arbitrary_column_rotation_by(3));
+        Ok(())
+    }
+
+    #[test]
+    fn non_adjacent_form() {
+        for i in 0..1000usize {
+            let naf = super::non_adjacent_form(i);
+            let value = naf
+                .iter()
+                .enumerate()
+                .map(|(k, d)| (*d as isize) << k)
+                .sum::<isize>();
+            assert_eq!(value, i as isize);
+            assert!(naf.windows(2).all(|w| w[0] == 0 || w[1] == 0));
+        }
+        assert_eq!(super::non_adjacent_form(7), vec![-1, 0, 0, 1]);
+    }
+
+    #[test]
+    fn galois_automorphism() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BfvParameters::default_arc(4, 16);
+        let degree = params.degree();
+        for ciphertext_level in 0..=params.max_level() {
+            for evaluation_key_level in 0..=min(params.max_level() - 1, ciphertext_level) {
+                let sk = SecretKey::random(&params, &mut rng);
+                let mut builder =
+                    EvaluationKeyBuilder::new_leveled(&sk, ciphertext_level, evaluation_key_level)?;
+                assert!(builder.enable_galois_element(2).is_err());
+                let ek = builder
+                    .enable_galois_element(3)?
+                    .enable_galois_element(2 * degree + 7)?
+                    .build(&mut rng)?;
+                assert!(ek.supports_galois(3));
+                assert!(ek.supports_galois(7));
+                assert!(!ek.supports_galois(5));
+
+                let v = params.plaintext.random_vec(degree, &mut rng);
+                let pt =
+                    Plaintext::try_encode(&v, Encoding::poly_at_level(ciphertext_level), &params)?;
+                let ct = sk.try_encrypt(&pt, &mut rng)?;
+                assert!(ek.apply_galois(&ct, 5).is_err());
+
+                for exponent in [3, 7] {
+                    // The coefficient of x^i is sent to x^(i * exponent) modulo
+                    // x^degree + 1.
+                    let mut expected = vec![0u64; degree];
+                    for (i, vi) in v.iter().enumerate() {
+                        let j = (i * exponent) % (2 * degree);
+                        if j < degree {
+                            expected[j] = *vi
+                        } else {
+                            expected[j - degree] = params.plaintext.neg(*vi)
+                        }
+                    }
+
+                    let ct2 = ek.apply_galois(&ct, exponent)?;
+                    let pt = sk.try_decrypt(&ct2)?;
+                    assert_eq!(
+                        Vec::<u64>::try_decode(&pt, Encoding::poly_at_level(ciphertext_level))?,
+                        expected
+                    )
+                }
+            }
+        }
+        Ok(())
+    }
+
     #[test]
     fn expansion() -> Result<(), Box<dyn Error>> {
         let mut rng = thread_rng();
@@ -778,6 +1155,81 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_determinism() -> Result<(), Box<dyn Error>> {
+        // The results do not depend on the number of threads.
+        let mut rng = thread_rng();
+        let params = BfvParameters::default_arc(6, 16);
+        let sk = SecretKey::random(&params, &mut rng);
+        let v = params.plaintext.random_vec(params.degree(), &mut rng);
+        let pt = Plaintext::try_encode(&v, Encoding::simd(), &params)?;
+        let ct = sk.try_encrypt(&pt, &mut rng)?;
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(1).build()?;
+
+        for dnum in [None, Some(2)] {
+            let mut builder = EvaluationKeyBuilder::new(&sk)?;
+            builder.enable_expansion(4)?.enable_column_rotation(1)?;
+            if let Some(dnum) = dnum {
+                builder.enable_hybrid_key_switching(dnum)?;
+            }
+            let ek = builder.build(&mut rng)?;
+
+            let evaluate = || -> crate::Result<_> {
+                let mut out = ek.expands(&ct, 16)?;
+                out.push(ek.rotates_columns_by(&ct, 1)?);
+                Ok(out)
+            };
+            assert_eq!(pool.install(evaluate)?, evaluate()?);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn hybrid_key_switching() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BfvParameters::default_arc(4, 8);
+        let sk = SecretKey::random(&params, &mut rng);
+
+        assert_eq!(
+            EvaluationKeyBuilder::new(&sk)?
+                .enable_hybrid_key_switching(0)
+                .err(),
+            Some(crate::Error::InvalidDnum { dnum: 0, max: 4 })
+        );
+        assert_eq!(
+            EvaluationKeyBuilder::new_leveled(&sk, 1, 0)?
+                .enable_hybrid_key_switching(params.moduli.len())
+                .err(),
+            Some(crate::Error::InvalidDnum { dnum: 4, max: 3 })
+        );
+
+        let ek = EvaluationKeyBuilder::new(&sk)?
+            .enable_inner_sum()?
+            .enable_hybrid_key_switching(2)?
+            .build(&mut rng)?;
+        let v = params.plaintext.random_vec(params.degree(), &mut rng);
+        let expected = params
+            .plaintext
+            .reduce_u128(v.iter().map(|vi| *vi as u128).sum());
+
+        let pt = Plaintext::try_encode(&v, Encoding::simd(), &params)?;
+        let ct = sk.try_encrypt(&pt, &mut rng)?;
+        let ct2 = ek.computes_inner_sum(&ct)?;
+        println!("Noise: {}", unsafe { sk.measure_noise(&ct2)? });
+        let pt = sk.try_decrypt(&ct2)?;
+        assert_eq!(
+            Vec::<u64>::try_decode(&pt, Encoding::simd())?,
+            vec![expected; params.degree()]
+        );
+
+        let bytes = ek.to_bytes();
+        assert_eq!(ek, EvaluationKey::from_bytes(&bytes, &params)?);
+
+        Ok(())
+    }
+
     #[test]
     fn proto_conversion() -> Result<(), Box<dyn Error>> {
         let mut rng = thread_rng();
@@ -866,4 +1318,27 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn deterministic_build() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BfvParameters::default_arc(3, 16);
+
+        let mut seed = <ChaCha8Rng as SeedableRng>::Seed::default();
+        rng.fill_bytes(&mut seed);
+        let mut outputs = vec![];
+        for _ in 0..2 {
+            let mut rng = ChaCha8Rng::from_seed(seed);
+            let sk = SecretKey::random(&params, &mut rng);
+            let ek = EvaluationKeyBuilder::new(&sk)?
+                .enable_inner_sum()?
+                .enable_column_rotation(1)?
+                .enable_expansion(params.degree().ilog2() as usize)?
+                .build(&mut rng)?;
+            outputs.push(ek.to_bytes());
+        }
+        assert_eq!(outputs[0], outputs[1]);
+
+        Ok(())
+    }
 }
@@ -1,7 +1,8 @@
 //! Galois keys for the BFV encryption scheme
 
-use super::key_switching_key::KeySwitchingKey;
+use super::key_switching_key::{KeySwitchingKey, KeySwitchingMode};
 use crate::bfv::{
+    noise,
     proto::bfv::{GaloisKey as GaloisKeyProto, KeySwitchingKey as KeySwitchingKeyProto},
     traits::TryConvertFrom,
     BfvParameters, Ciphertext, SecretKey,
@@ -33,6 +34,26 @@ impl GaloisKey {
         ciphertext_level: usize,
         galois_key_level: usize,
         rng: &mut R,
+    ) -> Result<Self> {
+        Self::new_with_mode(
+            sk,
+            exponent,
+            ciphertext_level,
+            galois_key_level,
+            KeySwitchingMode::default(),
+            rng,
+        )
+    }
+
+    /// Generate a [`GaloisKey`] from a [`SecretKey`], using the specified key
+    /// switching decomposition.
+    pub fn new_with_mode<R: RngCore + CryptoRng>(
+        sk: &SecretKey,
+        exponent: usize,
+        ciphertext_level: usize,
+        galois_key_level: usize,
+        mode: KeySwitchingMode,
+        rng: &mut R,
     ) -> Result<Self> {
         let ctx_galois_key = sk.par.ctx_at_level(galois_key_level)?;
         let ctx_ciphertext = sk.par.ctx_at_level(ciphertext_level)?;
@@ -51,11 +72,12 @@ impl GaloisKey {
         let mut s_sub_switched_up = Zeroizing::new(s_sub.mod_switch_to(&switcher_up)?);
         s_sub_switched_up.change_representation(Representation::PowerBasis);
 
-        let ksk = KeySwitchingKey::new(
+        let ksk = KeySwitchingKey::new_with_mode(
             sk,
             &s_sub_switched_up,
             ciphertext_level,
             galois_key_level,
+            mode,
             rng,
         )?;
 
@@ -65,10 +87,30 @@ impl GaloisKey {
         })
     }
 
-    /// Relinearize a [`Ciphertext`] using the [`GaloisKey`]
+    /// Returns the exponent `i` of the substitution `x -> x^i`, reduced modulo
+    /// twice the degree.
+    pub fn exponent(&self) -> usize {
+        self.element.exponent
+    }
+
+    /// Apply the substitution `x -> x^i` to a [`Ciphertext`], and relinearize
+    /// the result using the [`GaloisKey`] so that it decrypts under the
+    /// original secret key. Returns an error if the ciphertext does not have
+    /// size 2, or if it is not at the level of the key.
     pub fn relinearize(&self, ct: &Ciphertext) -> Result<Ciphertext> {
-        // assert_eq!(ct.par, self.ksk.par);
-        assert_eq!(ct.c.len(), 2);
+        if ct.c.len() != 2 {
+            return Err(Error::InvalidCiphertextSize {
+                expected: 2,
+                found: ct.c.len(),
+            });
+        } else if ct.par != self.ksk.par {
+            return Err(Error::ParametersMismatch);
+        } else if ct.level != self.ksk.ciphertext_level {
+            return Err(Error::LevelMismatch {
+                expected: self.ksk.ciphertext_level,
+                found: ct.level,
+            });
+        }
 
         let mut c2 = ct.c[1].substitute(&self.element)?;
         c2.change_representation(Representation::PowerBasis);
@@ -90,6 +132,9 @@ impl GaloisKey {
             seed: None,
             c: vec![c0, c1],
             level: self.ksk.ciphertext_level,
+            noise: ct
+                .noise
+                .map(|noise| noise::addition(noise::rounding(noise), self.ksk.noise())),
         })
     }
 }
@@ -106,9 +151,7 @@ impl From<&GaloisKey> for GaloisKeyProto {
 impl TryConvertFrom<&GaloisKeyProto> for GaloisKey {
     fn try_convert_from(value: &GaloisKeyProto, par: &Arc<BfvParameters>) -> Result<Self> {
         if par.moduli.len() == 1 {
-            Err(Error::DefaultError(
-                "Invalid parameters for a relinearization key".to_string(),
-            ))
+            Err(Error::NotEnoughModuli)
         } else if value.ksk.is_some() {
             let ksk = KeySwitchingKey::try_convert_from(value.ksk.as_ref().unwrap(), par)?;
 
@@ -118,7 +161,7 @@ impl TryConvertFrom<&GaloisKeyProto> for GaloisKey {
 
             Ok(GaloisKey { element, ksk })
         } else {
-            Err(Error::DefaultError("Invalid serialization".to_string()))
+            Err(Error::SerializationError)
         }
     }
 }
@@ -128,7 +171,7 @@ mod tests {
     use super::GaloisKey;
     use crate::bfv::{
         proto::bfv::GaloisKey as GaloisKeyProto, traits::TryConvertFrom, BfvParameters, Encoding,
-        Plaintext, SecretKey,
+        KeySwitchingMode, Plaintext, SecretKey,
     };
     use fhe_traits::{FheDecoder, FheDecrypter, FheEncoder, FheEncrypter};
     use rand::thread_rng;
@@ -184,6 +227,40 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn rotation_hybrid() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BfvParameters::default_arc(4, 8);
+        let row_size = params.degree() >> 1;
+        for dnum in 1..=params.moduli.len() {
+            let sk = SecretKey::random(&params, &mut rng);
+            let v = params.plaintext.random_vec(params.degree(), &mut rng);
+            let pt = Plaintext::try_encode(&v, Encoding::simd(), &params)?;
+            let ct = sk.try_encrypt(&pt, &mut rng)?;
+
+            let gk = GaloisKey::new_with_mode(
+                &sk,
+                3,
+                0,
+                0,
+                KeySwitchingMode::Hybrid { dnum },
+                &mut rng,
+            )?;
+            let ct2 = gk.relinearize(&ct)?;
+            println!("Noise: {}", unsafe { sk.measure_noise(&ct2)? });
+
+            // The expected result is rotated one on the left
+            let pt = sk.try_decrypt(&ct2)?;
+            let mut expected = vec![0u64; params.degree()];
+            expected[..row_size - 1].copy_from_slice(&v[1..row_size]);
+            expected[row_size - 1] = v[0];
+            expected[row_size..2 * row_size - 1].copy_from_slice(&v[row_size + 1..]);
+            expected[2 * row_size - 1] = v[row_size];
+            assert_eq!(&Vec::<u64>::try_decode(&pt, Encoding::simd())?, &expected)
+        }
+        Ok(())
+    }
+
     #[test]
     fn proto_conversion() -> Result<(), Box<dyn Error>> {
         let mut rng = thread_rng();
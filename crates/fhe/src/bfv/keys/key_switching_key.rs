@@ -1,23 +1,277 @@
 //! Key-switching keys for the BFV encryption scheme
 
 use crate::bfv::{
-    proto::bfv::KeySwitchingKey as KeySwitchingKeyProto,
-    traits::TryConvertFrom as BfvTryConvertFrom, BfvParameters, SecretKey,
+    noise, proto::bfv::KeySwitchingKey as KeySwitchingKeyProto,
+    traits::TryConvertFrom as BfvTryConvertFrom, BfvParameters, SecretKey, SecurityLevel,
 };
-use crate::{Error, Result};
+use crate::{Error, ParametersError, Result};
 use fhe_math::rq::traits::TryConvertFrom;
 use fhe_math::rq::Context;
 use fhe_math::{
-    rns::RnsContext,
-    rq::{Poly, Representation},
+    rns::{RnsContext, ScalingFactor},
+    rq::{scaler::Scaler, Poly, Representation},
+    zq::primes::generate_prime,
 };
 use fhe_traits::{DeserializeWithContext, Serialize};
-use itertools::izip;
+use fhe_util::div_ceil;
+use itertools::{izip, Itertools};
+use ndarray::s;
+use num_bigint::BigUint;
+use num_traits::One;
 use rand::{CryptoRng, Rng, RngCore, SeedableRng};
 use rand_chacha::ChaCha8Rng;
-use std::sync::Arc;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
 use zeroize::Zeroizing;
 
+/// Decomposition used when generating key switching keys.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum KeySwitchingMode {
+    /// Decompose the polynomial to key switch into one digit per ciphertext
+    /// modulus.
+    #[default]
+    Rns,
+
+    /// Hybrid key switching: decompose the polynomial to key switch into
+    /// `dnum` digits, each spanning several ciphertext moduli, and perform the
+    /// key switching modulo an auxiliary basis of special primes, which is
+    /// then divided out.
+    ///
+    /// The key has `dnum` components instead of one per ciphertext modulus,
+    /// and there are as many special primes as moduli in the largest digit.
+    /// Note that the key is defined modulo a larger modulus than the
+    /// ciphertexts: generating the key fails if this modulus does not provide
+    /// the minimum security level required when building the parameters.
+    Hybrid {
+        /// The number of digits, between 1 and the number of ciphertext
+        /// moduli.
+        dnum: usize,
+    },
+}
+
+/// Precomputed data for hybrid key switching.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(crate) struct HybridKeySwitching {
+    /// The number of digits.
+    dnum: usize,
+
+    /// The special primes.
+    special_moduli: Box<[u64]>,
+
+    /// The range of ciphertext moduli spanned by each digit, and its context.
+    digits: Box<[Range<usize>]>,
+    ctx_digits: Box<[Arc<Context>]>,
+
+    /// The context of the key switching key extended with the special primes.
+    ctx_extended: Arc<Context>,
+
+    /// Extenders from the digits, and from the context of the key switching
+    /// key, to the extended context.
+    extenders: Box<[Scaler]>,
+    ksk_extender: Scaler,
+
+    /// Scaler from the extended context to the context of the key switching
+    /// key, dividing by the product of the special primes.
+    down_scaler: Scaler,
+}
+
+/// Cache of the precomputed data for hybrid key switching of a set of
+/// parameters, indexed by the ciphertext level, the level of the key switching
+/// key, and the number of digits. The cached data uses the generated special
+/// primes.
+#[derive(Debug, Default)]
+pub(crate) struct HybridKeySwitchingCache(
+    Mutex<HashMap<(usize, usize, usize), Arc<HybridKeySwitching>>>,
+);
+
+impl PartialEq for HybridKeySwitchingCache {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for HybridKeySwitchingCache {}
+
+impl HybridKeySwitching {
+    /// Returns the data for hybrid key switching with `dnum` digits, which is
+    /// precomputed once per parameters when the special primes are not
+    /// provided, or when they are the generated ones.
+    fn get(
+        par: &BfvParameters,
+        ciphertext_level: usize,
+        ksk_level: usize,
+        dnum: usize,
+        special_moduli: Option<&[u64]>,
+    ) -> Result<Arc<Self>> {
+        let key = (ciphertext_level, ksk_level, dnum);
+        let mut cache = par.hybrid_cache.0.lock().unwrap();
+        let cached = if let Some(hybrid) = cache.get(&key) {
+            Ok(hybrid.clone())
+        } else {
+            let hybrid = Self::new(par, ciphertext_level, ksk_level, dnum, None).map(Arc::new);
+            if let Ok(hybrid) = hybrid.as_ref() {
+                cache.insert(key, hybrid.clone());
+            }
+            hybrid
+        };
+        drop(cache);
+
+        match (cached, special_moduli) {
+            (cached, None) => cached,
+            (Ok(hybrid), Some(special_moduli)) if *hybrid.special_moduli == *special_moduli => {
+                Ok(hybrid)
+            }
+            (_, Some(special_moduli)) => {
+                Self::new(par, ciphertext_level, ksk_level, dnum, Some(special_moduli))
+                    .map(Arc::new)
+            }
+        }
+    }
+
+    /// Precompute the data for hybrid key switching with `dnum` digits. The
+    /// special primes are generated when they are not provided.
+    fn new(
+        par: &BfvParameters,
+        ciphertext_level: usize,
+        ksk_level: usize,
+        dnum: usize,
+        special_moduli: Option<&[u64]>,
+    ) -> Result<Self> {
+        let ctx_ciphertext = par.ctx_at_level(ciphertext_level)?;
+        let ctx_ksk = par.ctx_at_level(ksk_level)?;
+        let num_moduli = ctx_ciphertext.moduli().len();
+        if dnum == 0 || dnum > num_moduli {
+            return Err(Error::InvalidDnum {
+                dnum,
+                max: num_moduli,
+            });
+        }
+
+        // Split the ciphertext moduli in `dnum` digits of balanced sizes.
+        let digits = (0..dnum)
+            .map(|j| (j * num_moduli / dnum)..((j + 1) * num_moduli / dnum))
+            .collect::<Vec<_>>();
+
+        let num_special_moduli = div_ceil(num_moduli, dnum);
+        let special_moduli = if let Some(special_moduli) = special_moduli {
+            if special_moduli.len() < num_special_moduli {
+                return Err(Error::TooFewValues(
+                    special_moduli.len(),
+                    num_special_moduli,
+                ));
+            } else if special_moduli.len() > num_special_moduli {
+                return Err(Error::TooManyValues(
+                    special_moduli.len(),
+                    num_special_moduli,
+                ));
+            } else if !special_moduli.iter().all_unique()
+                || special_moduli.iter().any(|pi| par.moduli.contains(pi))
+            {
+                return Err(Error::InvalidSpecialModuli);
+            }
+            special_moduli.to_vec()
+        } else {
+            Self::generate_special_moduli(par, num_special_moduli)?
+        };
+
+        // The key is defined modulo the product of the moduli of the key
+        // switching key and of the special primes, which must still provide the
+        // minimum security level of the parameters.
+        let degree = par.degree();
+        if let Some(min_security_level) = par.min_security_level {
+            let modulus_size = ctx_ksk
+                .moduli()
+                .iter()
+                .chain(special_moduli.iter())
+                .map(|m| 64 - m.leading_zeros() as usize)
+                .sum::<usize>();
+            let security_level = SecurityLevel::estimate(degree, modulus_size);
+            if security_level.is_none_or(|level| level < min_security_level) {
+                return Err(Error::ParametersError(
+                    ParametersError::InsufficientSecurity(
+                        security_level.map_or(0, |level| level.bits()),
+                        min_security_level.bits(),
+                    ),
+                ));
+            }
+        }
+
+        let ctx_extended = Arc::new(Context::new(
+            &[ctx_ksk.moduli(), &special_moduli].concat(),
+            degree,
+        )?);
+        let ctx_digits = digits
+            .iter()
+            .map(|digit| Context::new_arc(&ctx_ciphertext.moduli()[digit.clone()], degree))
+            .collect::<fhe_math::Result<Vec<_>>>()?;
+        let extenders = ctx_digits
+            .iter()
+            .map(|ctx_digit| Scaler::new(ctx_digit, &ctx_extended, ScalingFactor::one()))
+            .collect::<fhe_math::Result<Vec<_>>>()?;
+        let ksk_extender = Scaler::new(ctx_ksk, &ctx_extended, ScalingFactor::one())?;
+        let special_product = special_moduli
+            .iter()
+            .fold(BigUint::one(), |acc, pi| acc * pi);
+        let down_scaler = Scaler::new(
+            &ctx_extended,
+            ctx_ksk,
+            ScalingFactor::new(&BigUint::one(), &special_product),
+        )?;
+
+        Ok(Self {
+            dnum,
+            special_moduli: special_moduli.into_boxed_slice(),
+            digits: digits.into_boxed_slice(),
+            ctx_digits: ctx_digits.into_boxed_slice(),
+            ctx_extended,
+            extenders: extenders.into_boxed_slice(),
+            ksk_extender,
+            down_scaler,
+        })
+    }
+
+    /// Generate `count` special primes of 62 bits, distinct from the
+    /// ciphertext moduli.
+    fn generate_special_moduli(par: &BfvParameters, count: usize) -> Result<Vec<u64>> {
+        let mut special_moduli = Vec::with_capacity(count);
+        let mut upper_bound = 1u64 << 62;
+        while special_moduli.len() < count {
+            let pi = generate_prime(62, 2 * par.degree() as u64, upper_bound).ok_or(
+                Error::ParametersError(ParametersError::NotEnoughPrimes(62, par.degree())),
+            )?;
+            if !par.moduli.contains(&pi) {
+                special_moduli.push(pi)
+            }
+            upper_bound = pi;
+        }
+        Ok(special_moduli)
+    }
+
+    /// The gadget vector, i.e., the product of the special primes with the CRT
+    /// idempotents of the digits modulo the ciphertext modulus.
+    fn gadget(&self, ctx_ciphertext: &Arc<Context>) -> Result<Vec<BigUint>> {
+        let rns = RnsContext::new(ctx_ciphertext.moduli())?;
+        let special_product = self
+            .special_moduli
+            .iter()
+            .fold(BigUint::one(), |acc, pi| acc * pi);
+        Ok(self
+            .digits
+            .iter()
+            .map(|digit| {
+                let idempotent = digit
+                    .clone()
+                    .map(|i| rns.get_garner(i).unwrap())
+                    .sum::<BigUint>();
+                &special_product * idempotent
+            })
+            .collect())
+    }
+}
+
 /// Key switching key for the BFV encryption scheme.
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct KeySwitchingKey {
@@ -40,6 +294,10 @@ pub struct KeySwitchingKey {
     /// The level and context of the key switching key.
     pub(crate) ksk_level: usize,
     pub(crate) ctx_ksk: Arc<Context>,
+
+    /// The precomputations for hybrid key switching, if enabled, shared by
+    /// the keys of the same parameters.
+    pub(crate) hybrid: Option<Arc<HybridKeySwitching>>,
 }
 
 impl KeySwitchingKey {
@@ -51,26 +309,67 @@ impl KeySwitchingKey {
         ciphertext_level: usize,
         ksk_level: usize,
         rng: &mut R,
+    ) -> Result<Self> {
+        Self::new_with_mode(
+            sk,
+            from,
+            ciphertext_level,
+            ksk_level,
+            KeySwitchingMode::Rns,
+            rng,
+        )
+    }
+
+    /// Generate a [`KeySwitchingKey`] to this [`SecretKey`] from a polynomial
+    /// `from`, using the specified decomposition.
+    pub fn new_with_mode<R: RngCore + CryptoRng>(
+        sk: &SecretKey,
+        from: &Poly,
+        ciphertext_level: usize,
+        ksk_level: usize,
+        mode: KeySwitchingMode,
+        rng: &mut R,
     ) -> Result<Self> {
         let ctx_ksk = sk.par.ctx_at_level(ksk_level)?;
         let ctx_ciphertext = sk.par.ctx_at_level(ciphertext_level)?;
 
-        if ctx_ksk.moduli().len() == 1 {
-            return Err(Error::DefaultError(
-                "These parameters do not support key switching".to_string(),
-            ));
-        }
+        let hybrid = match mode {
+            KeySwitchingMode::Rns => {
+                if ctx_ksk.moduli().len() == 1 {
+                    return Err(Error::NotEnoughModuli);
+                }
+                None
+            }
+            KeySwitchingMode::Hybrid { dnum } => Some(HybridKeySwitching::get(
+                &sk.par,
+                ciphertext_level,
+                ksk_level,
+                dnum,
+                None,
+            )?),
+        };
 
         if from.ctx() != ctx_ksk {
-            return Err(Error::DefaultError(
-                "Incorrect context for polynomial from".to_string(),
-            ));
+            return Err(Error::MathError(fhe_math::Error::InvalidContext));
         }
 
         let mut seed = <ChaCha8Rng as SeedableRng>::Seed::default();
         rng.fill(&mut seed);
-        let c1 = Self::generate_c1(ctx_ksk, seed, ctx_ciphertext.moduli().len());
-        let c0 = Self::generate_c0(sk, from, &c1, rng)?;
+        let (c0, c1) = if let Some(hybrid) = hybrid.as_ref() {
+            let c1 = Self::generate_c1(&hybrid.ctx_extended, seed, hybrid.dnum);
+            let from_extended = Zeroizing::new(from.scale(&hybrid.ksk_extender)?);
+            let gadget = hybrid.gadget(ctx_ciphertext)?;
+            let c0 = Self::generate_c0(sk, &from_extended, &c1, &gadget, rng)?;
+            (c0, c1)
+        } else {
+            let c1 = Self::generate_c1(ctx_ksk, seed, ctx_ciphertext.moduli().len());
+            let rns = RnsContext::new(ctx_ciphertext.moduli())?;
+            let gadget = (0..c1.len())
+                .map(|i| rns.get_garner(i).unwrap().clone())
+                .collect::<Vec<_>>();
+            let c0 = Self::generate_c0(sk, from, &c1, &gadget, rng)?;
+            (c0, c1)
+        };
 
         Ok(Self {
             par: sk.par.clone(),
@@ -81,6 +380,7 @@ impl KeySwitchingKey {
             ctx_ciphertext: ctx_ciphertext.clone(),
             ksk_level,
             ctx_ksk: ctx_ksk.clone(),
+            hybrid,
         })
     }
 
@@ -102,24 +402,24 @@ impl KeySwitchingKey {
         c1
     }
 
-    /// Generate the c0's from the c1's and the secret key
+    /// Generate the c0's from the c1's, the secret key, and the gadget vector
     fn generate_c0<R: RngCore + CryptoRng>(
         sk: &SecretKey,
         from: &Poly,
         c1: &[Poly],
+        gadget: &[BigUint],
         rng: &mut R,
     ) -> Result<Vec<Poly>> {
         if c1.is_empty() {
-            return Err(Error::DefaultError("Empty number of c1's".to_string()));
+            return Err(Error::TooFewValues(0, 1));
         }
         if from.representation() != &Representation::PowerBasis {
-            return Err(Error::DefaultError(
-                "Unexpected representation for from".to_string(),
-            ));
+            return Err(Error::MathError(fhe_math::Error::IncorrectRepresentation(
+                from.representation().clone(),
+                Representation::PowerBasis,
+            )));
         }
 
-        let size = c1.len();
-
         let mut s = Zeroizing::new(Poly::try_convert_from(
             sk.coeffs.as_ref(),
             c1[0].ctx(),
@@ -128,11 +428,8 @@ impl KeySwitchingKey {
         )?);
         s.change_representation(Representation::Ntt);
 
-        let rns = RnsContext::new(&sk.par.moduli[..size])?;
-        let c0 = c1
-            .iter()
-            .enumerate()
-            .map(|(i, c1i)| {
+        let c0 = izip!(c1.iter(), gadget.iter())
+            .map(|(c1i, gi)| {
                 let mut a_s = Zeroizing::new(c1i.clone());
                 a_s.disallow_variable_time_computations();
                 a_s.change_representation(Representation::Ntt);
@@ -143,7 +440,6 @@ impl KeySwitchingKey {
                     Poly::small(a_s.ctx(), Representation::PowerBasis, sk.par.variance, rng)?;
                 b -= &a_s;
 
-                let gi = rns.get_garner(i).unwrap();
                 let g_i_from = Zeroizing::new(gi * from);
                 b += &g_i_from;
 
@@ -160,33 +456,107 @@ impl KeySwitchingKey {
     /// Key switch a polynomial.
     pub fn key_switch(&self, p: &Poly) -> Result<(Poly, Poly)> {
         if p.ctx().as_ref() != self.ctx_ciphertext.as_ref() {
-            return Err(Error::DefaultError(
-                "The input polynomial does not have the correct context.".to_string(),
-            ));
+            return Err(Error::MathError(fhe_math::Error::InvalidContext));
         }
         if p.representation() != &Representation::PowerBasis {
-            return Err(Error::DefaultError("Incorrect representation".to_string()));
+            return Err(Error::MathError(fhe_math::Error::IncorrectRepresentation(
+                p.representation().clone(),
+                Representation::PowerBasis,
+            )));
         }
 
-        let mut c0 = Poly::zero(&self.ctx_ksk, Representation::Ntt);
-        let mut c1 = Poly::zero(&self.ctx_ksk, Representation::Ntt);
-        for (c2_i_coefficients, c0_i, c1_i) in izip!(
-            p.coefficients().outer_iter(),
-            self.c0.iter(),
-            self.c1.iter()
-        ) {
+        if let Some(hybrid) = self.hybrid.as_ref() {
+            return self.key_switch_hybrid(p, hybrid);
+        }
+
+        // The products with the digits are computed independently (in parallel
+        // with the `rayon` feature), and summed in order.
+        let c2 = p.coefficients();
+        let product = |i: usize| {
             let mut c2_i = unsafe {
                 Poly::create_constant_ntt_polynomial_with_lazy_coefficients_and_variable_time(
-                    c2_i_coefficients.as_slice().unwrap(),
+                    c2.row(i).as_slice().unwrap(),
                     &self.ctx_ksk,
                 )
             };
-            c0 += &(&c2_i * c0_i);
-            c2_i *= c1_i;
-            c1 += &c2_i;
+            let c0_i = &c2_i * &self.c0[i];
+            c2_i *= &self.c1[i];
+            (c0_i, c2_i)
+        };
+        #[cfg(not(feature = "rayon"))]
+        let products = (0..c2.nrows().min(self.c0.len())).map(product);
+        #[cfg(feature = "rayon")]
+        let products = (0..c2.nrows().min(self.c0.len()))
+            .into_par_iter()
+            .map(product)
+            .collect::<Vec<_>>();
+
+        let mut c0 = Poly::zero(&self.ctx_ksk, Representation::Ntt);
+        let mut c1 = Poly::zero(&self.ctx_ksk, Representation::Ntt);
+        for (c0_i, c1_i) in products {
+            c0 += &c0_i;
+            c1 += &c1_i;
         }
         Ok((c0, c1))
     }
+
+    /// Key switch a polynomial using hybrid key switching.
+    fn key_switch_hybrid(&self, p: &Poly, hybrid: &HybridKeySwitching) -> Result<(Poly, Poly)> {
+        let product = |i: usize| -> Result<(Poly, Poly)> {
+            let c2_i = Poly::try_convert_from(
+                p.coefficients()
+                    .slice(s![hybrid.digits[i].clone(), ..])
+                    .to_owned(),
+                &hybrid.ctx_digits[i],
+                true,
+                Representation::PowerBasis,
+            )?;
+            let mut c2_i = c2_i.scale(&hybrid.extenders[i])?;
+            c2_i.change_representation(Representation::Ntt);
+            let c0_i = &c2_i * &self.c0[i];
+            c2_i *= &self.c1[i];
+            Ok((c0_i, c2_i))
+        };
+        #[cfg(not(feature = "rayon"))]
+        let products = (0..hybrid.digits.len()).map(product);
+        #[cfg(feature = "rayon")]
+        let products = (0..hybrid.digits.len())
+            .into_par_iter()
+            .map(product)
+            .collect::<Vec<_>>();
+
+        let mut c0 = Poly::zero(&hybrid.ctx_extended, Representation::Ntt);
+        let mut c1 = Poly::zero(&hybrid.ctx_extended, Representation::Ntt);
+        for product in products {
+            let (c0_i, c1_i) = product?;
+            c0 += &c0_i;
+            c1 += &c1_i;
+        }
+        Ok((
+            c0.scale(&hybrid.down_scaler)?,
+            c1.scale(&hybrid.down_scaler)?,
+        ))
+    }
+
+    /// Returns the base-2 logarithm of a heuristic bound on the noise added by
+    /// a key switching, once switched down to the context of the ciphertext.
+    pub(crate) fn noise(&self) -> f64 {
+        let moduli = self.ctx_ciphertext.moduli();
+        let mut log2_divisor =
+            noise::log2_product(self.ctx_ksk.moduli()) - noise::log2_product(moduli);
+        if let Some(hybrid) = self.hybrid.as_ref() {
+            // The extension of a digit to the extended context is approximate, and
+            // may add a small multiple of the digit modulus.
+            log2_divisor += noise::log2_product(&hybrid.special_moduli);
+            let digits = hybrid.digits.iter().map(|digit| {
+                ((digit.len() + 1) as f64).log2() + noise::log2_product(&moduli[digit.clone()])
+            });
+            noise::key_switching(&self.par, digits, log2_divisor)
+        } else {
+            let digits = moduli.iter().map(|qi| (*qi as f64).log2());
+            noise::key_switching(&self.par, digits, log2_divisor)
+        }
+    }
 }
 
 impl From<&KeySwitchingKey> for KeySwitchingKeyProto {
@@ -206,6 +576,10 @@ impl From<&KeySwitchingKey> for KeySwitchingKeyProto {
         }
         ksk.ciphertext_level = value.ciphertext_level as u32;
         ksk.ksk_level = value.ksk_level as u32;
+        if let Some(hybrid) = value.hybrid.as_ref() {
+            ksk.dnum = hybrid.dnum as u32;
+            ksk.special_moduli = hybrid.special_moduli.to_vec();
+        }
         ksk
     }
 }
@@ -217,41 +591,57 @@ impl BfvTryConvertFrom<&KeySwitchingKeyProto> for KeySwitchingKey {
         let ctx_ksk = par.ctx_at_level(ksk_level)?;
         let ctx_ciphertext = par.ctx_at_level(ciphertext_level)?;
 
-        if value.c0.len() != ctx_ciphertext.moduli().len() {
-            return Err(Error::DefaultError(
-                "Incorrect number of values in c0".to_string(),
-            ));
+        let hybrid = if value.dnum == 0 {
+            if !value.special_moduli.is_empty() {
+                return Err(Error::SerializationError);
+            }
+            None
+        } else {
+            Some(HybridKeySwitching::get(
+                par,
+                ciphertext_level,
+                ksk_level,
+                value.dnum as usize,
+                Some(&value.special_moduli),
+            )?)
+        };
+        let (ctx_key, size) = if let Some(hybrid) = hybrid.as_ref() {
+            (&hybrid.ctx_extended, hybrid.dnum)
+        } else {
+            (ctx_ksk, ctx_ciphertext.moduli().len())
+        };
+
+        if value.c0.len() != size {
+            return Err(Error::SerializationError);
         }
 
         let seed = if value.seed.is_empty() {
-            if value.c1.len() != ctx_ciphertext.moduli().len() {
-                return Err(Error::DefaultError(
-                    "Incorrect number of values in c1".to_string(),
-                ));
+            if value.c1.len() != size {
+                return Err(Error::SerializationError);
             }
             None
         } else {
             let unwrapped = <ChaCha8Rng as SeedableRng>::Seed::try_from(value.seed.clone());
             if unwrapped.is_err() {
-                return Err(Error::DefaultError("Invalid seed".to_string()));
+                return Err(Error::SerializationError);
             }
             Some(unwrapped.unwrap())
         };
 
         let c1 = if let Some(seed) = seed {
-            Self::generate_c1(ctx_ksk, seed, value.c0.len())
+            Self::generate_c1(ctx_key, seed, value.c0.len())
         } else {
             value
                 .c1
                 .iter()
-                .map(|c1i| Poly::from_bytes(c1i, ctx_ksk).map_err(Error::MathError))
+                .map(|c1i| Poly::from_bytes(c1i, ctx_key).map_err(Error::MathError))
                 .collect::<Result<Vec<Poly>>>()?
         };
 
         let c0 = value
             .c0
             .iter()
-            .map(|c0i| Poly::from_bytes(c0i, ctx_ksk).map_err(Error::MathError))
+            .map(|c0i| Poly::from_bytes(c0i, ctx_key).map_err(Error::MathError))
             .collect::<Result<Vec<Poly>>>()?;
 
         Ok(Self {
@@ -263,6 +653,7 @@ impl BfvTryConvertFrom<&KeySwitchingKeyProto> for KeySwitchingKey {
             ctx_ciphertext: ctx_ciphertext.clone(),
             ksk_level,
             ctx_ksk: ctx_ksk.clone(),
+            hybrid,
         })
     }
 }
@@ -270,17 +661,20 @@ impl BfvTryConvertFrom<&KeySwitchingKeyProto> for KeySwitchingKey {
 #[cfg(test)]
 mod tests {
     use crate::bfv::{
-        keys::key_switching_key::KeySwitchingKey,
-        proto::bfv::KeySwitchingKey as KeySwitchingKeyProto, traits::TryConvertFrom, BfvParameters,
-        SecretKey,
+        keys::key_switching_key::{HybridKeySwitching, KeySwitchingKey, KeySwitchingMode},
+        proto::bfv::KeySwitchingKey as KeySwitchingKeyProto,
+        traits::TryConvertFrom,
+        BfvParameters, BfvParametersBuilder, SecretKey,
     };
+    use crate::{Error as FheError, ParametersError};
     use fhe_math::{
         rns::RnsContext,
         rq::{traits::TryConvertFrom as TryConvertFromPoly, Poly, Representation},
+        zq::primes::generate_prime,
     };
     use num_bigint::BigUint;
     use rand::thread_rng;
-    use std::error::Error;
+    use std::{error::Error, sync::Arc};
 
     #[test]
     fn constructor() -> Result<(), Box<dyn Error>> {
@@ -336,6 +730,121 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn key_switch_hybrid() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        for params in [
+            BfvParameters::default_arc(1, 8),
+            BfvParameters::default_arc(6, 8),
+        ] {
+            for dnum in 1..=params.moduli.len() {
+                for _ in 0..20 {
+                    let sk = SecretKey::random(&params, &mut rng);
+                    let ctx = params.ctx_at_level(0)?;
+                    let mut p = Poly::small(ctx, Representation::PowerBasis, 10, &mut rng)?;
+                    let ksk = KeySwitchingKey::new_with_mode(
+                        &sk,
+                        &p,
+                        0,
+                        0,
+                        KeySwitchingMode::Hybrid { dnum },
+                        &mut rng,
+                    )?;
+                    assert_eq!(ksk.c0.len(), dnum);
+                    let mut s = Poly::try_convert_from(
+                        sk.coeffs.as_ref(),
+                        ctx,
+                        false,
+                        Representation::PowerBasis,
+                    )
+                    .map_err(crate::Error::MathError)?;
+                    s.change_representation(Representation::Ntt);
+
+                    let mut input = Poly::random(ctx, Representation::PowerBasis, &mut rng);
+                    let (c0, c1) = ksk.key_switch(&input)?;
+
+                    let mut c2 = &c0 + &(&c1 * &s);
+                    c2.change_representation(Representation::PowerBasis);
+
+                    input.change_representation(Representation::Ntt);
+                    p.change_representation(Representation::Ntt);
+                    let mut c3 = &input * &p;
+                    c3.change_representation(Representation::PowerBasis);
+
+                    // The noise is divided by the special primes, and is much
+                    // smaller than with one digit per modulus.
+                    let rns = RnsContext::new(&params.moduli)?;
+                    Vec::<BigUint>::from(&(&c2 - &c3)).iter().for_each(|b| {
+                        assert!(std::cmp::min(b.bits(), (rns.modulus() - b).bits()) <= 16)
+                    });
+                }
+            }
+
+            let sk = SecretKey::random(&params, &mut rng);
+            let p = Poly::small(
+                params.ctx_at_level(0)?,
+                Representation::PowerBasis,
+                10,
+                &mut rng,
+            )?;
+            for dnum in [0, params.moduli.len() + 1] {
+                assert!(KeySwitchingKey::new_with_mode(
+                    &sk,
+                    &p,
+                    0,
+                    0,
+                    KeySwitchingMode::Hybrid { dnum },
+                    &mut rng
+                )
+                .is_err());
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn key_switch_hybrid_security() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        // 156 bits of ciphertext modulus provide 128 bits of security, and so
+        // do 218 bits for the key when adding one special prime of 62 bits.
+        let params = BfvParametersBuilder::new()
+            .set_degree(8192)
+            .set_plaintext_modulus(1153)
+            .set_moduli_sizes(&[52, 52, 52])
+            .build_arc()?;
+        let sk = SecretKey::random(&params, &mut rng);
+        let p = Poly::small(
+            params.ctx_at_level(0)?,
+            Representation::PowerBasis,
+            10,
+            &mut rng,
+        )?;
+        assert!(KeySwitchingKey::new_with_mode(
+            &sk,
+            &p,
+            0,
+            0,
+            KeySwitchingMode::Hybrid { dnum: 3 },
+            &mut rng
+        )
+        .is_ok());
+
+        // Three special primes bring the key modulus beyond the bound.
+        assert_eq!(
+            KeySwitchingKey::new_with_mode(
+                &sk,
+                &p,
+                0,
+                0,
+                KeySwitchingMode::Hybrid { dnum: 1 },
+                &mut rng
+            )
+            .unwrap_err(),
+            FheError::ParametersError(ParametersError::InsufficientSecurity(0, 128))
+        );
+        Ok(())
+    }
+
     #[test]
     fn proto_conversion() -> Result<(), Box<dyn Error>> {
         let mut rng = thread_rng();
@@ -349,7 +858,75 @@ mod tests {
             let ksk = KeySwitchingKey::new(&sk, &p, 0, 0, &mut rng)?;
             let ksk_proto = KeySwitchingKeyProto::from(&ksk);
             assert_eq!(ksk, KeySwitchingKey::try_convert_from(&ksk_proto, &params)?);
+
+            let ksk = KeySwitchingKey::new_with_mode(
+                &sk,
+                &p,
+                0,
+                0,
+                KeySwitchingMode::Hybrid { dnum: 2 },
+                &mut rng,
+            )?;
+            let ksk_proto = KeySwitchingKeyProto::from(&ksk);
+            assert_eq!(ksk, KeySwitchingKey::try_convert_from(&ksk_proto, &params)?);
+
+            // The special moduli cannot be duplicated, nor be ciphertext moduli.
+            let ksk = KeySwitchingKey::new_with_mode(
+                &sk,
+                &p,
+                0,
+                0,
+                KeySwitchingMode::Hybrid { dnum: 1 },
+                &mut rng,
+            )?;
+            let mut ksk_proto = KeySwitchingKeyProto::from(&ksk);
+            ksk_proto.special_moduli[1] = ksk_proto.special_moduli[0];
+            assert!(KeySwitchingKey::try_convert_from(&ksk_proto, &params).is_err());
+            ksk_proto.special_moduli[1] = params.moduli[1];
+            assert!(KeySwitchingKey::try_convert_from(&ksk_proto, &params).is_err());
         }
         Ok(())
     }
+
+    #[test]
+    fn hybrid_precomputations_are_shared() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BfvParameters::default_arc(6, 8);
+        let sk = SecretKey::random(&params, &mut rng);
+        let ctx = params.ctx_at_level(0)?;
+        let mut keys = vec![];
+        for _ in 0..2 {
+            let p = Poly::small(ctx, Representation::PowerBasis, 10, &mut rng)?;
+            keys.push(KeySwitchingKey::new_with_mode(
+                &sk,
+                &p,
+                0,
+                0,
+                KeySwitchingMode::Hybrid { dnum: 3 },
+                &mut rng,
+            )?);
+        }
+        let ksk_proto = KeySwitchingKeyProto::from(&keys[0]);
+        keys.push(KeySwitchingKey::try_convert_from(&ksk_proto, &params)?);
+        let hybrid = keys[0].hybrid.as_ref().unwrap();
+        assert!(keys[1..]
+            .iter()
+            .all(|ksk| Arc::ptr_eq(hybrid, ksk.hybrid.as_ref().unwrap())));
+
+        // Other special primes do not use the cached precomputations.
+        let mut special_moduli = hybrid.special_moduli.to_vec();
+        special_moduli[0] = generate_prime(61, 2 * params.degree() as u64, 1 << 61).unwrap();
+        let other = HybridKeySwitching::get(&params, 0, 0, 3, Some(&special_moduli))?;
+        assert!(!Arc::ptr_eq(hybrid, &other));
+        assert_eq!(*other.special_moduli, *special_moduli);
+
+        // Different parameters do not share their precomputations.
+        let other_params = BfvParameters::default_arc(6, 8);
+        let ksk = KeySwitchingKey::try_convert_from(
+            &KeySwitchingKeyProto::from(&keys[0]),
+            &other_params,
+        )?;
+        assert!(!Arc::ptr_eq(hybrid, ksk.hybrid.as_ref().unwrap()));
+        Ok(())
+    }
 }
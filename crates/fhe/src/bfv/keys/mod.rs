@@ -5,10 +5,11 @@ mod public_key;
 mod relinearization_key;
 mod secret_key;
 
-pub use evaluation_key::{EvaluationKey, EvaluationKeyBuilder};
+pub use evaluation_key::{EvaluationKey, EvaluationKeyBuilder, RotationKeyStrategy};
 pub use galois_key::GaloisKey;
 pub use public_key::PublicKey;
 pub use relinearization_key::RelinearizationKey;
-pub use secret_key::SecretKey;
+pub use secret_key::{SecretKey, WrappingKey};
 
-pub(crate) use key_switching_key::KeySwitchingKey;
+pub use key_switching_key::KeySwitchingMode;
+pub(crate) use key_switching_key::{HybridKeySwitchingCache, KeySwitchingKey};
@@ -2,8 +2,9 @@
 
 use crate::bfv::traits::TryConvertFrom;
 use crate::bfv::{
+    check_smudging_bits, noise,
     proto::bfv::{Ciphertext as CiphertextProto, PublicKey as PublicKeyProto},
-    BfvParameters, Ciphertext, Encoding, Plaintext,
+    smudging_noise, BfvParameters, Ciphertext, Encoding, Plaintext,
 };
 use crate::{Error, Result};
 use fhe_math::rq::{Poly, Representation};
@@ -36,6 +37,32 @@ impl PublicKey {
             c,
         }
     }
+
+    /// Encrypt zero at the given level, adding a flooding noise with
+    /// coefficients uniform in `[-2^flooding_bits, 2^flooding_bits]`.
+    ///
+    /// Adding this encryption to a ciphertext at the same level statistically
+    /// hides the noise of the ciphertext, and thus the computation which
+    /// produced it, when the flooding noise exceeds this noise by a statistical
+    /// security margin. Returns an error if the flooding noise exceeds the
+    /// noise budget of a ciphertext at this level.
+    pub fn try_encrypt_zero_with_flooding<R: RngCore + CryptoRng>(
+        &self,
+        level: usize,
+        flooding_bits: usize,
+        rng: &mut R,
+    ) -> Result<Ciphertext> {
+        let ctx = self.par.ctx_at_level(level)?;
+        check_smudging_bits(&self.par, ctx, flooding_bits)?;
+
+        let zero = Plaintext::zero(Encoding::poly_at_level(level), &self.par)?;
+        let mut ct = self.try_encrypt(&zero, rng)?;
+        ct.c[0] += &smudging_noise(ctx, self.par.degree(), flooding_bits, rng)?;
+        ct.noise = ct
+            .noise
+            .map(|noise| noise::log2_add(noise, flooding_bits as f64));
+        Ok(ct)
+    }
 }
 
 impl FheParametrized for PublicKey {
@@ -93,6 +120,9 @@ impl FheEncrypter<Plaintext, Ciphertext> for PublicKey {
             seed: None,
             c: vec![c0, c1],
             level: ct.level,
+            noise: ct
+                .noise
+                .map(|noise| noise::public_key_encryption(&self.par, noise)),
         })
     }
 }
@@ -141,8 +171,10 @@ impl DeserializeParametrized for PublicKey {
 mod tests {
     use super::PublicKey;
     use crate::bfv::{parameters::BfvParameters, Encoding, Plaintext, SecretKey};
+    use crate::Error as FheError;
     use fhe_traits::{DeserializeParametrized, FheDecrypter, FheEncoder, FheEncrypter, Serialize};
-    use rand::thread_rng;
+    use rand::{thread_rng, RngCore, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
     use std::error::Error;
 
     #[test]
@@ -188,6 +220,40 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn encrypt_zero_with_flooding() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BfvParameters::default_arc(6, 8);
+        let sk = SecretKey::random(&params, &mut rng);
+        let pk = PublicKey::new(&sk, &mut rng);
+        for (level, flooding_bits) in [(0, 100), (0, 300), (3, 150)] {
+            let mut ct = pk.try_encrypt_zero_with_flooding(level, flooding_bits, &mut rng)?;
+            assert_eq!(
+                sk.try_decrypt(&ct)?,
+                Plaintext::zero(Encoding::poly_at_level(level), &params)?
+            );
+            let noise = unsafe { sk.measure_noise(&ct)? };
+            assert!(noise > flooding_bits - 10 && noise <= flooding_bits + 1);
+            assert!(ct.estimated_noise_bits().unwrap() >= flooding_bits);
+
+            // The flooding noise does not prevent computations on the sum.
+            let pt = Plaintext::try_encode(
+                &params.plaintext.random_vec(params.degree(), &mut rng),
+                Encoding::poly_at_level(level),
+                &params,
+            )?;
+            ct += &pk.try_encrypt(&pt, &mut rng)?;
+            assert_eq!(sk.try_decrypt(&ct)?, pt);
+        }
+
+        // The flooding noise must fit in the noise budget of the level.
+        assert_eq!(
+            pk.try_encrypt_zero_with_flooding(5, 62, &mut rng).err(),
+            Some(FheError::SmudgingTooLarge(62))
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_serialize() -> Result<(), Box<dyn Error>> {
         let mut rng = thread_rng();
@@ -202,4 +268,29 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn deterministic_keygen_and_encryption() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BfvParameters::default_arc(2, 8);
+        let pt = Plaintext::try_encode(
+            &params.plaintext.random_vec(params.degree(), &mut rng),
+            Encoding::poly(),
+            &params,
+        )?;
+
+        let mut seed = <ChaCha8Rng as SeedableRng>::Seed::default();
+        rng.fill_bytes(&mut seed);
+        let mut outputs = vec![];
+        for _ in 0..2 {
+            let mut rng = ChaCha8Rng::from_seed(seed);
+            let sk = SecretKey::random(&params, &mut rng);
+            let pk = PublicKey::new(&sk, &mut rng);
+            let ct = pk.try_encrypt(&pt, &mut rng)?;
+            outputs.push((pk.to_bytes(), ct));
+        }
+        assert_eq!(outputs[0], outputs[1]);
+
+        Ok(())
+    }
 }
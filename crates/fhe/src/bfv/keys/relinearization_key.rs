@@ -2,8 +2,9 @@
 
 use std::sync::Arc;
 
-use super::key_switching_key::KeySwitchingKey;
+use super::key_switching_key::{KeySwitchingKey, KeySwitchingMode};
 use crate::bfv::{
+    noise,
     proto::bfv::{
         KeySwitchingKey as KeySwitchingKeyProto, RelinearizationKey as RelinearizationKeyProto,
     },
@@ -30,7 +31,7 @@ pub struct RelinearizationKey {
 impl RelinearizationKey {
     /// Generate a [`RelinearizationKey`] from a [`SecretKey`].
     pub fn new<R: RngCore + CryptoRng>(sk: &SecretKey, rng: &mut R) -> Result<Self> {
-        Self::new_leveled_internal(sk, 0, 0, rng)
+        Self::new_leveled_internal(sk, 0, 0, KeySwitchingMode::default(), rng)
     }
 
     /// Generate a [`RelinearizationKey`] from a [`SecretKey`].
@@ -40,22 +41,39 @@ impl RelinearizationKey {
         key_level: usize,
         rng: &mut R,
     ) -> Result<Self> {
-        Self::new_leveled_internal(sk, ciphertext_level, key_level, rng)
+        Self::new_leveled_internal(
+            sk,
+            ciphertext_level,
+            key_level,
+            KeySwitchingMode::default(),
+            rng,
+        )
+    }
+
+    /// Generate a [`RelinearizationKey`] from a [`SecretKey`], using the
+    /// specified key switching decomposition.
+    pub fn new_leveled_with_mode<R: RngCore + CryptoRng>(
+        sk: &SecretKey,
+        ciphertext_level: usize,
+        key_level: usize,
+        mode: KeySwitchingMode,
+        rng: &mut R,
+    ) -> Result<Self> {
+        Self::new_leveled_internal(sk, ciphertext_level, key_level, mode, rng)
     }
 
     fn new_leveled_internal<R: RngCore + CryptoRng>(
         sk: &SecretKey,
         ciphertext_level: usize,
         key_level: usize,
+        mode: KeySwitchingMode,
         rng: &mut R,
     ) -> Result<Self> {
         let ctx_relin_key = sk.par.ctx_at_level(key_level)?;
         let ctx_ciphertext = sk.par.ctx_at_level(ciphertext_level)?;
 
-        if ctx_relin_key.moduli().len() == 1 {
-            return Err(Error::DefaultError(
-                "These parameters do not support key switching".to_string(),
-            ));
+        if mode == KeySwitchingMode::Rns && ctx_relin_key.moduli().len() == 1 {
+            return Err(Error::NotEnoughModuli);
         }
 
         let mut s = Zeroizing::new(Poly::try_convert_from(
@@ -69,20 +87,29 @@ impl RelinearizationKey {
         s2.change_representation(Representation::PowerBasis);
         let switcher_up = Switcher::new(ctx_ciphertext, ctx_relin_key)?;
         let s2_switched_up = Zeroizing::new(s2.mod_switch_to(&switcher_up)?);
-        let ksk = KeySwitchingKey::new(sk, &s2_switched_up, ciphertext_level, key_level, rng)?;
+        let ksk = KeySwitchingKey::new_with_mode(
+            sk,
+            &s2_switched_up,
+            ciphertext_level,
+            key_level,
+            mode,
+            rng,
+        )?;
         Ok(Self { ksk })
     }
 
     /// Relinearize an "extended" ciphertext (c0, c1, c2) into a [`Ciphertext`]
     pub fn relinearizes(&self, ct: &mut Ciphertext) -> Result<()> {
         if ct.c.len() != 3 {
-            Err(Error::DefaultError(
-                "Only supports relinearization of ciphertext with 3 parts".to_string(),
-            ))
+            Err(Error::InvalidCiphertextSize {
+                expected: 3,
+                found: ct.c.len(),
+            })
         } else if ct.level != self.ksk.ciphertext_level {
-            Err(Error::DefaultError(
-                "Ciphertext has incorrect level".to_string(),
-            ))
+            Err(Error::LevelMismatch {
+                expected: self.ksk.ciphertext_level,
+                found: ct.level,
+            })
         } else {
             let mut c2 = ct.c[2].clone();
             c2.change_representation(Representation::PowerBasis);
@@ -102,6 +129,9 @@ impl RelinearizationKey {
             ct.c[0] += &c0;
             ct.c[1] += &c1;
             ct.c.truncate(2);
+            ct.noise = ct
+                .noise
+                .map(|noise| noise::addition(noise, self.ksk.noise()));
             Ok(())
         }
     }
@@ -123,15 +153,13 @@ impl From<&RelinearizationKey> for RelinearizationKeyProto {
 impl TryConvertFrom<&RelinearizationKeyProto> for RelinearizationKey {
     fn try_convert_from(value: &RelinearizationKeyProto, par: &Arc<BfvParameters>) -> Result<Self> {
         if par.moduli.len() == 1 {
-            Err(Error::DefaultError(
-                "Invalid parameters for a relinearization key".to_string(),
-            ))
+            Err(Error::NotEnoughModuli)
         } else if value.ksk.is_some() {
             Ok(RelinearizationKey {
                 ksk: KeySwitchingKey::try_convert_from(value.ksk.as_ref().unwrap(), par)?,
             })
         } else {
-            Err(Error::DefaultError("Invalid serialization".to_string()))
+            Err(Error::SerializationError)
         }
     }
 }
@@ -156,7 +184,7 @@ impl DeserializeParametrized for RelinearizationKey {
         if let Ok(rk) = rk {
             RelinearizationKey::try_convert_from(&rk, par)
         } else {
-            Err(Error::DefaultError("Invalid serialization".to_string()))
+            Err(Error::SerializationError)
         }
     }
 }
@@ -166,7 +194,7 @@ mod tests {
     use super::RelinearizationKey;
     use crate::bfv::{
         proto::bfv::RelinearizationKey as RelinearizationKeyProto, traits::TryConvertFrom,
-        BfvParameters, Ciphertext, Encoding, SecretKey,
+        BfvParameters, Ciphertext, Encoding, KeySwitchingMode, SecretKey,
     };
     use fhe_math::rq::{traits::TryConvertFrom as TryConvertFromPoly, Poly, Representation};
     use fhe_traits::{FheDecoder, FheDecrypter};
@@ -290,6 +318,55 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn relinearization_hybrid() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BfvParameters::default_arc(5, 8);
+        for ciphertext_level in 0..3 {
+            for key_level in 0..=ciphertext_level {
+                for dnum in 1..=params.moduli.len() - ciphertext_level {
+                    let sk = SecretKey::random(&params, &mut rng);
+                    let rk = RelinearizationKey::new_leveled_with_mode(
+                        &sk,
+                        ciphertext_level,
+                        key_level,
+                        KeySwitchingMode::Hybrid { dnum },
+                        &mut rng,
+                    )?;
+
+                    let ctx = params.ctx_at_level(ciphertext_level)?;
+                    let mut s = Poly::try_convert_from(
+                        sk.coeffs.as_ref(),
+                        ctx,
+                        false,
+                        Representation::PowerBasis,
+                    )
+                    .map_err(crate::Error::MathError)?;
+                    s.change_representation(Representation::Ntt);
+                    let s2 = &s * &s;
+                    // Let's generate manually an "extended" ciphertext (c0 = e - c1 * s - c2 *
+                    // s^2, c1, c2) encrypting 0.
+                    let c2 = Poly::random(ctx, Representation::Ntt, &mut rng);
+                    let c1 = Poly::random(ctx, Representation::Ntt, &mut rng);
+                    let mut c0 = Poly::small(ctx, Representation::PowerBasis, 16, &mut rng)?;
+                    c0.change_representation(Representation::Ntt);
+                    c0 -= &(&c1 * &s);
+                    c0 -= &(&c2 * &s2);
+                    let mut ct = Ciphertext::new(vec![c0, c1, c2], &params)?;
+
+                    rk.relinearizes(&mut ct)?;
+                    assert_eq!(ct.c.len(), 2);
+
+                    println!("Noise: {}", unsafe { sk.measure_noise(&ct)? });
+                    let pt = sk.try_decrypt(&ct)?;
+                    let w = Vec::<u64>::try_decode(&pt, Encoding::poly())?;
+                    assert_eq!(w, &[0u64; 8]);
+                }
+            }
+        }
+        Ok(())
+    }
+
     #[test]
     fn proto_conversion() -> Result<(), Box<dyn Error>> {
         let mut rng = thread_rng();
@@ -301,6 +378,16 @@ mod tests {
             let rk = RelinearizationKey::new(&sk, &mut rng)?;
             let proto = RelinearizationKeyProto::from(&rk);
             assert_eq!(rk, RelinearizationKey::try_convert_from(&proto, &params)?);
+
+            let rk = RelinearizationKey::new_leveled_with_mode(
+                &sk,
+                0,
+                0,
+                KeySwitchingMode::Hybrid { dnum: 2 },
+                &mut rng,
+            )?;
+            let proto = RelinearizationKeyProto::from(&rk);
+            assert_eq!(rk, RelinearizationKey::try_convert_from(&proto, &params)?);
         }
         Ok(())
     }
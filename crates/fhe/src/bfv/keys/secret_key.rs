@@ -1,16 +1,25 @@
 //! Secret keys for the BFV encryption scheme
 
-use crate::bfv::{BfvParameters, Ciphertext, Plaintext};
+use crate::bfv::{
+    noise,
+    proto::bfv::{
+        wrapped_secret_key::Kdf, SecretKey as SecretKeyProto,
+        WrappedSecretKey as WrappedSecretKeyProto,
+    },
+    BfvParameters, Ciphertext, Plaintext,
+};
 use crate::{Error, Result};
-use fhe_math::{
-    rq::{traits::TryConvertFrom, Poly, Representation},
-    zq::Modulus,
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::{
+    aead::{Aead, Payload},
+    ChaCha20Poly1305, Key, KeyInit, Nonce,
 };
-use fhe_traits::{FheDecrypter, FheEncrypter, FheParametrized};
+use fhe_math::rq::{traits::TryConvertFrom, Poly, Representation};
+use fhe_traits::{DeserializeParametrized, FheDecrypter, FheEncrypter, FheParametrized, Serialize};
 use fhe_util::sample_vec_cbd;
-use itertools::Itertools;
 use num_bigint::BigUint;
-use rand::{thread_rng, CryptoRng, Rng, RngCore, SeedableRng};
+use protobuf::Message;
+use rand::{CryptoRng, Rng, RngCore, SeedableRng};
 use rand_chacha::ChaCha8Rng;
 use std::sync::Arc;
 use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
@@ -30,6 +39,46 @@ impl Zeroize for SecretKey {
 
 impl ZeroizeOnDrop for SecretKey {}
 
+/// Key material used to wrap a [`SecretKey`] for storage at rest.
+#[derive(Clone, Copy)]
+pub enum WrappingKey<'a> {
+    /// A password, from which the encryption key is derived using Argon2id.
+    Password(&'a [u8]),
+
+    /// A uniformly random 256-bit key, used as is.
+    Key(&'a [u8; 32]),
+}
+
+/// Length in bytes of the salt used for password-based key derivation.
+const WRAPPING_SALT_SIZE: usize = 16;
+
+/// Length in bytes of the ChaCha20-Poly1305 nonce.
+const WRAPPING_NONCE_SIZE: usize = 12;
+
+/// Maximum Argon2id memory cost, in KiB, accepted when unwrapping a key. The
+/// costs are read from the envelope, and are bounded so that a malicious
+/// envelope cannot exhaust the memory or the time of the reader.
+const MAX_ARGON2_MEMORY_COST: u32 = 1 << 21;
+
+/// Maximum Argon2id number of passes accepted when unwrapping a key.
+const MAX_ARGON2_TIME_COST: u32 = 16;
+
+/// Maximum Argon2id degree of parallelism accepted when unwrapping a key.
+const MAX_ARGON2_PARALLELISM: u32 = 16;
+
+/// Derive a 256-bit wrapping key from a password using Argon2id.
+fn derive_wrapping_key(
+    password: &[u8],
+    salt: &[u8],
+    params: Params,
+) -> Result<Zeroizing<[u8; 32]>> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(password, salt, key.as_mut())
+        .map_err(|_| Error::InvalidKdfParameters)?;
+    Ok(key)
+}
+
 impl SecretKey {
     /// Generate a random [`SecretKey`].
     pub fn random<R: RngCore + CryptoRng>(par: &Arc<BfvParameters>, rng: &mut R) -> Self {
@@ -45,6 +94,110 @@ impl SecretKey {
         }
     }
 
+    /// Export the [`SecretKey`] encrypted under a [`WrappingKey`].
+    ///
+    /// The serialized key is encrypted using ChaCha20-Poly1305 with a fresh
+    /// nonce, and the parameters are authenticated alongside, so that the key
+    /// can only be unwrapped for the same parameters. When wrapping with a
+    /// password, the encryption key is derived using Argon2id with a fresh
+    /// salt; the salt and the Argon2id costs are stored in the envelope.
+    pub fn to_wrapped_bytes<R: RngCore + CryptoRng>(
+        &self,
+        wrapping_key: WrappingKey,
+        rng: &mut R,
+    ) -> Result<Vec<u8>> {
+        let mut wrapped = WrappedSecretKeyProto::new();
+
+        let key = match wrapping_key {
+            WrappingKey::Password(password) => {
+                let params = Params::default();
+                let mut salt = vec![0u8; WRAPPING_SALT_SIZE];
+                rng.fill_bytes(&mut salt);
+                let key = derive_wrapping_key(password, &salt, params.clone())?;
+                wrapped.kdf = Kdf::ARGON2ID.into();
+                wrapped.salt = salt;
+                wrapped.memory_cost = params.m_cost();
+                wrapped.time_cost = params.t_cost();
+                wrapped.parallelism = params.p_cost();
+                key
+            }
+            WrappingKey::Key(key) => Zeroizing::new(*key),
+        };
+
+        let mut nonce = [0u8; WRAPPING_NONCE_SIZE];
+        rng.fill_bytes(&mut nonce);
+
+        let plaintext = Zeroizing::new(self.to_bytes());
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(key.as_ref()));
+        wrapped.ciphertext = cipher
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &plaintext,
+                    aad: &self.par.to_bytes(),
+                },
+            )
+            .unwrap();
+        wrapped.nonce = nonce.to_vec();
+
+        Ok(wrapped.write_to_bytes().unwrap())
+    }
+
+    /// Import a [`SecretKey`] exported with [`SecretKey::to_wrapped_bytes`].
+    ///
+    /// Returns an error if the wrapping key is incorrect, if the envelope has
+    /// been tampered with, or if the key was wrapped for other parameters. The
+    /// Argon2id costs stored in the envelope are bounded, to at most 2 GiB of
+    /// memory, 16 passes and 16 lanes.
+    pub fn from_wrapped_bytes(
+        bytes: &[u8],
+        wrapping_key: WrappingKey,
+        par: &Arc<BfvParameters>,
+    ) -> Result<Self> {
+        let wrapped = WrappedSecretKeyProto::parse_from_bytes(bytes)
+            .map_err(|_| Error::SerializationError)?;
+        if wrapped.nonce.len() != WRAPPING_NONCE_SIZE {
+            return Err(Error::SerializationError);
+        }
+
+        let key = match (wrapped.kdf.enum_value(), wrapping_key) {
+            (Ok(Kdf::ARGON2ID), WrappingKey::Password(password)) => {
+                if wrapped.memory_cost > MAX_ARGON2_MEMORY_COST
+                    || wrapped.time_cost > MAX_ARGON2_TIME_COST
+                    || wrapped.parallelism > MAX_ARGON2_PARALLELISM
+                {
+                    return Err(Error::InvalidKdfParameters);
+                }
+                let params = Params::new(
+                    wrapped.memory_cost,
+                    wrapped.time_cost,
+                    wrapped.parallelism,
+                    None,
+                )
+                .map_err(|_| Error::InvalidKdfParameters)?;
+                derive_wrapping_key(password, &wrapped.salt, params)?
+            }
+            (Ok(Kdf::NONE), WrappingKey::Key(key)) => Zeroizing::new(*key),
+            (Ok(_), _) => return Err(Error::WrappingKeyMismatch),
+            (Err(_), _) => return Err(Error::SerializationError),
+        };
+
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(key.as_ref()));
+        let plaintext = Zeroizing::new(
+            cipher
+                .decrypt(
+                    Nonce::from_slice(&wrapped.nonce),
+                    Payload {
+                        msg: &wrapped.ciphertext,
+                        aad: &par.to_bytes(),
+                    },
+                )
+                .map_err(|_| Error::AuthenticationFailed)?,
+        );
+
+        Self::from_bytes(&plaintext, par)
+    }
+
     /// Measure the noise in a [`Ciphertext`].
     ///
     /// # Safety
@@ -101,7 +254,7 @@ impl SecretKey {
         let level = self.par.level_of_ctx(p.ctx())?;
 
         let mut seed = <ChaCha8Rng as SeedableRng>::Seed::default();
-        thread_rng().fill(&mut seed);
+        rng.fill(&mut seed);
 
         // Let's create a secret key with the ciphertext context
         let mut s = Zeroizing::new(Poly::try_convert_from(
@@ -131,6 +284,7 @@ impl SecretKey {
             seed: Some(seed),
             c: vec![b, a],
             level,
+            noise: Some(noise::encryption(&self.par)),
         })
     }
 }
@@ -139,6 +293,45 @@ impl FheParametrized for SecretKey {
     type Parameters = BfvParameters;
 }
 
+impl From<&SecretKey> for SecretKeyProto {
+    fn from(sk: &SecretKey) -> Self {
+        let mut proto = SecretKeyProto::new();
+        proto.coeffs = sk.coeffs.to_vec();
+        proto
+    }
+}
+
+impl Serialize for SecretKey {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut proto = SecretKeyProto::from(self);
+        let bytes = proto.write_to_bytes().unwrap();
+        proto.coeffs.zeroize();
+        bytes
+    }
+}
+
+/// The coefficients must be in the range of the centered binomial distribution
+/// from which the secret keys are sampled, i.e., of absolute value at most
+/// twice the variance of the parameters.
+impl DeserializeParametrized for SecretKey {
+    type Error = Error;
+
+    fn from_bytes(bytes: &[u8], par: &Arc<Self::Parameters>) -> Result<Self> {
+        let mut proto =
+            SecretKeyProto::parse_from_bytes(bytes).map_err(|_| Error::SerializationError)?;
+        let bound = 2 * par.variance as u64;
+        let sk = if proto.coeffs.len() != par.degree()
+            || proto.coeffs.iter().any(|c| c.unsigned_abs() > bound)
+        {
+            Err(Error::SerializationError)
+        } else {
+            Ok(Self::new(proto.coeffs.clone(), par))
+        };
+        proto.coeffs.zeroize();
+        sk
+    }
+}
+
 impl FheEncrypter<Plaintext, Ciphertext> for SecretKey {
     type Error = Error;
 
@@ -158,9 +351,7 @@ impl FheDecrypter<Plaintext, Ciphertext> for SecretKey {
 
     fn try_decrypt(&self, ct: &Ciphertext) -> Result<Plaintext> {
         if self.par != ct.par {
-            Err(Error::DefaultError(
-                "Incompatible BFV parameters".to_string(),
-            ))
+            Err(Error::ParametersMismatch)
         } else {
             // Let's create a secret key with the ciphertext context
             let mut s = Zeroizing::new(Poly::try_convert_from(
@@ -182,45 +373,24 @@ impl FheDecrypter<Plaintext, Ciphertext> for SecretKey {
                 *c.as_mut() += &cis;
                 *si.as_mut() *= s.as_ref();
             }
-            c.change_representation(Representation::PowerBasis);
-
-            let d = Zeroizing::new(c.scale(&self.par.scalers[ct.level])?);
 
-            // TODO: Can we handle plaintext moduli that are BigUint?
-            let v = Zeroizing::new(
-                Vec::<u64>::from(d.as_ref())
-                    .iter_mut()
-                    .map(|vi| *vi + self.par.plaintext.modulus())
-                    .collect_vec(),
-            );
-            let mut w = v[..self.par.degree()].to_vec();
-            let q = Modulus::new(self.par.moduli[0]).map_err(Error::MathError)?;
-            q.reduce_vec(&mut w);
-            self.par.plaintext.reduce_vec(&mut w);
-
-            let mut poly =
-                Poly::try_convert_from(&w, ct.c[0].ctx(), false, Representation::PowerBasis)?;
-            poly.change_representation(Representation::Ntt);
-
-            let pt = Plaintext {
-                par: self.par.clone(),
-                value: w.into_boxed_slice(),
-                encoding: None,
-                poly_ntt: poly,
-                level: ct.level,
-            };
-
-            Ok(pt)
+            Plaintext::from_decrypted_poly(c.as_ref(), &self.par)
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::SecretKey;
-    use crate::bfv::{parameters::BfvParameters, Encoding, Plaintext};
-    use fhe_traits::{FheDecrypter, FheEncoder, FheEncrypter};
+    use super::{SecretKey, WrappingKey};
+    use crate::bfv::{
+        parameters::BfvParameters, proto::bfv::WrappedSecretKey as WrappedSecretKeyProto,
+        Ciphertext, Encoding, Plaintext,
+    };
+    use fhe_traits::{DeserializeParametrized, FheDecrypter, FheEncoder, FheEncrypter, Serialize};
+    use protobuf::Message;
     use rand::thread_rng;
+    use rand::{RngCore, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
     use std::error::Error;
 
     #[test]
@@ -263,4 +433,143 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn serialize() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        for params in [
+            BfvParameters::default_arc(1, 8),
+            BfvParameters::default_arc(6, 8),
+        ] {
+            let sk = SecretKey::random(&params, &mut rng);
+            let bytes = sk.to_bytes();
+            assert_eq!(sk, SecretKey::from_bytes(&bytes, &params)?);
+
+            let other_params = BfvParameters::default_arc(1, 16);
+            assert!(SecretKey::from_bytes(&bytes, &other_params).is_err());
+
+            // The coefficients must be small.
+            let bound = 2 * params.variance as i64;
+            let mut coeffs = vec![0i64; params.degree()];
+            coeffs[0] = -bound;
+            coeffs[1] = bound;
+            let sk = SecretKey::new(coeffs.clone(), &params);
+            assert_eq!(sk, SecretKey::from_bytes(&sk.to_bytes(), &params)?);
+            coeffs[1] = bound + 1;
+            let sk = SecretKey::new(coeffs, &params);
+            assert_eq!(
+                SecretKey::from_bytes(&sk.to_bytes(), &params).err(),
+                Some(crate::Error::SerializationError)
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn wrapped_serialize() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BfvParameters::default_arc(2, 8);
+        let other_params = BfvParameters::default_arc(3, 8);
+        let sk = SecretKey::random(&params, &mut rng);
+
+        let mut key = [0u8; 32];
+        rng.fill_bytes(&mut key);
+        let mut other_key = [0u8; 32];
+        rng.fill_bytes(&mut other_key);
+
+        let bytes = sk.to_wrapped_bytes(WrappingKey::Key(&key), &mut rng)?;
+        assert_eq!(
+            sk,
+            SecretKey::from_wrapped_bytes(&bytes, WrappingKey::Key(&key), &params)?
+        );
+        assert_eq!(
+            SecretKey::from_wrapped_bytes(&bytes, WrappingKey::Key(&other_key), &params).err(),
+            Some(crate::Error::AuthenticationFailed)
+        );
+        assert_eq!(
+            SecretKey::from_wrapped_bytes(&bytes, WrappingKey::Key(&key), &other_params).err(),
+            Some(crate::Error::AuthenticationFailed)
+        );
+        assert_eq!(
+            SecretKey::from_wrapped_bytes(&bytes, WrappingKey::Password(b"password"), &params)
+                .err(),
+            Some(crate::Error::WrappingKeyMismatch)
+        );
+
+        let bytes = sk.to_wrapped_bytes(WrappingKey::Password(b"password"), &mut rng)?;
+        assert_eq!(
+            sk,
+            SecretKey::from_wrapped_bytes(&bytes, WrappingKey::Password(b"password"), &params)?
+        );
+        assert_eq!(
+            SecretKey::from_wrapped_bytes(&bytes, WrappingKey::Password(b"passw0rd"), &params)
+                .err(),
+            Some(crate::Error::AuthenticationFailed)
+        );
+        assert_eq!(
+            SecretKey::from_wrapped_bytes(&bytes, WrappingKey::Key(&key), &params).err(),
+            Some(crate::Error::WrappingKeyMismatch)
+        );
+
+        // Tampering with the envelope is detected.
+        let mut tampered = bytes.clone();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 1;
+        assert_eq!(
+            SecretKey::from_wrapped_bytes(&tampered, WrappingKey::Password(b"password"), &params)
+                .err(),
+            Some(crate::Error::AuthenticationFailed)
+        );
+
+        // The Argon2id costs of the envelope are bounded.
+        for (memory_cost, time_cost, parallelism) in
+            [(u32::MAX, 2, 1), (19456, u32::MAX, 1), (19456, 2, u32::MAX)]
+        {
+            let mut wrapped = WrappedSecretKeyProto::parse_from_bytes(&bytes)?;
+            wrapped.memory_cost = memory_cost;
+            wrapped.time_cost = time_cost;
+            wrapped.parallelism = parallelism;
+            assert_eq!(
+                SecretKey::from_wrapped_bytes(
+                    &wrapped.write_to_bytes()?,
+                    WrappingKey::Password(b"password"),
+                    &params
+                )
+                .err(),
+                Some(crate::Error::InvalidKdfParameters)
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn deterministic_encryption() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BfvParameters::default_arc(2, 8);
+        let pt = Plaintext::try_encode(
+            &params.plaintext.random_vec(params.degree(), &mut rng),
+            Encoding::poly(),
+            &params,
+        )?;
+
+        let mut seed = <ChaCha8Rng as SeedableRng>::Seed::default();
+        rng.fill_bytes(&mut seed);
+        let mut rng1 = ChaCha8Rng::from_seed(seed);
+        let mut rng2 = ChaCha8Rng::from_seed(seed);
+
+        let sk1 = SecretKey::random(&params, &mut rng1);
+        let sk2 = SecretKey::random(&params, &mut rng2);
+        assert_eq!(sk1, sk2);
+
+        let ct1: Ciphertext = sk1.try_encrypt(&pt, &mut rng1)?;
+        let ct2: Ciphertext = sk2.try_encrypt(&pt, &mut rng2)?;
+        assert_eq!(ct1, ct2);
+
+        // Subsequent encryptions consume fresh randomness.
+        let ct3: Ciphertext = sk1.try_encrypt(&pt, &mut rng1)?;
+        assert_ne!(ct1, ct3);
+
+        Ok(())
+    }
 }
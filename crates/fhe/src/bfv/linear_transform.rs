@@ -0,0 +1,350 @@
+//! Homomorphic linear transforms over the SIMD slots in the BFV encryption
+//! scheme.
+
+use crate::{
+    bfv::{dot_product_scalar, BfvParameters, Ciphertext, Encoding, EvaluationKey, Plaintext},
+    Error, Result,
+};
+use fhe_traits::FheEncoder;
+use std::collections::{hash_map::Entry, BTreeMap, BTreeSet, HashMap};
+use std::sync::Arc;
+
+/// A linear transform over the slots of a SIMD-encoded plaintext, i.e., the
+/// multiplication of the `degree` slots by a `degree x degree` matrix.
+///
+/// The slots are viewed as a `2 x (degree / 2)` matrix, and are ordered row by
+/// row, as in [`Encoding::simd`]. The transform is evaluated using the
+/// diagonal method of Halevi and Shoup with a baby-step/giant-step strategy:
+/// each generalized diagonal of the matrix is pre-encoded as a plaintext, and
+/// the evaluation uses column rotations, as well as a row rotation when the
+/// matrix mixes the two rows of slots. The zero diagonals are skipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinearTransform {
+    par: Arc<BfvParameters>,
+    level: usize,
+    baby_step: usize,
+    /// The non-zero diagonals, grouped by giant step and pre-rotated by the
+    /// giant step, along with whether the rows are swapped and the baby step.
+    diagonals: BTreeMap<usize, Vec<(bool, usize, Plaintext)>>,
+}
+
+impl LinearTransform {
+    /// Pre-encode the linear transform defined by `matrix` for ciphertexts at
+    /// level `level`, with a baby step of about the square root of the row
+    /// size.
+    pub fn new(matrix: &[Vec<u64>], level: usize, par: &Arc<BfvParameters>) -> Result<Self> {
+        let baby_step = ((par.degree() / 2) as f64).sqrt().ceil() as usize;
+        Self::new_with_baby_step(matrix, baby_step, level, par)
+    }
+
+    /// Pre-encode the linear transform defined by `matrix` for ciphertexts at
+    /// level `level`, with a baby step of `baby_step`, which must be between 1
+    /// and the row size. A baby step equal to the row size corresponds to the
+    /// diagonal method without giant steps.
+    pub fn new_with_baby_step(
+        matrix: &[Vec<u64>],
+        baby_step: usize,
+        level: usize,
+        par: &Arc<BfvParameters>,
+    ) -> Result<Self> {
+        let degree = par.degree();
+        let row_size = degree / 2;
+        if let Some(found) = std::iter::once(matrix.len())
+            .chain(matrix.iter().map(|row| row.len()))
+            .find(|len| *len != degree)
+        {
+            return Err(Error::InvalidMatrixSize {
+                expected: degree,
+                found,
+            });
+        }
+        if baby_step == 0 || baby_step > row_size {
+            return Err(Error::InvalidBabyStep(baby_step));
+        }
+
+        let mut diagonals: BTreeMap<usize, Vec<(bool, usize, Plaintext)>> = BTreeMap::new();
+        for swap_rows in [false, true] {
+            for j in 0..row_size {
+                let giant_step = j - j % baby_step;
+                // The generalized diagonal contains the coefficients multiplying the
+                // slots rotated by `j` (and with rows swapped if `swap_rows`); it is
+                // rotated to the right by the giant step.
+                let mut diagonal = vec![0u64; degree];
+                for r in 0..2 {
+                    let r_in = r ^ (swap_rows as usize);
+                    for c in 0..row_size {
+                        let c_out = (c + row_size - giant_step) % row_size;
+                        let c_in = (c_out + j) % row_size;
+                        diagonal[r * row_size + c] =
+                            matrix[r * row_size + c_out][r_in * row_size + c_in];
+                    }
+                }
+                par.plaintext.reduce_vec(&mut diagonal);
+                if diagonal.iter().any(|d| *d != 0) {
+                    let pt = Plaintext::try_encode(&diagonal, Encoding::simd_at_level(level), par)?;
+                    diagonals
+                        .entry(giant_step)
+                        .or_default()
+                        .push((swap_rows, j - giant_step, pt));
+                }
+            }
+        }
+
+        Ok(Self {
+            par: par.clone(),
+            level,
+            baby_step,
+            diagonals,
+        })
+    }
+
+    /// Returns the baby step of the linear transform.
+    pub fn baby_step(&self) -> usize {
+        self.baby_step
+    }
+
+    /// Returns the level of the ciphertexts this linear transform applies to.
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    /// Returns the (sorted) indices of the column rotations used to evaluate
+    /// the linear transform.
+    pub fn rotation_indices(&self) -> Vec<usize> {
+        let baby_steps = self.diagonals.values().flatten().map(|(_, t, _)| *t);
+        let giant_steps = self.diagonals.keys().copied();
+        baby_steps
+            .chain(giant_steps)
+            .filter(|i| *i != 0)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Reports whether the evaluation of the linear transform uses a row
+    /// rotation, i.e., whether the matrix mixes the two rows of slots.
+    pub fn requires_row_rotation(&self) -> bool {
+        self.diagonals.values().flatten().any(|(swap, _, _)| *swap)
+    }
+
+    /// Homomorphically apply the linear transform to the slots of `ct`. The
+    /// evaluation key must support the rotations reported by
+    /// [`Self::rotation_indices`] and [`Self::requires_row_rotation`], which
+    /// can be enabled using
+    /// [`EvaluationKeyBuilder::enable_linear_transform`](crate::bfv::EvaluationKeyBuilder::enable_linear_transform).
+    pub fn evaluate(&self, ct: &Ciphertext, ek: &EvaluationKey) -> Result<Ciphertext> {
+        if ct.par != self.par {
+            return Err(Error::ParametersMismatch);
+        }
+        if ct.level != self.level {
+            return Err(Error::LevelMismatch {
+                expected: self.level,
+                found: ct.level,
+            });
+        }
+        if self.diagonals.is_empty() {
+            // The matrix is zero: return an encryption of zero at the level of
+            // `ct`, which supports the same operations as `ct`.
+            return ct.try_sub(ct);
+        }
+
+        // Baby steps.
+        let ct_swapped = if self.requires_row_rotation() {
+            Some(ek.rotates_rows(ct)?)
+        } else {
+            None
+        };
+        let mut baby_steps = HashMap::new();
+        for (swap_rows, t, _) in self.diagonals.values().flatten() {
+            if let Entry::Vacant(e) = baby_steps.entry((*swap_rows, *t)) {
+                let u = if *swap_rows {
+                    ct_swapped.as_ref().unwrap()
+                } else {
+                    ct
+                };
+                if *t == 0 {
+                    e.insert(u.clone());
+                } else {
+                    e.insert(ek.rotates_columns_by(u, *t)?);
+                }
+            }
+        }
+
+        // Giant steps.
+        let mut out: Option<Ciphertext> = None;
+        for (giant_step, diagonals) in &self.diagonals {
+            let mut inner = dot_product_scalar(
+                diagonals
+                    .iter()
+                    .map(|(swap_rows, t, _)| &baby_steps[&(*swap_rows, *t)]),
+                diagonals.iter().map(|(_, _, pt)| pt),
+            )?;
+            if *giant_step != 0 {
+                inner = ek.rotates_columns_by(&inner, *giant_step)?;
+            }
+            match out.as_mut() {
+                Some(out) => *out += &inner,
+                None => out = Some(inner),
+            }
+        }
+        Ok(out.unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LinearTransform;
+    use crate::bfv::{
+        BfvParameters, Ciphertext, Encoding, EvaluationKeyBuilder, Plaintext, SecretKey,
+    };
+    use fhe_traits::{
+        DeserializeParametrized, FheDecoder, FheDecrypter, FheEncoder, FheEncrypter, Serialize,
+    };
+    use itertools::Itertools;
+    use rand::thread_rng;
+    use std::error::Error;
+
+    fn matrix_vector_product(m: &[Vec<u64>], v: &[u64], t: u64) -> Vec<u64> {
+        m.iter()
+            .map(|row| {
+                (row.iter()
+                    .zip(v)
+                    .map(|(a, b)| (*a as u128) * (*b as u128))
+                    .sum::<u128>()
+                    % (t as u128)) as u64
+            })
+            .collect_vec()
+    }
+
+    #[test]
+    fn evaluate() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BfvParameters::default_arc(3, 16);
+        let degree = params.degree();
+        let row_size = degree / 2;
+        let sk = SecretKey::random(&params, &mut rng);
+
+        // A dense matrix, and a block-diagonal matrix with a different transform
+        // for each row of slots.
+        let dense = (0..degree)
+            .map(|_| params.plaintext.random_vec(degree, &mut rng))
+            .collect_vec();
+        let mut block_diagonal = dense.clone();
+        for (i, row) in block_diagonal.iter_mut().enumerate() {
+            for (j, mij) in row.iter_mut().enumerate() {
+                if i / row_size != j / row_size {
+                    *mij = 0
+                }
+            }
+        }
+
+        for (matrix, requires_row_rotation) in [(&dense, true), (&block_diagonal, false)] {
+            for baby_step in [1, 3, row_size] {
+                let lt = LinearTransform::new_with_baby_step(matrix, baby_step, 0, &params)?;
+                assert_eq!(lt.baby_step(), baby_step);
+                assert_eq!(lt.requires_row_rotation(), requires_row_rotation);
+                assert!(lt
+                    .rotation_indices()
+                    .iter()
+                    .all(|i| *i > 0 && *i < row_size));
+
+                let ek = EvaluationKeyBuilder::new(&sk)?
+                    .enable_linear_transform(&lt)?
+                    .build(&mut rng)?;
+                assert_eq!(ek.supports_row_rotation(), requires_row_rotation);
+
+                let v = params.plaintext.random_vec(degree, &mut rng);
+                let pt = Plaintext::try_encode(&v, Encoding::simd(), &params)?;
+                let ct = sk.try_encrypt(&pt, &mut rng)?;
+                let ct2 = lt.evaluate(&ct, &ek)?;
+                let pt2 = sk.try_decrypt(&ct2)?;
+                assert_eq!(
+                    Vec::<u64>::try_decode(&pt2, Encoding::simd())?,
+                    matrix_vector_product(matrix, &v, params.plaintext())
+                );
+            }
+        }
+
+        // The identity needs no rotation.
+        let identity = (0..degree)
+            .map(|i| (0..degree).map(|j| (i == j) as u64).collect_vec())
+            .collect_vec();
+        let lt = LinearTransform::new(&identity, 0, &params)?;
+        assert!(lt.rotation_indices().is_empty());
+        assert!(!lt.requires_row_rotation());
+
+        Ok(())
+    }
+
+    #[test]
+    fn zero_matrix() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BfvParameters::default_arc(3, 16);
+        let degree = params.degree();
+        let matrix = vec![vec![0u64; degree]; degree];
+
+        let sk = SecretKey::random(&params, &mut rng);
+        let ek = EvaluationKeyBuilder::new_leveled(&sk, 1, 1)?.build(&mut rng)?;
+        let lt = LinearTransform::new(&matrix, 1, &params)?;
+        assert!(lt.rotation_indices().is_empty());
+
+        let v = params.plaintext.random_vec(degree, &mut rng);
+        let pt = Plaintext::try_encode(&v, Encoding::simd_at_level(1), &params)?;
+        let ct = sk.try_encrypt(&pt, &mut rng)?;
+        let zero = lt.evaluate(&ct, &ek)?;
+        assert_eq!(zero.level, ct.level);
+        assert_eq!(zero.c.len(), ct.c.len());
+
+        // The result can be serialized and added to other ciphertexts.
+        let zero = Ciphertext::from_bytes(&zero.to_bytes(), &params)?;
+        let sum = zero.try_add(&ct)?;
+        assert_eq!(
+            Vec::<u64>::try_decode(&sk.try_decrypt(&sum)?, Encoding::simd_at_level(1))?,
+            v
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn errors() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BfvParameters::default_arc(3, 16);
+        let degree = params.degree();
+        let matrix = vec![vec![1u64; degree]; degree];
+
+        assert_eq!(
+            LinearTransform::new(&matrix[1..], 0, &params).err(),
+            Some(crate::Error::InvalidMatrixSize {
+                expected: degree,
+                found: degree - 1
+            })
+        );
+        assert_eq!(
+            LinearTransform::new_with_baby_step(&matrix, 0, 0, &params).err(),
+            Some(crate::Error::InvalidBabyStep(0))
+        );
+        assert_eq!(
+            LinearTransform::new_with_baby_step(&matrix, degree, 0, &params).err(),
+            Some(crate::Error::InvalidBabyStep(degree))
+        );
+        assert!(LinearTransform::new(&matrix, 3, &params).is_err());
+
+        // The ciphertext must be at the level of the transform, and the evaluation
+        // key must support the rotations.
+        let sk = SecretKey::random(&params, &mut rng);
+        let lt = LinearTransform::new(&matrix, 1, &params)?;
+        let ek = EvaluationKeyBuilder::new_leveled(&sk, 1, 1)?
+            .enable_linear_transform(&lt)?
+            .build(&mut rng)?;
+        let pt = Plaintext::try_encode(&[1u64, 2, 3], Encoding::simd(), &params)?;
+        let ct = sk.try_encrypt(&pt, &mut rng)?;
+        assert!(lt.evaluate(&ct, &ek).is_err());
+
+        let ek = EvaluationKeyBuilder::new(&sk)?.build(&mut rng)?;
+        let lt = LinearTransform::new(&matrix, 0, &params)?;
+        assert!(lt.evaluate(&ct, &ek).is_err());
+
+        Ok(())
+    }
+}
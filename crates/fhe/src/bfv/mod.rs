@@ -3,21 +3,37 @@
 //! The Brakerski-Fan-Vercauteren homomorphic encryption scheme
 
 mod ciphertext;
+mod circuit;
 mod encoding;
 mod keys;
+mod linear_transform;
+mod noise;
 mod ops;
 mod parameters;
 mod plaintext;
 mod plaintext_vec;
-mod proto;
+mod planner;
+#[allow(renamed_and_removed_lints)]
+pub(crate) mod proto;
 mod rgsw_ciphertext;
+mod smudging;
 
 pub mod traits;
 pub use ciphertext::Ciphertext;
+pub use circuit::{Circuit, CircuitKeys, NodeId};
 pub use encoding::Encoding;
-pub use keys::{EvaluationKey, EvaluationKeyBuilder, PublicKey, RelinearizationKey, SecretKey};
-pub use ops::{dot_product_scalar, Multiplicator};
+pub(crate) use encoding::EncodingEnum;
+pub(crate) use keys::KeySwitchingKey;
+pub use keys::{
+    EvaluationKey, EvaluationKeyBuilder, GaloisKey, KeySwitchingMode, PublicKey,
+    RelinearizationKey, RotationKeyStrategy, SecretKey, WrappingKey,
+};
+pub use linear_transform::LinearTransform;
+pub use ops::{dot_product_scalar, Comparator, Multiplicator, PolynomialEvaluator};
+pub(crate) use parameters::matrix_reps_index_map;
 pub use parameters::{BfvParameters, BfvParametersBuilder};
 pub use plaintext::Plaintext;
-pub use plaintext_vec::PlaintextVec;
+pub use plaintext_vec::{PlaintextVec, PlaintextVecReader};
+pub use planner::{ParametersPlan, ParametersPlanner, SecurityLevel};
 pub use rgsw_ciphertext::RGSWCiphertext;
+pub(crate) use smudging::{check_smudging_bits, smudging_noise};
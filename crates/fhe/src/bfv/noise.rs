@@ -0,0 +1,127 @@
+//! Heuristic estimation of the noise in BFV ciphertexts, without the secret
+//! key.
+//!
+//! The noise of a ciphertext `c` encrypting a plaintext `pt` under the secret
+//! key `s` is the polynomial `v = c[0] + c[1] * s + ... - pt.to_poly()`, which
+//! is measured by
+//! [`SecretKey::measure_noise`](crate::bfv::SecretKey::measure_noise). The
+//! functions below track the base-2 logarithm of an upper bound on the
+//! infinity norm of `v` through the homomorphic operations. The bounds are
+//! worst-case bounds, so they are usually larger than the measured noise,
+//! and more so after multiplications.
+
+use crate::bfv::BfvParameters;
+
+/// Returns `log2(2^a + 2^b)`.
+pub(crate) fn log2_add(a: f64, b: f64) -> f64 {
+    let (max, min) = if a >= b { (a, b) } else { (b, a) };
+    if min == f64::NEG_INFINITY {
+        max
+    } else {
+        max + (1.0 + (min - max).exp2()).log2()
+    }
+}
+
+/// Returns the base-2 logarithm of the product of the moduli.
+pub(crate) fn log2_product(moduli: &[u64]) -> f64 {
+    moduli.iter().map(|qi| (*qi as f64).log2()).sum()
+}
+
+/// Returns the bound on the coefficients of the errors and of the secret key,
+/// which are sampled from a centered binomial distribution.
+fn error_bound(par: &BfvParameters) -> f64 {
+    (2 * par.variance) as f64
+}
+
+/// Returns the bound on the coefficients of the product of the secret key
+/// with a polynomial with coefficients of absolute value at most one.
+fn expansion(par: &BfvParameters) -> f64 {
+    par.degree() as f64 * error_bound(par)
+}
+
+/// Noise of a fresh encryption with the secret key.
+pub(crate) fn encryption(par: &BfvParameters) -> f64 {
+    error_bound(par).log2()
+}
+
+/// Noise of a fresh encryption with a public key of noise `pk`.
+pub(crate) fn public_key_encryption(par: &BfvParameters, pk: f64) -> f64 {
+    let b = error_bound(par);
+    log2_add(expansion(par).log2() + pk, (b + expansion(par) * b).log2())
+}
+
+/// Noise of the sum of two ciphertexts of noises `a` and `b`, including the
+/// rounding of the sum of the scaled plaintexts.
+pub(crate) fn addition(a: f64, b: f64) -> f64 {
+    log2_add(log2_add(a, b), 0.0)
+}
+
+/// Noise of a ciphertext of noise `a` after an operation which changes the
+/// scaled plaintext by at most one, such as the addition of a plaintext, a
+/// negation, or a signed permutation of the coefficients.
+pub(crate) fn rounding(a: f64) -> f64 {
+    log2_add(a, 0.0)
+}
+
+/// Noise of the product of a ciphertext of noise `a` with a plaintext.
+pub(crate) fn plaintext_multiplication(par: &BfvParameters, a: f64) -> f64 {
+    (par.degree() as f64).log2() + (par.plaintext() as f64).log2() + log2_add(a, 1.0)
+}
+
+/// Noise of the product of a ciphertext of noise `a` with the constant `c`.
+pub(crate) fn scalar_multiplication(c: u64, a: f64) -> f64 {
+    (c as f64).log2() + log2_add(a, 0.0)
+}
+
+/// Noise of the tensor product of two ciphertexts of two parts and of noises
+/// `a` and `b`, after scaling by `t / Q`.
+pub(crate) fn tensor(par: &BfvParameters, a: f64, b: f64) -> f64 {
+    let t = (par.plaintext() as f64).log2();
+    let n = (par.degree() as f64).log2();
+    log2_add(
+        t + n + (expansion(par) + 2.0).log2() + log2_add(log2_add(a, b), 1.0),
+        2.0 * (expansion(par) + 1.0).log2(),
+    )
+}
+
+/// Noise of a ciphertext of `size` parts and of noise `a` after dividing it by
+/// the modulus `q` and rounding.
+pub(crate) fn mod_switch(par: &BfvParameters, a: f64, q: u64, size: usize) -> f64 {
+    let rounding = (0..size.max(1))
+        .map(|i| expansion(par).powi(i as i32))
+        .sum::<f64>();
+    log2_add(a - (q as f64).log2(), (rounding / 2.0 + 1.0).log2())
+}
+
+/// Noise of a key switching, where the digits of the decomposition are bounded
+/// by the (base-2 logarithms) `digits`, and the result is divided by
+/// `2^log2_divisor` and rounded.
+pub(crate) fn key_switching(
+    par: &BfvParameters,
+    digits: impl Iterator<Item = f64>,
+    log2_divisor: f64,
+) -> f64 {
+    let sum = digits.fold(f64::NEG_INFINITY, log2_add);
+    let noise = expansion(par).log2() + sum - log2_divisor;
+    if log2_divisor > 0.0 {
+        log2_add(noise, (expansion(par) + 1.0).log2())
+    } else {
+        noise
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{addition, log2_add, rounding};
+
+    #[test]
+    fn log2_arithmetic() {
+        assert_eq!(log2_add(3.0, 3.0), 4.0);
+        assert_eq!(log2_add(f64::NEG_INFINITY, 5.0), 5.0);
+        assert_eq!(log2_add(5.0, f64::NEG_INFINITY), 5.0);
+        assert!((log2_add(2000.0, 1.0) - 2000.0).abs() < 1e-9);
+        assert_eq!(rounding(0.0), 1.0);
+        assert_eq!(addition(f64::NEG_INFINITY, f64::NEG_INFINITY), 0.0);
+        assert!((addition(1.0, 1.0) - 5f64.log2()).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,313 @@
+use fhe_math::zq::Modulus;
+use fhe_traits::FheEncoder;
+use fhe_util::is_prime;
+
+use crate::{
+    bfv::{
+        keys::RelinearizationKey, planner::estimated_noise_size, Ciphertext, Encoding, Plaintext,
+    },
+    Error, ParametersError, Result,
+};
+
+use super::PolynomialEvaluator;
+
+/// Largest plaintext modulus for which the comparison polynomial is
+/// interpolated.
+const MAX_COMPARISON_PLAINTEXT_MODULUS: u64 = 1 << 12;
+
+/// Slot-wise equality and comparison of encrypted integers modulo a prime
+/// plaintext modulus `t`; the outputs encrypt `1` for true and `0` for false.
+///
+/// The equality to zero is computed using Fermat's little theorem, i.e., as
+/// `1 - x^(t - 1)`, and the comparison evaluates a polynomial of degree
+/// `t - 1` interpolated over Z_t, which is only supported for small `t`. Before
+/// any homomorphic computation, the noise budget required by the
+/// multiplicative depth is estimated and an error is returned if the
+/// remaining noise budget of the ciphertext is too small.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comparator {
+    evaluator: PolynomialEvaluator,
+    plaintext: Modulus,
+    lt_coefficients: Option<Vec<u64>>,
+}
+
+impl Comparator {
+    /// Create a comparator from relinearization keys, one for each ciphertext
+    /// level at which multiplications may occur, as in
+    /// [`PolynomialEvaluator::new`]. The plaintext modulus must be prime.
+    pub fn new(rks: &[RelinearizationKey]) -> Result<Self> {
+        let evaluator = PolynomialEvaluator::new(rks)?;
+        let plaintext = rks[0].ksk.par.plaintext.clone();
+        if plaintext.modulus() == 2 || !is_prime(plaintext.modulus()) {
+            return Err(Error::ParametersError(ParametersError::InvalidPlaintext(
+                plaintext.modulus(),
+            )));
+        }
+
+        let lt_coefficients = if plaintext.modulus() <= MAX_COMPARISON_PLAINTEXT_MODULUS {
+            Some(Self::lt_coefficients(&plaintext))
+        } else {
+            None
+        };
+
+        Ok(Self {
+            evaluator,
+            plaintext,
+            lt_coefficients,
+        })
+    }
+
+    /// Homomorphically test whether the slots of `ct` are equal to zero.
+    pub fn is_zero(&self, ct: &Ciphertext) -> Result<Ciphertext> {
+        let t = self.plaintext.modulus() as usize;
+        self.check_noise_budget(ct, (t - 1).next_power_of_two().ilog2() as usize)?;
+
+        let x = self.evaluator.power(ct, t - 1)?;
+        let one = Plaintext::try_encode(&[1u64], Encoding::poly_at_level(x.level), &x.par)?;
+        Ok(&one - &x)
+    }
+
+    /// Homomorphically test whether the slots of `lhs` and `rhs` are equal.
+    pub fn eq(&self, lhs: &Ciphertext, rhs: &Ciphertext) -> Result<Ciphertext> {
+        self.is_zero(&Self::difference(lhs, rhs)?)
+    }
+
+    /// Homomorphically test whether the slots of `lhs` are smaller than the
+    /// slots of `rhs`. The slots must be in the range `[0, (t - 1) / 2]`, and
+    /// the plaintext modulus must be at most `2^12`.
+    pub fn lt(&self, lhs: &Ciphertext, rhs: &Ciphertext) -> Result<Ciphertext> {
+        let coefficients = self.lt_coefficients.as_ref().ok_or_else(|| {
+            Error::ParametersError(ParametersError::InvalidPlaintext(self.plaintext.modulus()))
+        })?;
+        let d = Self::difference(lhs, rhs)?;
+        self.check_noise_budget(&d, PolynomialEvaluator::depth(coefficients.len() - 1))?;
+
+        self.evaluator.evaluate(&d, coefficients)
+    }
+
+    /// Returns the coefficients of the polynomial over Z_t which is `1` on the
+    /// "negative" values `(t + 1) / 2, ..., t - 1`, and `0` elsewhere.
+    ///
+    /// The Lagrange interpolation of an indicator of a set S is the sum over `a`
+    /// in S of `1 - (x - a)^(t - 1) = 1 - sum_j a^(t - 1 - j) x^j`.
+    fn lt_coefficients(plaintext: &Modulus) -> Vec<u64> {
+        let t = plaintext.modulus();
+        let mut coefficients = vec![0u64; t as usize];
+        for a in t.div_ceil(2)..t {
+            let mut a_power = 1;
+            for j in (1..t as usize).rev() {
+                coefficients[j] = plaintext.sub(coefficients[j], a_power);
+                a_power = plaintext.mul(a_power, a);
+            }
+        }
+        coefficients
+    }
+
+    fn difference(lhs: &Ciphertext, rhs: &Ciphertext) -> Result<Ciphertext> {
+        lhs.try_sub(rhs)
+    }
+
+    /// Estimate the noise budget, in bits, consumed by a computation of
+    /// multiplicative depth `depth` on `ct`, and compare it to the remaining
+    /// noise budget of `ct` if its noise is tracked, or to the size of the
+    /// ciphertext modulus at the level of `ct` assuming a fresh ciphertext
+    /// otherwise.
+    fn check_noise_budget(&self, ct: &Ciphertext, depth: usize) -> Result<()> {
+        let par = &ct.par;
+        let t_bits = self.plaintext.modulus().ilog2() as usize + 1;
+        let (required, available) = if let Some(remaining) = ct.remaining_budget_bits() {
+            let growth = estimated_noise_size(t_bits, par.degree(), depth)
+                - estimated_noise_size(t_bits, par.degree(), 0);
+            (growth, remaining)
+        } else {
+            let required = estimated_noise_size(t_bits, par.degree(), depth) + t_bits;
+            let available = par.moduli_sizes()[..par.moduli().len() - ct.level]
+                .iter()
+                .sum::<usize>();
+            (required, available)
+        };
+        if required >= available {
+            Err(Error::InsufficientNoiseBudget(required, available))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        bfv::{
+            BfvParameters, BfvParametersBuilder, Ciphertext, Encoding, Plaintext,
+            RelinearizationKey, SecretKey,
+        },
+        Error,
+    };
+    use fhe_traits::{FheDecoder, FheDecrypter, FheEncoder, FheEncrypter};
+    use itertools::{izip, Itertools};
+    use rand::{thread_rng, Rng};
+    use std::error::Error as StdError;
+
+    use super::Comparator;
+
+    #[test]
+    fn lt_coefficients() {
+        for par in [
+            BfvParameters::default_arc(1, 16),
+            BfvParametersBuilder::new()
+                .set_degree(16)
+                .set_plaintext_modulus(97)
+                .set_moduli_sizes(&[62])
+                .allow_insecure()
+                .build_arc()
+                .unwrap(),
+        ] {
+            let t = &par.plaintext;
+            let coefficients = Comparator::lt_coefficients(t);
+            for x in 0..t.modulus() {
+                let y = coefficients
+                    .iter()
+                    .rev()
+                    .fold(0, |acc, c| t.add(t.mul(acc, x), *c));
+                assert_eq!(y, (x > (t.modulus() - 1) / 2) as u64);
+            }
+        }
+    }
+
+    #[test]
+    fn compare() -> Result<(), Box<dyn StdError>> {
+        let mut rng = thread_rng();
+        let par = BfvParametersBuilder::new()
+            .set_degree(16)
+            .set_plaintext_modulus(97)
+            .set_moduli_sizes(&[62; 5])
+            .allow_insecure()
+            .build_arc()?;
+        let t = par.plaintext();
+        let sk = SecretKey::random(&par, &mut rng);
+        let rks = (0..4)
+            .map(|level| RelinearizationKey::new_leveled(&sk, level, level, &mut rng))
+            .collect::<Result<Vec<_>, _>>()?;
+        let comparator = Comparator::new(&rks)?;
+
+        // Half of the slots are equal.
+        let a = (0..par.degree())
+            .map(|_| rng.gen_range(0..=(t - 1) / 2))
+            .collect_vec();
+        let b = a
+            .iter()
+            .enumerate()
+            .map(|(i, ai)| {
+                if i % 2 == 0 {
+                    *ai
+                } else {
+                    rng.gen_range(0..=(t - 1) / 2)
+                }
+            })
+            .collect_vec();
+        let ct_a = sk.try_encrypt(
+            &Plaintext::try_encode(&a, Encoding::simd(), &par)?,
+            &mut rng,
+        )?;
+        let ct_b = sk.try_encrypt(
+            &Plaintext::try_encode(&b, Encoding::simd(), &par)?,
+            &mut rng,
+        )?;
+
+        let decrypt = |ct: &Ciphertext| -> Result<Vec<u64>, Box<dyn StdError>> {
+            let pt = sk.try_decrypt(ct)?;
+            Ok(Vec::<u64>::try_decode(&pt, Encoding::simd())?)
+        };
+        assert_eq!(
+            decrypt(&comparator.is_zero(&ct_a)?)?,
+            a.iter().map(|ai| (*ai == 0) as u64).collect_vec()
+        );
+        assert_eq!(
+            decrypt(&comparator.eq(&ct_a, &ct_b)?)?,
+            izip!(&a, &b)
+                .map(|(ai, bi)| (ai == bi) as u64)
+                .collect_vec()
+        );
+        assert_eq!(
+            decrypt(&comparator.lt(&ct_a, &ct_b)?)?,
+            izip!(&a, &b).map(|(ai, bi)| (ai < bi) as u64).collect_vec()
+        );
+        assert_eq!(
+            decrypt(&comparator.lt(&ct_b, &ct_a)?)?,
+            izip!(&a, &b).map(|(ai, bi)| (bi < ai) as u64).collect_vec()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn noise_budget_of_ciphertext() -> Result<(), Box<dyn StdError>> {
+        let mut rng = thread_rng();
+        let par = BfvParametersBuilder::new()
+            .set_degree(16)
+            .set_plaintext_modulus(97)
+            .set_moduli_sizes(&[62; 5])
+            .allow_insecure()
+            .build_arc()?;
+        let sk = SecretKey::random(&par, &mut rng);
+        let rks = (0..4)
+            .map(|level| RelinearizationKey::new_leveled(&sk, level, level, &mut rng))
+            .collect::<Result<Vec<_>, _>>()?;
+        let comparator = Comparator::new(&rks)?;
+
+        let pt = Plaintext::try_encode(&[1u64, 2, 3], Encoding::simd(), &par)?;
+        let mut ct: Ciphertext = sk.try_encrypt(&pt, &mut rng)?;
+        assert!(comparator.is_zero(&ct).is_ok());
+
+        // A ciphertext whose noise already consumed most of the budget is
+        // rejected, even though a fresh ciphertext at its level is not.
+        ct.noise = ct.noise.map(|noise| noise + 250.0);
+        assert!(matches!(
+            comparator.is_zero(&ct),
+            Err(Error::InsufficientNoiseBudget(_, _))
+        ));
+        ct.noise = None;
+        assert!(comparator.is_zero(&ct).is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn insufficient_noise_budget() -> Result<(), Box<dyn StdError>> {
+        let mut rng = thread_rng();
+        let par = BfvParameters::default_arc(2, 16);
+        let sk = SecretKey::random(&par, &mut rng);
+        let rk = RelinearizationKey::new(&sk, &mut rng)?;
+        let comparator = Comparator::new(&[rk])?;
+
+        let pt = Plaintext::try_encode(&[1u64, 2, 3], Encoding::simd(), &par)?;
+        let mut ct: Ciphertext = sk.try_encrypt(&pt, &mut rng)?;
+        let remaining = ct.remaining_budget_bits().unwrap();
+        assert!(matches!(
+            comparator.is_zero(&ct),
+            Err(Error::InsufficientNoiseBudget(_, available)) if available == remaining
+        ));
+        ct.noise = None;
+        assert!(matches!(
+            comparator.is_zero(&ct),
+            Err(Error::InsufficientNoiseBudget(_, 124))
+        ));
+        assert!(matches!(
+            comparator.lt(&ct, &ct),
+            Err(Error::InsufficientNoiseBudget(_, 124))
+        ));
+
+        // The plaintext modulus must be prime.
+        let par = BfvParametersBuilder::new()
+            .set_degree(16)
+            .set_plaintext_modulus(1 << 10)
+            .set_moduli_sizes(&[62; 2])
+            .allow_insecure()
+            .build_arc()?;
+        let sk = SecretKey::random(&par, &mut rng);
+        let rk = RelinearizationKey::new(&sk, &mut rng)?;
+        assert!(Comparator::new(&[rk]).is_err());
+
+        Ok(())
+    }
+}
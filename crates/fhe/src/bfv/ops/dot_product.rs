@@ -3,9 +3,11 @@ use std::cmp::min;
 use fhe_math::rq::{dot_product as poly_dot_product, traits::TryConvertFrom, Poly, Representation};
 use itertools::{izip, Itertools};
 use ndarray::{Array, Array2};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
 use crate::{
-    bfv::{Ciphertext, Plaintext},
+    bfv::{noise, Ciphertext, Plaintext},
     Error, Result,
 };
 
@@ -58,24 +60,33 @@ where
 {
     let count = min(ct.clone().count(), pt.clone().count());
     if count == 0 {
-        return Err(Error::DefaultError(
-            "At least one iterator is empty".to_string(),
-        ));
+        return Err(Error::TooFewValues(0, 1));
     }
     let ct_first = ct.clone().next().unwrap();
     let ctx = ct_first.c[0].ctx();
 
-    if izip!(ct.clone(), pt.clone()).any(|(cti, pti)| {
-        cti.par != ct_first.par || pti.par != ct_first.par || cti.c.len() != ct_first.c.len()
-    }) {
-        return Err(Error::DefaultError("Mismatched parameters".to_string()));
+    if izip!(ct.clone(), pt.clone())
+        .any(|(cti, pti)| cti.par != ct_first.par || pti.par != ct_first.par)
+    {
+        return Err(Error::ParametersMismatch);
     }
-    if ct.clone().any(|cti| cti.c.len() != ct_first.c.len()) {
-        return Err(Error::DefaultError(
-            "Mismatched number of parts in the ciphertexts".to_string(),
-        ));
+    if let Some(cti) = ct.clone().find(|cti| cti.c.len() != ct_first.c.len()) {
+        return Err(Error::InvalidCiphertextSize {
+            expected: ct_first.c.len(),
+            found: cti.c.len(),
+        });
     }
 
+    // The noise of the sum of the products, if the noise of all the ciphertexts
+    // is known.
+    let noise = ct
+        .clone()
+        .take(count)
+        .try_fold(f64::NEG_INFINITY, |acc, cti| {
+            cti.noise
+                .map(|a| noise::log2_add(acc, noise::plaintext_multiplication(&cti.par, a)))
+        });
+
     let max_acc = ctx
         .moduli()
         .iter()
@@ -101,9 +112,11 @@ where
             seed: None,
             c,
             level: ct_first.level,
+            noise,
         })
     } else {
         let mut acc = Array::zeros((ct_first.c.len(), ctx.moduli().len(), ct_first.par.degree()));
+        #[cfg(not(feature = "rayon"))]
         for (ciphertext, plaintext) in izip!(ct, pt) {
             let pt_coefficients = plaintext.poly_ntt.coefficients();
             for (mut acci, ci) in izip!(acc.outer_iter_mut(), ciphertext.c.iter()) {
@@ -123,6 +136,37 @@ where
                 }
             }
         }
+        // Each row of the accumulator, for a part of the ciphertexts and a
+        // modulus, is computed independently.
+        #[cfg(feature = "rayon")]
+        {
+            let ct = ct.take(count).collect_vec();
+            let pt = pt.take(count).collect_vec();
+            acc.outer_iter_mut()
+                .into_par_iter()
+                .enumerate()
+                .for_each(|(i, mut acci)| {
+                    acci.outer_iter_mut()
+                        .into_par_iter()
+                        .enumerate()
+                        .for_each(|(j, mut accij)| {
+                            for (ciphertext, plaintext) in izip!(&ct, &pt) {
+                                unsafe {
+                                    fma(
+                                        accij.as_slice_mut().unwrap(),
+                                        ciphertext.c[i].coefficients().row(j).as_slice().unwrap(),
+                                        plaintext
+                                            .poly_ntt
+                                            .coefficients()
+                                            .row(j)
+                                            .as_slice()
+                                            .unwrap(),
+                                    )
+                                }
+                            }
+                        })
+                });
+        }
 
         // Reduce
         let mut c = Vec::with_capacity(ct_first.c.len());
@@ -150,6 +194,7 @@ where
             seed: None,
             c,
             level: ct_first.level,
+            noise,
         })
     }
 }
@@ -1,39 +1,270 @@
 //! Operations over ciphertexts
 
+mod comparison;
+pub use comparison::Comparator;
+
 mod dot_product;
 pub use dot_product::dot_product_scalar;
 
 mod mul;
 pub use mul::Multiplicator;
 
-use super::{Ciphertext, Plaintext};
+mod polynomial;
+pub use polynomial::PolynomialEvaluator;
+
+use super::{noise, BfvParameters, Ciphertext, Plaintext};
 use crate::{Error, Result};
 use fhe_math::rq::{Poly, Representation};
 use itertools::{izip, Itertools};
+use num_bigint::BigUint;
 use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
+impl Ciphertext {
+    /// Checks that this ciphertext has the parameters `par` and the level
+    /// `level`.
+    fn check_compatible(&self, par: &BfvParameters, level: usize) -> Result<()> {
+        if self.par.as_ref() != par {
+            Err(Error::ParametersMismatch)
+        } else if self.level != level {
+            Err(Error::LevelMismatch {
+                expected: self.level,
+                found: level,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Add `rhs` to this ciphertext. Returns an error if the parameters, the
+    /// levels or the number of parts of the ciphertexts differ.
+    pub fn try_add_assign(&mut self, rhs: &Ciphertext) -> Result<()> {
+        if self.par != rhs.par {
+            return Err(Error::ParametersMismatch);
+        }
+
+        if self.c.is_empty() {
+            *self = rhs.clone()
+        } else if !rhs.c.is_empty() {
+            self.check_compatible(&rhs.par, rhs.level)?;
+            if self.c.len() != rhs.c.len() {
+                return Err(Error::InvalidCiphertextSize {
+                    expected: self.c.len(),
+                    found: rhs.c.len(),
+                });
+            }
+            izip!(&mut self.c, &rhs.c).for_each(|(c1i, c2i)| *c1i += c2i);
+            self.seed = None;
+            self.noise = self
+                .noise
+                .zip(rhs.noise)
+                .map(|(a, b)| noise::addition(a, b))
+        }
+        Ok(())
+    }
+
+    /// Returns the sum of this ciphertext and `rhs`. Returns an error if the
+    /// parameters, the levels or the number of parts of the ciphertexts differ.
+    pub fn try_add(&self, rhs: &Ciphertext) -> Result<Ciphertext> {
+        let mut self_clone = self.clone();
+        self_clone.try_add_assign(rhs)?;
+        Ok(self_clone)
+    }
+
+    /// Subtract `rhs` from this ciphertext. Returns an error if the parameters,
+    /// the levels or the number of parts of the ciphertexts differ.
+    pub fn try_sub_assign(&mut self, rhs: &Ciphertext) -> Result<()> {
+        if self.par != rhs.par {
+            return Err(Error::ParametersMismatch);
+        }
+
+        if self.c.is_empty() {
+            *self = -rhs
+        } else if !rhs.c.is_empty() {
+            self.check_compatible(&rhs.par, rhs.level)?;
+            if self.c.len() != rhs.c.len() {
+                return Err(Error::InvalidCiphertextSize {
+                    expected: self.c.len(),
+                    found: rhs.c.len(),
+                });
+            }
+            izip!(&mut self.c, &rhs.c).for_each(|(c1i, c2i)| *c1i -= c2i);
+            self.seed = None;
+            self.noise = self
+                .noise
+                .zip(rhs.noise)
+                .map(|(a, b)| noise::addition(a, b))
+        }
+        Ok(())
+    }
+
+    /// Returns the difference of this ciphertext and `rhs`. Returns an error if
+    /// the parameters, the levels or the number of parts of the ciphertexts
+    /// differ.
+    pub fn try_sub(&self, rhs: &Ciphertext) -> Result<Ciphertext> {
+        let mut self_clone = self.clone();
+        self_clone.try_sub_assign(rhs)?;
+        Ok(self_clone)
+    }
+
+    /// Add the plaintext `rhs` to this ciphertext. Returns an error if the
+    /// parameters or the levels differ, or if the ciphertext is empty.
+    pub fn try_add_plaintext_assign(&mut self, rhs: &Plaintext) -> Result<()> {
+        self.check_compatible(&rhs.par, rhs.level)?;
+        if self.c.is_empty() {
+            return Err(Error::InvalidCiphertextSize {
+                expected: 2,
+                found: 0,
+            });
+        }
+
+        let poly = rhs.to_poly();
+        self.c[0] += &poly;
+        self.seed = None;
+        self.noise = self.noise.map(noise::rounding);
+        Ok(())
+    }
+
+    /// Returns the sum of this ciphertext and the plaintext `rhs`. Returns an
+    /// error if the parameters or the levels differ, or if the ciphertext is
+    /// empty.
+    pub fn try_add_plaintext(&self, rhs: &Plaintext) -> Result<Ciphertext> {
+        let mut self_clone = self.clone();
+        self_clone.try_add_plaintext_assign(rhs)?;
+        Ok(self_clone)
+    }
+
+    /// Subtract the plaintext `rhs` from this ciphertext. Returns an error if
+    /// the parameters or the levels differ, or if the ciphertext is empty.
+    pub fn try_sub_plaintext_assign(&mut self, rhs: &Plaintext) -> Result<()> {
+        self.check_compatible(&rhs.par, rhs.level)?;
+        if self.c.is_empty() {
+            return Err(Error::InvalidCiphertextSize {
+                expected: 2,
+                found: 0,
+            });
+        }
+
+        let poly = rhs.to_poly();
+        self.c[0] -= &poly;
+        self.seed = None;
+        self.noise = self.noise.map(noise::rounding);
+        Ok(())
+    }
+
+    /// Returns the difference of this ciphertext and the plaintext `rhs`.
+    /// Returns an error if the parameters or the levels differ, or if the
+    /// ciphertext is empty.
+    pub fn try_sub_plaintext(&self, rhs: &Plaintext) -> Result<Ciphertext> {
+        let mut self_clone = self.clone();
+        self_clone.try_sub_plaintext_assign(rhs)?;
+        Ok(self_clone)
+    }
+
+    /// Multiply this ciphertext by the plaintext `rhs`. Returns an error if the
+    /// parameters or the levels differ.
+    pub fn try_mul_plaintext_assign(&mut self, rhs: &Plaintext) -> Result<()> {
+        if self.par != rhs.par {
+            return Err(Error::ParametersMismatch);
+        }
+        if !self.c.is_empty() {
+            self.check_compatible(&rhs.par, rhs.level)?;
+            self.c.iter_mut().for_each(|ci| *ci *= &rhs.poly_ntt);
+            self.noise = self
+                .noise
+                .map(|noise| noise::plaintext_multiplication(&self.par, noise));
+        }
+        self.seed = None;
+        Ok(())
+    }
+
+    /// Returns the product of this ciphertext and the plaintext `rhs`. Returns
+    /// an error if the parameters or the levels differ.
+    pub fn try_mul_plaintext(&self, rhs: &Plaintext) -> Result<Ciphertext> {
+        let mut self_clone = self.clone();
+        self_clone.try_mul_plaintext_assign(rhs)?;
+        Ok(self_clone)
+    }
+
+    /// Returns the tensor product of this ciphertext and `rhs`, which is not
+    /// relinearized. Returns an error if the parameters or the levels differ.
+    pub fn try_mul(&self, rhs: &Ciphertext) -> Result<Ciphertext> {
+        if self.c.is_empty() {
+            return Ok(self.clone());
+        }
+        if self.par != rhs.par {
+            return Err(Error::ParametersMismatch);
+        }
+        if rhs.c.is_empty() {
+            return Ok(rhs.clone());
+        }
+        self.check_compatible(&rhs.par, rhs.level)?;
+
+        let mp = &self.par.mul_params[self.level];
+
+        // Scale all ciphertexts
+        let self_c = self
+            .c
+            .iter()
+            .map(|ci| ci.scale(&mp.extender).map_err(Error::MathError))
+            .collect::<Result<Vec<Poly>>>()?;
+        let other_c = if rhs == self {
+            // Squaring operation
+            self_c.clone()
+        } else {
+            rhs.c
+                .iter()
+                .map(|ci| ci.scale(&mp.extender).map_err(Error::MathError))
+                .collect::<Result<Vec<Poly>>>()?
+        };
+
+        // Multiply
+        let mut c = vec![Poly::zero(&mp.to, Representation::Ntt); self_c.len() + other_c.len() - 1];
+        for i in 0..self_c.len() {
+            for j in 0..other_c.len() {
+                c[i + j] += &(&self_c[i] * &other_c[j])
+            }
+        }
+
+        // Scale
+        let c = c
+            .iter_mut()
+            .map(|ci| {
+                ci.change_representation(Representation::PowerBasis);
+                let mut ci = ci.scale(&mp.down_scaler).map_err(Error::MathError)?;
+                ci.change_representation(Representation::Ntt);
+                Ok(ci)
+            })
+            .collect::<Result<Vec<Poly>>>()?;
+
+        let noise = if self.c.len() == 2 && rhs.c.len() == 2 {
+            self.noise
+                .zip(rhs.noise)
+                .map(|(a, b)| noise::tensor(&self.par, a, b))
+        } else {
+            None
+        };
+
+        Ok(Ciphertext {
+            par: self.par.clone(),
+            seed: None,
+            c,
+            level: rhs.level,
+            noise,
+        })
+    }
+}
+
 impl Add<&Ciphertext> for &Ciphertext {
     type Output = Ciphertext;
 
     fn add(self, rhs: &Ciphertext) -> Ciphertext {
-        let mut self_clone = self.clone();
-        self_clone += rhs;
-        self_clone
+        self.try_add(rhs).unwrap()
     }
 }
 
 impl AddAssign<&Ciphertext> for Ciphertext {
     fn add_assign(&mut self, rhs: &Ciphertext) {
-        assert_eq!(self.par, rhs.par);
-
-        if self.c.is_empty() {
-            *self = rhs.clone()
-        } else if !rhs.c.is_empty() {
-            assert_eq!(self.level, rhs.level);
-            assert_eq!(self.c.len(), rhs.c.len());
-            izip!(&mut self.c, &rhs.c).for_each(|(c1i, c2i)| *c1i += c2i);
-            self.seed = None
-        }
+        self.try_add_assign(rhs).unwrap()
     }
 }
 
@@ -41,9 +272,7 @@ impl Add<&Plaintext> for &Ciphertext {
     type Output = Ciphertext;
 
     fn add(self, rhs: &Plaintext) -> Ciphertext {
-        let mut self_clone = self.clone();
-        self_clone += rhs;
-        self_clone
+        self.try_add_plaintext(rhs).unwrap()
     }
 }
 
@@ -57,13 +286,7 @@ impl Add<&Ciphertext> for &Plaintext {
 
 impl AddAssign<&Plaintext> for Ciphertext {
     fn add_assign(&mut self, rhs: &Plaintext) {
-        assert_eq!(self.par, rhs.par);
-        assert!(!self.c.is_empty());
-        assert_eq!(self.level, rhs.level);
-
-        let poly = rhs.to_poly();
-        self.c[0] += &poly;
-        self.seed = None
+        self.try_add_plaintext_assign(rhs).unwrap()
     }
 }
 
@@ -71,24 +294,13 @@ impl Sub<&Ciphertext> for &Ciphertext {
     type Output = Ciphertext;
 
     fn sub(self, rhs: &Ciphertext) -> Ciphertext {
-        let mut self_clone = self.clone();
-        self_clone -= rhs;
-        self_clone
+        self.try_sub(rhs).unwrap()
     }
 }
 
 impl SubAssign<&Ciphertext> for Ciphertext {
     fn sub_assign(&mut self, rhs: &Ciphertext) {
-        assert_eq!(self.par, rhs.par);
-
-        if self.c.is_empty() {
-            *self = -rhs
-        } else if !rhs.c.is_empty() {
-            assert_eq!(self.level, rhs.level);
-            assert_eq!(self.c.len(), rhs.c.len());
-            izip!(&mut self.c, &rhs.c).for_each(|(c1i, c2i)| *c1i -= c2i);
-            self.seed = None
-        }
+        self.try_sub_assign(rhs).unwrap()
     }
 }
 
@@ -96,9 +308,7 @@ impl Sub<&Plaintext> for &Ciphertext {
     type Output = Ciphertext;
 
     fn sub(self, rhs: &Plaintext) -> Ciphertext {
-        let mut self_clone = self.clone();
-        self_clone -= rhs;
-        self_clone
+        self.try_sub_plaintext(rhs).unwrap()
     }
 }
 
@@ -112,13 +322,7 @@ impl Sub<&Ciphertext> for &Plaintext {
 
 impl SubAssign<&Plaintext> for Ciphertext {
     fn sub_assign(&mut self, rhs: &Plaintext) {
-        assert_eq!(self.par, rhs.par);
-        assert!(!self.c.is_empty());
-        assert_eq!(self.level, rhs.level);
-
-        let poly = rhs.to_poly();
-        self.c[0] -= &poly;
-        self.seed = None
+        self.try_sub_plaintext_assign(rhs).unwrap()
     }
 }
 
@@ -132,6 +336,7 @@ impl Neg for &Ciphertext {
             seed: None,
             c,
             level: self.level,
+            noise: self.noise.map(noise::rounding),
         }
     }
 }
@@ -142,18 +347,14 @@ impl Neg for Ciphertext {
     fn neg(mut self) -> Ciphertext {
         self.c.iter_mut().for_each(|c1i| *c1i = -&*c1i);
         self.seed = None;
+        self.noise = self.noise.map(noise::rounding);
         self
     }
 }
 
 impl MulAssign<&Plaintext> for Ciphertext {
     fn mul_assign(&mut self, rhs: &Plaintext) {
-        assert_eq!(self.par, rhs.par);
-        if !self.c.is_empty() {
-            assert_eq!(self.level, rhs.level);
-            self.c.iter_mut().for_each(|ci| *ci *= &rhs.poly_ntt);
-        }
-        self.seed = None
+        self.try_mul_plaintext_assign(rhs).unwrap()
     }
 }
 
@@ -161,118 +362,95 @@ impl Mul<&Plaintext> for &Ciphertext {
     type Output = Ciphertext;
 
     fn mul(self, rhs: &Plaintext) -> Ciphertext {
+        self.try_mul_plaintext(rhs).unwrap()
+    }
+}
+
+impl AddAssign<u64> for Ciphertext {
+    fn add_assign(&mut self, rhs: u64) {
+        assert!(!self.c.is_empty());
+
+        // Same as the encoding of a constant plaintext. Since `delta` is stored in
+        // NttShoup representation, converting it to Ntt does not compute an Ntt.
+        let m = self
+            .par
+            .plaintext
+            .mul(self.par.plaintext.reduce(rhs), self.par.q_mod_t[self.level]);
+        let mut delta_m = self.par.delta[self.level].clone();
+        delta_m.change_representation(Representation::Ntt);
+        delta_m *= &BigUint::from(m);
+        self.c[0] += &delta_m;
+        self.seed = None;
+        self.noise = self.noise.map(noise::rounding)
+    }
+}
+
+impl Add<u64> for &Ciphertext {
+    type Output = Ciphertext;
+
+    fn add(self, rhs: u64) -> Ciphertext {
         let mut self_clone = self.clone();
-        self_clone *= rhs;
+        self_clone += rhs;
         self_clone
     }
 }
 
-impl Mul<&Ciphertext> for &Ciphertext {
+/// The multiplication by a constant uses the representative of the constant
+/// modulo the plaintext modulus of smallest absolute value, to minimize the
+/// noise growth.
+impl MulAssign<u64> for Ciphertext {
+    fn mul_assign(&mut self, rhs: u64) {
+        let t = self.par.plaintext.modulus();
+        let rhs = self.par.plaintext.reduce(rhs);
+        if rhs > t >> 1 {
+            let rhs = BigUint::from(t - rhs);
+            self.c.iter_mut().for_each(|ci| {
+                *ci *= &rhs;
+                *ci = -&*ci
+            });
+        } else {
+            let rhs = BigUint::from(rhs);
+            self.c.iter_mut().for_each(|ci| *ci *= &rhs);
+        }
+        let c = rhs.min(t - rhs);
+        self.seed = None;
+        self.noise = self
+            .noise
+            .map(|noise| noise::scalar_multiplication(c, noise))
+    }
+}
+
+impl MulAssign<i64> for Ciphertext {
+    fn mul_assign(&mut self, rhs: i64) {
+        *self *= (rhs as i128).rem_euclid(self.par.plaintext.modulus() as i128) as u64
+    }
+}
+
+impl Mul<u64> for &Ciphertext {
     type Output = Ciphertext;
 
-    fn mul(self, rhs: &Ciphertext) -> Ciphertext {
-        if self.c.is_empty() {
-            return self.clone();
-        }
+    fn mul(self, rhs: u64) -> Ciphertext {
+        let mut self_clone = self.clone();
+        self_clone *= rhs;
+        self_clone
+    }
+}
 
-        if rhs == self {
-            // Squaring operation
-            let mp = &self.par.mul_params[self.level];
+impl Mul<i64> for &Ciphertext {
+    type Output = Ciphertext;
 
-            // Scale all ciphertexts
-            // let mut now = std::time::SystemTime::now();
-            let self_c = self
-                .c
-                .iter()
-                .map(|ci| ci.scale(&mp.extender).map_err(Error::MathError))
-                .collect::<Result<Vec<Poly>>>()
-                .unwrap();
-            // println!("Extend: {:?}", now.elapsed().unwrap());
-
-            // Multiply
-            // now = std::time::SystemTime::now();
-            let mut c = vec![Poly::zero(&mp.to, Representation::Ntt); 2 * self_c.len() - 1];
-            for i in 0..self_c.len() {
-                for j in 0..self_c.len() {
-                    c[i + j] += &(&self_c[i] * &self_c[j])
-                }
-            }
-            // println!("Multiply: {:?}", now.elapsed().unwrap());
-
-            // Scale
-            // now = std::time::SystemTime::now();
-            let c = c
-                .iter_mut()
-                .map(|ci| {
-                    ci.change_representation(Representation::PowerBasis);
-                    let mut ci = ci.scale(&mp.down_scaler).map_err(Error::MathError)?;
-                    ci.change_representation(Representation::Ntt);
-                    Ok(ci)
-                })
-                .collect::<Result<Vec<Poly>>>()
-                .unwrap();
-            // println!("Scale: {:?}", now.elapsed().unwrap());
-
-            Ciphertext {
-                par: self.par.clone(),
-                seed: None,
-                c,
-                level: rhs.level,
-            }
-        } else {
-            assert_eq!(self.par, rhs.par);
-            assert_eq!(self.level, rhs.level);
+    fn mul(self, rhs: i64) -> Ciphertext {
+        let mut self_clone = self.clone();
+        self_clone *= rhs;
+        self_clone
+    }
+}
 
-            let mp = &self.par.mul_params[self.level];
+impl Mul<&Ciphertext> for &Ciphertext {
+    type Output = Ciphertext;
 
-            // Scale all ciphertexts
-            // let mut now = std::time::SystemTime::now();
-            let self_c = self
-                .c
-                .iter()
-                .map(|ci| ci.scale(&mp.extender).map_err(Error::MathError))
-                .collect::<Result<Vec<Poly>>>()
-                .unwrap();
-            let other_c = rhs
-                .c
-                .iter()
-                .map(|ci| ci.scale(&mp.extender).map_err(Error::MathError))
-                .collect::<Result<Vec<Poly>>>()
-                .unwrap();
-            // println!("Extend: {:?}", now.elapsed().unwrap());
-
-            // Multiply
-            // now = std::time::SystemTime::now();
-            let mut c =
-                vec![Poly::zero(&mp.to, Representation::Ntt); self_c.len() + other_c.len() - 1];
-            for i in 0..self_c.len() {
-                for j in 0..other_c.len() {
-                    c[i + j] += &(&self_c[i] * &other_c[j])
-                }
-            }
-            // println!("Multiply: {:?}", now.elapsed().unwrap());
-
-            // Scale
-            // now = std::time::SystemTime::now();
-            let c = c
-                .iter_mut()
-                .map(|ci| {
-                    ci.change_representation(Representation::PowerBasis);
-                    let mut ci = ci.scale(&mp.down_scaler).map_err(Error::MathError)?;
-                    ci.change_representation(Representation::Ntt);
-                    Ok(ci)
-                })
-                .collect::<Result<Vec<Poly>>>()
-                .unwrap();
-            // println!("Scale: {:?}", now.elapsed().unwrap());
-
-            Ciphertext {
-                par: self.par.clone(),
-                seed: None,
-                c,
-                level: rhs.level,
-            }
-        }
+    fn mul(self, rhs: &Ciphertext) -> Ciphertext {
+        self.try_mul(rhs).unwrap()
     }
 }
 
@@ -282,7 +460,7 @@ mod tests {
         encoding::EncodingEnum, BfvParameters, Ciphertext, Encoding, Plaintext, SecretKey,
     };
     use fhe_traits::{FheDecoder, FheDecrypter, FheEncoder, FheEncrypter};
-    use rand::{rngs::OsRng, thread_rng};
+    use rand::{rngs::OsRng, thread_rng, RngCore};
     use std::error::Error;
 
     #[test]
@@ -559,6 +737,159 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn try_operations() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BfvParameters::default_arc(3, 8);
+        let other_params = BfvParameters::default_arc(2, 8);
+        let sk = SecretKey::random(&params, &mut rng);
+        let other_sk = SecretKey::random(&other_params, &mut rng);
+
+        let v = params.plaintext.random_vec(params.degree(), &mut rng);
+        let pt = Plaintext::try_encode(&v, Encoding::simd(), &params)?;
+        let pt1 = Plaintext::try_encode(&v, Encoding::simd_at_level(1), &params)?;
+        let other_pt = Plaintext::try_encode(&v, Encoding::simd(), &other_params)?;
+        let ct: Ciphertext = sk.try_encrypt(&pt, &mut rng)?;
+        let ct1: Ciphertext = sk.try_encrypt(&pt1, &mut rng)?;
+        let other_ct: Ciphertext = other_sk.try_encrypt(&other_pt, &mut rng)?;
+        let zero = Ciphertext::zero(&params);
+
+        // The fallible operations agree with the operators.
+        assert_eq!(ct.try_add(&ct)?, &ct + &ct);
+        assert_eq!(ct.try_sub(&ct)?, &ct - &ct);
+        assert_eq!(ct.try_add_plaintext(&pt)?, &ct + &pt);
+        assert_eq!(ct.try_sub_plaintext(&pt)?, &ct - &pt);
+        assert_eq!(ct.try_mul_plaintext(&pt)?, &ct * &pt);
+        assert_eq!(ct.try_mul(&ct)?, &ct * &ct);
+        assert_eq!(zero.try_add(&ct)?, ct);
+        assert_eq!(zero.try_mul(&ct)?, zero);
+
+        // Mismatched levels.
+        let mismatched_levels = Err(crate::Error::LevelMismatch {
+            expected: 0,
+            found: 1,
+        });
+        assert_eq!(ct.try_add(&ct1), mismatched_levels);
+        assert_eq!(ct.try_sub(&ct1), mismatched_levels);
+        assert_eq!(ct.try_add_plaintext(&pt1), mismatched_levels);
+        assert_eq!(ct.try_sub_plaintext(&pt1), mismatched_levels);
+        assert_eq!(ct.try_mul_plaintext(&pt1), mismatched_levels);
+        assert_eq!(ct.try_mul(&ct1), mismatched_levels);
+
+        // Mismatched parameters.
+        let mismatched_parameters = Err(crate::Error::ParametersMismatch);
+        assert_eq!(ct.try_add(&other_ct), mismatched_parameters);
+        assert_eq!(ct.try_sub(&other_ct), mismatched_parameters);
+        assert_eq!(ct.try_add_plaintext(&other_pt), mismatched_parameters);
+        assert_eq!(ct.try_sub_plaintext(&other_pt), mismatched_parameters);
+        assert_eq!(ct.try_mul_plaintext(&other_pt), mismatched_parameters);
+        assert_eq!(ct.try_mul(&other_ct), mismatched_parameters);
+
+        // Mismatched sizes.
+        let ct2 = &ct * &ct;
+        assert_eq!(
+            ct2.try_add(&ct),
+            Err(crate::Error::InvalidCiphertextSize {
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            zero.try_add_plaintext(&pt),
+            Err(crate::Error::InvalidCiphertextSize {
+                expected: 2,
+                found: 0
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn add_constant() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+
+        for params in [
+            BfvParameters::default_arc(1, 8),
+            BfvParameters::default_arc(6, 8),
+        ] {
+            for _ in 0..50 {
+                let a = params.plaintext.random_vec(params.degree(), &mut rng);
+                let b = rng.next_u64();
+                let sk = SecretKey::random(&params, &mut rng);
+
+                for encoding in [Encoding::poly(), Encoding::simd()] {
+                    let b_reduced = params.plaintext.reduce(b);
+                    let mut c = a.clone();
+                    match encoding.encoding {
+                        EncodingEnum::Poly => c[0] = params.plaintext.add(c[0], b_reduced),
+                        EncodingEnum::Simd => c
+                            .iter_mut()
+                            .for_each(|ci| *ci = params.plaintext.add(*ci, b_reduced)),
+                    }
+
+                    let pt_a = Plaintext::try_encode(&a, encoding.clone(), &params)?;
+                    let mut ct_a = sk.try_encrypt(&pt_a, &mut rng)?;
+                    let ct_c = &ct_a + b;
+                    ct_a += b;
+
+                    let pt_c = sk.try_decrypt(&ct_c)?;
+                    assert_eq!(Vec::<u64>::try_decode(&pt_c, encoding.clone())?, c);
+                    let pt_c = sk.try_decrypt(&ct_a)?;
+                    assert_eq!(Vec::<u64>::try_decode(&pt_c, encoding.clone())?, c);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn mul_constant() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+
+        for params in [
+            BfvParameters::default_arc(1, 8),
+            BfvParameters::default_arc(6, 8),
+        ] {
+            let t = params.plaintext();
+            for _ in 0..50 {
+                let a = params.plaintext.random_vec(params.degree(), &mut rng);
+                let b = rng.next_u64();
+                let b_signed = rng.next_u64() as i64;
+                let sk = SecretKey::random(&params, &mut rng);
+
+                for encoding in [Encoding::poly(), Encoding::simd()] {
+                    let mut c = a.clone();
+                    params.plaintext.scalar_mul_vec(&mut c, b % t);
+                    let mut c_signed = a.clone();
+                    params.plaintext.scalar_mul_vec(
+                        &mut c_signed,
+                        (b_signed as i128).rem_euclid(t as i128) as u64,
+                    );
+
+                    let pt_a = Plaintext::try_encode(&a, encoding.clone(), &params)?;
+                    let mut ct_a = sk.try_encrypt(&pt_a, &mut rng)?;
+                    for (ct, expected) in [
+                        (&ct_a * b, &c),
+                        (&ct_a * b_signed, &c_signed),
+                        (&ct_a * (t - 1), &a.iter().map(|ai| (t - ai) % t).collect()),
+                        (&ct_a * -1i64, &a.iter().map(|ai| (t - ai) % t).collect()),
+                    ] {
+                        let pt = sk.try_decrypt(&ct)?;
+                        assert_eq!(&Vec::<u64>::try_decode(&pt, encoding.clone())?, expected);
+                    }
+
+                    ct_a *= b;
+                    let pt_c = sk.try_decrypt(&ct_a)?;
+                    assert_eq!(Vec::<u64>::try_decode(&pt_c, encoding.clone())?, c);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn mul() -> Result<(), Box<dyn Error>> {
         let mut rng = thread_rng();
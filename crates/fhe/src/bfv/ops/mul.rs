@@ -9,7 +9,7 @@ use fhe_util::div_ceil;
 use num_bigint::BigUint;
 
 use crate::{
-    bfv::{keys::RelinearizationKey, BfvParameters, Ciphertext},
+    bfv::{keys::RelinearizationKey, noise, BfvParameters, Ciphertext},
     Error, Result,
 };
 
@@ -31,6 +31,9 @@ pub struct Multiplicator {
     rk: Option<RelinearizationKey>,
     mod_switch: bool,
     level: usize,
+    /// Whether the multiplication follows the default strategy, for which the
+    /// noise of the product is estimated.
+    estimates_noise: bool,
 }
 
 impl Multiplicator {
@@ -96,6 +99,7 @@ impl Multiplicator {
             rk: None,
             mod_switch: false,
             level,
+            estimates_noise: false,
         })
     }
 
@@ -131,6 +135,7 @@ impl Multiplicator {
         )?;
 
         multiplicator.enable_relinearization(rk)?;
+        multiplicator.estimates_noise = true;
         Ok(multiplicator)
     }
 
@@ -138,9 +143,10 @@ impl Multiplicator {
     pub fn enable_relinearization(&mut self, rk: &RelinearizationKey) -> Result<()> {
         let rk_ctx = self.par.ctx_at_level(rk.ksk.ciphertext_level)?;
         if rk_ctx != &self.base_ctx {
-            return Err(Error::DefaultError(
-                "Invalid relinearization key context".to_string(),
-            ));
+            return Err(Error::LevelMismatch {
+                expected: self.level,
+                found: rk.ksk.ciphertext_level,
+            });
         }
         self.rk = Some(rk.clone());
         Ok(())
@@ -150,9 +156,7 @@ impl Multiplicator {
     /// applicable).
     pub fn enable_mod_switching(&mut self) -> Result<()> {
         if self.par.ctx_at_level(self.par.max_level())? == &self.base_ctx {
-            Err(Error::DefaultError(
-                "Cannot modulo switch as this is already the last level".to_string(),
-            ))
+            Err(Error::MathError(fhe_math::Error::NoMoreContext))
         } else {
             self.mod_switch = true;
             Ok(())
@@ -162,19 +166,21 @@ impl Multiplicator {
     /// Multiply two ciphertexts using the defined multiplication strategy.
     pub fn multiply(&self, lhs: &Ciphertext, rhs: &Ciphertext) -> Result<Ciphertext> {
         if lhs.par != self.par || rhs.par != self.par {
-            return Err(Error::DefaultError(
-                "Ciphertexts do not have the same parameters".to_string(),
-            ));
+            return Err(Error::ParametersMismatch);
         }
-        if lhs.level != self.level || rhs.level != self.level {
-            return Err(Error::DefaultError(
-                "Ciphertexts are not at expected level".to_string(),
-            ));
-        }
-        if lhs.c.len() != 2 || rhs.c.len() != 2 {
-            return Err(Error::DefaultError(
-                "Multiplication can only be performed on ciphertexts of size 2".to_string(),
-            ));
+        for ct in [lhs, rhs] {
+            if ct.level != self.level {
+                return Err(Error::LevelMismatch {
+                    expected: self.level,
+                    found: ct.level,
+                });
+            }
+            if ct.c.len() != 2 {
+                return Err(Error::InvalidCiphertextSize {
+                    expected: 2,
+                    found: ct.c.len(),
+                });
+            }
         }
 
         // Extend
@@ -198,6 +204,13 @@ impl Multiplicator {
         let c2 = c2.scale(&self.down_scaler)?;
 
         let mut c = vec![c0, c1, c2];
+        let mut noise = if self.estimates_noise {
+            lhs.noise
+                .zip(rhs.noise)
+                .map(|(a, b)| noise::tensor(&self.par, a, b))
+        } else {
+            None
+        };
 
         // Relinearize
         if let Some(rk) = self.rk.as_ref() {
@@ -217,6 +230,7 @@ impl Multiplicator {
             c[0] += &c0r;
             c[1] += &c1r;
             c.truncate(2);
+            noise = noise.map(|noise| noise::addition(noise, rk.ksk.noise()));
         }
 
         // We construct a ciphertext, but it may not have the right representation for
@@ -226,6 +240,7 @@ impl Multiplicator {
             seed: None,
             c,
             level: self.level,
+            noise,
         };
 
         if self.mod_switch {
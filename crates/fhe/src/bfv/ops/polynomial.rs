@@ -0,0 +1,393 @@
+use std::{collections::HashMap, sync::Arc};
+
+use fhe_traits::FheEncoder;
+
+use crate::{
+    bfv::{
+        dot_product_scalar, keys::RelinearizationKey, BfvParameters, Ciphertext, Encoding,
+        Plaintext,
+    },
+    Error, Result,
+};
+
+use super::Multiplicator;
+
+/// Evaluator of polynomials with coefficients in Z_t over the slots of SIMD
+/// encoded ciphertexts.
+///
+/// The polynomials are evaluated using the Paterson-Stockmeyer algorithm,
+/// which minimizes the number of non-scalar multiplications; the powers of the
+/// input ciphertext are computed with a balanced product tree to minimize the
+/// depth. Each multiplication is relinearized, and the product is switched to
+/// the next level when a relinearization key was provided for that level.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolynomialEvaluator {
+    par: Arc<BfvParameters>,
+    multiplicators: HashMap<usize, Multiplicator>,
+}
+
+impl PolynomialEvaluator {
+    /// Create a polynomial evaluator from relinearization keys, one for each
+    /// ciphertext level at which multiplications may occur. Multiplications at
+    /// a level `l` are followed by a modulus switching whenever a key for the
+    /// level `l + 1` is provided.
+    pub fn new(rks: &[RelinearizationKey]) -> Result<Self> {
+        if rks.is_empty() {
+            return Err(Error::TooFewValues(0, 1));
+        }
+        let par = rks[0].ksk.par.clone();
+        if rks.iter().any(|rk| rk.ksk.par != par) {
+            return Err(Error::ParametersMismatch);
+        }
+
+        let mut multiplicators = HashMap::new();
+        for rk in rks {
+            if multiplicators
+                .insert(rk.ksk.ciphertext_level, Multiplicator::default(rk)?)
+                .is_some()
+            {
+                return Err(Error::DuplicateRelinearizationKey(rk.ksk.ciphertext_level));
+            }
+        }
+        let levels = multiplicators.keys().copied().collect::<Vec<_>>();
+        for level in levels {
+            if multiplicators.contains_key(&(level + 1)) {
+                multiplicators
+                    .get_mut(&level)
+                    .unwrap()
+                    .enable_mod_switching()?;
+            }
+        }
+
+        Ok(Self {
+            par,
+            multiplicators,
+        })
+    }
+
+    /// Homomorphically evaluate the polynomial `coefficients[0] +
+    /// coefficients[1] * x + ...` over the slots of `ct`. The coefficients are
+    /// reduced modulo the plaintext modulus.
+    pub fn evaluate(&self, ct: &Ciphertext, coefficients: &[u64]) -> Result<Ciphertext> {
+        if ct.par != self.par {
+            return Err(Error::ParametersMismatch);
+        }
+        if coefficients.is_empty() {
+            return Err(Error::TooFewValues(0, 1));
+        }
+
+        let mut coefficients = coefficients.to_vec();
+        self.par.plaintext.reduce_vec(&mut coefficients);
+        while coefficients.len() > 1 && coefficients.last() == Some(&0) {
+            coefficients.pop();
+        }
+
+        let (k, m) = Self::paterson_stockmeyer_parameters(coefficients.len() - 1);
+
+        // Baby steps: the powers `x^1, ..., x^k` with a balanced product tree; only
+        // the powers up to the degree are needed without giant steps. The first
+        // entry, standing for `x^0`, is never used.
+        let max_power = if m > 0 { k } else { coefficients.len() - 1 };
+        let mut powers = vec![ct.clone(), ct.clone()];
+        for i in 2..=max_power {
+            let power = self.multiply(&powers[i - i / 2], &powers[i / 2])?;
+            powers.push(power);
+        }
+
+        // Giant steps: the powers `x^(k * 2^j)` for `j < m`, by repeated squaring.
+        let mut giant_steps = Vec::with_capacity(m);
+        if m > 0 {
+            giant_steps.push(powers[k].clone());
+        }
+        for j in 1..m {
+            let power = self.multiply(&giant_steps[j - 1], &giant_steps[j - 1])?;
+            giant_steps.push(power);
+        }
+
+        self.evaluate_recursive(&coefficients, k, &powers, &giant_steps)
+    }
+
+    /// Homomorphically raise the slots of `ct` to the power `exponent`, which
+    /// must be positive, using square-and-multiply with a multiplicative depth
+    /// of `ceil(log2(exponent))`.
+    pub fn power(&self, ct: &Ciphertext, exponent: usize) -> Result<Ciphertext> {
+        if ct.par != self.par {
+            return Err(Error::ParametersMismatch);
+        }
+        if exponent == 0 {
+            return Err(Error::InvalidExponent(exponent));
+        }
+
+        // The powers `x^(2^i)` are multiplied starting from the lowest bits, so that
+        // the depth of the accumulator never exceeds the depth of the next square.
+        let mut square = ct.clone();
+        let mut out: Option<Ciphertext> = None;
+        for i in 0..exponent.ilog2() + 1 {
+            if i > 0 {
+                square = self.multiply(&square, &square)?;
+            }
+            if (exponent >> i) & 1 == 1 {
+                out = Some(match out {
+                    Some(out) => self.multiply(&out, &square)?,
+                    None => square.clone(),
+                });
+            }
+        }
+        Ok(out.unwrap())
+    }
+
+    /// Returns the multiplicative depth of the evaluation of a polynomial of
+    /// degree `degree`.
+    pub(crate) fn depth(degree: usize) -> usize {
+        let (k, m) = Self::paterson_stockmeyer_parameters(degree);
+        if m == 0 {
+            degree.max(1).next_power_of_two().ilog2() as usize
+        } else {
+            k.next_power_of_two().ilog2() as usize + m
+        }
+    }
+
+    /// Returns the baby step `k` and the number of giant steps `m` minimizing
+    /// the number of non-scalar multiplications for a polynomial of degree
+    /// `degree`, such that `k * 2^m > degree`. Ties are broken by minimizing
+    /// the depth.
+    fn paterson_stockmeyer_parameters(degree: usize) -> (usize, usize) {
+        (2..=degree.max(1) + 1)
+            .map(|k| {
+                let mut m = 0;
+                while k << m <= degree {
+                    m += 1;
+                }
+                let multiplications = (k - 1) + m + (1 << m) - 1;
+                let depth = k.next_power_of_two().ilog2() as usize + m;
+                ((multiplications, depth), (k, m))
+            })
+            .min()
+            .unwrap()
+            .1
+    }
+
+    /// Evaluate the polynomial with at most `k * 2^m` coefficients, where `k`
+    /// is the baby step and `m` the number of giant steps, by dividing it by
+    /// the largest giant step.
+    fn evaluate_recursive(
+        &self,
+        coefficients: &[u64],
+        k: usize,
+        baby_steps: &[Ciphertext],
+        giant_steps: &[Ciphertext],
+    ) -> Result<Ciphertext> {
+        if giant_steps.is_empty() {
+            return self.evaluate_linear(coefficients, baby_steps);
+        }
+
+        let m = giant_steps.len() - 1;
+        let split = k << m;
+        if coefficients.len() <= split {
+            return self.evaluate_recursive(coefficients, k, baby_steps, &giant_steps[..m]);
+        }
+
+        let quotient =
+            self.evaluate_recursive(&coefficients[split..], k, baby_steps, &giant_steps[..m])?;
+        let remainder =
+            self.evaluate_recursive(&coefficients[..split], k, baby_steps, &giant_steps[..m])?;
+        let mut out = self.multiply(&quotient, &giant_steps[m])?;
+        let level = out.level.max(remainder.level);
+        out = Self::mod_switch_to(out, level)?;
+        out += &Self::mod_switch_to(remainder, level)?;
+        Ok(out)
+    }
+
+    /// Evaluate the polynomial with at most `k` coefficients as a linear
+    /// combination of the baby steps, using scalar multiplications only.
+    fn evaluate_linear(
+        &self,
+        coefficients: &[u64],
+        baby_steps: &[Ciphertext],
+    ) -> Result<Ciphertext> {
+        // We always use at least one power, so that the output is a ciphertext even
+        // for a constant polynomial.
+        let mut indices = (1..coefficients.len())
+            .filter(|i| coefficients[*i] != 0)
+            .collect::<Vec<_>>();
+        if indices.is_empty() {
+            indices.push(1)
+        }
+        let level = indices.iter().map(|i| baby_steps[*i].level).max().unwrap();
+
+        let cts = indices
+            .iter()
+            .map(|i| Self::mod_switch_to(baby_steps[*i].clone(), level))
+            .collect::<Result<Vec<_>>>()?;
+        let pts = indices
+            .iter()
+            .map(|i| self.constant(coefficients.get(*i).copied().unwrap_or_default(), level))
+            .collect::<Result<Vec<_>>>()?;
+        let mut out = dot_product_scalar(cts.iter(), pts.iter())?;
+        if coefficients[0] != 0 {
+            out += &self.constant(coefficients[0], level)?;
+        }
+        Ok(out)
+    }
+
+    /// Multiply two ciphertexts at the largest of their levels.
+    fn multiply(&self, lhs: &Ciphertext, rhs: &Ciphertext) -> Result<Ciphertext> {
+        let level = lhs.level.max(rhs.level);
+        let multiplicator = self
+            .multiplicators
+            .get(&level)
+            .ok_or(Error::MissingRelinearizationKey(level))?;
+        multiplicator.multiply(
+            &Self::mod_switch_to(lhs.clone(), level)?,
+            &Self::mod_switch_to(rhs.clone(), level)?,
+        )
+    }
+
+    /// Encode a constant, which multiplies all the slots, at a given level.
+    fn constant(&self, value: u64, level: usize) -> Result<Plaintext> {
+        Plaintext::try_encode(&[value], Encoding::poly_at_level(level), &self.par)
+    }
+
+    fn mod_switch_to(mut ct: Ciphertext, level: usize) -> Result<Ciphertext> {
+        while ct.level < level {
+            ct.mod_switch_to_next_level()?;
+        }
+        Ok(ct)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bfv::{BfvParameters, Encoding, Plaintext, RelinearizationKey, SecretKey};
+    use fhe_traits::{FheDecoder, FheDecrypter, FheEncoder, FheEncrypter};
+    use itertools::Itertools;
+    use rand::thread_rng;
+    use std::error::Error;
+
+    use super::PolynomialEvaluator;
+
+    #[test]
+    fn paterson_stockmeyer_parameters() {
+        assert_eq!(
+            PolynomialEvaluator::paterson_stockmeyer_parameters(0),
+            (2, 0)
+        );
+        assert_eq!(
+            PolynomialEvaluator::paterson_stockmeyer_parameters(1),
+            (2, 0)
+        );
+        assert_eq!(
+            PolynomialEvaluator::paterson_stockmeyer_parameters(15),
+            (4, 2)
+        );
+        for degree in 0..200 {
+            let (k, m) = PolynomialEvaluator::paterson_stockmeyer_parameters(degree);
+            assert!(k << m > degree);
+            assert!(PolynomialEvaluator::depth(degree) <= (degree + 1).ilog2() as usize + 2);
+        }
+    }
+
+    #[test]
+    fn power() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let par = BfvParameters::default_arc(6, 16);
+        let sk = SecretKey::random(&par, &mut rng);
+        let rks = (0..5)
+            .map(|level| RelinearizationKey::new_leveled(&sk, level, level, &mut rng))
+            .collect::<Result<Vec<_>, _>>()?;
+        let evaluator = PolynomialEvaluator::new(&rks)?;
+
+        let values = par.plaintext.random_vec(par.degree(), &mut rng);
+        let pt = Plaintext::try_encode(&values, Encoding::simd(), &par)?;
+        let ct = sk.try_encrypt(&pt, &mut rng)?;
+        for exponent in [1, 2, 3, 7, 12, 33] {
+            let expected = values
+                .iter()
+                .map(|x| par.plaintext.pow(*x, exponent as u64))
+                .collect_vec();
+            let ct2 = evaluator.power(&ct, exponent)?;
+            assert_eq!(
+                ct2.level,
+                (exponent as f64).log2().ceil().min((rks.len() - 1) as f64) as usize
+            );
+            let pt2 = sk.try_decrypt(&ct2)?;
+            assert_eq!(Vec::<u64>::try_decode(&pt2, Encoding::simd())?, expected);
+        }
+        assert_eq!(
+            evaluator.power(&ct, 0).err(),
+            Some(crate::Error::InvalidExponent(0))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn evaluate() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let par = BfvParameters::default_arc(6, 16);
+        let t = par.plaintext();
+        let sk = SecretKey::random(&par, &mut rng);
+        let rks = (0..5)
+            .map(|level| RelinearizationKey::new_leveled(&sk, level, level, &mut rng))
+            .collect::<Result<Vec<_>, _>>()?;
+        let evaluator = PolynomialEvaluator::new(&rks)?;
+
+        let values = par.plaintext.random_vec(par.degree(), &mut rng);
+        let pt = Plaintext::try_encode(&values, Encoding::simd(), &par)?;
+        let ct = sk.try_encrypt(&pt, &mut rng)?;
+
+        for degree in [0, 1, 2, 3, 5, 8, 15, 16, 31] {
+            let mut coefficients = par.plaintext.random_vec(degree + 1, &mut rng);
+            if degree == 8 {
+                // A sparse polynomial.
+                coefficients[1..8].iter_mut().for_each(|c| *c = 0);
+            }
+            let expected = values
+                .iter()
+                .map(|x| {
+                    coefficients
+                        .iter()
+                        .rev()
+                        .fold(0, |acc, c| (acc * x + c) % t)
+                })
+                .collect_vec();
+
+            let ct2 = evaluator.evaluate(&ct, &coefficients)?;
+            let pt2 = sk.try_decrypt(&ct2)?;
+            assert_eq!(Vec::<u64>::try_decode(&pt2, Encoding::simd())?, expected);
+        }
+
+        // Trailing zero coefficients do not count towards the degree.
+        let ct2 = evaluator.evaluate(&ct, &[3, 1, 0, 0])?;
+        assert_eq!(ct2.level, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn errors() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let par = BfvParameters::default_arc(3, 16);
+        let sk = SecretKey::random(&par, &mut rng);
+        let rk = RelinearizationKey::new(&sk, &mut rng)?;
+
+        assert!(PolynomialEvaluator::new(&[]).is_err());
+        assert!(matches!(
+            PolynomialEvaluator::new(&[rk.clone(), rk.clone()]),
+            Err(crate::Error::DuplicateRelinearizationKey(0))
+        ));
+
+        let evaluator = PolynomialEvaluator::new(&[rk])?;
+        let pt = Plaintext::try_encode(&[1u64, 2, 3], Encoding::simd(), &par)?;
+        let ct = sk.try_encrypt(&pt, &mut rng)?;
+        assert!(evaluator.evaluate(&ct, &[]).is_err());
+
+        // There is no relinearization key at level 1.
+        let pt = Plaintext::try_encode(&[1u64, 2, 3], Encoding::simd_at_level(1), &par)?;
+        let ct = sk.try_encrypt(&pt, &mut rng)?;
+        assert!(evaluator.evaluate(&ct, &[1, 2, 3]).is_err());
+        assert!(evaluator.evaluate(&ct, &[1, 2]).is_ok());
+
+        Ok(())
+    }
+}
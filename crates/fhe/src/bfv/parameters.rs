@@ -1,6 +1,6 @@
 //! Create parameters for the BFV encryption scheme
 
-use crate::bfv::proto::bfv::Parameters;
+use crate::bfv::{keys::HybridKeySwitchingCache, proto::bfv::Parameters, SecurityLevel};
 use crate::{Error, ParametersError, Result};
 use fhe_math::{
     rns::{RnsContext, ScalingFactor},
@@ -13,10 +13,62 @@ use itertools::Itertools;
 use num_bigint::BigUint;
 use num_traits::ToPrimitive;
 use protobuf::Message;
-use std::collections::HashMap;
 use std::fmt::Debug;
 use std::sync::Arc;
 
+/// Ciphertext moduli of the default parameters providing about 128 bits of
+/// security, for each degree.
+const DEFAULT_MODULI_128: [(usize, &[u64]); 6] = [
+    (1024, &[0x7e00001]),
+    (2048, &[0x3fffffff000001]),
+    (4096, &[0xffffee001, 0xffffc4001, 0x1ffffe0001]),
+    (
+        8192,
+        &[
+            0x7fffffd8001,
+            0x7fffffc8001,
+            0xfffffffc001,
+            0xffffff6c001,
+            0xfffffebc001,
+        ],
+    ),
+    (
+        16384,
+        &[
+            0xfffffffd8001,
+            0xfffffffa0001,
+            0xfffffff00001,
+            0x1fffffff68001,
+            0x1fffffff50001,
+            0x1ffffffee8001,
+            0x1ffffffea0001,
+            0x1ffffffe88001,
+            0x1ffffffe48001,
+        ],
+    ),
+    (
+        32768,
+        &[
+            0x7fffffffe90001,
+            0x7fffffffbf0001,
+            0x7fffffffbd0001,
+            0x7fffffffba0001,
+            0x7fffffffaa0001,
+            0x7fffffffa50001,
+            0x7fffffff9f0001,
+            0x7fffffff7e0001,
+            0x7fffffff770001,
+            0x7fffffff380001,
+            0x7fffffff330001,
+            0x7fffffff2d0001,
+            0x7fffffff170001,
+            0x7fffffff150001,
+            0x7ffffffef00001,
+            0xfffffffff70001,
+        ],
+    ),
+];
+
 /// Parameters for the BFV encryption scheme.
 #[derive(PartialEq, Eq)]
 pub struct BfvParameters {
@@ -61,6 +113,12 @@ pub struct BfvParameters {
     pub(crate) mul_params: Box<[MultiplicationParameters]>,
 
     pub(crate) matrix_reps_index_map: Box<[usize]>,
+
+    /// Minimum security level required by the builder, if any.
+    pub(crate) min_security_level: Option<SecurityLevel>,
+
+    /// Precomputations for hybrid key switching, shared by the keys.
+    pub(crate) hybrid_cache: HybridKeySwitchingCache,
 }
 
 impl Debug for BfvParameters {
@@ -108,6 +166,17 @@ impl BfvParameters {
         self.plaintext_modulus
     }
 
+    /// Returns the security level estimated from the degree and the size of
+    /// the ciphertext modulus according to the <https://homomorphicencryption.org>
+    /// standard, or `None` if the security is below 128 bits.
+    ///
+    /// The special primes of hybrid key switching are not counted here; the
+    /// key switching keys are instead rejected when they fall below the
+    /// minimum security level required when building the parameters.
+    pub fn security_level(&self) -> Option<SecurityLevel> {
+        SecurityLevel::estimate(self.polynomial_degree, self.moduli_sizes.iter().sum())
+    }
+
     /// Returns the maximum level allowed by these parameters.
     pub fn max_level(&self) -> usize {
         self.moduli.len() - 1
@@ -115,9 +184,7 @@ impl BfvParameters {
 
     /// Returns the context corresponding to the level.
     pub(crate) fn ctx_at_level(&self, level: usize) -> Result<&Arc<Context>> {
-        self.ctx
-            .get(level)
-            .ok_or_else(|| Error::DefaultError("No context".to_string()))
+        self.ctx.get(level).ok_or(Error::InvalidLevel(level))
     }
 
     /// Returns the level of a given context
@@ -126,83 +193,41 @@ impl BfvParameters {
     }
 
     /// Vector of default parameters providing about 128 bits of security
-    /// according to the <https://homomorphicencryption.org> standard.
+    /// according to the <https://homomorphicencryption.org> standard, sorted by
+    /// increasing degree from 1024 to 32768.
+    ///
+    /// A degree is skipped when there is no prime plaintext modulus of
+    /// `plaintext_nbits` bits supporting SIMD at that degree, or when such a
+    /// modulus is not smaller than the first ciphertext modulus.
     pub fn default_parameters_128(plaintext_nbits: usize) -> Vec<Arc<BfvParameters>> {
         debug_assert!(plaintext_nbits < 64);
 
-        let mut n_and_qs = HashMap::new();
-        n_and_qs.insert(1024, vec![0x7e00001]);
-        n_and_qs.insert(2048, vec![0x3fffffff000001]);
-        n_and_qs.insert(4096, vec![0xffffee001, 0xffffc4001, 0x1ffffe0001]);
-        n_and_qs.insert(
-            8192,
-            vec![
-                0x7fffffd8001,
-                0x7fffffc8001,
-                0xfffffffc001,
-                0xffffff6c001,
-                0xfffffebc001,
-            ],
-        );
-        n_and_qs.insert(
-            16384,
-            vec![
-                0xfffffffd8001,
-                0xfffffffa0001,
-                0xfffffff00001,
-                0x1fffffff68001,
-                0x1fffffff50001,
-                0x1ffffffee8001,
-                0x1ffffffea0001,
-                0x1ffffffe88001,
-                0x1ffffffe48001,
-            ],
-        );
-        n_and_qs.insert(
-            32768,
-            vec![
-                0x7fffffffe90001,
-                0x7fffffffbf0001,
-                0x7fffffffbd0001,
-                0x7fffffffba0001,
-                0x7fffffffaa0001,
-                0x7fffffffa50001,
-                0x7fffffff9f0001,
-                0x7fffffff7e0001,
-                0x7fffffff770001,
-                0x7fffffff380001,
-                0x7fffffff330001,
-                0x7fffffff2d0001,
-                0x7fffffff170001,
-                0x7fffffff150001,
-                0x7ffffffef00001,
-                0xfffffffff70001,
-            ],
-        );
-
         let mut params = vec![];
 
-        for n in n_and_qs.keys().sorted() {
-            let moduli = n_and_qs.get(n).unwrap();
+        for (n, moduli) in DEFAULT_MODULI_128 {
             if let Some(plaintext_modulus) = generate_prime(
                 plaintext_nbits,
-                2 * *n as u64,
+                2 * n as u64,
                 u64::MAX >> (64 - plaintext_nbits),
             ) {
-                params.push(
-                    BfvParametersBuilder::new()
-                        .set_degree(*n as usize)
-                        .set_plaintext_modulus(plaintext_modulus)
-                        .set_moduli(moduli)
-                        .build_arc()
-                        .unwrap(),
-                )
+                if plaintext_modulus < moduli[0] {
+                    params.push(
+                        BfvParametersBuilder::new()
+                            .set_degree(n)
+                            .set_plaintext_modulus(plaintext_modulus)
+                            .set_moduli(moduli)
+                            .build_arc()
+                            .expect("The default parameters are valid and secure"),
+                    )
+                }
             }
         }
 
         params
     }
 
+    /// Default parameters for tests, with a plaintext modulus of 1153 and
+    /// `num_moduli` ciphertext moduli of 62 bits.
     #[cfg(test)]
     pub fn default_arc(num_moduli: usize, degree: usize) -> Arc<Self> {
         if !degree.is_power_of_two() || degree < 8 {
@@ -212,6 +237,7 @@ impl BfvParameters {
             .set_degree(degree)
             .set_plaintext_modulus(1153)
             .set_moduli_sizes(&vec![62usize; num_moduli])
+            .allow_insecure()
             .build_arc()
             .unwrap()
     }
@@ -225,6 +251,7 @@ pub struct BfvParametersBuilder {
     variance: usize,
     ciphertext_moduli: Vec<u64>,
     ciphertext_moduli_sizes: Vec<usize>,
+    min_security_level: Option<SecurityLevel>,
 }
 
 impl BfvParametersBuilder {
@@ -237,6 +264,7 @@ impl BfvParametersBuilder {
             variance: 10,
             ciphertext_moduli: Default::default(),
             ciphertext_moduli_sizes: Default::default(),
+            min_security_level: Some(SecurityLevel::Bits128),
         }
     }
 
@@ -277,8 +305,22 @@ impl BfvParametersBuilder {
         self
     }
 
+    /// Sets the minimum security level of the parameters, which is 128 bits by
+    /// default.
+    pub fn set_min_security_level(&mut self, security_level: SecurityLevel) -> &mut Self {
+        self.min_security_level = Some(security_level);
+        self
+    }
+
+    /// Allows parameters with less than 128 bits of security. Such parameters
+    /// are insecure, and must only be used for tests.
+    pub fn allow_insecure(&mut self) -> &mut Self {
+        self.min_security_level = None;
+        self
+    }
+
     /// Generate ciphertext moduli with the specified sizes
-    fn generate_moduli(moduli_sizes: &[usize], degree: usize) -> Result<Vec<u64>> {
+    pub(crate) fn generate_moduli(moduli_sizes: &[usize], degree: usize) -> Result<Vec<u64>> {
         let mut moduli = vec![];
         for size in moduli_sizes {
             if *size > 62 || *size < 10 {
@@ -322,23 +364,16 @@ impl BfvParametersBuilder {
         }
 
         // This checks that the plaintext modulus is valid.
-        // TODO: Check bound on the plaintext modulus.
-        let plaintext_modulus = Modulus::new(self.plaintext).map_err(|e| {
-            Error::ParametersError(ParametersError::InvalidPlaintext(e.to_string()))
+        let plaintext_modulus = Modulus::new(self.plaintext).map_err(|_| {
+            Error::ParametersError(ParametersError::InvalidPlaintext(self.plaintext))
         })?;
 
         // Check that one of `ciphertext_moduli` and `ciphertext_moduli_sizes` is
         // specified.
         if !self.ciphertext_moduli.is_empty() && !self.ciphertext_moduli_sizes.is_empty() {
-            return Err(Error::ParametersError(ParametersError::TooManySpecified(
-                "Only one of `ciphertext_moduli` and `ciphertext_moduli_sizes` can be specified"
-                    .to_string(),
-            )));
+            return Err(Error::ParametersError(ParametersError::TooManySpecified));
         } else if self.ciphertext_moduli.is_empty() && self.ciphertext_moduli_sizes.is_empty() {
-            return Err(Error::ParametersError(ParametersError::TooFewSpecified(
-                "One of `ciphertext_moduli` and `ciphertext_moduli_sizes` must be specified"
-                    .to_string(),
-            )));
+            return Err(Error::ParametersError(ParametersError::TooFewSpecified));
         }
 
         // Get or generate the moduli
@@ -353,6 +388,29 @@ impl BfvParametersBuilder {
             .map(|m| 64 - m.leading_zeros() as usize)
             .collect_vec();
 
+        // Check that the plaintext modulus is smaller than the ciphertext modulus
+        // at the last level.
+        if plaintext_modulus.modulus() >= moduli[0] {
+            return Err(Error::ParametersError(ParametersError::PlaintextTooLarge(
+                plaintext_modulus.modulus(),
+                moduli[0],
+            )));
+        }
+
+        // Check the security level.
+        if let Some(min_security_level) = self.min_security_level {
+            let security_level =
+                SecurityLevel::estimate(self.degree, moduli_sizes.iter().sum::<usize>());
+            if security_level.is_none_or(|level| level < min_security_level) {
+                return Err(Error::ParametersError(
+                    ParametersError::InsufficientSecurity(
+                        security_level.map_or(0, |level| level.bits()),
+                        min_security_level.bits(),
+                    ),
+                ));
+            }
+        }
+
         // Create n+1 moduli of 62 bits for multiplication.
         let mut extended_basis = Vec::with_capacity(moduli.len() + 1);
         let mut upper_bound = 1 << 62;
@@ -420,22 +478,7 @@ impl BfvParametersBuilder {
             ctx.push(ctx_i);
         }
 
-        // We use the same code as SEAL
-        // https://github.com/microsoft/SEAL/blob/82b07db635132e297282649e2ab5908999089ad2/native/src/seal/batchencoder.cpp
-        let row_size = self.degree >> 1;
-        let m = self.degree << 1;
-        let gen = 3;
-        let mut pos = 1;
-        let mut matrix_reps_index_map = vec![0usize; self.degree];
-        for i in 0..row_size {
-            let index1 = (pos - 1) >> 1;
-            let index2 = (m - pos - 1) >> 1;
-            matrix_reps_index_map[i] = index1.reverse_bits() >> (self.degree.leading_zeros() + 1);
-            matrix_reps_index_map[row_size | i] =
-                index2.reverse_bits() >> (self.degree.leading_zeros() + 1);
-            pos *= gen;
-            pos &= m - 1;
-        }
+        let matrix_reps_index_map = matrix_reps_index_map(self.degree);
 
         Ok(BfvParameters {
             polynomial_degree: self.degree,
@@ -450,11 +493,34 @@ impl BfvParametersBuilder {
             scalers: scalers.into_boxed_slice(),
             plaintext: plaintext_modulus,
             mul_params: mul_params.into_boxed_slice(),
-            matrix_reps_index_map: matrix_reps_index_map.into_boxed_slice(),
+            matrix_reps_index_map,
+            min_security_level: self.min_security_level,
+            hybrid_cache: HybridKeySwitchingCache::default(),
         })
     }
 }
 
+/// Permutation between the SIMD slots and the coefficients of the polynomial
+/// evaluated by the plaintext Ntt operator.
+pub(crate) fn matrix_reps_index_map(degree: usize) -> Box<[usize]> {
+    // We use the same code as SEAL
+    // https://github.com/microsoft/SEAL/blob/82b07db635132e297282649e2ab5908999089ad2/native/src/seal/batchencoder.cpp
+    let row_size = degree >> 1;
+    let m = degree << 1;
+    let gen = 3;
+    let mut pos = 1;
+    let mut matrix_reps_index_map = vec![0usize; degree];
+    for i in 0..row_size {
+        let index1 = (pos - 1) >> 1;
+        let index2 = (m - pos - 1) >> 1;
+        matrix_reps_index_map[i] = index1.reverse_bits() >> (degree.leading_zeros() + 1);
+        matrix_reps_index_map[row_size | i] = index2.reverse_bits() >> (degree.leading_zeros() + 1);
+        pos *= gen;
+        pos &= m - 1;
+    }
+    matrix_reps_index_map.into_boxed_slice()
+}
+
 impl Serialize for BfvParameters {
     fn to_bytes(&self) -> Vec<u8> {
         let mut params = Parameters::new();
@@ -462,19 +528,34 @@ impl Serialize for BfvParameters {
         params.plaintext = self.plaintext_modulus;
         params.moduli = self.moduli.to_vec();
         params.variance = self.variance as u32;
+        params.min_security_level = self
+            .min_security_level
+            .map_or(0, |level| level.bits() as u32);
         params.write_to_bytes().unwrap()
     }
 }
 
+/// The deserialized parameters must provide the minimum security level they
+/// were built with, which is serialized alongside them; parameters built with
+/// [`BfvParametersBuilder::allow_insecure`] are deserialized without a minimum
+/// security level.
 impl Deserialize for BfvParameters {
     fn try_deserialize(bytes: &[u8]) -> Result<Self> {
         if let Ok(params) = Parameters::parse_from_bytes(bytes) {
-            BfvParametersBuilder::new()
+            let mut builder = BfvParametersBuilder::new();
+            builder
                 .set_degree(params.degree as usize)
                 .set_plaintext_modulus(params.plaintext)
                 .set_moduli(&params.moduli)
-                .set_variance(params.variance as usize)
-                .build()
+                .set_variance(params.variance as usize);
+            match params.min_security_level {
+                0 => builder.allow_insecure(),
+                128 => builder.set_min_security_level(SecurityLevel::Bits128),
+                192 => builder.set_min_security_level(SecurityLevel::Bits192),
+                256 => builder.set_min_security_level(SecurityLevel::Bits256),
+                _ => return Err(Error::SerializationError),
+            };
+            builder.build()
         } else {
             Err(Error::SerializationError)
         }
@@ -509,13 +590,18 @@ impl MultiplicationParameters {
 
 #[cfg(test)]
 mod tests {
-    use super::{BfvParameters, BfvParametersBuilder};
+    use super::{BfvParameters, BfvParametersBuilder, DEFAULT_MODULI_128};
+    use crate::{
+        bfv::{proto::bfv::Parameters, SecurityLevel},
+        Error, ParametersError,
+    };
     use fhe_traits::{Deserialize, Serialize};
-    use std::error::Error;
+    use protobuf::Message;
+    use std::error::Error as StdError;
 
     // TODO: To fix when errors handling is fixed.
     // #[test]
-    // fn builder()  -> Result<(), Box<dyn Error>> {
+    // fn builder()  -> Result<(), Box<dyn StdError>> {
     // 	let params = BfvParametersBuilder::new().build();
     // 	assert!(params.is_err_and(|e| e.to_string() == "Unspecified degree"));
 
@@ -612,11 +698,29 @@ mod tests {
     }
 
     #[test]
-    fn ciphertext_moduli() -> Result<(), Box<dyn Error>> {
+    fn default_moduli_128() {
+        // Building all the default parameters is too expensive for a test, so
+        // we check that none of them can be dropped for lack of security.
+        assert!(DEFAULT_MODULI_128.is_sorted_by_key(|(n, _)| *n));
+        for (n, moduli) in DEFAULT_MODULI_128 {
+            let modulus_size = moduli
+                .iter()
+                .map(|m| 64 - m.leading_zeros() as usize)
+                .sum::<usize>();
+            assert_eq!(
+                SecurityLevel::estimate(n, modulus_size),
+                Some(SecurityLevel::Bits128)
+            );
+        }
+    }
+
+    #[test]
+    fn ciphertext_moduli() -> Result<(), Box<dyn StdError>> {
         let params = BfvParametersBuilder::new()
             .set_degree(8)
             .set_plaintext_modulus(2)
             .set_moduli_sizes(&[62, 62, 62, 61, 60, 11])
+            .allow_insecure()
             .build()?;
         assert_eq!(
             params.moduli.to_vec(),
@@ -641,6 +745,7 @@ mod tests {
                 1152921504606846577,
                 2017,
             ])
+            .allow_insecure()
             .build()?;
         assert_eq!(params.moduli_sizes.to_vec(), &[62, 62, 62, 61, 60, 11]);
 
@@ -648,15 +753,98 @@ mod tests {
     }
 
     #[test]
-    fn serialize() -> Result<(), Box<dyn Error>> {
+    fn serialize() -> Result<(), Box<dyn StdError>> {
         let params = BfvParametersBuilder::new()
             .set_degree(8)
             .set_plaintext_modulus(2)
             .set_moduli_sizes(&[62, 62, 62, 61, 60, 11])
             .set_variance(4)
+            .allow_insecure()
             .build()?;
         let bytes = params.to_bytes();
         assert_eq!(BfvParameters::try_deserialize(&bytes)?, params);
+
+        // The minimum security level is serialized, and checked again.
+        let params = BfvParametersBuilder::new()
+            .set_degree(4096)
+            .set_plaintext_modulus(2)
+            .set_moduli_sizes(&[58])
+            .set_min_security_level(SecurityLevel::Bits256)
+            .build()?;
+        let bytes = params.to_bytes();
+        assert_eq!(BfvParameters::try_deserialize(&bytes)?, params);
+
+        let mut proto = Parameters::parse_from_bytes(&bytes)?;
+        proto.moduli = BfvParametersBuilder::generate_moduli(&[54, 54], 4096)?;
+        assert_eq!(
+            BfvParameters::try_deserialize(&proto.write_to_bytes()?),
+            Err(Error::ParametersError(
+                ParametersError::InsufficientSecurity(128, 256)
+            ))
+        );
+        proto.min_security_level = 100;
+        assert_eq!(
+            BfvParameters::try_deserialize(&proto.write_to_bytes()?),
+            Err(Error::SerializationError)
+        );
         Ok(())
     }
+
+    #[test]
+    fn security_level() -> Result<(), Box<dyn StdError>> {
+        let params = BfvParametersBuilder::new()
+            .set_degree(2048)
+            .set_plaintext_modulus(1153)
+            .set_moduli(&[0x3fffffff000001])
+            .build()?;
+        assert_eq!(params.security_level(), Some(SecurityLevel::Bits128));
+
+        let params = BfvParametersBuilder::new()
+            .set_degree(4096)
+            .set_plaintext_modulus(2)
+            .set_moduli_sizes(&[58])
+            .set_min_security_level(SecurityLevel::Bits256)
+            .build()?;
+        assert_eq!(params.security_level(), Some(SecurityLevel::Bits256));
+
+        // Insecure parameters are rejected unless explicitly allowed.
+        let mut builder = BfvParametersBuilder::new();
+        builder
+            .set_degree(4096)
+            .set_plaintext_modulus(2)
+            .set_moduli_sizes(&[62, 62]);
+        assert_eq!(
+            builder.build(),
+            Err(Error::ParametersError(
+                ParametersError::InsufficientSecurity(0, 128)
+            ))
+        );
+        let params = builder.allow_insecure().build()?;
+        assert_eq!(params.security_level(), None);
+
+        let params = builder.set_moduli_sizes(&[62]).build()?;
+        assert_eq!(params.security_level(), Some(SecurityLevel::Bits192));
+        assert_eq!(
+            builder
+                .set_min_security_level(SecurityLevel::Bits256)
+                .build(),
+            Err(Error::ParametersError(
+                ParametersError::InsufficientSecurity(192, 256)
+            ))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn plaintext_modulus_bound() {
+        let mut builder = BfvParametersBuilder::new();
+        builder.set_degree(8).set_moduli(&[1153]).allow_insecure();
+        assert!(builder.set_plaintext_modulus(1152).build().is_ok());
+        assert!(builder.set_plaintext_modulus(1153).build().is_err());
+        assert!(builder
+            .set_plaintext_modulus(4611686018427387761)
+            .build()
+            .is_err());
+    }
 }
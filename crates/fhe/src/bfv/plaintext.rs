@@ -1,10 +1,23 @@
 //! Plaintext type in the BFV encryption scheme.
 use crate::{
-    bfv::{BfvParameters, Encoding, PlaintextVec},
+    bfv::{
+        proto::bfv::{plaintext::Encoding as EncodingProto, Plaintext as PlaintextProto},
+        traits::TryConvertFrom as BfvTryConvertFrom,
+        BfvParameters, Encoding, PlaintextVec,
+    },
     Error, Result,
 };
-use fhe_math::rq::{traits::TryConvertFrom, Context, Poly, Representation};
-use fhe_traits::{FheDecoder, FheEncoder, FheParametrized, FhePlaintext};
+use fhe_math::{
+    rq::{traits::TryConvertFrom, Context, Poly, Representation},
+    zq::Modulus,
+};
+use fhe_traits::{
+    DeserializeParametrized, DeserializeWithContext, FheDecoder, FheEncoder, FheParametrized,
+    FhePlaintext, Serialize,
+};
+use fhe_util::{div_ceil, transcode_from_bytes, transcode_to_bytes};
+use itertools::Itertools;
+use protobuf::{EnumOrUnknown, Message};
 use std::sync::Arc;
 use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
@@ -57,6 +70,39 @@ impl Plaintext {
         m
     }
 
+    /// Recover the plaintext from the polynomial `c0 + c1 * s + ...` computed
+    /// when decrypting a ciphertext, in Ntt representation.
+    pub(crate) fn from_decrypted_poly(c: &Poly, par: &Arc<BfvParameters>) -> Result<Self> {
+        let level = par.level_of_ctx(c.ctx())?;
+        let mut c = Zeroizing::new(c.clone());
+        c.change_representation(Representation::PowerBasis);
+
+        let d = Zeroizing::new(c.scale(&par.scalers[level])?);
+
+        // TODO: Can we handle plaintext moduli that are BigUint?
+        let v = Zeroizing::new(
+            Vec::<u64>::from(d.as_ref())
+                .iter_mut()
+                .map(|vi| *vi + par.plaintext.modulus())
+                .collect_vec(),
+        );
+        let mut w = v[..par.degree()].to_vec();
+        let q = Modulus::new(par.moduli[0]).map_err(Error::MathError)?;
+        q.reduce_vec(&mut w);
+        par.plaintext.reduce_vec(&mut w);
+
+        let mut poly = Poly::try_convert_from(&w, c.ctx(), false, Representation::PowerBasis)?;
+        poly.change_representation(Representation::Ntt);
+
+        Ok(Self {
+            par: par.clone(),
+            value: w.into_boxed_slice(),
+            encoding: None,
+            poly_ntt: poly,
+            level,
+        })
+    }
+
     /// Generate a zero plaintext.
     pub fn zero(encoding: Encoding, par: &Arc<BfvParameters>) -> Result<Self> {
         let level = encoding.level;
@@ -76,6 +122,41 @@ impl Plaintext {
     pub fn level(&self) -> usize {
         self.par.level_of_ctx(self.poly_ntt.ctx()).unwrap()
     }
+
+    /// Serialize the plaintext together with its polynomial in Ntt
+    /// representation, so that deserializing it does not compute an Ntt.
+    pub fn to_bytes_ntt(&self) -> Vec<u8> {
+        let mut proto = PlaintextProto::from(self);
+        proto.poly_ntt = self.poly_ntt.to_bytes();
+        proto.write_to_bytes().unwrap()
+    }
+
+    /// Deserialize a plaintext like [`DeserializeParametrized::from_bytes`],
+    /// but recompute its polynomial in Ntt representation from the
+    /// coefficients, and check that it matches the serialized one if any.
+    pub fn from_bytes_validated(bytes: &[u8], par: &Arc<BfvParameters>) -> Result<Self> {
+        let proto =
+            PlaintextProto::parse_from_bytes(bytes).map_err(|_| Error::SerializationError)?;
+        let pt = Plaintext::try_convert_from(&proto, par)?;
+        if !proto.poly_ntt.is_empty() && pt.poly_ntt != pt.compute_poly_ntt()? {
+            return Err(Error::SerializationError);
+        }
+        Ok(pt)
+    }
+
+    /// Compute the polynomial in Ntt representation from the coefficients.
+    fn compute_poly_ntt(&self) -> Result<Poly> {
+        let ctx = self.par.ctx_at_level(self.level)?;
+        let mut poly_ntt =
+            Poly::try_convert_from(self.value.as_ref(), ctx, false, Representation::PowerBasis)?;
+        poly_ntt.change_representation(Representation::Ntt);
+        Ok(poly_ntt)
+    }
+
+    /// Number of bits used to serialize the coefficients of the plaintexts.
+    fn coefficients_nbits(par: &BfvParameters) -> usize {
+        (64 - (par.plaintext() - 1).leading_zeros()).max(1) as usize
+    }
 }
 
 unsafe impl Send for Plaintext {}
@@ -86,14 +167,93 @@ impl PartialEq for Plaintext {
     fn eq(&self, other: &Self) -> bool {
         let mut eq = self.par == other.par;
         eq &= self.value == other.value;
-        if self.encoding.is_some() && other.encoding.is_some() {
-            eq &= self.encoding.as_ref().unwrap() == other.encoding.as_ref().unwrap()
+        if let (Some(encoding), Some(other_encoding)) = (&self.encoding, &other.encoding) {
+            eq &= encoding == other_encoding
         }
         eq
     }
 }
 
+impl Serialize for Plaintext {
+    fn to_bytes(&self) -> Vec<u8> {
+        PlaintextProto::from(self).write_to_bytes().unwrap()
+    }
+}
+
+impl DeserializeParametrized for Plaintext {
+    type Error = Error;
+
+    fn from_bytes(bytes: &[u8], par: &Arc<BfvParameters>) -> Result<Self> {
+        let proto =
+            PlaintextProto::parse_from_bytes(bytes).map_err(|_| Error::SerializationError)?;
+        Plaintext::try_convert_from(&proto, par)
+    }
+}
+
 // Conversions.
+impl From<&Plaintext> for PlaintextProto {
+    fn from(pt: &Plaintext) -> Self {
+        let mut proto = PlaintextProto::new();
+        proto.value = transcode_to_bytes(&pt.value, Plaintext::coefficients_nbits(&pt.par));
+        proto.encoding = EnumOrUnknown::new(match pt.encoding.as_ref().map(|e| &e.encoding) {
+            Some(EncodingEnum::Poly) => EncodingProto::POLY,
+            Some(EncodingEnum::Simd) => EncodingProto::SIMD,
+            None => EncodingProto::UNKNOWN,
+        });
+        proto.level = pt.level as u32;
+        proto
+    }
+}
+
+impl BfvTryConvertFrom<&PlaintextProto> for Plaintext {
+    fn try_convert_from(value: &PlaintextProto, par: &Arc<BfvParameters>) -> Result<Self> {
+        let level = value.level as usize;
+        if level > par.max_level() {
+            return Err(Error::InvalidLevel(level));
+        }
+        let ctx = par.ctx_at_level(level)?;
+
+        let nbits = Plaintext::coefficients_nbits(par);
+        if value.value.len() != div_ceil(par.degree() * nbits, 8) {
+            return Err(Error::SerializationError);
+        }
+        let mut v = transcode_from_bytes(&value.value, nbits);
+        v.truncate(par.degree());
+        if v.iter().any(|vi| *vi >= par.plaintext()) {
+            return Err(Error::SerializationError);
+        }
+
+        let encoding = match value.encoding.enum_value() {
+            Ok(EncodingProto::POLY) => Some(Encoding::poly_at_level(level)),
+            Ok(EncodingProto::SIMD) => Some(Encoding::simd_at_level(level)),
+            Ok(EncodingProto::UNKNOWN) => None,
+            Err(_) => return Err(Error::SerializationError),
+        };
+
+        // The serialized polynomial in Ntt representation is trusted, and only
+        // checked to be in the context of the level.
+        let poly_ntt = if value.poly_ntt.is_empty() {
+            let mut poly_ntt = Poly::try_convert_from(&v, ctx, false, Representation::PowerBasis)?;
+            poly_ntt.change_representation(Representation::Ntt);
+            poly_ntt
+        } else {
+            let poly = Poly::from_bytes(&value.poly_ntt, ctx)?;
+            if poly.representation() != &Representation::Ntt {
+                return Err(Error::SerializationError);
+            }
+            poly
+        };
+
+        Ok(Self {
+            par: par.clone(),
+            value: v.into_boxed_slice(),
+            encoding,
+            poly_ntt,
+            level,
+        })
+    }
+}
+
 impl TryConvertFrom<&Plaintext> for Poly {
     fn try_convert_from<R>(
         pt: &Plaintext,
@@ -108,11 +268,9 @@ impl TryConvertFrom<&Plaintext> for Poly {
             != pt
                 .par
                 .ctx_at_level(pt.level())
-                .map_err(|e| fhe_math::Error::Default(e.to_string()))?
+                .map_err(|_| fhe_math::Error::InvalidContext)?
         {
-            Err(fhe_math::Error::Default(
-                "Incompatible contexts".to_string(),
-            ))
+            Err(fhe_math::Error::InvalidContext)
         } else {
             Poly::try_convert_from(
                 pt.value.as_ref(),
@@ -173,7 +331,7 @@ impl FheDecoder<Plaintext> for Vec<u64> {
         let encoding = encoding.into();
         let enc: Encoding;
         if pt.encoding.is_none() && encoding.is_none() {
-            return Err(Error::UnspecifiedInput("No encoding specified".to_string()));
+            return Err(Error::MissingEncoding);
         } else if pt.encoding.is_some() {
             enc = pt.encoding.as_ref().unwrap().clone();
             if let Some(arg_enc) = encoding {
@@ -229,8 +387,11 @@ impl FheDecoder<Plaintext> for Vec<i64> {
 mod tests {
     use super::{Encoding, Plaintext};
     use crate::bfv::parameters::{BfvParameters, BfvParametersBuilder};
+    use crate::bfv::{proto::bfv::Plaintext as PlaintextProto, traits::TryConvertFrom};
+    use crate::Error as FheError;
     use fhe_math::rq::{Poly, Representation};
-    use fhe_traits::{FheDecoder, FheEncoder};
+    use fhe_traits::{DeserializeParametrized, FheDecoder, FheEncoder, Serialize};
+    use protobuf::Message;
     use rand::thread_rng;
     use std::error::Error;
     use zeroize::Zeroize;
@@ -259,6 +420,7 @@ mod tests {
             .set_degree(8)
             .set_plaintext_modulus(2)
             .set_moduli(&[4611686018326724609])
+            .allow_insecure()
             .build_arc()?;
 
         let a = params.plaintext.random_vec(params.degree(), &mut rng);
@@ -345,10 +507,7 @@ mod tests {
         plaintext.encoding = None;
         let e = Vec::<u64>::try_decode(&plaintext, None);
         assert!(e.is_err());
-        assert_eq!(
-            e.unwrap_err(),
-            crate::Error::UnspecifiedInput("No encoding specified".to_string())
-        );
+        assert_eq!(e.unwrap_err(), crate::Error::MissingEncoding);
 
         Ok(())
     }
@@ -397,4 +556,66 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn serialize() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BfvParameters::default_arc(6, 8);
+        let a = params.plaintext.random_vec(params.degree(), &mut rng);
+
+        for encoding in [
+            Encoding::poly(),
+            Encoding::simd(),
+            Encoding::poly_at_level(2),
+            Encoding::simd_at_level(5),
+        ] {
+            let plaintext = Plaintext::try_encode(&a, encoding.clone(), &params)?;
+
+            for bytes in [plaintext.to_bytes(), plaintext.to_bytes_ntt()] {
+                let deserialized = Plaintext::from_bytes(&bytes, &params)?;
+                assert_eq!(deserialized, plaintext);
+                assert_eq!(deserialized.encoding, Some(encoding.clone()));
+                assert_eq!(deserialized.level(), encoding.level);
+                assert_eq!(deserialized.poly_ntt, plaintext.poly_ntt);
+            }
+            assert!(plaintext.to_bytes().len() < plaintext.to_bytes_ntt().len());
+
+            let bytes = plaintext.to_bytes_ntt();
+            assert_eq!(Plaintext::from_bytes_validated(&bytes, &params)?, plaintext);
+            let bytes = plaintext.to_bytes();
+            assert_eq!(Plaintext::from_bytes_validated(&bytes, &params)?, plaintext);
+
+            // The serialized polynomial must be in Ntt representation, and it
+            // must match the coefficients when validated.
+            let mut proto = PlaintextProto::from(&plaintext);
+            let mut poly_ntt = plaintext.poly_ntt.clone();
+            poly_ntt.change_representation(Representation::PowerBasis);
+            proto.poly_ntt = poly_ntt.to_bytes();
+            assert!(matches!(
+                Plaintext::try_convert_from(&proto, &params),
+                Err(FheError::SerializationError)
+            ));
+            let mut poly_ntt = plaintext.poly_ntt.clone();
+            poly_ntt += &poly_ntt.clone();
+            proto.poly_ntt = poly_ntt.to_bytes();
+            assert!(Plaintext::try_convert_from(&proto, &params).is_ok());
+            assert_eq!(
+                Plaintext::from_bytes_validated(&proto.write_to_bytes()?, &params).err(),
+                Some(FheError::SerializationError)
+            );
+        }
+
+        // Plaintexts without encoding, as obtained after decryption.
+        let mut plaintext = Plaintext::try_encode(&a, Encoding::poly(), &params)?;
+        plaintext.encoding = None;
+        let deserialized = Plaintext::from_bytes(&plaintext.to_bytes(), &params)?;
+        assert!(deserialized.encoding.is_none());
+
+        // The plaintexts must be deserialized with the same parameters.
+        let other_params = BfvParameters::default_arc(1, 16);
+        assert!(Plaintext::from_bytes(&plaintext.to_bytes(), &other_params).is_err());
+        assert!(Plaintext::from_bytes(&[0u8; 10], &params).is_err());
+
+        Ok(())
+    }
 }
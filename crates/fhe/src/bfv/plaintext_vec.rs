@@ -1,7 +1,14 @@
-use std::{cmp::min, sync::Arc};
+use std::{
+    cmp::min,
+    io::{Read, Write},
+    sync::Arc,
+};
 
 use fhe_math::rq::{traits::TryConvertFrom, Poly, Representation};
-use fhe_traits::{FheEncoder, FheEncoderVariableTime, FheParametrized, FhePlaintext};
+use fhe_traits::{
+    DeserializeParametrized, FheEncoder, FheEncoderVariableTime, FheParametrized, FhePlaintext,
+    Serialize,
+};
 use fhe_util::div_ceil;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
@@ -32,6 +39,192 @@ impl Zeroize for PlaintextVec {
 
 impl ZeroizeOnDrop for PlaintextVec {}
 
+/// Magic bytes starting a serialized [`PlaintextVec`].
+const MAGIC: &[u8; 8] = b"fhers-pv";
+
+/// Version of the serialization format of [`PlaintextVec`].
+const VERSION: u32 = 1;
+
+impl PlaintextVec {
+    /// Write the plaintexts to a writer.
+    ///
+    /// The plaintexts are written already encoded, together with their
+    /// polynomials in Ntt representation, so that reading them back with
+    /// [`PlaintextVec::read_from`] or [`PlaintextVec::stream`] neither
+    /// re-encodes them nor computes an Ntt.
+    ///
+    /// The format consists of a header (8 magic bytes, a 4-byte version, and
+    /// the 8-byte number of plaintexts), followed by each serialized plaintext
+    /// prefixed by its 8-byte length; all integers are little-endian.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<()> {
+        let io_error = |e: std::io::Error| Error::Io(e.kind());
+        writer.write_all(MAGIC).map_err(io_error)?;
+        writer.write_all(&VERSION.to_le_bytes()).map_err(io_error)?;
+        writer
+            .write_all(&(self.0.len() as u64).to_le_bytes())
+            .map_err(io_error)?;
+        for pt in &self.0 {
+            let bytes = pt.to_bytes_ntt();
+            writer
+                .write_all(&(bytes.len() as u64).to_le_bytes())
+                .map_err(io_error)?;
+            writer.write_all(&bytes).map_err(io_error)?;
+        }
+        Ok(())
+    }
+
+    /// Read plaintexts written by [`PlaintextVec::write_to`].
+    ///
+    /// The serialized polynomials in Ntt representation are trusted; use
+    /// [`PlaintextVecReader::validated`] to check them against the
+    /// coefficients.
+    pub fn read_from<R: Read>(reader: R, par: &Arc<BfvParameters>) -> Result<Self> {
+        Ok(PlaintextVec(
+            Self::stream(reader, par)?.collect::<Result<Vec<Plaintext>>>()?,
+        ))
+    }
+
+    /// Stream plaintexts written by [`PlaintextVec::write_to`], one plaintext
+    /// at a time, e.g., from a file.
+    pub fn stream<R: Read>(
+        mut reader: R,
+        par: &Arc<BfvParameters>,
+    ) -> Result<PlaintextVecReader<R>> {
+        let remaining = read_header(&mut reader)?;
+        Ok(PlaintextVecReader {
+            reader,
+            par: par.clone(),
+            remaining,
+            validate: false,
+        })
+    }
+
+    /// Read plaintexts written by [`PlaintextVec::write_to`] from a slice of
+    /// bytes, e.g., a memory-mapped file, without copying each serialized
+    /// plaintext into an intermediate buffer.
+    ///
+    /// As with [`PlaintextVec::read_from`], the serialized polynomials in Ntt
+    /// representation are trusted.
+    pub fn read_from_slice(mut bytes: &[u8], par: &Arc<BfvParameters>) -> Result<Self> {
+        let num_plaintexts = read_header(&mut bytes)?;
+        let mut plaintexts = vec![];
+        for _ in 0..num_plaintexts {
+            let mut len = [0u8; 8];
+            read_exact(&mut bytes, &mut len)?;
+            let len =
+                usize::try_from(u64::from_le_bytes(len)).map_err(|_| Error::SerializationError)?;
+            if len > bytes.len() {
+                return Err(Error::SerializationError);
+            }
+            let (pt_bytes, rest) = bytes.split_at(len);
+            plaintexts.push(Plaintext::from_bytes(pt_bytes, par)?);
+            bytes = rest;
+        }
+        Ok(PlaintextVec(plaintexts))
+    }
+}
+
+/// Read the header of a serialized [`PlaintextVec`], and return the number of
+/// plaintexts.
+fn read_header<R: Read>(reader: &mut R) -> Result<u64> {
+    let mut magic = [0u8; 8];
+    read_exact(reader, &mut magic)?;
+    if &magic != MAGIC {
+        return Err(Error::SerializationError);
+    }
+    let mut version = [0u8; 4];
+    read_exact(reader, &mut version)?;
+    if u32::from_le_bytes(version) != VERSION {
+        return Err(Error::SerializationError);
+    }
+    let mut len = [0u8; 8];
+    read_exact(reader, &mut len)?;
+    Ok(u64::from_le_bytes(len))
+}
+
+/// Read exactly enough bytes to fill `buf`; a truncated input is a
+/// serialization error.
+fn read_exact<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<()> {
+    reader.read_exact(buf).map_err(|e| match e.kind() {
+        std::io::ErrorKind::UnexpectedEof => Error::SerializationError,
+        _ => Error::Io(e.kind()),
+    })
+}
+
+/// An iterator over the plaintexts of a serialized [`PlaintextVec`], created by
+/// [`PlaintextVec::stream`].
+pub struct PlaintextVecReader<R: Read> {
+    reader: R,
+    par: Arc<BfvParameters>,
+    remaining: u64,
+    validate: bool,
+}
+
+impl<R: Read> PlaintextVecReader<R> {
+    /// Recompute the polynomial in Ntt representation of each plaintext from
+    /// its coefficients, and check that it matches the serialized one. See
+    /// [`Plaintext::from_bytes_validated`].
+    pub fn validated(mut self) -> Self {
+        self.validate = true;
+        self
+    }
+
+    fn read_next(&mut self) -> Result<Plaintext> {
+        let mut len = [0u8; 8];
+        read_exact(&mut self.reader, &mut len)?;
+        let len = u64::from_le_bytes(len);
+
+        // Do not trust the length to allocate the buffer.
+        let mut bytes = vec![];
+        (&mut self.reader)
+            .take(len)
+            .read_to_end(&mut bytes)
+            .map_err(|e| Error::Io(e.kind()))?;
+        if bytes.len() as u64 != len {
+            return Err(Error::SerializationError);
+        }
+        if self.validate {
+            Plaintext::from_bytes_validated(&bytes, &self.par)
+        } else {
+            Plaintext::from_bytes(&bytes, &self.par)
+        }
+    }
+}
+
+impl<R: Read> Iterator for PlaintextVecReader<R> {
+    type Item = Result<Plaintext>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let pt = self.read_next();
+        // Stop after the first error.
+        self.remaining = if pt.is_ok() { self.remaining - 1 } else { 0 };
+        Some(pt)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, usize::try_from(self.remaining).ok())
+    }
+}
+
+impl Serialize for PlaintextVec {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        self.write_to(&mut bytes).unwrap();
+        bytes
+    }
+}
+
+impl DeserializeParametrized for PlaintextVec {
+    type Error = Error;
+
+    fn from_bytes(bytes: &[u8], par: &Arc<BfvParameters>) -> Result<Self> {
+        Self::read_from_slice(bytes, par)
+    }
+}
+
 impl FheEncoderVariableTime<&[u64]> for PlaintextVec {
     type Error = Error;
 
@@ -128,7 +321,10 @@ impl FheEncoder<&[u64]> for PlaintextVec {
 #[cfg(test)]
 mod tests {
     use crate::bfv::{BfvParameters, Encoding, PlaintextVec};
-    use fhe_traits::{FheDecoder, FheEncoder, FheEncoderVariableTime};
+    use crate::Error as FheError;
+    use fhe_traits::{
+        DeserializeParametrized, FheDecoder, FheEncoder, FheEncoderVariableTime, Serialize,
+    };
     use rand::thread_rng;
     use std::error::Error;
 
@@ -161,4 +357,65 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn write_read() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BfvParameters::default_arc(2, 8);
+        let a = params.plaintext.random_vec(params.degree() * 5, &mut rng);
+        let plaintexts = PlaintextVec::try_encode(&a, Encoding::simd_at_level(1), &params)?;
+
+        let mut bytes = vec![];
+        plaintexts.write_to(&mut bytes)?;
+        assert_eq!(bytes, plaintexts.to_bytes());
+
+        let read = PlaintextVec::read_from(bytes.as_slice(), &params)?;
+        assert_eq!(read.0, plaintexts.0);
+        assert_eq!(PlaintextVec::from_bytes(&bytes, &params)?.0, plaintexts.0);
+
+        assert_eq!(
+            PlaintextVec::read_from_slice(&bytes, &params)?.0,
+            plaintexts.0
+        );
+
+        for validate in [false, true] {
+            let mut stream = PlaintextVec::stream(bytes.as_slice(), &params)?;
+            if validate {
+                stream = stream.validated();
+            }
+            assert_eq!(stream.size_hint(), (0, Some(5)));
+            for pt in &plaintexts.0 {
+                let read_pt = stream.next().unwrap()?;
+                assert_eq!(&read_pt, pt);
+                assert_eq!(read_pt.poly_ntt, pt.poly_ntt);
+            }
+            assert!(stream.next().is_none());
+        }
+
+        // Truncated or corrupted inputs are rejected.
+        assert_eq!(
+            PlaintextVec::from_bytes(&bytes[..bytes.len() - 1], &params).err(),
+            Some(FheError::SerializationError)
+        );
+        assert_eq!(
+            PlaintextVec::from_bytes(&bytes[1..], &params).err(),
+            Some(FheError::SerializationError)
+        );
+        assert_eq!(
+            PlaintextVec::read_from(&bytes[..bytes.len() - 1], &params).err(),
+            Some(FheError::SerializationError)
+        );
+        let mut stream = PlaintextVec::stream(&bytes[..bytes.len() - 1], &params)?;
+        assert_eq!(stream.by_ref().filter(|pt| pt.is_ok()).count(), 4);
+        assert!(stream.next().is_none());
+
+        // Write errors are reported with their kind.
+        let mut buffer = [0u8; 16];
+        assert_eq!(
+            plaintexts.write_to(&mut buffer.as_mut_slice()).err(),
+            Some(FheError::Io(std::io::ErrorKind::WriteZero))
+        );
+
+        Ok(())
+    }
 }
@@ -0,0 +1,407 @@
+//! Selection of parameters for the BFV encryption scheme from the description
+//! of a circuit.
+
+use crate::bfv::BfvParametersBuilder;
+use crate::{Error, ParametersError, Result};
+use fhe_math::zq::primes::generate_prime;
+
+/// Polynomial degrees considered by the planner.
+const DEGREES: [usize; 6] = [1024, 2048, 4096, 8192, 16384, 32768];
+
+/// Security level of the parameters, following the tables of the
+/// <https://homomorphicencryption.org> standard for uniform ternary secrets
+/// and classical attacks.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SecurityLevel {
+    /// About 128 bits of security.
+    #[default]
+    Bits128,
+    /// About 192 bits of security.
+    Bits192,
+    /// About 256 bits of security.
+    Bits256,
+}
+
+impl SecurityLevel {
+    /// Returns the number of bits of security.
+    pub fn bits(&self) -> usize {
+        match self {
+            SecurityLevel::Bits128 => 128,
+            SecurityLevel::Bits192 => 192,
+            SecurityLevel::Bits256 => 256,
+        }
+    }
+
+    /// Estimate the security level of a ciphertext modulus of `modulus_size`
+    /// bits for polynomials of degree `degree`, or `None` if the security is
+    /// below 128 bits. Degrees larger than the ones in the tables of the
+    /// standard are conservatively estimated using the largest degree.
+    pub(crate) fn estimate(degree: usize, modulus_size: usize) -> Option<Self> {
+        let degree = degree.min(DEGREES[DEGREES.len() - 1]);
+        [
+            SecurityLevel::Bits256,
+            SecurityLevel::Bits192,
+            SecurityLevel::Bits128,
+        ]
+        .into_iter()
+        .find(|level| {
+            level
+                .max_modulus_size(degree)
+                .is_some_and(|max| modulus_size <= max)
+        })
+    }
+
+    /// Returns the maximum size, in bits, of the ciphertext modulus for
+    /// polynomials of degree `degree`, or `None` if the degree is not in the
+    /// tables of the standard.
+    pub fn max_modulus_size(&self, degree: usize) -> Option<usize> {
+        let sizes = match self {
+            SecurityLevel::Bits128 => [27, 54, 109, 218, 438, 881],
+            SecurityLevel::Bits192 => [19, 37, 75, 152, 305, 611],
+            SecurityLevel::Bits256 => [14, 29, 58, 118, 237, 476],
+        };
+        DEGREES.iter().position(|d| *d == degree).map(|i| sizes[i])
+    }
+}
+
+/// Estimate, in bits, of the noise of a ciphertext after `depth` sequential
+/// multiplications of fresh ciphertexts. A fresh ciphertext has a noise of
+/// about `t * n * B`, and each multiplication increases the noise by a factor
+/// of about `t * n`.
+pub(crate) fn estimated_noise_size(plaintext_size: usize, degree: usize, depth: usize) -> usize {
+    let n_bits = degree.ilog2() as usize;
+    (plaintext_size + n_bits + 4) + depth * (plaintext_size + n_bits + 2)
+}
+
+/// Planner selecting parameters for the BFV encryption scheme from the size of
+/// the plaintext modulus, the multiplicative depth and the number of rotations
+/// of a circuit, and a security level.
+///
+/// The planner selects the smallest polynomial degree for which the ciphertext
+/// modulus needed by the estimated noise growth of the circuit is allowed by
+/// the security level, and a plaintext modulus supporting the SIMD encoding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParametersPlanner {
+    plaintext_nbits: usize,
+    depth: usize,
+    rotations: usize,
+    security_level: SecurityLevel,
+}
+
+/// Parameters selected by a [`ParametersPlanner`], along with the estimates
+/// justifying them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParametersPlan {
+    degree: usize,
+    plaintext_modulus: u64,
+    moduli_sizes: Vec<usize>,
+    noise_budget: Vec<usize>,
+    security_level: SecurityLevel,
+    security_margin: usize,
+}
+
+impl ParametersPlanner {
+    /// Creates a new planner for a circuit without multiplications nor
+    /// rotations, with a plaintext modulus of 20 bits and 128 bits of
+    /// security.
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            plaintext_nbits: 20,
+            depth: 0,
+            rotations: 0,
+            security_level: SecurityLevel::default(),
+        }
+    }
+
+    /// Sets the size of the plaintext modulus, in bits.
+    pub fn set_plaintext_nbits(&mut self, plaintext_nbits: usize) -> &mut Self {
+        self.plaintext_nbits = plaintext_nbits;
+        self
+    }
+
+    /// Sets the multiplicative depth of the circuit.
+    pub fn set_depth(&mut self, depth: usize) -> &mut Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Sets the number of rotations, or other key switchings, performed
+    /// sequentially by the circuit.
+    pub fn set_rotations(&mut self, rotations: usize) -> &mut Self {
+        self.rotations = rotations;
+        self
+    }
+
+    /// Sets the security level.
+    pub fn set_security_level(&mut self, security_level: SecurityLevel) -> &mut Self {
+        self.security_level = security_level;
+        self
+    }
+
+    /// Estimate, in bits, of the noise after `depth` multiplications, when the
+    /// ciphertext modulus consists of `num_moduli` moduli of `modulus_size`
+    /// bits. The noise added by a key switching is about `L * q_i * n * B`,
+    /// and each rotation adds about one bit to the noise.
+    fn noise_size(
+        &self,
+        degree: usize,
+        depth: usize,
+        num_moduli: usize,
+        modulus_size: usize,
+    ) -> usize {
+        let mut noise = estimated_noise_size(self.plaintext_nbits, degree, depth);
+        if self.depth > 0 || self.rotations > 0 {
+            let key_switching_noise =
+                modulus_size + degree.ilog2() as usize + ceil_log2(num_moduli) + 4;
+            noise = noise.max(key_switching_noise);
+        }
+        noise + ceil_log2(self.rotations + 1)
+    }
+
+    /// Returns the smallest size of `num_moduli` moduli with enough noise
+    /// budget for the circuit, or `None` if the moduli would exceed 62 bits.
+    fn moduli_size(&self, degree: usize, num_moduli: usize) -> Option<usize> {
+        let mut size = degree.ilog2() as usize + ceil_log2(num_moduli) + 3;
+        loop {
+            // The ciphertext modulus must be larger than t times the noise.
+            let required =
+                self.noise_size(degree, self.depth, num_moduli, size) + self.plaintext_nbits + 1;
+            let needed = required.div_ceil(num_moduli);
+            if needed > 62 {
+                return None;
+            } else if needed <= size {
+                break;
+            }
+            size = needed
+        }
+        // Increase the size until there are enough primes supporting the NTT.
+        (size..=62).find(|size| {
+            BfvParametersBuilder::generate_moduli(&vec![*size; num_moduli], degree).is_ok()
+        })
+    }
+
+    /// Selects the parameters. Returns an error if the plaintext modulus size
+    /// is not between 10 and 62 bits, or if no polynomial degree provides
+    /// enough noise budget at the requested security level.
+    pub fn plan(&self) -> Result<ParametersPlan> {
+        if !(10..=62).contains(&self.plaintext_nbits) {
+            return Err(Error::ParametersError(
+                ParametersError::InvalidPlaintextSize(self.plaintext_nbits, 10, 62),
+            ));
+        }
+
+        let min_num_moduli = if self.depth > 0 || self.rotations > 0 {
+            2
+        } else {
+            1
+        };
+        let mut smallest_modulus_size = usize::MAX;
+        let mut largest_max_modulus_size = 0;
+        for degree in DEGREES {
+            let max_modulus_size = self.security_level.max_modulus_size(degree).unwrap();
+            largest_max_modulus_size = max_modulus_size;
+            let Some(plaintext_modulus) = generate_prime(
+                self.plaintext_nbits,
+                2 * degree as u64,
+                u64::MAX >> (64 - self.plaintext_nbits),
+            ) else {
+                continue;
+            };
+
+            // Use as few moduli as possible.
+            let moduli_sizes =
+                (min_num_moduli..=max_modulus_size.div_ceil(10)).find_map(|num_moduli| {
+                    self.moduli_size(degree, num_moduli)
+                        .map(|size| vec![size; num_moduli])
+                });
+            let Some(moduli_sizes) = moduli_sizes else {
+                continue;
+            };
+            let modulus_size = moduli_sizes.iter().sum::<usize>();
+            smallest_modulus_size = smallest_modulus_size.min(modulus_size);
+            if modulus_size > max_modulus_size {
+                continue;
+            }
+
+            let noise_budget = (0..=self.depth)
+                .map(|depth| {
+                    let noise = self.noise_size(degree, depth, moduli_sizes.len(), moduli_sizes[0]);
+                    modulus_size - self.plaintext_nbits - 1 - noise
+                })
+                .collect();
+            return Ok(ParametersPlan {
+                degree,
+                plaintext_modulus,
+                moduli_sizes,
+                noise_budget,
+                security_level: self.security_level,
+                security_margin: max_modulus_size - modulus_size,
+            });
+        }
+
+        if smallest_modulus_size == usize::MAX {
+            // No plaintext modulus of this size supports the SIMD encoding.
+            Err(Error::ParametersError(ParametersError::NotEnoughPrimes(
+                self.plaintext_nbits,
+                DEGREES[0],
+            )))
+        } else {
+            Err(Error::ParametersError(ParametersError::ModulusTooLarge(
+                smallest_modulus_size,
+                largest_max_modulus_size,
+            )))
+        }
+    }
+}
+
+impl ParametersPlan {
+    /// Returns the polynomial degree.
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /// Returns the plaintext modulus, which supports the SIMD encoding.
+    pub fn plaintext_modulus(&self) -> u64 {
+        self.plaintext_modulus
+    }
+
+    /// Returns the sizes of the ciphertext moduli.
+    pub fn moduli_sizes(&self) -> &[usize] {
+        &self.moduli_sizes
+    }
+
+    /// Returns the estimated noise budget, in bits, remaining after `i`
+    /// multiplications, for `i` between 0 and the depth of the circuit.
+    pub fn noise_budget(&self) -> &[usize] {
+        &self.noise_budget
+    }
+
+    /// Returns the difference, in bits, between the largest ciphertext modulus
+    /// allowed by the security level and the size of the ciphertext modulus.
+    pub fn security_margin(&self) -> usize {
+        self.security_margin
+    }
+
+    /// Returns a builder configured with the selected parameters, and which
+    /// enforces the security level of the plan.
+    pub fn builder(&self) -> BfvParametersBuilder {
+        let mut builder = BfvParametersBuilder::new();
+        builder
+            .set_degree(self.degree)
+            .set_plaintext_modulus(self.plaintext_modulus)
+            .set_moduli_sizes(&self.moduli_sizes)
+            .set_min_security_level(self.security_level);
+        builder
+    }
+}
+
+fn ceil_log2(x: usize) -> usize {
+    x.next_power_of_two().ilog2() as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParametersPlanner, SecurityLevel};
+    use crate::{
+        bfv::{
+            Ciphertext, Encoding, EvaluationKeyBuilder, Plaintext, RelinearizationKey, SecretKey,
+        },
+        Error, ParametersError,
+    };
+    use fhe_traits::{FheDecoder, FheDecrypter, FheEncoder, FheEncrypter};
+    use itertools::{izip, Itertools};
+    use rand::thread_rng;
+    use std::error::Error as StdError;
+
+    #[test]
+    fn plan() -> Result<(), Box<dyn StdError>> {
+        let plan = ParametersPlanner::new().plan()?;
+        assert_eq!(plan.moduli_sizes().len(), 1);
+        assert_eq!(plan.noise_budget().len(), 1);
+
+        let plan = ParametersPlanner::new()
+            .set_plaintext_nbits(20)
+            .set_depth(1)
+            .set_rotations(1)
+            .plan()?;
+        assert_eq!(plan.degree(), 4096);
+        assert_eq!(plan.plaintext_modulus() % 8192, 1);
+        assert_eq!(plan.plaintext_modulus().ilog2(), 19);
+        assert!(plan.moduli_sizes().len() >= 2);
+        assert_eq!(plan.noise_budget().len(), 2);
+        assert!(plan.noise_budget()[0] > plan.noise_budget()[1]);
+        assert_eq!(
+            plan.security_margin(),
+            109 - plan.moduli_sizes().iter().sum::<usize>()
+        );
+
+        // A higher security level requires a larger degree.
+        for security_level in [SecurityLevel::Bits192, SecurityLevel::Bits256] {
+            let plan_security = ParametersPlanner::new()
+                .set_plaintext_nbits(20)
+                .set_depth(1)
+                .set_rotations(1)
+                .set_security_level(security_level)
+                .plan()?;
+            assert!(plan_security.degree() >= plan.degree());
+            assert!(
+                plan_security.moduli_sizes().iter().sum::<usize>()
+                    <= security_level
+                        .max_modulus_size(plan_security.degree())
+                        .unwrap()
+            );
+        }
+
+        // The selected parameters support the circuit.
+        let mut rng = thread_rng();
+        let par = plan.builder().build_arc()?;
+        assert_eq!(par.security_level(), Some(SecurityLevel::Bits128));
+        let sk = SecretKey::random(&par, &mut rng);
+        let rk = RelinearizationKey::new(&sk, &mut rng)?;
+        let ek = EvaluationKeyBuilder::new(&sk)?
+            .enable_column_rotation(1)?
+            .build(&mut rng)?;
+        let a = par.plaintext.random_vec(par.degree(), &mut rng);
+        let b = par.plaintext.random_vec(par.degree(), &mut rng);
+        let ct_a: Ciphertext = sk.try_encrypt(
+            &Plaintext::try_encode(&a, Encoding::simd(), &par)?,
+            &mut rng,
+        )?;
+        let ct_b: Ciphertext = sk.try_encrypt(
+            &Plaintext::try_encode(&b, Encoding::simd(), &par)?,
+            &mut rng,
+        )?;
+        let mut ct = &ct_a * &ct_b;
+        rk.relinearizes(&mut ct)?;
+        let ct = ek.rotates_columns_by(&ct, 1)?;
+        let c = Vec::<u64>::try_decode(&sk.try_decrypt(&ct)?, Encoding::simd())?;
+        let row_size = par.degree() / 2;
+        let expected = izip!(&a, &b)
+            .map(|(ai, bi)| par.plaintext.mul(*ai, *bi))
+            .collect_vec();
+        for (i, ci) in c.iter().enumerate() {
+            let j = (i / row_size) * row_size + (i + 1) % row_size;
+            assert_eq!(*ci, expected[j]);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            ParametersPlanner::new().set_plaintext_nbits(8).plan(),
+            Err(Error::ParametersError(
+                ParametersError::InvalidPlaintextSize(8, 10, 62)
+            ))
+        );
+        assert!(matches!(
+            ParametersPlanner::new().set_depth(40).plan(),
+            Err(Error::ParametersError(ParametersError::ModulusTooLarge(
+                _,
+                881
+            )))
+        ));
+    }
+}
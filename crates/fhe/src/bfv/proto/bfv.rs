@@ -339,6 +339,10 @@ pub struct KeySwitchingKey {
     pub ciphertext_level: u32,
     // @@protoc_insertion_point(field:fhers.KeySwitchingKey.ksk_level)
     pub ksk_level: u32,
+    // @@protoc_insertion_point(field:fhers.KeySwitchingKey.dnum)
+    pub dnum: u32,
+    // @@protoc_insertion_point(field:fhers.KeySwitchingKey.special_moduli)
+    pub special_moduli: ::std::vec::Vec<u64>,
     // special fields
     // @@protoc_insertion_point(special_field:fhers.KeySwitchingKey.special_fields)
     pub special_fields: ::protobuf::SpecialFields,
@@ -356,7 +360,7 @@ impl KeySwitchingKey {
     }
 
     fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
-        let mut fields = ::std::vec::Vec::with_capacity(5);
+        let mut fields = ::std::vec::Vec::with_capacity(7);
         let mut oneofs = ::std::vec::Vec::with_capacity(0);
         fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
             "c0",
@@ -383,6 +387,16 @@ impl KeySwitchingKey {
             |m: &KeySwitchingKey| { &m.ksk_level },
             |m: &mut KeySwitchingKey| { &mut m.ksk_level },
         ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "dnum",
+            |m: &KeySwitchingKey| { &m.dnum },
+            |m: &mut KeySwitchingKey| { &mut m.dnum },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "special_moduli",
+            |m: &KeySwitchingKey| { &m.special_moduli },
+            |m: &mut KeySwitchingKey| { &mut m.special_moduli },
+        ));
         ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<KeySwitchingKey>(
             "KeySwitchingKey",
             fields,
@@ -416,6 +430,15 @@ impl ::protobuf::Message for KeySwitchingKey {
                 40 => {
                     self.ksk_level = is.read_uint32()?;
                 },
+                48 => {
+                    self.dnum = is.read_uint32()?;
+                },
+                58 => {
+                    is.read_repeated_packed_uint64_into(&mut self.special_moduli)?;
+                },
+                56 => {
+                    self.special_moduli.push(is.read_uint64()?);
+                },
                 tag => {
                     ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
                 },
@@ -443,6 +466,12 @@ impl ::protobuf::Message for KeySwitchingKey {
         if self.ksk_level != 0 {
             my_size += ::protobuf::rt::uint32_size(5, self.ksk_level);
         }
+        if self.dnum != 0 {
+            my_size += ::protobuf::rt::uint32_size(6, self.dnum);
+        }
+        for value in &self.special_moduli {
+            my_size += ::protobuf::rt::uint64_size(7, *value);
+        };
         my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
         self.special_fields.cached_size().set(my_size as u32);
         my_size
@@ -464,6 +493,12 @@ impl ::protobuf::Message for KeySwitchingKey {
         if self.ksk_level != 0 {
             os.write_uint32(5, self.ksk_level)?;
         }
+        if self.dnum != 0 {
+            os.write_uint32(6, self.dnum)?;
+        }
+        for v in &self.special_moduli {
+            os.write_uint64(7, *v)?;
+        };
         os.write_unknown_fields(self.special_fields.unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -486,6 +521,8 @@ impl ::protobuf::Message for KeySwitchingKey {
         self.seed.clear();
         self.ciphertext_level = 0;
         self.ksk_level = 0;
+        self.dnum = 0;
+        self.special_moduli.clear();
         self.special_fields.clear();
     }
 
@@ -496,6 +533,8 @@ impl ::protobuf::Message for KeySwitchingKey {
             seed: ::std::vec::Vec::new(),
             ciphertext_level: 0,
             ksk_level: 0,
+            dnum: 0,
+            special_moduli: ::std::vec::Vec::new(),
             special_fields: ::protobuf::SpecialFields::new(),
         };
         &instance
@@ -954,6 +993,8 @@ pub struct Parameters {
     pub plaintext: u64,
     // @@protoc_insertion_point(field:fhers.Parameters.variance)
     pub variance: u32,
+    // @@protoc_insertion_point(field:fhers.Parameters.min_security_level)
+    pub min_security_level: u32,
     // special fields
     // @@protoc_insertion_point(special_field:fhers.Parameters.special_fields)
     pub special_fields: ::protobuf::SpecialFields,
@@ -971,7 +1012,7 @@ impl Parameters {
     }
 
     fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
-        let mut fields = ::std::vec::Vec::with_capacity(4);
+        let mut fields = ::std::vec::Vec::with_capacity(5);
         let mut oneofs = ::std::vec::Vec::with_capacity(0);
         fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
             "degree",
@@ -993,6 +1034,11 @@ impl Parameters {
             |m: &Parameters| { &m.variance },
             |m: &mut Parameters| { &mut m.variance },
         ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "min_security_level",
+            |m: &Parameters| { &m.min_security_level },
+            |m: &mut Parameters| { &mut m.min_security_level },
+        ));
         ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<Parameters>(
             "Parameters",
             fields,
@@ -1026,6 +1072,9 @@ impl ::protobuf::Message for Parameters {
                 32 => {
                     self.variance = is.read_uint32()?;
                 },
+                40 => {
+                    self.min_security_level = is.read_uint32()?;
+                },
                 tag => {
                     ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
                 },
@@ -1050,6 +1099,9 @@ impl ::protobuf::Message for Parameters {
         if self.variance != 0 {
             my_size += ::protobuf::rt::uint32_size(4, self.variance);
         }
+        if self.min_security_level != 0 {
+            my_size += ::protobuf::rt::uint32_size(5, self.min_security_level);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
         self.special_fields.cached_size().set(my_size as u32);
         my_size
@@ -1068,6 +1120,9 @@ impl ::protobuf::Message for Parameters {
         if self.variance != 0 {
             os.write_uint32(4, self.variance)?;
         }
+        if self.min_security_level != 0 {
+            os.write_uint32(5, self.min_security_level)?;
+        }
         os.write_unknown_fields(self.special_fields.unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -1089,6 +1144,7 @@ impl ::protobuf::Message for Parameters {
         self.moduli.clear();
         self.plaintext = 0;
         self.variance = 0;
+        self.min_security_level = 0;
         self.special_fields.clear();
     }
 
@@ -1098,6 +1154,7 @@ impl ::protobuf::Message for Parameters {
             moduli: ::std::vec::Vec::new(),
             plaintext: 0,
             variance: 0,
+            min_security_level: 0,
             special_fields: ::protobuf::SpecialFields::new(),
         };
         &instance
@@ -1244,99 +1301,692 @@ impl ::protobuf::reflect::ProtobufValue for PublicKey {
     type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
 }
 
+#[derive(PartialEq,Clone,Default,Debug)]
+// @@protoc_insertion_point(message:fhers.SecretKey)
+pub struct SecretKey {
+    // message fields
+    // @@protoc_insertion_point(field:fhers.SecretKey.coeffs)
+    pub coeffs: ::std::vec::Vec<i64>,
+    // special fields
+    // @@protoc_insertion_point(special_field:fhers.SecretKey.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a SecretKey {
+    fn default() -> &'a SecretKey {
+        <SecretKey as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl SecretKey {
+    pub fn new() -> SecretKey {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(1);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "coeffs",
+            |m: &SecretKey| { &m.coeffs },
+            |m: &mut SecretKey| { &mut m.coeffs },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<SecretKey>(
+            "SecretKey",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for SecretKey {
+    const NAME: &'static str = "SecretKey";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    is.read_repeated_packed_sint64_into(&mut self.coeffs)?;
+                },
+                8 => {
+                    self.coeffs.push(is.read_sint64()?);
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        for value in &self.coeffs {
+            my_size += ::protobuf::rt::sint64_size(1, *value);
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        for v in &self.coeffs {
+            os.write_sint64(1, *v)?;
+        };
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> SecretKey {
+        SecretKey::new()
+    }
+
+    fn clear(&mut self) {
+        self.coeffs.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static SecretKey {
+        static instance: SecretKey = SecretKey {
+            coeffs: ::std::vec::Vec::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for SecretKey {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("SecretKey").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for SecretKey {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for SecretKey {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+#[derive(PartialEq,Clone,Default,Debug)]
+// @@protoc_insertion_point(message:fhers.WrappedSecretKey)
+pub struct WrappedSecretKey {
+    // message fields
+    // @@protoc_insertion_point(field:fhers.WrappedSecretKey.kdf)
+    pub kdf: ::protobuf::EnumOrUnknown<wrapped_secret_key::Kdf>,
+    // @@protoc_insertion_point(field:fhers.WrappedSecretKey.salt)
+    pub salt: ::std::vec::Vec<u8>,
+    // @@protoc_insertion_point(field:fhers.WrappedSecretKey.memory_cost)
+    pub memory_cost: u32,
+    // @@protoc_insertion_point(field:fhers.WrappedSecretKey.time_cost)
+    pub time_cost: u32,
+    // @@protoc_insertion_point(field:fhers.WrappedSecretKey.parallelism)
+    pub parallelism: u32,
+    // @@protoc_insertion_point(field:fhers.WrappedSecretKey.nonce)
+    pub nonce: ::std::vec::Vec<u8>,
+    // @@protoc_insertion_point(field:fhers.WrappedSecretKey.ciphertext)
+    pub ciphertext: ::std::vec::Vec<u8>,
+    // special fields
+    // @@protoc_insertion_point(special_field:fhers.WrappedSecretKey.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a WrappedSecretKey {
+    fn default() -> &'a WrappedSecretKey {
+        <WrappedSecretKey as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl WrappedSecretKey {
+    pub fn new() -> WrappedSecretKey {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(7);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "kdf",
+            |m: &WrappedSecretKey| { &m.kdf },
+            |m: &mut WrappedSecretKey| { &mut m.kdf },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "salt",
+            |m: &WrappedSecretKey| { &m.salt },
+            |m: &mut WrappedSecretKey| { &mut m.salt },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "memory_cost",
+            |m: &WrappedSecretKey| { &m.memory_cost },
+            |m: &mut WrappedSecretKey| { &mut m.memory_cost },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "time_cost",
+            |m: &WrappedSecretKey| { &m.time_cost },
+            |m: &mut WrappedSecretKey| { &mut m.time_cost },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "parallelism",
+            |m: &WrappedSecretKey| { &m.parallelism },
+            |m: &mut WrappedSecretKey| { &mut m.parallelism },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "nonce",
+            |m: &WrappedSecretKey| { &m.nonce },
+            |m: &mut WrappedSecretKey| { &mut m.nonce },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "ciphertext",
+            |m: &WrappedSecretKey| { &m.ciphertext },
+            |m: &mut WrappedSecretKey| { &mut m.ciphertext },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<WrappedSecretKey>(
+            "WrappedSecretKey",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for WrappedSecretKey {
+    const NAME: &'static str = "WrappedSecretKey";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                8 => {
+                    self.kdf = is.read_enum_or_unknown()?;
+                },
+                18 => {
+                    self.salt = is.read_bytes()?;
+                },
+                24 => {
+                    self.memory_cost = is.read_uint32()?;
+                },
+                32 => {
+                    self.time_cost = is.read_uint32()?;
+                },
+                40 => {
+                    self.parallelism = is.read_uint32()?;
+                },
+                50 => {
+                    self.nonce = is.read_bytes()?;
+                },
+                58 => {
+                    self.ciphertext = is.read_bytes()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if self.kdf != ::protobuf::EnumOrUnknown::new(wrapped_secret_key::Kdf::NONE) {
+            my_size += ::protobuf::rt::int32_size(1, self.kdf.value());
+        }
+        if !self.salt.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(2, &self.salt);
+        }
+        if self.memory_cost != 0 {
+            my_size += ::protobuf::rt::uint32_size(3, self.memory_cost);
+        }
+        if self.time_cost != 0 {
+            my_size += ::protobuf::rt::uint32_size(4, self.time_cost);
+        }
+        if self.parallelism != 0 {
+            my_size += ::protobuf::rt::uint32_size(5, self.parallelism);
+        }
+        if !self.nonce.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(6, &self.nonce);
+        }
+        if !self.ciphertext.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(7, &self.ciphertext);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if self.kdf != ::protobuf::EnumOrUnknown::new(wrapped_secret_key::Kdf::NONE) {
+            os.write_enum(1, ::protobuf::EnumOrUnknown::value(&self.kdf))?;
+        }
+        if !self.salt.is_empty() {
+            os.write_bytes(2, &self.salt)?;
+        }
+        if self.memory_cost != 0 {
+            os.write_uint32(3, self.memory_cost)?;
+        }
+        if self.time_cost != 0 {
+            os.write_uint32(4, self.time_cost)?;
+        }
+        if self.parallelism != 0 {
+            os.write_uint32(5, self.parallelism)?;
+        }
+        if !self.nonce.is_empty() {
+            os.write_bytes(6, &self.nonce)?;
+        }
+        if !self.ciphertext.is_empty() {
+            os.write_bytes(7, &self.ciphertext)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> WrappedSecretKey {
+        WrappedSecretKey::new()
+    }
+
+    fn clear(&mut self) {
+        self.kdf = ::protobuf::EnumOrUnknown::new(wrapped_secret_key::Kdf::NONE);
+        self.salt.clear();
+        self.memory_cost = 0;
+        self.time_cost = 0;
+        self.parallelism = 0;
+        self.nonce.clear();
+        self.ciphertext.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static WrappedSecretKey {
+        static instance: WrappedSecretKey = WrappedSecretKey {
+            kdf: ::protobuf::EnumOrUnknown::from_i32(0),
+            salt: ::std::vec::Vec::new(),
+            memory_cost: 0,
+            time_cost: 0,
+            parallelism: 0,
+            nonce: ::std::vec::Vec::new(),
+            ciphertext: ::std::vec::Vec::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for WrappedSecretKey {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("WrappedSecretKey").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for WrappedSecretKey {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for WrappedSecretKey {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+/// Nested message and enums of message `WrappedSecretKey`
+pub mod wrapped_secret_key {
+    #[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
+    // @@protoc_insertion_point(enum:fhers.WrappedSecretKey.Kdf)
+    pub enum Kdf {
+        // @@protoc_insertion_point(enum_value:fhers.WrappedSecretKey.Kdf.NONE)
+        NONE = 0,
+        // @@protoc_insertion_point(enum_value:fhers.WrappedSecretKey.Kdf.ARGON2ID)
+        ARGON2ID = 1,
+    }
+
+    impl ::protobuf::Enum for Kdf {
+        const NAME: &'static str = "Kdf";
+
+        fn value(&self) -> i32 {
+            *self as i32
+        }
+
+        fn from_i32(value: i32) -> ::std::option::Option<Kdf> {
+            match value {
+                0 => ::std::option::Option::Some(Kdf::NONE),
+                1 => ::std::option::Option::Some(Kdf::ARGON2ID),
+                _ => ::std::option::Option::None
+            }
+        }
+
+        const VALUES: &'static [Kdf] = &[
+            Kdf::NONE,
+            Kdf::ARGON2ID,
+        ];
+    }
+
+    impl ::protobuf::EnumFull for Kdf {
+        fn enum_descriptor() -> ::protobuf::reflect::EnumDescriptor {
+            static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::rt::Lazy::new();
+            descriptor.get(|| super::file_descriptor().enum_by_package_relative_name("WrappedSecretKey.Kdf").unwrap()).clone()
+        }
+
+        fn descriptor(&self) -> ::protobuf::reflect::EnumValueDescriptor {
+            let index = *self as usize;
+            Self::enum_descriptor().value_by_index(index)
+        }
+    }
+
+    impl ::std::default::Default for Kdf {
+        fn default() -> Self {
+            Kdf::NONE
+        }
+    }
+
+    impl Kdf {
+        pub(in super) fn generated_enum_descriptor_data() -> ::protobuf::reflect::GeneratedEnumDescriptorData {
+            ::protobuf::reflect::GeneratedEnumDescriptorData::new::<Kdf>("WrappedSecretKey.Kdf")
+        }
+    }
+}
+
+#[derive(PartialEq,Clone,Default,Debug)]
+// @@protoc_insertion_point(message:fhers.Plaintext)
+pub struct Plaintext {
+    // message fields
+    // @@protoc_insertion_point(field:fhers.Plaintext.value)
+    pub value: ::std::vec::Vec<u8>,
+    // @@protoc_insertion_point(field:fhers.Plaintext.encoding)
+    pub encoding: ::protobuf::EnumOrUnknown<plaintext::Encoding>,
+    // @@protoc_insertion_point(field:fhers.Plaintext.level)
+    pub level: u32,
+    // @@protoc_insertion_point(field:fhers.Plaintext.poly_ntt)
+    pub poly_ntt: ::std::vec::Vec<u8>,
+    // special fields
+    // @@protoc_insertion_point(special_field:fhers.Plaintext.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a Plaintext {
+    fn default() -> &'a Plaintext {
+        <Plaintext as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Plaintext {
+    pub fn new() -> Plaintext {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(4);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "value",
+            |m: &Plaintext| { &m.value },
+            |m: &mut Plaintext| { &mut m.value },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "encoding",
+            |m: &Plaintext| { &m.encoding },
+            |m: &mut Plaintext| { &mut m.encoding },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "level",
+            |m: &Plaintext| { &m.level },
+            |m: &mut Plaintext| { &mut m.level },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "poly_ntt",
+            |m: &Plaintext| { &m.poly_ntt },
+            |m: &mut Plaintext| { &mut m.poly_ntt },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<Plaintext>(
+            "Plaintext",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for Plaintext {
+    const NAME: &'static str = "Plaintext";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.value = is.read_bytes()?;
+                },
+                16 => {
+                    self.encoding = is.read_enum_or_unknown()?;
+                },
+                24 => {
+                    self.level = is.read_uint32()?;
+                },
+                34 => {
+                    self.poly_ntt = is.read_bytes()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.value.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(1, &self.value);
+        }
+        if self.encoding != ::protobuf::EnumOrUnknown::new(plaintext::Encoding::UNKNOWN) {
+            my_size += ::protobuf::rt::int32_size(2, self.encoding.value());
+        }
+        if self.level != 0 {
+            my_size += ::protobuf::rt::uint32_size(3, self.level);
+        }
+        if !self.poly_ntt.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(4, &self.poly_ntt);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.value.is_empty() {
+            os.write_bytes(1, &self.value)?;
+        }
+        if self.encoding != ::protobuf::EnumOrUnknown::new(plaintext::Encoding::UNKNOWN) {
+            os.write_enum(2, ::protobuf::EnumOrUnknown::value(&self.encoding))?;
+        }
+        if self.level != 0 {
+            os.write_uint32(3, self.level)?;
+        }
+        if !self.poly_ntt.is_empty() {
+            os.write_bytes(4, &self.poly_ntt)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> Plaintext {
+        Plaintext::new()
+    }
+
+    fn clear(&mut self) {
+        self.value.clear();
+        self.encoding = ::protobuf::EnumOrUnknown::new(plaintext::Encoding::UNKNOWN);
+        self.level = 0;
+        self.poly_ntt.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static Plaintext {
+        static instance: Plaintext = Plaintext {
+            value: ::std::vec::Vec::new(),
+            encoding: ::protobuf::EnumOrUnknown::from_i32(0),
+            level: 0,
+            poly_ntt: ::std::vec::Vec::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for Plaintext {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("Plaintext").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for Plaintext {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Plaintext {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+/// Nested message and enums of message `Plaintext`
+pub mod plaintext {
+    #[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
+    // @@protoc_insertion_point(enum:fhers.Plaintext.Encoding)
+    pub enum Encoding {
+        // @@protoc_insertion_point(enum_value:fhers.Plaintext.Encoding.UNKNOWN)
+        UNKNOWN = 0,
+        // @@protoc_insertion_point(enum_value:fhers.Plaintext.Encoding.POLY)
+        POLY = 1,
+        // @@protoc_insertion_point(enum_value:fhers.Plaintext.Encoding.SIMD)
+        SIMD = 2,
+    }
+
+    impl ::protobuf::Enum for Encoding {
+        const NAME: &'static str = "Encoding";
+
+        fn value(&self) -> i32 {
+            *self as i32
+        }
+
+        fn from_i32(value: i32) -> ::std::option::Option<Encoding> {
+            match value {
+                0 => ::std::option::Option::Some(Encoding::UNKNOWN),
+                1 => ::std::option::Option::Some(Encoding::POLY),
+                2 => ::std::option::Option::Some(Encoding::SIMD),
+                _ => ::std::option::Option::None
+            }
+        }
+
+        const VALUES: &'static [Encoding] = &[
+            Encoding::UNKNOWN,
+            Encoding::POLY,
+            Encoding::SIMD,
+        ];
+    }
+
+    impl ::protobuf::EnumFull for Encoding {
+        fn enum_descriptor() -> ::protobuf::reflect::EnumDescriptor {
+            static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::rt::Lazy::new();
+            descriptor.get(|| super::file_descriptor().enum_by_package_relative_name("Plaintext.Encoding").unwrap()).clone()
+        }
+
+        fn descriptor(&self) -> ::protobuf::reflect::EnumValueDescriptor {
+            let index = *self as usize;
+            Self::enum_descriptor().value_by_index(index)
+        }
+    }
+
+    impl ::std::default::Default for Encoding {
+        fn default() -> Self {
+            Encoding::UNKNOWN
+        }
+    }
+
+    impl Encoding {
+        pub(in super) fn generated_enum_descriptor_data() -> ::protobuf::reflect::GeneratedEnumDescriptorData {
+            ::protobuf::reflect::GeneratedEnumDescriptorData::new::<Encoding>("Plaintext.Encoding")
+        }
+    }
+}
+
 static file_descriptor_proto_data: &'static [u8] = b"\
     \n\tbfv.proto\x12\x05fhers\"D\n\nCiphertext\x12\x0c\n\x01c\x18\x01\x20\
     \x03(\x0cR\x01c\x12\x12\n\x04seed\x18\x02\x20\x01(\x0cR\x04seed\x12\x14\
     \n\x05level\x18\x03\x20\x01(\rR\x05level\"h\n\x0eRGSWCiphertext\x12*\n\
     \x04ksk0\x18\x01\x20\x01(\x0b2\x16.fhers.KeySwitchingKeyR\x04ksk0\x12*\n\
-    \x04ksk1\x18\x02\x20\x01(\x0b2\x16.fhers.KeySwitchingKeyR\x04ksk1\"\x8d\
+    \x04ksk1\x18\x02\x20\x01(\x0b2\x16.fhers.KeySwitchingKeyR\x04ksk1\"\xc8\
     \x01\n\x0fKeySwitchingKey\x12\x0e\n\x02c0\x18\x01\x20\x03(\x0cR\x02c0\
     \x12\x0e\n\x02c1\x18\x02\x20\x03(\x0cR\x02c1\x12\x12\n\x04seed\x18\x03\
     \x20\x01(\x0cR\x04seed\x12)\n\x10ciphertext_level\x18\x04\x20\x01(\rR\
     \x0fciphertextLevel\x12\x1b\n\tksk_level\x18\x05\x20\x01(\rR\x08kskLevel\
-    \">\n\x12RelinearizationKey\x12(\n\x03ksk\x18\x01\x20\x01(\x0b2\x16.fher\
-    s.KeySwitchingKeyR\x03ksk\"Q\n\tGaloisKey\x12(\n\x03ksk\x18\x01\x20\x01(\
-    \x0b2\x16.fhers.KeySwitchingKeyR\x03ksk\x12\x1a\n\x08exponent\x18\x02\
-    \x20\x01(\rR\x08exponent\"\x8e\x01\n\rEvaluationKey\x12\x20\n\x02gk\x18\
-    \x02\x20\x03(\x0b2\x10.fhers.GaloisKeyR\x02gk\x12)\n\x10ciphertext_level\
-    \x18\x03\x20\x01(\rR\x0fciphertextLevel\x120\n\x14evaluation_key_level\
-    \x18\x04\x20\x01(\rR\x12evaluationKeyLevel\"v\n\nParameters\x12\x16\n\
-    \x06degree\x18\x01\x20\x01(\rR\x06degree\x12\x16\n\x06moduli\x18\x02\x20\
-    \x03(\x04R\x06moduli\x12\x1c\n\tplaintext\x18\x03\x20\x01(\x04R\tplainte\
-    xt\x12\x1a\n\x08variance\x18\x04\x20\x01(\rR\x08variance\",\n\tPublicKey\
-    \x12\x1f\n\x01c\x18\x01\x20\x01(\x0b2\x11.fhers.CiphertextR\x01cJ\xa5\
-    \x0b\n\x06\x12\x04\0\0/\x01\n\x08\n\x01\x0c\x12\x03\0\0\x12\n\x08\n\x01\
-    \x02\x12\x03\x02\0\x0e\n\n\n\x02\x04\0\x12\x04\x04\0\x08\x01\n\n\n\x03\
-    \x04\0\x01\x12\x03\x04\x08\x12\n\x0b\n\x04\x04\0\x02\0\x12\x03\x05\x04\
-    \x19\n\x0c\n\x05\x04\0\x02\0\x04\x12\x03\x05\x04\x0c\n\x0c\n\x05\x04\0\
-    \x02\0\x05\x12\x03\x05\r\x12\n\x0c\n\x05\x04\0\x02\0\x01\x12\x03\x05\x13\
-    \x14\n\x0c\n\x05\x04\0\x02\0\x03\x12\x03\x05\x17\x18\n\x0b\n\x04\x04\0\
-    \x02\x01\x12\x03\x06\x04\x13\n\x0c\n\x05\x04\0\x02\x01\x05\x12\x03\x06\
-    \x04\t\n\x0c\n\x05\x04\0\x02\x01\x01\x12\x03\x06\n\x0e\n\x0c\n\x05\x04\0\
-    \x02\x01\x03\x12\x03\x06\x11\x12\n\x0b\n\x04\x04\0\x02\x02\x12\x03\x07\
-    \x04\x15\n\x0c\n\x05\x04\0\x02\x02\x05\x12\x03\x07\x04\n\n\x0c\n\x05\x04\
-    \0\x02\x02\x01\x12\x03\x07\x0b\x10\n\x0c\n\x05\x04\0\x02\x02\x03\x12\x03\
-    \x07\x13\x14\n\n\n\x02\x04\x01\x12\x04\n\0\r\x01\n\n\n\x03\x04\x01\x01\
-    \x12\x03\n\x08\x16\n\x0b\n\x04\x04\x01\x02\0\x12\x03\x0b\x04\x1d\n\x0c\n\
-    \x05\x04\x01\x02\0\x06\x12\x03\x0b\x04\x13\n\x0c\n\x05\x04\x01\x02\0\x01\
-    \x12\x03\x0b\x14\x18\n\x0c\n\x05\x04\x01\x02\0\x03\x12\x03\x0b\x1b\x1c\n\
-    \x0b\n\x04\x04\x01\x02\x01\x12\x03\x0c\x04\x1d\n\x0c\n\x05\x04\x01\x02\
-    \x01\x06\x12\x03\x0c\x04\x13\n\x0c\n\x05\x04\x01\x02\x01\x01\x12\x03\x0c\
-    \x14\x18\n\x0c\n\x05\x04\x01\x02\x01\x03\x12\x03\x0c\x1b\x1c\n\n\n\x02\
-    \x04\x02\x12\x04\x0f\0\x15\x01\n\n\n\x03\x04\x02\x01\x12\x03\x0f\x08\x17\
-    \n\x0b\n\x04\x04\x02\x02\0\x12\x03\x10\x04\x1a\n\x0c\n\x05\x04\x02\x02\0\
-    \x04\x12\x03\x10\x04\x0c\n\x0c\n\x05\x04\x02\x02\0\x05\x12\x03\x10\r\x12\
-    \n\x0c\n\x05\x04\x02\x02\0\x01\x12\x03\x10\x13\x15\n\x0c\n\x05\x04\x02\
-    \x02\0\x03\x12\x03\x10\x18\x19\n\x0b\n\x04\x04\x02\x02\x01\x12\x03\x11\
-    \x04\x1a\n\x0c\n\x05\x04\x02\x02\x01\x04\x12\x03\x11\x04\x0c\n\x0c\n\x05\
-    \x04\x02\x02\x01\x05\x12\x03\x11\r\x12\n\x0c\n\x05\x04\x02\x02\x01\x01\
-    \x12\x03\x11\x13\x15\n\x0c\n\x05\x04\x02\x02\x01\x03\x12\x03\x11\x18\x19\
-    \n\x0b\n\x04\x04\x02\x02\x02\x12\x03\x12\x04\x13\n\x0c\n\x05\x04\x02\x02\
-    \x02\x05\x12\x03\x12\x04\t\n\x0c\n\x05\x04\x02\x02\x02\x01\x12\x03\x12\n\
-    \x0e\n\x0c\n\x05\x04\x02\x02\x02\x03\x12\x03\x12\x11\x12\n\x0b\n\x04\x04\
-    \x02\x02\x03\x12\x03\x13\x04\x20\n\x0c\n\x05\x04\x02\x02\x03\x05\x12\x03\
-    \x13\x04\n\n\x0c\n\x05\x04\x02\x02\x03\x01\x12\x03\x13\x0b\x1b\n\x0c\n\
-    \x05\x04\x02\x02\x03\x03\x12\x03\x13\x1e\x1f\n\x0b\n\x04\x04\x02\x02\x04\
-    \x12\x03\x14\x04\x19\n\x0c\n\x05\x04\x02\x02\x04\x05\x12\x03\x14\x04\n\n\
-    \x0c\n\x05\x04\x02\x02\x04\x01\x12\x03\x14\x0b\x14\n\x0c\n\x05\x04\x02\
-    \x02\x04\x03\x12\x03\x14\x17\x18\n\n\n\x02\x04\x03\x12\x04\x17\0\x19\x01\
-    \n\n\n\x03\x04\x03\x01\x12\x03\x17\x08\x1a\n\x0b\n\x04\x04\x03\x02\0\x12\
-    \x03\x18\x04\x1c\n\x0c\n\x05\x04\x03\x02\0\x06\x12\x03\x18\x04\x13\n\x0c\
-    \n\x05\x04\x03\x02\0\x01\x12\x03\x18\x14\x17\n\x0c\n\x05\x04\x03\x02\0\
-    \x03\x12\x03\x18\x1a\x1b\n\n\n\x02\x04\x04\x12\x04\x1b\0\x1e\x01\n\n\n\
-    \x03\x04\x04\x01\x12\x03\x1b\x08\x11\n\x0b\n\x04\x04\x04\x02\0\x12\x03\
-    \x1c\x04\x1c\n\x0c\n\x05\x04\x04\x02\0\x06\x12\x03\x1c\x04\x13\n\x0c\n\
-    \x05\x04\x04\x02\0\x01\x12\x03\x1c\x14\x17\n\x0c\n\x05\x04\x04\x02\0\x03\
-    \x12\x03\x1c\x1a\x1b\n\x0b\n\x04\x04\x04\x02\x01\x12\x03\x1d\x04\x18\n\
-    \x0c\n\x05\x04\x04\x02\x01\x05\x12\x03\x1d\x04\n\n\x0c\n\x05\x04\x04\x02\
-    \x01\x01\x12\x03\x1d\x0b\x13\n\x0c\n\x05\x04\x04\x02\x01\x03\x12\x03\x1d\
-    \x16\x17\n\n\n\x02\x04\x05\x12\x04\x20\0$\x01\n\n\n\x03\x04\x05\x01\x12\
-    \x03\x20\x08\x15\n\x0b\n\x04\x04\x05\x02\0\x12\x03!\x04\x1e\n\x0c\n\x05\
-    \x04\x05\x02\0\x04\x12\x03!\x04\x0c\n\x0c\n\x05\x04\x05\x02\0\x06\x12\
-    \x03!\r\x16\n\x0c\n\x05\x04\x05\x02\0\x01\x12\x03!\x17\x19\n\x0c\n\x05\
-    \x04\x05\x02\0\x03\x12\x03!\x1c\x1d\n\x0b\n\x04\x04\x05\x02\x01\x12\x03\
-    \"\x04\x20\n\x0c\n\x05\x04\x05\x02\x01\x05\x12\x03\"\x04\n\n\x0c\n\x05\
-    \x04\x05\x02\x01\x01\x12\x03\"\x0b\x1b\n\x0c\n\x05\x04\x05\x02\x01\x03\
-    \x12\x03\"\x1e\x1f\n\x0b\n\x04\x04\x05\x02\x02\x12\x03#\x04$\n\x0c\n\x05\
-    \x04\x05\x02\x02\x05\x12\x03#\x04\n\n\x0c\n\x05\x04\x05\x02\x02\x01\x12\
-    \x03#\x0b\x1f\n\x0c\n\x05\x04\x05\x02\x02\x03\x12\x03#\"#\n\n\n\x02\x04\
-    \x06\x12\x04&\0+\x01\n\n\n\x03\x04\x06\x01\x12\x03&\x08\x12\n\x0b\n\x04\
-    \x04\x06\x02\0\x12\x03'\x04\x16\n\x0c\n\x05\x04\x06\x02\0\x05\x12\x03'\
-    \x04\n\n\x0c\n\x05\x04\x06\x02\0\x01\x12\x03'\x0b\x11\n\x0c\n\x05\x04\
-    \x06\x02\0\x03\x12\x03'\x14\x15\n\x0b\n\x04\x04\x06\x02\x01\x12\x03(\x04\
-    \x1f\n\x0c\n\x05\x04\x06\x02\x01\x04\x12\x03(\x04\x0c\n\x0c\n\x05\x04\
-    \x06\x02\x01\x05\x12\x03(\r\x13\n\x0c\n\x05\x04\x06\x02\x01\x01\x12\x03(\
-    \x14\x1a\n\x0c\n\x05\x04\x06\x02\x01\x03\x12\x03(\x1d\x1e\n\x0b\n\x04\
-    \x04\x06\x02\x02\x12\x03)\x04\x19\n\x0c\n\x05\x04\x06\x02\x02\x05\x12\
-    \x03)\x04\n\n\x0c\n\x05\x04\x06\x02\x02\x01\x12\x03)\x0b\x14\n\x0c\n\x05\
-    \x04\x06\x02\x02\x03\x12\x03)\x17\x18\n\x0b\n\x04\x04\x06\x02\x03\x12\
-    \x03*\x04\x18\n\x0c\n\x05\x04\x06\x02\x03\x05\x12\x03*\x04\n\n\x0c\n\x05\
-    \x04\x06\x02\x03\x01\x12\x03*\x0b\x13\n\x0c\n\x05\x04\x06\x02\x03\x03\
-    \x12\x03*\x16\x17\n\n\n\x02\x04\x07\x12\x04-\0/\x01\n\n\n\x03\x04\x07\
-    \x01\x12\x03-\x08\x11\n\x0b\n\x04\x04\x07\x02\0\x12\x03.\x04\x15\n\x0c\n\
-    \x05\x04\x07\x02\0\x06\x12\x03.\x04\x0e\n\x0c\n\x05\x04\x07\x02\0\x01\
-    \x12\x03.\x0f\x10\n\x0c\n\x05\x04\x07\x02\0\x03\x12\x03.\x13\x14b\x06pro\
-    to3\
+    \x12\x12\n\x04dnum\x18\x06\x20\x01(\rR\x04dnum\x12%\n\x0especial_moduli\
+    \x18\x07\x20\x03(\x04R\rspecialModuli\">\n\x12RelinearizationKey\x12(\n\
+    \x03ksk\x18\x01\x20\x01(\x0b2\x16.fhers.KeySwitchingKeyR\x03ksk\"Q\n\tGa\
+    loisKey\x12(\n\x03ksk\x18\x01\x20\x01(\x0b2\x16.fhers.KeySwitchingKeyR\
+    \x03ksk\x12\x1a\n\x08exponent\x18\x02\x20\x01(\rR\x08exponent\"\x8e\x01\
+    \n\rEvaluationKey\x12\x20\n\x02gk\x18\x02\x20\x03(\x0b2\x10.fhers.Galois\
+    KeyR\x02gk\x12)\n\x10ciphertext_level\x18\x03\x20\x01(\rR\x0fciphertextL\
+    evel\x120\n\x14evaluation_key_level\x18\x04\x20\x01(\rR\x12evaluationKey\
+    Level\"\xa4\x01\n\nParameters\x12\x16\n\x06degree\x18\x01\x20\x01(\rR\
+    \x06degree\x12\x16\n\x06moduli\x18\x02\x20\x03(\x04R\x06moduli\x12\x1c\n\
+    \tplaintext\x18\x03\x20\x01(\x04R\tplaintext\x12\x1a\n\x08variance\x18\
+    \x04\x20\x01(\rR\x08variance\x12,\n\x12min_security_level\x18\x05\x20\
+    \x01(\rR\x10minSecurityLevel\",\n\tPublicKey\x12\x1f\n\x01c\x18\x01\x20\
+    \x01(\x0b2\x11.fhers.CiphertextR\x01c\"#\n\tSecretKey\x12\x16\n\x06coeff\
+    s\x18\x01\x20\x03(\x12R\x06coeffs\"\x8a\x02\n\x10WrappedSecretKey\x12-\n\
+    \x03kdf\x18\x01\x20\x01(\x0e2\x1b.fhers.WrappedSecretKey.KdfR\x03kdf\x12\
+    \x12\n\x04salt\x18\x02\x20\x01(\x0cR\x04salt\x12\x1f\n\x0bmemory_cost\
+    \x18\x03\x20\x01(\rR\nmemoryCost\x12\x1b\n\ttime_cost\x18\x04\x20\x01(\r\
+    R\x08timeCost\x12\x20\n\x0bparallelism\x18\x05\x20\x01(\rR\x0bparallelis\
+    m\x12\x14\n\x05nonce\x18\x06\x20\x01(\x0cR\x05nonce\x12\x1e\n\nciphertex\
+    t\x18\x07\x20\x01(\x0cR\nciphertext\"\x1d\n\x03Kdf\x12\x08\n\x04NONE\x10\
+    \0\x12\x0c\n\x08ARGON2ID\x10\x01\"\xb6\x01\n\tPlaintext\x12\x14\n\x05val\
+    ue\x18\x01\x20\x01(\x0cR\x05value\x125\n\x08encoding\x18\x02\x20\x01(\
+    \x0e2\x19.fhers.Plaintext.EncodingR\x08encoding\x12\x14\n\x05level\x18\
+    \x03\x20\x01(\rR\x05level\x12\x19\n\x08poly_ntt\x18\x04\x20\x01(\x0cR\
+    \x07polyNtt\"+\n\x08Encoding\x12\x0b\n\x07UNKNOWN\x10\0\x12\x08\n\x04POL\
+    Y\x10\x01\x12\x08\n\x04SIMD\x10\x02b\x06proto3\
 ";
 
 /// `FileDescriptorProto` object which was a source for this generated file
@@ -1354,7 +2004,7 @@ pub fn file_descriptor() -> &'static ::protobuf::reflect::FileDescriptor {
     file_descriptor.get(|| {
         let generated_file_descriptor = generated_file_descriptor_lazy.get(|| {
             let mut deps = ::std::vec::Vec::with_capacity(0);
-            let mut messages = ::std::vec::Vec::with_capacity(8);
+            let mut messages = ::std::vec::Vec::with_capacity(11);
             messages.push(Ciphertext::generated_message_descriptor_data());
             messages.push(RGSWCiphertext::generated_message_descriptor_data());
             messages.push(KeySwitchingKey::generated_message_descriptor_data());
@@ -1363,7 +2013,12 @@ pub fn file_descriptor() -> &'static ::protobuf::reflect::FileDescriptor {
             messages.push(EvaluationKey::generated_message_descriptor_data());
             messages.push(Parameters::generated_message_descriptor_data());
             messages.push(PublicKey::generated_message_descriptor_data());
-            let mut enums = ::std::vec::Vec::with_capacity(0);
+            messages.push(SecretKey::generated_message_descriptor_data());
+            messages.push(WrappedSecretKey::generated_message_descriptor_data());
+            messages.push(Plaintext::generated_message_descriptor_data());
+            let mut enums = ::std::vec::Vec::with_capacity(2);
+            enums.push(wrapped_secret_key::Kdf::generated_enum_descriptor_data());
+            enums.push(plaintext::Encoding::generated_enum_descriptor_data());
             ::protobuf::reflect::GeneratedFileDescriptor::new_generated(
                 file_descriptor_proto(),
                 deps,
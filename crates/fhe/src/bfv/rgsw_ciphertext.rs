@@ -109,34 +109,50 @@ impl FheEncrypter<Plaintext, RGSWCiphertext> for SecretKey {
     }
 }
 
-impl Mul<&RGSWCiphertext> for &Ciphertext {
-    type Output = Ciphertext;
-
-    fn mul(self, rhs: &RGSWCiphertext) -> Self::Output {
-        assert_eq!(
-            self.par, rhs.ksk0.par,
-            "Ciphertext and RGSWCiphertext must have the same parameters"
-        );
-        assert_eq!(
-            self.level, rhs.ksk0.ciphertext_level,
-            "Ciphertext and RGSWCiphertext must have the same level"
-        );
-        assert_eq!(self.c.len(), 2, "Ciphertext must have two parts");
+impl Ciphertext {
+    /// Returns the external product of this ciphertext and the
+    /// [`RGSWCiphertext`] `rhs`. Returns an error if the parameters or the
+    /// levels differ, or if this ciphertext does not have two parts.
+    pub fn try_external_product(&self, rhs: &RGSWCiphertext) -> Result<Ciphertext> {
+        if self.par != rhs.ksk0.par {
+            return Err(Error::ParametersMismatch);
+        }
+        if self.level != rhs.ksk0.ciphertext_level {
+            return Err(Error::LevelMismatch {
+                expected: rhs.ksk0.ciphertext_level,
+                found: self.level,
+            });
+        }
+        if self.c.len() != 2 {
+            return Err(Error::InvalidCiphertextSize {
+                expected: 2,
+                found: self.c.len(),
+            });
+        }
 
         let mut ct0 = self.c[0].clone();
         let mut ct1 = self.c[1].clone();
         ct0.change_representation(Representation::PowerBasis);
         ct1.change_representation(Representation::PowerBasis);
 
-        let (c0, c1) = rhs.ksk0.key_switch(&ct0).unwrap();
-        let (c0p, c1p) = rhs.ksk1.key_switch(&ct1).unwrap();
+        let (c0, c1) = rhs.ksk0.key_switch(&ct0)?;
+        let (c0p, c1p) = rhs.ksk1.key_switch(&ct1)?;
 
-        Ciphertext {
+        Ok(Ciphertext {
             par: self.par.clone(),
             seed: None,
             c: vec![&c0 + &c0p, &c1 + &c1p],
             level: self.level,
-        }
+            noise: None,
+        })
+    }
+}
+
+impl Mul<&RGSWCiphertext> for &Ciphertext {
+    type Output = Ciphertext;
+
+    fn mul(self, rhs: &RGSWCiphertext) -> Self::Output {
+        self.try_external_product(rhs).unwrap()
     }
 }
 
@@ -154,7 +170,8 @@ mod tests {
 
     use crate::bfv::{BfvParameters, Ciphertext, Encoding, Plaintext, SecretKey};
     use fhe_traits::{DeserializeParametrized, FheDecrypter, FheEncoder, FheEncrypter, Serialize};
-    use rand::thread_rng;
+    use rand::{thread_rng, RngCore, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
 
     use super::RGSWCiphertext;
 
@@ -190,6 +207,45 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn try_external_product() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BfvParameters::default_arc(3, 8);
+        let sk = SecretKey::random(&params, &mut rng);
+        let v = params.plaintext.random_vec(params.degree(), &mut rng);
+        let pt = Plaintext::try_encode(&v, Encoding::simd(), &params)?;
+        let ct: Ciphertext = sk.try_encrypt(&pt, &mut rng)?;
+        let ct_rgsw: RGSWCiphertext = sk.try_encrypt(&pt, &mut rng)?;
+        assert_eq!(ct.try_external_product(&ct_rgsw)?, &ct * &ct_rgsw);
+
+        let mut ct1 = ct.clone();
+        ct1.mod_switch_to_next_level()?;
+        assert_eq!(
+            ct1.try_external_product(&ct_rgsw),
+            Err(crate::Error::LevelMismatch {
+                expected: 0,
+                found: 1
+            })
+        );
+        assert_eq!(
+            (&ct * &ct).try_external_product(&ct_rgsw),
+            Err(crate::Error::InvalidCiphertextSize {
+                expected: 2,
+                found: 3
+            })
+        );
+
+        let other_params = BfvParameters::default_arc(2, 8);
+        let other_sk = SecretKey::random(&other_params, &mut rng);
+        let other_pt = Plaintext::try_encode(&v, Encoding::simd(), &other_params)?;
+        let other_ct: Ciphertext = other_sk.try_encrypt(&other_pt, &mut rng)?;
+        assert_eq!(
+            other_ct.try_external_product(&ct_rgsw),
+            Err(crate::Error::ParametersMismatch)
+        );
+        Ok(())
+    }
+
     #[test]
     fn serialize() -> Result<(), Box<dyn Error>> {
         let mut rng = thread_rng();
@@ -208,4 +264,28 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn deterministic_encryption() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BfvParameters::default_arc(2, 8);
+        let pt = Plaintext::try_encode(
+            &params.plaintext.random_vec(params.degree(), &mut rng),
+            Encoding::poly(),
+            &params,
+        )?;
+
+        let mut seed = <ChaCha8Rng as SeedableRng>::Seed::default();
+        rng.fill_bytes(&mut seed);
+        let mut outputs = vec![];
+        for _ in 0..2 {
+            let mut rng = ChaCha8Rng::from_seed(seed);
+            let sk = SecretKey::random(&params, &mut rng);
+            let ct: RGSWCiphertext = sk.try_encrypt(&pt, &mut rng)?;
+            outputs.push(ct);
+        }
+        assert_eq!(outputs[0], outputs[1]);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,78 @@
+//! Smudging noise, which statistically hides the noise of ciphertexts in the
+//! multiparty protocols and when flooding ciphertexts.
+
+use crate::bfv::BfvParameters;
+use crate::{Error, Result};
+use fhe_math::rq::{traits::TryConvertFrom, Context, Poly, Representation};
+use rand::{CryptoRng, Rng, RngCore};
+use std::sync::Arc;
+use zeroize::Zeroizing;
+
+/// Returns an error if a smudging noise of `smudging_bits` bits exceeds the
+/// noise budget of a ciphertext in the context `ctx`, i.e., `q / (2 * t)`.
+pub(crate) fn check_smudging_bits(
+    par: &BfvParameters,
+    ctx: &Arc<Context>,
+    smudging_bits: usize,
+) -> Result<()> {
+    let plaintext_bits = 64 - par.plaintext().leading_zeros() as usize;
+    if smudging_bits + plaintext_bits + 1 >= ctx.modulus().bits() as usize {
+        Err(Error::SmudgingTooLarge(smudging_bits))
+    } else {
+        Ok(())
+    }
+}
+
+/// Sample a polynomial of degree `degree` in Ntt representation with
+/// coefficients uniform in `[-2^bits, 2^bits]`.
+///
+/// The coefficients may be larger than the moduli: each coefficient is sampled
+/// as an integer `x` uniform in `[0, 2^(bits + 1)]`, from words of 64 bits,
+/// and `x - 2^bits` is reduced modulo every modulus.
+pub(crate) fn smudging_noise<R: RngCore + CryptoRng>(
+    ctx: &Arc<Context>,
+    degree: usize,
+    bits: usize,
+    rng: &mut R,
+) -> Result<Poly> {
+    // The integers x have bits + 2 bits, stored in little-endian words.
+    let words = (bits + 2).div_ceil(64);
+    let top_bits = (bits + 2) - 64 * (words - 1);
+    let top_mask = u64::MAX >> (64 - top_bits);
+    let top_bit = 1u64 << ((bits + 1) % 64);
+    let bounds = ctx
+        .moduli_operators()
+        .iter()
+        .map(|qi| qi.pow(2, bits as u64))
+        .collect::<Vec<_>>();
+
+    let mut x = Zeroizing::new(vec![0u64; words]);
+    let mut residues = Zeroizing::new(vec![0u64; ctx.moduli().len() * degree]);
+    for j in 0..degree {
+        // Rejection sampling of x <= 2^(bits + 1).
+        loop {
+            rng.fill(x.as_mut_slice());
+            x[words - 1] &= top_mask;
+            let above = x[words - 1] & top_bit != 0
+                && (x[words - 1] != top_bit || x[..words - 1].iter().any(|w| *w != 0));
+            if !above {
+                break;
+            }
+        }
+        for (i, (qi, bound)) in ctx.moduli_operators().iter().zip(&bounds).enumerate() {
+            let xi = x.iter().rev().fold(0u64, |acc, w| {
+                qi.reduce_u128(((acc as u128) << 64) | *w as u128)
+            });
+            residues[i * degree + j] = qi.sub(xi, *bound);
+        }
+    }
+
+    let mut e = Poly::try_convert_from(
+        std::mem::take(&mut *residues),
+        ctx,
+        false,
+        Representation::PowerBasis,
+    )?;
+    e.change_representation(Representation::Ntt);
+    Ok(e)
+}
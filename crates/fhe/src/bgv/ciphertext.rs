@@ -0,0 +1,260 @@
+//! Ciphertext type in the BGV encryption scheme.
+
+use crate::bgv::{parameters::BgvParameters, proto::bgv::Ciphertext as CiphertextProto};
+use crate::{Error, Result};
+use fhe_math::rq::{traits::TryConvertFrom, Poly, Representation};
+use fhe_traits::{
+    DeserializeParametrized, DeserializeWithContext, FheCiphertext, FheParametrized, Serialize,
+};
+use protobuf::Message;
+use std::sync::Arc;
+
+/// A ciphertext encrypting a plaintext.
+///
+/// In BGV, a ciphertext `c` at level `l` satisfies `<c, s> = f * m + t * e`
+/// modulo the ciphertext modulus `Q_l`, where `t` is the plaintext modulus and
+/// `f` is the correction factor, which is updated by the modulus switching.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ciphertext {
+    /// The parameters of the underlying BGV encryption scheme.
+    pub(crate) par: Arc<BgvParameters>,
+
+    /// The ciphertext elements.
+    pub(crate) c: Vec<Poly>,
+
+    /// The ciphertext level
+    pub(crate) level: usize,
+
+    /// The factor by which the plaintext is multiplied, modulo the plaintext
+    /// modulus.
+    pub(crate) correction_factor: u64,
+}
+
+impl Ciphertext {
+    /// Modulo switch the ciphertext to the last level.
+    pub fn mod_switch_to_last_level(&mut self) -> Result<()> {
+        while self.level < self.par.max_level() {
+            self.mod_switch_to_next_level()?
+        }
+        Ok(())
+    }
+
+    /// Modulo switch the ciphertext to the next level.
+    ///
+    /// The ciphertext is divided by the last modulus q of its context, after
+    /// being corrected by a multiple of the plaintext modulus so that the
+    /// division is exact. The encrypted plaintext is therefore multiplied by
+    /// q^(-1) modulo the plaintext modulus, which is accounted for in the
+    /// correction factor.
+    pub fn mod_switch_to_next_level(&mut self) -> Result<()> {
+        if self.level < self.par.max_level() {
+            let ms = &self.par.mod_switch[self.level];
+            let next_ctx = self.par.ctx_at_level(self.level + 1)?;
+            for ci in self.c.iter_mut() {
+                ci.change_representation(Representation::PowerBasis);
+                *ci = ms.mod_switch_down(ci, next_ctx)?;
+                ci.change_representation(Representation::Ntt);
+            }
+            self.correction_factor = self
+                .par
+                .plaintext
+                .mul(self.correction_factor, ms.q_last_inv_mod_t);
+            self.level += 1
+        }
+        Ok(())
+    }
+
+    /// Create a ciphertext from a vector of polynomials and a correction
+    /// factor. A ciphertext must contain at least two polynomials, and all
+    /// polynomials must be in Ntt representation and with the same context.
+    pub fn new(c: Vec<Poly>, correction_factor: u64, par: &Arc<BgvParameters>) -> Result<Self> {
+        if c.len() < 2 {
+            return Err(Error::TooFewValues(c.len(), 2));
+        }
+
+        let ctx = c[0].ctx();
+        let level = par.level_of_ctx(ctx)?;
+
+        // Check that all polynomials have the expected representation and context.
+        for ci in c.iter() {
+            if ci.representation() != &Representation::Ntt {
+                return Err(Error::MathError(fhe_math::Error::IncorrectRepresentation(
+                    ci.representation().clone(),
+                    Representation::Ntt,
+                )));
+            }
+            if ci.ctx() != ctx {
+                return Err(Error::MathError(fhe_math::Error::InvalidContext));
+            }
+        }
+
+        // The correction factor must be invertible modulo the plaintext modulus.
+        let correction_factor = par.plaintext.reduce(correction_factor);
+        par.plaintext_inv(correction_factor)?;
+
+        Ok(Self {
+            par: par.clone(),
+            c,
+            level,
+            correction_factor,
+        })
+    }
+
+    /// Get the i-th polynomial of the ciphertext.
+    pub fn get(&self, i: usize) -> Option<&Poly> {
+        self.c.get(i)
+    }
+
+    /// Returns the level of this ciphertext.
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    /// Returns the correction factor of this ciphertext.
+    pub fn correction_factor(&self) -> u64 {
+        self.correction_factor
+    }
+
+    /// Multiply the ciphertext by a scalar, so that its correction factor
+    /// becomes `correction_factor`.
+    pub(crate) fn set_correction_factor(&mut self, correction_factor: u64) -> Result<()> {
+        if correction_factor != self.correction_factor {
+            let scalar = self.par.plaintext.mul(
+                correction_factor,
+                self.par.plaintext_inv(self.correction_factor)?,
+            );
+            let ctx = self.par.ctx_at_level(self.level)?;
+            let mut scalar_poly =
+                Poly::try_convert_from(&[scalar], ctx, false, Representation::PowerBasis)?;
+            scalar_poly.change_representation(Representation::Ntt);
+            self.c.iter_mut().for_each(|ci| *ci *= &scalar_poly);
+            self.correction_factor = correction_factor;
+        }
+        Ok(())
+    }
+}
+
+impl FheCiphertext for Ciphertext {}
+
+impl FheParametrized for Ciphertext {
+    type Parameters = BgvParameters;
+}
+
+impl Serialize for Ciphertext {
+    fn to_bytes(&self) -> Vec<u8> {
+        CiphertextProto::from(self).write_to_bytes().unwrap()
+    }
+}
+
+impl DeserializeParametrized for Ciphertext {
+    fn from_bytes(bytes: &[u8], par: &Arc<BgvParameters>) -> Result<Self> {
+        let ctp =
+            CiphertextProto::parse_from_bytes(bytes).map_err(|_| Error::SerializationError)?;
+        if ctp.level as usize > par.max_level() {
+            return Err(Error::SerializationError);
+        }
+        let ctx = par.ctx_at_level(ctp.level as usize)?;
+        let c = ctp
+            .c
+            .iter()
+            .map(|ci| Poly::from_bytes(ci, ctx).map_err(Error::MathError))
+            .collect::<Result<Vec<Poly>>>()?;
+        Ciphertext::new(c, ctp.correction_factor, par)
+    }
+
+    type Error = Error;
+}
+
+/// Conversions from and to protobuf.
+impl From<&Ciphertext> for CiphertextProto {
+    fn from(ct: &Ciphertext) -> Self {
+        let mut proto = CiphertextProto::new();
+        proto.c = ct.c.iter().map(|ci| ci.to_bytes()).collect();
+        proto.level = ct.level as u32;
+        proto.correction_factor = ct.correction_factor;
+        proto
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bgv::{BgvParameters, Ciphertext, Encoding, Plaintext, SecretKey};
+    use fhe_traits::{
+        DeserializeParametrized, FheDecoder, FheDecrypter, FheEncoder, FheEncrypter, Serialize,
+    };
+    use rand::thread_rng;
+    use std::error::Error;
+
+    #[test]
+    fn serialize() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        for params in [
+            BgvParameters::default_arc(1, 8),
+            BgvParameters::default_arc(6, 8),
+        ] {
+            let sk = SecretKey::random(&params, &mut rng);
+            let v = params.plaintext.random_vec(params.degree(), &mut rng);
+            let pt = Plaintext::try_encode(&v, Encoding::simd(), &params)?;
+            let mut ct: Ciphertext = sk.try_encrypt(&pt, &mut rng)?;
+            assert_eq!(ct, Ciphertext::from_bytes(&ct.to_bytes(), &params)?);
+
+            ct.mod_switch_to_last_level()?;
+            assert_eq!(ct, Ciphertext::from_bytes(&ct.to_bytes(), &params)?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn new() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BgvParameters::default_arc(6, 8);
+        let sk = SecretKey::random(&params, &mut rng);
+        let v = params.plaintext.random_vec(params.degree(), &mut rng);
+        let pt = Plaintext::try_encode(&v, Encoding::simd(), &params)?;
+        let mut ct: Ciphertext = sk.try_encrypt(&pt, &mut rng)?;
+        ct.mod_switch_to_next_level()?;
+
+        let c = vec![ct.get(0).unwrap().clone(), ct.get(1).unwrap().clone()];
+        assert_eq!(
+            ct,
+            Ciphertext::new(c.clone(), ct.correction_factor(), &params)?
+        );
+        assert!(Ciphertext::new(c[..1].to_vec(), 1, &params).is_err());
+        assert!(Ciphertext::new(c, 0, &params).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn mod_switch() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        for params in [
+            BgvParameters::default_arc(1, 8),
+            BgvParameters::default_arc(6, 8),
+        ] {
+            for _ in 0..20 {
+                let sk = SecretKey::random(&params, &mut rng);
+                let v = params.plaintext.random_vec(params.degree(), &mut rng);
+                let pt = Plaintext::try_encode(&v, Encoding::simd(), &params)?;
+                let mut ct: Ciphertext = sk.try_encrypt(&pt, &mut rng)?;
+                assert_eq!(ct.correction_factor(), 1);
+
+                while ct.level() < params.max_level() {
+                    ct.mod_switch_to_next_level()?;
+                    println!("Noise: {}", unsafe { sk.measure_noise(&ct)? });
+                    let pt2 = sk.try_decrypt(&ct)?;
+                    assert_eq!(Vec::<u64>::try_decode(&pt2, Encoding::simd())?, v);
+                }
+                assert_eq!(ct.level(), params.max_level());
+
+                let mut ct: Ciphertext = sk.try_encrypt(&pt, &mut rng)?;
+                ct.mod_switch_to_last_level()?;
+                assert_eq!(ct.level(), params.max_level());
+                let pt2 = sk.try_decrypt(&ct)?;
+                assert_eq!(Vec::<u64>::try_decode(&pt2, Encoding::simd())?, v);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,170 @@
+//! Public keys for the BGV encryption scheme
+
+use crate::bgv::{parameters::reduce_to_ctx, BgvParameters, Ciphertext, Plaintext};
+use crate::{Error, Result};
+use fhe_math::rq::{Poly, Representation};
+use fhe_traits::{DeserializeParametrized, FheEncrypter, FheParametrized, Serialize};
+use num_bigint::BigUint;
+use rand::{CryptoRng, RngCore};
+use std::sync::Arc;
+use zeroize::Zeroizing;
+
+use super::SecretKey;
+
+/// Public key for the BGV encryption scheme.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PublicKey {
+    pub(crate) par: Arc<BgvParameters>,
+    pub(crate) c: Ciphertext,
+}
+
+impl PublicKey {
+    /// Generate a new [`PublicKey`] from a [`SecretKey`].
+    pub fn new<R: RngCore + CryptoRng>(sk: &SecretKey, rng: &mut R) -> Self {
+        let zero = Poly::zero(&sk.par.ctx[0], Representation::Ntt);
+        let mut c = sk.encrypt_poly(&zero, rng).unwrap();
+        // The polynomials of a public key should not allow for variable time
+        // computation.
+        c.c.iter_mut()
+            .for_each(|p| p.disallow_variable_time_computations());
+        Self {
+            par: sk.par.clone(),
+            c,
+        }
+    }
+}
+
+impl FheParametrized for PublicKey {
+    type Parameters = BgvParameters;
+}
+
+impl FheEncrypter<Plaintext, Ciphertext> for PublicKey {
+    type Error = Error;
+
+    fn try_encrypt<R: RngCore + CryptoRng>(
+        &self,
+        pt: &Plaintext,
+        rng: &mut R,
+    ) -> Result<Ciphertext> {
+        assert_eq!(self.par, pt.par);
+
+        // The public key is an encryption of zero with an error multiple of the
+        // plaintext modulus, so it remains valid after dropping moduli.
+        let ctx = self.par.ctx_at_level(pt.level)?;
+        let p0 = reduce_to_ctx(&self.c.c[0], ctx)?;
+        let p1 = reduce_to_ctx(&self.c.c[1], ctx)?;
+
+        let t = BigUint::from(self.par.plaintext());
+        let u = Zeroizing::new(Poly::small(
+            ctx,
+            Representation::Ntt,
+            self.par.variance,
+            rng,
+        )?);
+        let mut e1 = Zeroizing::new(Poly::small(
+            ctx,
+            Representation::Ntt,
+            self.par.variance,
+            rng,
+        )?);
+        let mut e2 = Zeroizing::new(Poly::small(
+            ctx,
+            Representation::Ntt,
+            self.par.variance,
+            rng,
+        )?);
+        *e1.as_mut() *= &t;
+        *e2.as_mut() *= &t;
+
+        let m = Zeroizing::new(pt.to_poly(1));
+        let mut c0 = u.as_ref() * &p0;
+        c0 += &e1;
+        c0 += &m;
+        let mut c1 = u.as_ref() * &p1;
+        c1 += &e2;
+
+        // It is now safe to enable variable time computations.
+        unsafe {
+            c0.allow_variable_time_computations();
+            c1.allow_variable_time_computations()
+        }
+
+        Ok(Ciphertext {
+            par: self.par.clone(),
+            c: vec![c0, c1],
+            level: pt.level,
+            correction_factor: 1,
+        })
+    }
+}
+
+impl Serialize for PublicKey {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.c.to_bytes()
+    }
+}
+
+impl DeserializeParametrized for PublicKey {
+    type Error = Error;
+
+    fn from_bytes(bytes: &[u8], par: &Arc<Self::Parameters>) -> Result<Self> {
+        let mut c = Ciphertext::from_bytes(bytes, par)?;
+        if c.level != 0 || c.c.len() != 2 || c.correction_factor != 1 {
+            return Err(Error::SerializationError);
+        }
+        c.c.iter_mut()
+            .for_each(|p| p.disallow_variable_time_computations());
+        Ok(Self {
+            par: par.clone(),
+            c,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PublicKey;
+    use crate::bgv::{BgvParameters, Ciphertext, Encoding, Plaintext, SecretKey};
+    use fhe_traits::{
+        DeserializeParametrized, FheDecoder, FheDecrypter, FheEncoder, FheEncrypter, Serialize,
+    };
+    use rand::thread_rng;
+    use std::error::Error;
+
+    #[test]
+    fn encrypt_decrypt() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        for params in [
+            BgvParameters::default_arc(1, 8),
+            BgvParameters::default_arc(6, 8),
+        ] {
+            for level in 0..=params.max_level() {
+                for _ in 0..20 {
+                    let sk = SecretKey::random(&params, &mut rng);
+                    let pk = PublicKey::new(&sk, &mut rng);
+
+                    let v = params.plaintext.random_vec(params.degree(), &mut rng);
+                    let pt = Plaintext::try_encode(&v, Encoding::simd_at_level(level), &params)?;
+                    let ct: Ciphertext = pk.try_encrypt(&pt, &mut rng)?;
+                    assert_eq!(ct.level(), level);
+
+                    println!("Noise: {}", unsafe { sk.measure_noise(&ct)? });
+                    let pt2 = sk.try_decrypt(&ct)?;
+                    assert_eq!(Vec::<u64>::try_decode(&pt2, Encoding::simd())?, v);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn serialize() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BgvParameters::default_arc(6, 8);
+        let sk = SecretKey::random(&params, &mut rng);
+        let pk = PublicKey::new(&sk, &mut rng);
+        assert_eq!(pk, PublicKey::from_bytes(&pk.to_bytes(), &params)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,209 @@
+//! Relinearization keys for the BGV encryption scheme
+
+use crate::bfv::{
+    proto::bfv::{
+        KeySwitchingKey as KeySwitchingKeyProto, RelinearizationKey as RelinearizationKeyProto,
+    },
+    traits::TryConvertFrom,
+    KeySwitchingKey, KeySwitchingMode, SecretKey as BfvSecretKey,
+};
+use crate::bgv::{parameters::reduce_to_ctx, BgvParameters, Ciphertext};
+use crate::{Error, Result};
+use fhe_math::{
+    rns::RnsContext,
+    rq::{Poly, Representation},
+};
+use fhe_traits::{DeserializeParametrized, FheParametrized, Serialize};
+use fhe_util::inverse;
+use itertools::Itertools;
+use num_bigint::BigUint;
+use protobuf::{Message, MessageField};
+use rand::{CryptoRng, RngCore};
+use std::sync::Arc;
+use zeroize::Zeroizing;
+
+use super::SecretKey;
+
+/// Relinearization key for the BGV encryption scheme.
+///
+/// The key is a key switching key from `s^2 / t` to `s` at the first level,
+/// where `t` is the plaintext modulus and the division is modulo the
+/// ciphertext modulus: multiplying the output of the key switching by `t`
+/// yields an encryption of `s^2` whose noise is a multiple of the plaintext
+/// modulus. Ciphertexts at lower levels are lifted to the first level before
+/// being relinearized.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RelinearizationKey {
+    pub(crate) par: Arc<BgvParameters>,
+    pub(crate) ksk: KeySwitchingKey,
+}
+
+impl RelinearizationKey {
+    /// Generate a [`RelinearizationKey`] from a [`SecretKey`].
+    pub fn new<R: RngCore + CryptoRng>(sk: &SecretKey, rng: &mut R) -> Result<Self> {
+        Self::new_with_mode(sk, KeySwitchingMode::default(), rng)
+    }
+
+    /// Generate a [`RelinearizationKey`] from a [`SecretKey`], using the
+    /// specified key switching decomposition.
+    pub fn new_with_mode<R: RngCore + CryptoRng>(
+        sk: &SecretKey,
+        mode: KeySwitchingMode,
+        rng: &mut R,
+    ) -> Result<Self> {
+        let par = &sk.par;
+        // The builder checks that the plaintext modulus is coprime with the
+        // ciphertext moduli.
+        let t_inv_rests = par
+            .moduli
+            .iter()
+            .map(|qi| inverse(par.plaintext() % qi, *qi).unwrap())
+            .collect_vec();
+        let t_inv = RnsContext::new(&par.moduli)?.lift((&t_inv_rests).into());
+
+        let s = sk.to_poly(&Poly::zero(&par.ctx[0], Representation::Ntt))?;
+        let mut s2_over_t = Zeroizing::new(s.as_ref() * s.as_ref());
+        *s2_over_t.as_mut() *= &t_inv;
+        s2_over_t.change_representation(Representation::PowerBasis);
+
+        let bfv_sk = BfvSecretKey::new(sk.coeffs.to_vec(), &par.key_switching_par);
+        let ksk = KeySwitchingKey::new_with_mode(&bfv_sk, &s2_over_t, 0, 0, mode, rng)?;
+        Ok(Self {
+            par: par.clone(),
+            ksk,
+        })
+    }
+
+    /// Relinearize an "extended" ciphertext (c0, c1, c2) into a [`Ciphertext`]
+    pub fn relinearizes(&self, ct: &mut Ciphertext) -> Result<()> {
+        if ct.c.len() != 3 {
+            Err(Error::InvalidCiphertextSize {
+                expected: 3,
+                found: ct.c.len(),
+            })
+        } else if ct.par != self.par {
+            Err(Error::ParametersMismatch)
+        } else {
+            let mut c2 = ct.c.pop().unwrap();
+            c2.change_representation(Representation::PowerBasis);
+
+            // The lift of c2 is congruent to c2 modulo the ciphertext modulus of
+            // its level, so reducing the output of the key switching back to
+            // that level yields the expected result.
+            let level = self.par.level_of_ctx(c2.ctx())?;
+            let c2_lifted = c2.scale(&self.par.extenders[level])?;
+            let (c0, c1) = self.ksk.key_switch(&c2_lifted)?;
+            let t = BigUint::from(self.par.plaintext());
+            let mut c0 = reduce_to_ctx(&c0, c2.ctx())?;
+            let mut c1 = reduce_to_ctx(&c1, c2.ctx())?;
+            c0 *= &t;
+            c1 *= &t;
+            ct.c[0] += &c0;
+            ct.c[1] += &c1;
+            Ok(())
+        }
+    }
+}
+
+impl FheParametrized for RelinearizationKey {
+    type Parameters = BgvParameters;
+}
+
+impl From<&RelinearizationKey> for RelinearizationKeyProto {
+    fn from(value: &RelinearizationKey) -> Self {
+        let mut rk = RelinearizationKeyProto::new();
+        rk.ksk = MessageField::some(KeySwitchingKeyProto::from(&value.ksk));
+        rk
+    }
+}
+
+impl Serialize for RelinearizationKey {
+    fn to_bytes(&self) -> Vec<u8> {
+        RelinearizationKeyProto::from(self)
+            .write_to_bytes()
+            .unwrap()
+    }
+}
+
+impl DeserializeParametrized for RelinearizationKey {
+    type Error = Error;
+
+    fn from_bytes(bytes: &[u8], par: &Arc<Self::Parameters>) -> Result<Self> {
+        let rk = RelinearizationKeyProto::parse_from_bytes(bytes)
+            .map_err(|_| Error::SerializationError)?;
+        let ksk_proto = rk.ksk.as_ref().ok_or(Error::SerializationError)?;
+        let ksk = KeySwitchingKey::try_convert_from(ksk_proto, &par.key_switching_par)?;
+        if ksk.ciphertext_level != 0 || ksk.ksk_level != 0 {
+            return Err(Error::SerializationError);
+        }
+        Ok(Self {
+            par: par.clone(),
+            ksk,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RelinearizationKey;
+    use crate::bfv::KeySwitchingMode;
+    use crate::bgv::{BgvParameters, Ciphertext, Encoding, Plaintext, SecretKey};
+    use fhe_traits::{
+        DeserializeParametrized, FheDecoder, FheDecrypter, FheEncoder, FheEncrypter, Serialize,
+    };
+    use rand::thread_rng;
+    use std::error::Error;
+
+    #[test]
+    fn relinearization() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BgvParameters::default_arc(4, 8);
+        for mode in [
+            KeySwitchingMode::Rns,
+            KeySwitchingMode::Hybrid { dnum: 1 },
+            KeySwitchingMode::Hybrid { dnum: 2 },
+            KeySwitchingMode::Hybrid { dnum: 4 },
+        ] {
+            for _ in 0..10 {
+                let sk = SecretKey::random(&params, &mut rng);
+                let rk = RelinearizationKey::new_with_mode(&sk, mode, &mut rng)?;
+
+                for level in 0..params.max_level() {
+                    let v1 = params.plaintext.random_vec(params.degree(), &mut rng);
+                    let v2 = params.plaintext.random_vec(params.degree(), &mut rng);
+                    let mut expected = v1.clone();
+                    params.plaintext.mul_vec(&mut expected, &v2);
+
+                    let pt1 = Plaintext::try_encode(&v1, Encoding::simd_at_level(level), &params)?;
+                    let pt2 = Plaintext::try_encode(&v2, Encoding::simd_at_level(level), &params)?;
+                    let ct1: Ciphertext = sk.try_encrypt(&pt1, &mut rng)?;
+                    let ct2: Ciphertext = sk.try_encrypt(&pt2, &mut rng)?;
+
+                    let mut ct3 = &ct1 * &ct2;
+                    assert!(rk.relinearizes(&mut ct1.clone()).is_err());
+                    rk.relinearizes(&mut ct3)?;
+                    assert_eq!(ct3.c.len(), 2);
+
+                    println!("Noise: {}", unsafe { sk.measure_noise(&ct3)? });
+                    let pt = sk.try_decrypt(&ct3)?;
+                    assert_eq!(Vec::<u64>::try_decode(&pt, Encoding::simd())?, expected);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn serialize() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BgvParameters::default_arc(4, 8);
+        let sk = SecretKey::random(&params, &mut rng);
+        for mode in [KeySwitchingMode::Rns, KeySwitchingMode::Hybrid { dnum: 2 }] {
+            let rk = RelinearizationKey::new_with_mode(&sk, mode, &mut rng)?;
+            let bytes = rk.to_bytes();
+            assert_eq!(rk, RelinearizationKey::from_bytes(&bytes, &params)?);
+        }
+        assert!(RelinearizationKey::from_bytes(&[1, 2, 3], &params).is_err());
+        Ok(())
+    }
+}
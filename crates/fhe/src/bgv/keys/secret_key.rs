@@ -0,0 +1,266 @@
+//! Secret keys for the BGV encryption scheme
+
+use crate::bgv::{BgvParameters, Ciphertext, Plaintext};
+use crate::{Error, Result};
+use fhe_math::rq::{traits::TryConvertFrom, Poly, Representation};
+use fhe_traits::{FheDecrypter, FheEncrypter, FheParametrized};
+use fhe_util::sample_vec_cbd;
+use itertools::izip;
+use ndarray::{ArrayViewMut1, Axis};
+use num_bigint::BigUint;
+use rand::{CryptoRng, RngCore};
+use std::sync::Arc;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+/// Secret key for the BGV encryption scheme.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SecretKey {
+    pub(crate) par: Arc<BgvParameters>,
+    pub(crate) coeffs: Box<[i64]>,
+}
+
+impl Zeroize for SecretKey {
+    fn zeroize(&mut self) {
+        self.coeffs.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SecretKey {}
+
+impl SecretKey {
+    /// Generate a random [`SecretKey`].
+    pub fn random<R: RngCore + CryptoRng>(par: &Arc<BgvParameters>, rng: &mut R) -> Self {
+        let s_coefficients = sample_vec_cbd(par.degree(), par.variance, rng).unwrap();
+        Self::new(s_coefficients, par)
+    }
+
+    /// Generate a [`SecretKey`] from its coefficients.
+    pub(crate) fn new(coeffs: Vec<i64>, par: &Arc<BgvParameters>) -> Self {
+        Self {
+            par: par.clone(),
+            coeffs: coeffs.into_boxed_slice(),
+        }
+    }
+
+    /// Returns the secret key as a polynomial in Ntt representation at the
+    /// level of the provided polynomial.
+    pub(crate) fn to_poly(&self, p: &Poly) -> Result<Zeroizing<Poly>> {
+        let mut s = Zeroizing::new(Poly::try_convert_from(
+            self.coeffs.as_ref(),
+            p.ctx(),
+            false,
+            Representation::PowerBasis,
+        )?);
+        s.change_representation(Representation::Ntt);
+        Ok(s)
+    }
+
+    /// Computes the inner product of the ciphertext with the powers of the
+    /// secret key, in PowerBasis representation.
+    fn decryption_poly(&self, ct: &Ciphertext) -> Result<Zeroizing<Poly>> {
+        let s = self.to_poly(&ct.c[0])?;
+        let mut si = s.clone();
+
+        let mut c = Zeroizing::new(ct.c[0].clone());
+        c.disallow_variable_time_computations();
+
+        for i in 1..ct.c.len() {
+            let mut cis = Zeroizing::new(ct.c[i].clone());
+            cis.disallow_variable_time_computations();
+            *cis.as_mut() *= si.as_ref();
+            *c.as_mut() += &cis;
+            *si.as_mut() *= s.as_ref();
+        }
+        c.change_representation(Representation::PowerBasis);
+        Ok(c)
+    }
+
+    /// Measure the noise in a [`Ciphertext`], i.e., the number of bits of the
+    /// largest coefficient of the centered decryption polynomial. Decryption
+    /// is correct as long as it is smaller than the size of the ciphertext
+    /// modulus.
+    ///
+    /// # Safety
+    ///
+    /// This operations may run in a variable time depending on the value of the
+    /// noise.
+    pub unsafe fn measure_noise(&self, ct: &Ciphertext) -> Result<usize> {
+        let c = self.decryption_poly(ct)?;
+
+        let ciphertext_modulus = ct.c[0].ctx().modulus();
+        let mut noise = 0usize;
+        for coeff in Vec::<BigUint>::from(c.as_ref()) {
+            noise = std::cmp::max(
+                noise,
+                std::cmp::min(coeff.bits(), (ciphertext_modulus - &coeff).bits()) as usize,
+            )
+        }
+
+        Ok(noise)
+    }
+
+    /// Encrypt a polynomial in Ntt representation, i.e., output a ciphertext
+    /// `(b, a)` such that `b + a * s = p + t * e` for a small error `e`.
+    pub(crate) fn encrypt_poly<R: RngCore + CryptoRng>(
+        &self,
+        p: &Poly,
+        rng: &mut R,
+    ) -> Result<Ciphertext> {
+        assert_eq!(p.representation(), &Representation::Ntt);
+
+        let level = self.par.level_of_ctx(p.ctx())?;
+        let s = self.to_poly(p)?;
+
+        let mut a = Poly::random(p.ctx(), Representation::Ntt, rng);
+        let a_s = Zeroizing::new(&a * s.as_ref());
+
+        let mut b = Poly::small(p.ctx(), Representation::Ntt, self.par.variance, rng)
+            .map_err(Error::MathError)?;
+        b *= &BigUint::from(self.par.plaintext());
+        b -= &a_s;
+        b += p;
+
+        // It is now safe to enable variable time computations.
+        unsafe {
+            a.allow_variable_time_computations();
+            b.allow_variable_time_computations()
+        }
+
+        Ok(Ciphertext {
+            par: self.par.clone(),
+            c: vec![b, a],
+            level,
+            correction_factor: 1,
+        })
+    }
+}
+
+impl FheParametrized for SecretKey {
+    type Parameters = BgvParameters;
+}
+
+impl FheEncrypter<Plaintext, Ciphertext> for SecretKey {
+    type Error = Error;
+
+    fn try_encrypt<R: RngCore + CryptoRng>(
+        &self,
+        pt: &Plaintext,
+        rng: &mut R,
+    ) -> Result<Ciphertext> {
+        assert_eq!(self.par, pt.par);
+        let m = Zeroizing::new(pt.to_poly(1));
+        self.encrypt_poly(m.as_ref(), rng)
+    }
+}
+
+impl FheDecrypter<Plaintext, Ciphertext> for SecretKey {
+    type Error = Error;
+
+    fn try_decrypt(&self, ct: &Ciphertext) -> Result<Plaintext> {
+        if self.par != ct.par {
+            Err(Error::ParametersMismatch)
+        } else {
+            let c = self.decryption_poly(ct)?;
+
+            // Reduce the centered coefficients modulo the plaintext modulus.
+            let mut w = vec![0u64; self.par.degree()];
+            izip!(c.coefficients().axis_iter(Axis(1)), w.iter_mut()).for_each(|(column, wi)| {
+                self.par.plaintext_scalers[ct.level].scale(
+                    column,
+                    ArrayViewMut1::from(std::slice::from_mut(wi)),
+                    0,
+                )
+            });
+
+            // Remove the correction factor.
+            let correction_factor_inv = self.par.plaintext_inv(ct.correction_factor)?;
+            self.par
+                .plaintext
+                .scalar_mul_vec(&mut w, correction_factor_inv);
+
+            Ok(Plaintext {
+                par: self.par.clone(),
+                value: w.into_boxed_slice(),
+                encoding: None,
+                level: ct.level,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SecretKey;
+    use crate::bgv::{BgvParameters, BgvParametersBuilder, Ciphertext, Encoding, Plaintext};
+    use fhe_traits::{FheDecoder, FheDecrypter, FheEncoder, FheEncrypter};
+    use rand::{thread_rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+    use std::error::Error;
+
+    #[test]
+    fn keygen() {
+        let mut rng = thread_rng();
+        let params = BgvParameters::default_arc(1, 8);
+        let sk = SecretKey::random(&params, &mut rng);
+        assert_eq!(sk.par, params);
+
+        sk.coeffs.iter().for_each(|ci| {
+            // Check that this is a small polynomial
+            assert!((*ci).abs() <= 2 * sk.par.variance as i64)
+        })
+    }
+
+    #[test]
+    fn encrypt_decrypt() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        for params in [
+            BgvParameters::default_arc(1, 8),
+            BgvParameters::default_arc(6, 8),
+            // A plaintext modulus which is not prime.
+            BgvParametersBuilder::new()
+                .set_degree(8)
+                .set_plaintext_modulus(1 << 16)
+                .set_moduli_sizes(&[62, 62])
+                .build_arc()?,
+        ] {
+            for level in 0..=params.max_level() {
+                for _ in 0..20 {
+                    let sk = SecretKey::random(&params, &mut rng);
+
+                    let pt = Plaintext::try_encode(
+                        &params.plaintext.random_vec(params.degree(), &mut rng),
+                        Encoding::poly_at_level(level),
+                        &params,
+                    )?;
+                    let ct: Ciphertext = sk.try_encrypt(&pt, &mut rng)?;
+                    let pt2 = sk.try_decrypt(&ct)?;
+
+                    println!("Noise: {}", unsafe { sk.measure_noise(&ct)? });
+                    assert_eq!(pt2, pt);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn deterministic_encryption() -> Result<(), Box<dyn Error>> {
+        let params = BgvParameters::default_arc(2, 8);
+        let sk = SecretKey::random(&params, &mut thread_rng());
+        let v = params
+            .plaintext
+            .random_vec(params.degree(), &mut thread_rng());
+        let pt = Plaintext::try_encode(&v, Encoding::simd(), &params)?;
+
+        let ct1: Ciphertext = sk.try_encrypt(&pt, &mut ChaCha8Rng::from_seed([1u8; 32]))?;
+        let ct2: Ciphertext = sk.try_encrypt(&pt, &mut ChaCha8Rng::from_seed([1u8; 32]))?;
+        assert_eq!(ct1, ct2);
+        assert_eq!(
+            Vec::<u64>::try_decode(&sk.try_decrypt(&ct1)?, Encoding::simd())?,
+            v
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,18 @@
+#![warn(missing_docs, unused_imports)]
+
+//! The Brakerski-Gentry-Vaikuntanathan homomorphic encryption scheme
+
+mod ciphertext;
+mod keys;
+mod ops;
+mod parameters;
+mod plaintext;
+#[allow(renamed_and_removed_lints)]
+mod proto;
+
+pub use crate::bfv::Encoding;
+pub use ciphertext::Ciphertext;
+pub use keys::{PublicKey, RelinearizationKey, SecretKey};
+pub(crate) use parameters::reduce_to_ctx;
+pub use parameters::{BgvParameters, BgvParametersBuilder};
+pub use plaintext::Plaintext;
@@ -0,0 +1,323 @@
+//! Operations over ciphertexts
+
+use super::{Ciphertext, Plaintext};
+use fhe_math::rq::{Poly, Representation};
+use itertools::{izip, Itertools};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+impl Add<&Ciphertext> for &Ciphertext {
+    type Output = Ciphertext;
+
+    fn add(self, rhs: &Ciphertext) -> Ciphertext {
+        let mut self_clone = self.clone();
+        self_clone += rhs;
+        self_clone
+    }
+}
+
+impl AddAssign<&Ciphertext> for Ciphertext {
+    fn add_assign(&mut self, rhs: &Ciphertext) {
+        assert_eq!(self.par, rhs.par);
+        assert_eq!(self.level, rhs.level);
+        assert_eq!(self.c.len(), rhs.c.len());
+
+        if self.correction_factor == rhs.correction_factor {
+            izip!(&mut self.c, &rhs.c).for_each(|(c1i, c2i)| *c1i += c2i);
+        } else {
+            let mut rhs = rhs.clone();
+            rhs.set_correction_factor(self.correction_factor).unwrap();
+            izip!(&mut self.c, &rhs.c).for_each(|(c1i, c2i)| *c1i += c2i);
+        }
+    }
+}
+
+impl Add<&Plaintext> for &Ciphertext {
+    type Output = Ciphertext;
+
+    fn add(self, rhs: &Plaintext) -> Ciphertext {
+        let mut self_clone = self.clone();
+        self_clone += rhs;
+        self_clone
+    }
+}
+
+impl Add<&Ciphertext> for &Plaintext {
+    type Output = Ciphertext;
+
+    fn add(self, rhs: &Ciphertext) -> Ciphertext {
+        rhs + self
+    }
+}
+
+impl AddAssign<&Plaintext> for Ciphertext {
+    fn add_assign(&mut self, rhs: &Plaintext) {
+        assert_eq!(self.par, rhs.par);
+        assert_eq!(self.level, rhs.level);
+
+        let poly = rhs.to_poly(self.correction_factor);
+        self.c[0] += &poly;
+    }
+}
+
+impl Sub<&Ciphertext> for &Ciphertext {
+    type Output = Ciphertext;
+
+    fn sub(self, rhs: &Ciphertext) -> Ciphertext {
+        let mut self_clone = self.clone();
+        self_clone -= rhs;
+        self_clone
+    }
+}
+
+impl SubAssign<&Ciphertext> for Ciphertext {
+    fn sub_assign(&mut self, rhs: &Ciphertext) {
+        assert_eq!(self.par, rhs.par);
+        assert_eq!(self.level, rhs.level);
+        assert_eq!(self.c.len(), rhs.c.len());
+
+        if self.correction_factor == rhs.correction_factor {
+            izip!(&mut self.c, &rhs.c).for_each(|(c1i, c2i)| *c1i -= c2i);
+        } else {
+            let mut rhs = rhs.clone();
+            rhs.set_correction_factor(self.correction_factor).unwrap();
+            izip!(&mut self.c, &rhs.c).for_each(|(c1i, c2i)| *c1i -= c2i);
+        }
+    }
+}
+
+impl Sub<&Plaintext> for &Ciphertext {
+    type Output = Ciphertext;
+
+    fn sub(self, rhs: &Plaintext) -> Ciphertext {
+        let mut self_clone = self.clone();
+        self_clone -= rhs;
+        self_clone
+    }
+}
+
+impl Sub<&Ciphertext> for &Plaintext {
+    type Output = Ciphertext;
+
+    fn sub(self, rhs: &Ciphertext) -> Ciphertext {
+        -(rhs - self)
+    }
+}
+
+impl SubAssign<&Plaintext> for Ciphertext {
+    fn sub_assign(&mut self, rhs: &Plaintext) {
+        assert_eq!(self.par, rhs.par);
+        assert_eq!(self.level, rhs.level);
+
+        let poly = rhs.to_poly(self.correction_factor);
+        self.c[0] -= &poly;
+    }
+}
+
+impl Neg for &Ciphertext {
+    type Output = Ciphertext;
+
+    fn neg(self) -> Ciphertext {
+        let c = self.c.iter().map(|c1i| -c1i).collect_vec();
+        Ciphertext {
+            par: self.par.clone(),
+            c,
+            level: self.level,
+            correction_factor: self.correction_factor,
+        }
+    }
+}
+
+impl Neg for Ciphertext {
+    type Output = Ciphertext;
+
+    fn neg(mut self) -> Ciphertext {
+        self.c.iter_mut().for_each(|c1i| *c1i = -&*c1i);
+        self
+    }
+}
+
+impl MulAssign<&Plaintext> for Ciphertext {
+    fn mul_assign(&mut self, rhs: &Plaintext) {
+        assert_eq!(self.par, rhs.par);
+        assert_eq!(self.level, rhs.level);
+
+        let poly = rhs.to_poly(1);
+        self.c.iter_mut().for_each(|ci| *ci *= &poly);
+    }
+}
+
+impl Mul<&Plaintext> for &Ciphertext {
+    type Output = Ciphertext;
+
+    fn mul(self, rhs: &Plaintext) -> Ciphertext {
+        let mut self_clone = self.clone();
+        self_clone *= rhs;
+        self_clone
+    }
+}
+
+/// The tensor product of two ciphertexts. Unlike in BFV, there is no need to
+/// extend and scale down the ciphertexts: the product is computed directly
+/// modulo the ciphertext modulus, and the correction factors are multiplied.
+/// The output ciphertext can be relinearized using a
+/// [`RelinearizationKey`](super::RelinearizationKey).
+impl Mul<&Ciphertext> for &Ciphertext {
+    type Output = Ciphertext;
+
+    fn mul(self, rhs: &Ciphertext) -> Ciphertext {
+        assert_eq!(self.par, rhs.par);
+        assert_eq!(self.level, rhs.level);
+
+        let ctx = self.c[0].ctx();
+        let mut c = vec![Poly::zero(ctx, Representation::Ntt); self.c.len() + rhs.c.len() - 1];
+        for i in 0..self.c.len() {
+            for j in 0..rhs.c.len() {
+                c[i + j] += &(&self.c[i] * &rhs.c[j])
+            }
+        }
+
+        Ciphertext {
+            par: self.par.clone(),
+            c,
+            level: self.level,
+            correction_factor: self
+                .par
+                .plaintext
+                .mul(self.correction_factor, rhs.correction_factor),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bgv::{
+        BgvParameters, Ciphertext, Encoding, Plaintext, RelinearizationKey, SecretKey,
+    };
+    use fhe_traits::{FheDecoder, FheDecrypter, FheEncoder, FheEncrypter};
+    use rand::thread_rng;
+    use std::error::Error;
+
+    #[test]
+    fn add_sub_neg() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        for params in [
+            BgvParameters::default_arc(1, 8),
+            BgvParameters::default_arc(6, 8),
+        ] {
+            for _ in 0..20 {
+                let a = params.plaintext.random_vec(params.degree(), &mut rng);
+                let b = params.plaintext.random_vec(params.degree(), &mut rng);
+                let mut sum = a.clone();
+                params.plaintext.add_vec(&mut sum, &b);
+                let mut diff = a.clone();
+                params.plaintext.sub_vec(&mut diff, &b);
+                let mut neg = a.clone();
+                params.plaintext.neg_vec(&mut neg);
+
+                let sk = SecretKey::random(&params, &mut rng);
+                for encoding in [Encoding::poly(), Encoding::simd()] {
+                    let pt_a = Plaintext::try_encode(&a, encoding.clone(), &params)?;
+                    let pt_b = Plaintext::try_encode(&b, encoding.clone(), &params)?;
+                    let ct_a: Ciphertext = sk.try_encrypt(&pt_a, &mut rng)?;
+                    let ct_b: Ciphertext = sk.try_encrypt(&pt_b, &mut rng)?;
+
+                    for (ct, expected) in [
+                        (&ct_a + &ct_b, &sum),
+                        (&ct_a + &pt_b, &sum),
+                        (&pt_a + &ct_b, &sum),
+                        (&ct_a - &ct_b, &diff),
+                        (&ct_a - &pt_b, &diff),
+                        (&pt_a - &ct_b, &diff),
+                        (-&ct_a, &neg),
+                        (-ct_a.clone(), &neg),
+                    ] {
+                        let pt = sk.try_decrypt(&ct)?;
+                        assert_eq!(&Vec::<u64>::try_decode(&pt, encoding.clone())?, expected);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn add_after_mod_switch() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BgvParameters::default_arc(3, 8);
+        for _ in 0..20 {
+            let a = params.plaintext.random_vec(params.degree(), &mut rng);
+            let b = params.plaintext.random_vec(params.degree(), &mut rng);
+            let mut sum = a.clone();
+            params.plaintext.add_vec(&mut sum, &b);
+
+            let sk = SecretKey::random(&params, &mut rng);
+            let pt_a = Plaintext::try_encode(&a, Encoding::simd(), &params)?;
+            let pt_b = Plaintext::try_encode(&b, Encoding::simd_at_level(1), &params)?;
+            let mut ct_a: Ciphertext = sk.try_encrypt(&pt_a, &mut rng)?;
+            let ct_b: Ciphertext = sk.try_encrypt(&pt_b, &mut rng)?;
+
+            // The two ciphertexts now have different correction factors.
+            ct_a.mod_switch_to_next_level()?;
+            assert_ne!(ct_a.correction_factor(), ct_b.correction_factor());
+
+            for ct in [&ct_a + &ct_b, &ct_b + &ct_a, &ct_a + &pt_b] {
+                let pt = sk.try_decrypt(&ct)?;
+                assert_eq!(Vec::<u64>::try_decode(&pt, Encoding::simd())?, sum);
+            }
+            let pt = sk.try_decrypt(&(&ct_a - &ct_b))?;
+            let mut diff = a.clone();
+            params.plaintext.sub_vec(&mut diff, &b);
+            assert_eq!(Vec::<u64>::try_decode(&pt, Encoding::simd())?, diff);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn mul() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BgvParameters::default_arc(6, 8);
+        for _ in 0..20 {
+            let v1 = params.plaintext.random_vec(params.degree(), &mut rng);
+            let v2 = params.plaintext.random_vec(params.degree(), &mut rng);
+            let mut expected = v1.clone();
+            params.plaintext.mul_vec(&mut expected, &v2);
+
+            let sk = SecretKey::random(&params, &mut rng);
+            let rk = RelinearizationKey::new(&sk, &mut rng)?;
+            let pt1 = Plaintext::try_encode(&v1, Encoding::simd(), &params)?;
+            let pt2 = Plaintext::try_encode(&v2, Encoding::simd(), &params)?;
+            let ct1: Ciphertext = sk.try_encrypt(&pt1, &mut rng)?;
+            let ct2: Ciphertext = sk.try_encrypt(&pt2, &mut rng)?;
+
+            // Multiplication by a plaintext.
+            let pt = sk.try_decrypt(&(&ct1 * &pt2))?;
+            assert_eq!(Vec::<u64>::try_decode(&pt, Encoding::simd())?, expected);
+
+            // Multiplication, relinearization and modulus switching.
+            let mut ct3 = &ct1 * &ct2;
+            println!("Noise: {}", unsafe { sk.measure_noise(&ct3)? });
+            let pt = sk.try_decrypt(&ct3)?;
+            assert_eq!(Vec::<u64>::try_decode(&pt, Encoding::simd())?, expected);
+
+            rk.relinearizes(&mut ct3)?;
+            println!("Noise: {}", unsafe { sk.measure_noise(&ct3)? });
+            ct3.mod_switch_to_next_level()?;
+            println!("Noise: {}", unsafe { sk.measure_noise(&ct3)? });
+            let pt = sk.try_decrypt(&ct3)?;
+            assert_eq!(Vec::<u64>::try_decode(&pt, Encoding::simd())?, expected);
+
+            // Square, relinearize and switch again.
+            let e = expected.clone();
+            params.plaintext.mul_vec(&mut expected, &e);
+            let mut ct4 = &ct3 * &ct3;
+            rk.relinearizes(&mut ct4)?;
+            ct4.mod_switch_to_next_level()?;
+            println!("Noise: {}", unsafe { sk.measure_noise(&ct4)? });
+            let pt = sk.try_decrypt(&ct4)?;
+            assert_eq!(Vec::<u64>::try_decode(&pt, Encoding::simd())?, expected);
+        }
+        Ok(())
+    }
+}
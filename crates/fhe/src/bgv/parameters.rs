@@ -0,0 +1,493 @@
+//! Create parameters for the BGV encryption scheme
+
+use crate::bfv::{matrix_reps_index_map, BfvParameters, BfvParametersBuilder};
+use crate::bgv::proto::bgv::Parameters;
+use crate::{Error, ParametersError, Result};
+use fhe_math::{
+    rns::{RnsContext, RnsScaler, ScalingFactor},
+    rq::{scaler::Scaler, traits::TryConvertFrom, Context, Poly, Representation},
+    zq::{ntt::NttOperator, Modulus},
+};
+use fhe_traits::{Deserialize, FheParameters, Serialize};
+use fhe_util::inverse;
+use itertools::{izip, Itertools};
+use ndarray::{s, Array2, Axis};
+use protobuf::Message;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// Parameters for the BGV encryption scheme.
+#[derive(PartialEq, Eq)]
+pub struct BgvParameters {
+    /// Number of coefficients in a polynomial.
+    polynomial_degree: usize,
+
+    /// Modulus of the plaintext.
+    plaintext_modulus: u64,
+
+    /// Vector of coprime moduli q_i for the ciphertext.
+    pub(crate) moduli: Box<[u64]>,
+
+    /// Vector of the sized of the coprime moduli q_i for the ciphertext.
+    moduli_sizes: Box<[usize]>,
+
+    /// Error variance
+    pub(crate) variance: usize,
+
+    /// Context for the underlying polynomials
+    pub(crate) ctx: Vec<Arc<Context>>,
+
+    /// Ntt operator for the SIMD plaintext, if possible.
+    pub(crate) op: Option<Arc<NttOperator>>,
+
+    /// Centered reduction from the ciphertext modulus to the plaintext modulus
+    pub(crate) plaintext_scalers: Box<[RnsScaler]>,
+
+    /// Parameters to switch from one level to the next
+    pub(crate) mod_switch: Box<[ModSwitchParameters]>,
+
+    /// Plaintext Modulus
+    pub(crate) plaintext: Modulus,
+
+    pub(crate) matrix_reps_index_map: Box<[usize]>,
+
+    /// Scalers lifting a polynomial at each level to the first level.
+    pub(crate) extenders: Box<[Scaler]>,
+
+    /// BFV parameters with the same degree, ciphertext moduli, contexts and
+    /// variance, so that the BGV keys can be built on the BFV key switching
+    /// keys. Their plaintext modulus of 2 is never used, and their security is
+    /// not checked, like that of the BGV parameters.
+    pub(crate) key_switching_par: Arc<BfvParameters>,
+}
+
+impl Debug for BgvParameters {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BgvParameters")
+            .field("polynomial_degree", &self.polynomial_degree)
+            .field("plaintext_modulus", &self.plaintext_modulus)
+            .field("moduli", &self.moduli)
+            .finish()
+    }
+}
+
+impl FheParameters for BgvParameters {}
+
+unsafe impl Send for BgvParameters {}
+
+impl BgvParameters {
+    /// Returns the underlying polynomial degree
+    pub const fn degree(&self) -> usize {
+        self.polynomial_degree
+    }
+
+    /// Returns a reference to the ciphertext moduli
+    pub fn moduli(&self) -> &[u64] {
+        &self.moduli
+    }
+
+    /// Returns a reference to the ciphertext moduli
+    pub fn moduli_sizes(&self) -> &[usize] {
+        &self.moduli_sizes
+    }
+
+    /// Returns the plaintext modulus
+    pub const fn plaintext(&self) -> u64 {
+        self.plaintext_modulus
+    }
+
+    /// Returns the maximum level allowed by these parameters.
+    pub fn max_level(&self) -> usize {
+        self.moduli.len() - 1
+    }
+
+    /// Returns the context corresponding to the level.
+    pub(crate) fn ctx_at_level(&self, level: usize) -> Result<&Arc<Context>> {
+        self.ctx.get(level).ok_or(Error::InvalidLevel(level))
+    }
+
+    /// Returns the level of a given context
+    pub(crate) fn level_of_ctx(&self, ctx: &Arc<Context>) -> Result<usize> {
+        self.ctx[0].niterations_to(ctx).map_err(Error::MathError)
+    }
+
+    /// Returns the inverse of `a` modulo the plaintext modulus.
+    pub(crate) fn plaintext_inv(&self, a: u64) -> Result<u64> {
+        inverse(a, self.plaintext_modulus).ok_or(Error::NonInvertible(a))
+    }
+
+    /// Default parameters for tests, with a plaintext modulus of 1153 and
+    /// `num_moduli` ciphertext moduli of 62 bits.
+    #[cfg(test)]
+    pub fn default_arc(num_moduli: usize, degree: usize) -> Arc<Self> {
+        if !degree.is_power_of_two() || degree < 8 {
+            panic!("Invalid degree");
+        }
+        BgvParametersBuilder::new()
+            .set_degree(degree)
+            .set_plaintext_modulus(1153)
+            .set_moduli_sizes(&vec![62usize; num_moduli])
+            .build_arc()
+            .unwrap()
+    }
+}
+
+/// Builder for parameters for the Bgv encryption scheme.
+#[derive(Debug)]
+pub struct BgvParametersBuilder {
+    degree: usize,
+    plaintext: u64,
+    variance: usize,
+    ciphertext_moduli: Vec<u64>,
+    ciphertext_moduli_sizes: Vec<usize>,
+}
+
+impl BgvParametersBuilder {
+    /// Creates a new instance of the builder
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            degree: Default::default(),
+            plaintext: Default::default(),
+            variance: 10,
+            ciphertext_moduli: Default::default(),
+            ciphertext_moduli_sizes: Default::default(),
+        }
+    }
+
+    /// Sets the polynomial degree. Returns an error if the degree is not
+    /// a power of two larger or equal to 8.
+    pub fn set_degree(&mut self, degree: usize) -> &mut Self {
+        self.degree = degree;
+        self
+    }
+
+    /// Sets the plaintext modulus. Returns an error if the plaintext is not
+    /// between 2 and 2^62 - 1, or if it is not coprime with the ciphertext
+    /// moduli.
+    pub fn set_plaintext_modulus(&mut self, plaintext: u64) -> &mut Self {
+        self.plaintext = plaintext;
+        self
+    }
+
+    /// Sets the sizes of the ciphertext moduli.
+    /// Only one of `set_moduli_sizes` and `set_moduli`
+    /// can be specified.
+    pub fn set_moduli_sizes(&mut self, sizes: &[usize]) -> &mut Self {
+        self.ciphertext_moduli_sizes = sizes.to_owned();
+        self
+    }
+
+    /// Sets the ciphertext moduli to use.
+    /// Only one of `set_moduli_sizes` and `set_moduli`
+    /// can be specified.
+    pub fn set_moduli(&mut self, moduli: &[u64]) -> &mut Self {
+        self.ciphertext_moduli = moduli.to_owned();
+        self
+    }
+
+    /// Sets the error variance. Returns an error if the variance is not between
+    /// one and sixteen.
+    pub fn set_variance(&mut self, variance: usize) -> &mut Self {
+        self.variance = variance;
+        self
+    }
+
+    /// Build a new `BgvParameters` inside an `Arc`.
+    pub fn build_arc(&self) -> Result<Arc<BgvParameters>> {
+        self.build().map(Arc::new)
+    }
+
+    /// Build a new `BgvParameters`.
+    pub fn build(&self) -> Result<BgvParameters> {
+        // Check that the degree is a power of 2 (and large enough).
+        if self.degree < 8 || !self.degree.is_power_of_two() {
+            return Err(Error::ParametersError(ParametersError::InvalidDegree(
+                self.degree,
+            )));
+        }
+
+        if !(1..=16).contains(&self.variance) {
+            return Err(Error::ParametersError(ParametersError::InvalidVariance(
+                self.variance,
+                1,
+                16,
+            )));
+        }
+
+        // This checks that the plaintext modulus is valid.
+        let plaintext_modulus = Modulus::new(self.plaintext).map_err(|_| {
+            Error::ParametersError(ParametersError::InvalidPlaintext(self.plaintext))
+        })?;
+
+        // Check that one of `ciphertext_moduli` and `ciphertext_moduli_sizes` is
+        // specified.
+        if !self.ciphertext_moduli.is_empty() && !self.ciphertext_moduli_sizes.is_empty() {
+            return Err(Error::ParametersError(ParametersError::TooManySpecified));
+        } else if self.ciphertext_moduli.is_empty() && self.ciphertext_moduli_sizes.is_empty() {
+            return Err(Error::ParametersError(ParametersError::TooFewSpecified));
+        }
+
+        // Get or generate the moduli
+        let mut moduli = self.ciphertext_moduli.clone();
+        if !self.ciphertext_moduli_sizes.is_empty() {
+            moduli =
+                BfvParametersBuilder::generate_moduli(&self.ciphertext_moduli_sizes, self.degree)?
+        }
+
+        // Modulus switching divides by the ciphertext moduli modulo the
+        // plaintext modulus, so they must be coprime.
+        if moduli
+            .iter()
+            .any(|qi| inverse(*qi % self.plaintext, self.plaintext).is_none())
+        {
+            return Err(Error::ParametersError(ParametersError::InvalidPlaintext(
+                self.plaintext,
+            )));
+        }
+
+        // Recomputes the moduli sizes
+        let moduli_sizes = moduli
+            .iter()
+            .map(|m| 64 - m.leading_zeros() as usize)
+            .collect_vec();
+
+        let op = NttOperator::new(&plaintext_modulus, self.degree);
+
+        // The plaintext modulus is irrelevant for key switching.
+        let key_switching_par = BfvParametersBuilder::new()
+            .set_degree(self.degree)
+            .set_plaintext_modulus(2)
+            .set_moduli(&moduli)
+            .set_variance(self.variance)
+            .allow_insecure()
+            .build_arc()?;
+        let ctx = key_switching_par.ctx.clone();
+
+        let plaintext_rns = Arc::new(RnsContext::new(&[self.plaintext])?);
+
+        let mut plaintext_scalers = Vec::with_capacity(moduli.len());
+        let mut mod_switch = Vec::with_capacity(moduli.len() - 1);
+        for i in 0..moduli.len() {
+            let rns = Arc::new(RnsContext::new(&moduli[..moduli.len() - i])?);
+            plaintext_scalers.push(RnsScaler::new(&rns, &plaintext_rns, ScalingFactor::one()));
+            if i < moduli.len() - 1 {
+                mod_switch.push(ModSwitchParameters::new(
+                    &moduli[..moduli.len() - i],
+                    &plaintext_modulus,
+                )?);
+            }
+        }
+        let extenders = ctx
+            .iter()
+            .map(|ctx_i| Scaler::new(ctx_i, &ctx[0], ScalingFactor::one()))
+            .collect::<fhe_math::Result<Vec<_>>>()?;
+
+        Ok(BgvParameters {
+            polynomial_degree: self.degree,
+            plaintext_modulus: self.plaintext,
+            moduli: moduli.into_boxed_slice(),
+            moduli_sizes: moduli_sizes.into_boxed_slice(),
+            variance: self.variance,
+            ctx,
+            op: op.map(Arc::new),
+            plaintext_scalers: plaintext_scalers.into_boxed_slice(),
+            mod_switch: mod_switch.into_boxed_slice(),
+            plaintext: plaintext_modulus,
+            matrix_reps_index_map: matrix_reps_index_map(self.degree),
+            extenders: extenders.into_boxed_slice(),
+            key_switching_par,
+        })
+    }
+}
+
+impl Serialize for BgvParameters {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut params = Parameters::new();
+        params.degree = self.polynomial_degree as u32;
+        params.plaintext = self.plaintext_modulus;
+        params.moduli = self.moduli.to_vec();
+        params.variance = self.variance as u32;
+        params.write_to_bytes().unwrap()
+    }
+}
+
+impl Deserialize for BgvParameters {
+    fn try_deserialize(bytes: &[u8]) -> Result<Self> {
+        if let Ok(params) = Parameters::parse_from_bytes(bytes) {
+            BgvParametersBuilder::new()
+                .set_degree(params.degree as usize)
+                .set_plaintext_modulus(params.plaintext)
+                .set_moduli(&params.moduli)
+                .set_variance(params.variance as usize)
+                .build()
+        } else {
+            Err(Error::SerializationError)
+        }
+    }
+    type Error = Error;
+}
+
+/// Parameters to switch a ciphertext from a modulus Q to Q / q_last while
+/// preserving the plaintext modulo t.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct ModSwitchParameters {
+    q_last: Modulus,
+    t_inv_mod_q_last: u64,
+    q: Box<[Modulus]>,
+    t_mod_qi: Box<[u64]>,
+    q_last_mod_qi: Box<[u64]>,
+    q_last_inv_mod_qi: Box<[u64]>,
+
+    /// The inverse of q_last modulo the plaintext modulus, by which the
+    /// correction factor of the ciphertext is multiplied.
+    pub(crate) q_last_inv_mod_t: u64,
+}
+
+impl ModSwitchParameters {
+    fn new(moduli: &[u64], plaintext: &Modulus) -> Result<Self> {
+        let (q_last, moduli) = moduli.split_last().unwrap();
+        let t = plaintext.modulus();
+        let q_last = Modulus::new(*q_last)?;
+        let q = moduli
+            .iter()
+            .map(|qi| Modulus::new(*qi))
+            .collect::<fhe_math::Result<Vec<Modulus>>>()?;
+        let not_invertible = || Error::MathError(fhe_math::Error::NonCoprimeModuli);
+        Ok(Self {
+            t_inv_mod_q_last: inverse(t % q_last.modulus(), q_last.modulus())
+                .ok_or_else(not_invertible)?,
+            t_mod_qi: q.iter().map(|qi| qi.reduce(t)).collect(),
+            q_last_mod_qi: q.iter().map(|qi| qi.reduce(q_last.modulus())).collect(),
+            q_last_inv_mod_qi: q
+                .iter()
+                .map(|qi| inverse(qi.reduce(q_last.modulus()), qi.modulus()))
+                .collect::<Option<Vec<u64>>>()
+                .ok_or_else(not_invertible)?
+                .into_boxed_slice(),
+            q_last_inv_mod_t: inverse(q_last.modulus() % t, t).ok_or_else(not_invertible)?,
+            q_last,
+            q: q.into_boxed_slice(),
+        })
+    }
+
+    /// Divide a polynomial in PowerBasis representation by the last modulus
+    /// of its context, after subtracting the smallest multiple of the plaintext
+    /// modulus congruent to it modulo that last modulus.
+    pub(crate) fn mod_switch_down(&self, p: &Poly, next_ctx: &Arc<Context>) -> Result<Poly> {
+        debug_assert_eq!(p.representation(), &Representation::PowerBasis);
+        let coefficients = p.coefficients();
+        let q_last_half = self.q_last.modulus() >> 1;
+        let n = coefficients.nrows() - 1;
+
+        let mut out = coefficients.slice(s![..n, ..]).to_owned();
+        izip!(
+            out.axis_iter_mut(Axis(1)),
+            coefficients.slice(s![n, ..]).iter()
+        )
+        .for_each(|(mut column, r)| {
+            // delta = plaintext * u, with u = [r / plaintext]_{q_last} centered, is
+            // congruent to r modulo q_last and to 0 modulo the plaintext modulus.
+            let u = self.q_last.mul(*r, self.t_inv_mod_q_last);
+            let is_negative = (u > q_last_half) as u64;
+            izip!(
+                column.iter_mut(),
+                self.q.iter(),
+                self.t_mod_qi.iter(),
+                self.q_last_mod_qi.iter(),
+                self.q_last_inv_mod_qi.iter()
+            )
+            .for_each(|(ci, qi, t_mod_qi, q_last_mod_qi, q_last_inv_mod_qi)| {
+                let ui = qi.sub(qi.reduce(u), is_negative * q_last_mod_qi);
+                let delta_i = qi.mul(ui, *t_mod_qi);
+                *ci = qi.mul(qi.sub(*ci, delta_i), *q_last_inv_mod_qi);
+            })
+        });
+
+        Ok(Poly::try_convert_from(
+            out,
+            next_ctx,
+            false,
+            Representation::PowerBasis,
+        )?)
+    }
+}
+
+/// Reduce a polynomial to a context whose moduli are a prefix of the moduli of
+/// the polynomial context, by dropping the other residues.
+pub(crate) fn reduce_to_ctx(p: &Poly, ctx: &Arc<Context>) -> Result<Poly> {
+    let n = p.ctx().moduli().len() - p.ctx().niterations_to(ctx)?;
+    let coefficients: Array2<u64> = p.coefficients().slice(s![..n, ..]).to_owned();
+    let mut representation = p.representation().clone();
+    if representation == Representation::NttShoup {
+        representation = Representation::Ntt
+    }
+    Ok(Poly::try_convert_from(
+        coefficients,
+        ctx,
+        false,
+        representation,
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BgvParameters, BgvParametersBuilder};
+    use crate::{Error, ParametersError};
+    use fhe_traits::{Deserialize, Serialize};
+    use std::error::Error as StdError;
+
+    #[test]
+    fn default() {
+        let params = BgvParameters::default_arc(1, 8);
+        assert_eq!(params.moduli.len(), 1);
+        assert_eq!(params.degree(), 8);
+        assert!(params.mod_switch.is_empty());
+
+        let params = BgvParameters::default_arc(3, 16);
+        assert_eq!(params.moduli.len(), 3);
+        assert_eq!(params.degree(), 16);
+        assert_eq!(params.mod_switch.len(), 2);
+    }
+
+    #[test]
+    fn plaintext_not_coprime() {
+        let params = BgvParametersBuilder::new()
+            .set_degree(8)
+            .set_plaintext_modulus(2017)
+            .set_moduli(&[4611686018427387761, 2017])
+            .build();
+        assert!(params.is_err());
+    }
+
+    #[test]
+    fn invalid_variance() {
+        let mut builder = BgvParametersBuilder::new();
+        builder
+            .set_degree(8)
+            .set_plaintext_modulus(1153)
+            .set_moduli_sizes(&[62]);
+        for variance in [1, 16] {
+            assert!(builder.set_variance(variance).build().is_ok());
+        }
+        for variance in [0, 17] {
+            assert_eq!(
+                builder.set_variance(variance).build().unwrap_err(),
+                Error::ParametersError(ParametersError::InvalidVariance(variance, 1, 16))
+            );
+        }
+    }
+
+    #[test]
+    fn serialize() -> Result<(), Box<dyn StdError>> {
+        let params = BgvParametersBuilder::new()
+            .set_degree(8)
+            .set_plaintext_modulus(65536)
+            .set_moduli_sizes(&[62, 62, 62, 61, 60, 11])
+            .set_variance(4)
+            .build()?;
+        let bytes = params.to_bytes();
+        assert_eq!(BgvParameters::try_deserialize(&bytes)?, params);
+        Ok(())
+    }
+}
@@ -0,0 +1,260 @@
+//! Plaintext type in the BGV encryption scheme.
+use crate::{
+    bfv::EncodingEnum,
+    bgv::{BgvParameters, Encoding},
+    Error, Result,
+};
+use fhe_math::rq::{traits::TryConvertFrom, Poly, Representation};
+use fhe_traits::{FheDecoder, FheEncoder, FheParametrized, FhePlaintext};
+use std::sync::Arc;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+/// A plaintext object, that encodes a vector according to a specific encoding.
+#[derive(Debug, Clone, Eq)]
+pub struct Plaintext {
+    /// The parameters of the underlying BGV encryption scheme.
+    pub(crate) par: Arc<BgvParameters>,
+    /// The value after encoding.
+    pub(crate) value: Box<[u64]>,
+    /// The encoding of the plaintext, if known
+    pub(crate) encoding: Option<Encoding>,
+    /// The level of the plaintext
+    pub(crate) level: usize,
+}
+
+impl FheParametrized for Plaintext {
+    type Parameters = BgvParameters;
+}
+
+impl FhePlaintext for Plaintext {
+    type Encoding = Encoding;
+}
+
+// Zeroizing of plaintexts.
+impl ZeroizeOnDrop for Plaintext {}
+
+impl Zeroize for Plaintext {
+    fn zeroize(&mut self) {
+        self.value.zeroize();
+    }
+}
+
+impl Plaintext {
+    /// The plaintext multiplied by `factor` modulo the plaintext modulus, as a
+    /// polynomial in Ntt representation at the level of the plaintext.
+    pub(crate) fn to_poly(&self, factor: u64) -> Poly {
+        let mut m_v = Zeroizing::new(self.value.clone());
+        self.par.plaintext.scalar_mul_vec(&mut m_v, factor);
+        let ctx = self.par.ctx_at_level(self.level).unwrap();
+        let mut m =
+            Poly::try_convert_from(m_v.as_ref(), ctx, false, Representation::PowerBasis).unwrap();
+        m.change_representation(Representation::Ntt);
+        m
+    }
+
+    /// Generate a zero plaintext.
+    pub fn zero(encoding: Encoding, par: &Arc<BgvParameters>) -> Result<Self> {
+        let level = encoding.level;
+        par.ctx_at_level(level)?;
+        Ok(Self {
+            par: par.clone(),
+            value: vec![0u64; par.degree()].into_boxed_slice(),
+            encoding: Some(encoding),
+            level,
+        })
+    }
+
+    /// Returns the level of this plaintext.
+    pub fn level(&self) -> usize {
+        self.level
+    }
+}
+
+unsafe impl Send for Plaintext {}
+
+// Implement the equality manually; we want to say that two plaintexts are equal
+// even if one of them doesn't store its encoding information.
+impl PartialEq for Plaintext {
+    fn eq(&self, other: &Self) -> bool {
+        let mut eq = self.par == other.par;
+        eq &= self.value == other.value;
+        if let (Some(encoding), Some(other_encoding)) = (&self.encoding, &other.encoding) {
+            eq &= encoding == other_encoding
+        }
+        eq
+    }
+}
+
+// Encoding and decoding.
+
+impl<'a, const N: usize, T> FheEncoder<&'a [T; N]> for Plaintext
+where
+    Plaintext: FheEncoder<&'a [T], Error = Error>,
+{
+    type Error = Error;
+    fn try_encode(value: &'a [T; N], encoding: Encoding, par: &Arc<BgvParameters>) -> Result<Self> {
+        Plaintext::try_encode(value.as_ref(), encoding, par)
+    }
+}
+
+impl<'a, T> FheEncoder<&'a Vec<T>> for Plaintext
+where
+    Plaintext: FheEncoder<&'a [T], Error = Error>,
+{
+    type Error = Error;
+    fn try_encode(value: &'a Vec<T>, encoding: Encoding, par: &Arc<BgvParameters>) -> Result<Self> {
+        Plaintext::try_encode(value.as_ref(), encoding, par)
+    }
+}
+
+impl<'a> FheEncoder<&'a [u64]> for Plaintext {
+    type Error = Error;
+    fn try_encode(value: &'a [u64], encoding: Encoding, par: &Arc<BgvParameters>) -> Result<Self> {
+        if value.len() > par.degree() {
+            return Err(Error::TooManyValues(value.len(), par.degree()));
+        }
+        par.ctx_at_level(encoding.level)?;
+
+        let mut v = vec![0u64; par.degree()];
+        match encoding.encoding {
+            EncodingEnum::Poly => v[..value.len()].copy_from_slice(value),
+            EncodingEnum::Simd => {
+                if let Some(op) = &par.op {
+                    for i in 0..value.len() {
+                        v[par.matrix_reps_index_map[i]] = value[i];
+                    }
+                    op.backward(&mut v);
+                } else {
+                    return Err(Error::EncodingNotSupported(EncodingEnum::Simd.to_string()));
+                }
+            }
+        };
+        par.plaintext.reduce_vec(&mut v);
+
+        Ok(Plaintext {
+            par: par.clone(),
+            value: v.into_boxed_slice(),
+            level: encoding.level,
+            encoding: Some(encoding),
+        })
+    }
+}
+
+impl<'a> FheEncoder<&'a [i64]> for Plaintext {
+    type Error = Error;
+    fn try_encode(value: &'a [i64], encoding: Encoding, par: &Arc<BgvParameters>) -> Result<Self> {
+        let w = Zeroizing::new(par.plaintext.reduce_vec_i64(value));
+        Plaintext::try_encode(w.as_ref() as &[u64], encoding, par)
+    }
+}
+
+impl FheDecoder<Plaintext> for Vec<u64> {
+    fn try_decode<O>(pt: &Plaintext, encoding: O) -> Result<Vec<u64>>
+    where
+        O: Into<Option<Encoding>>,
+    {
+        let encoding = encoding.into();
+        let enc = match (&pt.encoding, encoding) {
+            (None, None) => return Err(Error::MissingEncoding),
+            (Some(pt_enc), Some(arg_enc)) if pt_enc != &arg_enc => {
+                return Err(Error::EncodingMismatch(arg_enc.into(), pt_enc.into()))
+            }
+            (Some(pt_enc), _) => pt_enc.clone(),
+            (None, Some(arg_enc)) => arg_enc,
+        };
+
+        let mut w = pt.value.to_vec();
+
+        match enc.encoding {
+            EncodingEnum::Poly => Ok(w),
+            EncodingEnum::Simd => {
+                if let Some(op) = &pt.par.op {
+                    op.forward(&mut w);
+                    let mut w_reordered = w.clone();
+                    for i in 0..pt.par.degree() {
+                        w_reordered[i] = w[pt.par.matrix_reps_index_map[i]]
+                    }
+                    w.zeroize();
+                    Ok(w_reordered)
+                } else {
+                    Err(Error::EncodingNotSupported(EncodingEnum::Simd.to_string()))
+                }
+            }
+        }
+    }
+
+    type Error = Error;
+}
+
+impl FheDecoder<Plaintext> for Vec<i64> {
+    fn try_decode<E>(pt: &Plaintext, encoding: E) -> Result<Vec<i64>>
+    where
+        E: Into<Option<Encoding>>,
+    {
+        let v = Vec::<u64>::try_decode(pt, encoding)?;
+        Ok(unsafe { pt.par.plaintext.center_vec_vt(&v) })
+    }
+
+    type Error = Error;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Encoding, Plaintext};
+    use crate::bgv::{BgvParameters, BgvParametersBuilder};
+    use fhe_traits::{FheDecoder, FheEncoder};
+    use rand::thread_rng;
+    use std::error::Error;
+    use zeroize::Zeroize;
+
+    #[test]
+    fn try_encode() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        // The default test parameters support both Poly and Simd encodings
+        let params = BgvParameters::default_arc(1, 8);
+        let a = params.plaintext.random_vec(params.degree(), &mut rng);
+
+        assert!(Plaintext::try_encode(&[0u64; 9], Encoding::poly(), &params).is_err());
+        assert!(Plaintext::try_encode(&a, Encoding::poly(), &params).is_ok());
+        assert!(Plaintext::try_encode(&a, Encoding::simd(), &params).is_ok());
+        assert!(Plaintext::try_encode(&a, Encoding::poly_at_level(1), &params).is_err());
+
+        // The following parameters do not allow for Simd encoding
+        let params = BgvParametersBuilder::new()
+            .set_degree(8)
+            .set_plaintext_modulus(256)
+            .set_moduli(&[4611686018326724609])
+            .build_arc()?;
+
+        let a = params.plaintext.random_vec(params.degree(), &mut rng);
+        assert!(Plaintext::try_encode(&a, Encoding::poly(), &params).is_ok());
+        assert!(Plaintext::try_encode(&a, Encoding::simd(), &params).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn encode_decode() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BgvParameters::default_arc(1, 8);
+        let a = params.plaintext.random_vec(params.degree(), &mut rng);
+
+        let plaintext = Plaintext::try_encode(&a, Encoding::simd(), &params)?;
+        assert_eq!(Vec::<u64>::try_decode(&plaintext, Encoding::simd())?, a);
+
+        let a = unsafe { params.plaintext.center_vec_vt(&a) };
+        let plaintext = Plaintext::try_encode(&a, Encoding::poly(), &params)?;
+        assert_eq!(Vec::<i64>::try_decode(&plaintext, Encoding::poly())?, a);
+
+        let mut plaintext = Plaintext::try_encode(&a, Encoding::simd(), &params)?;
+        assert_eq!(Vec::<i64>::try_decode(&plaintext, None)?, a);
+        assert!(Vec::<u64>::try_decode(&plaintext, Encoding::poly()).is_err());
+
+        plaintext.zeroize();
+        assert_eq!(plaintext, Plaintext::zero(Encoding::simd(), &params)?);
+        plaintext.encoding = None;
+        assert!(Vec::<u64>::try_decode(&plaintext, None).is_err());
+
+        Ok(())
+    }
+}
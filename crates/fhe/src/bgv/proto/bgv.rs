@@ -0,0 +1,403 @@
+// This file is generated by rust-protobuf 3.2.0. Do not edit
+// .proto file is parsed by protoc --rust-out=...
+// @generated
+
+// https://github.com/rust-lang/rust-clippy/issues/702
+#![allow(unknown_lints)]
+#![allow(clippy::all)]
+
+#![allow(unused_attributes)]
+#![cfg_attr(rustfmt, rustfmt::skip)]
+
+#![allow(box_pointers)]
+#![allow(dead_code)]
+#![allow(missing_docs)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(trivial_casts)]
+#![allow(unused_results)]
+#![allow(unused_mut)]
+
+//! Generated file from `bgv.proto`
+
+/// Generated files are compatible only with the same version
+/// of protobuf runtime.
+const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_3_2_0;
+
+#[derive(PartialEq,Clone,Default,Debug)]
+// @@protoc_insertion_point(message:fhers.bgv.Ciphertext)
+pub struct Ciphertext {
+    // message fields
+    // @@protoc_insertion_point(field:fhers.bgv.Ciphertext.c)
+    pub c: ::std::vec::Vec<::std::vec::Vec<u8>>,
+    // @@protoc_insertion_point(field:fhers.bgv.Ciphertext.level)
+    pub level: u32,
+    // @@protoc_insertion_point(field:fhers.bgv.Ciphertext.correction_factor)
+    pub correction_factor: u64,
+    // special fields
+    // @@protoc_insertion_point(special_field:fhers.bgv.Ciphertext.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a Ciphertext {
+    fn default() -> &'a Ciphertext {
+        <Ciphertext as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Ciphertext {
+    pub fn new() -> Ciphertext {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(3);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "c",
+            |m: &Ciphertext| { &m.c },
+            |m: &mut Ciphertext| { &mut m.c },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "level",
+            |m: &Ciphertext| { &m.level },
+            |m: &mut Ciphertext| { &mut m.level },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "correction_factor",
+            |m: &Ciphertext| { &m.correction_factor },
+            |m: &mut Ciphertext| { &mut m.correction_factor },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<Ciphertext>(
+            "Ciphertext",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for Ciphertext {
+    const NAME: &'static str = "Ciphertext";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.c.push(is.read_bytes()?);
+                },
+                16 => {
+                    self.level = is.read_uint32()?;
+                },
+                24 => {
+                    self.correction_factor = is.read_uint64()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        for value in &self.c {
+            my_size += ::protobuf::rt::bytes_size(1, &value);
+        };
+        if self.level != 0 {
+            my_size += ::protobuf::rt::uint32_size(2, self.level);
+        }
+        if self.correction_factor != 0 {
+            my_size += ::protobuf::rt::uint64_size(3, self.correction_factor);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        for v in &self.c {
+            os.write_bytes(1, &v)?;
+        };
+        if self.level != 0 {
+            os.write_uint32(2, self.level)?;
+        }
+        if self.correction_factor != 0 {
+            os.write_uint64(3, self.correction_factor)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> Ciphertext {
+        Ciphertext::new()
+    }
+
+    fn clear(&mut self) {
+        self.c.clear();
+        self.level = 0;
+        self.correction_factor = 0;
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static Ciphertext {
+        static instance: Ciphertext = Ciphertext {
+            c: ::std::vec::Vec::new(),
+            level: 0,
+            correction_factor: 0,
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for Ciphertext {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("Ciphertext").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for Ciphertext {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Ciphertext {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+#[derive(PartialEq,Clone,Default,Debug)]
+// @@protoc_insertion_point(message:fhers.bgv.Parameters)
+pub struct Parameters {
+    // message fields
+    // @@protoc_insertion_point(field:fhers.bgv.Parameters.degree)
+    pub degree: u32,
+    // @@protoc_insertion_point(field:fhers.bgv.Parameters.moduli)
+    pub moduli: ::std::vec::Vec<u64>,
+    // @@protoc_insertion_point(field:fhers.bgv.Parameters.plaintext)
+    pub plaintext: u64,
+    // @@protoc_insertion_point(field:fhers.bgv.Parameters.variance)
+    pub variance: u32,
+    // special fields
+    // @@protoc_insertion_point(special_field:fhers.bgv.Parameters.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a Parameters {
+    fn default() -> &'a Parameters {
+        <Parameters as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Parameters {
+    pub fn new() -> Parameters {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(4);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "degree",
+            |m: &Parameters| { &m.degree },
+            |m: &mut Parameters| { &mut m.degree },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "moduli",
+            |m: &Parameters| { &m.moduli },
+            |m: &mut Parameters| { &mut m.moduli },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "plaintext",
+            |m: &Parameters| { &m.plaintext },
+            |m: &mut Parameters| { &mut m.plaintext },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "variance",
+            |m: &Parameters| { &m.variance },
+            |m: &mut Parameters| { &mut m.variance },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<Parameters>(
+            "Parameters",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for Parameters {
+    const NAME: &'static str = "Parameters";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                8 => {
+                    self.degree = is.read_uint32()?;
+                },
+                18 => {
+                    is.read_repeated_packed_uint64_into(&mut self.moduli)?;
+                },
+                16 => {
+                    self.moduli.push(is.read_uint64()?);
+                },
+                24 => {
+                    self.plaintext = is.read_uint64()?;
+                },
+                32 => {
+                    self.variance = is.read_uint32()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if self.degree != 0 {
+            my_size += ::protobuf::rt::uint32_size(1, self.degree);
+        }
+        for value in &self.moduli {
+            my_size += ::protobuf::rt::uint64_size(2, *value);
+        };
+        if self.plaintext != 0 {
+            my_size += ::protobuf::rt::uint64_size(3, self.plaintext);
+        }
+        if self.variance != 0 {
+            my_size += ::protobuf::rt::uint32_size(4, self.variance);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if self.degree != 0 {
+            os.write_uint32(1, self.degree)?;
+        }
+        for v in &self.moduli {
+            os.write_uint64(2, *v)?;
+        };
+        if self.plaintext != 0 {
+            os.write_uint64(3, self.plaintext)?;
+        }
+        if self.variance != 0 {
+            os.write_uint32(4, self.variance)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> Parameters {
+        Parameters::new()
+    }
+
+    fn clear(&mut self) {
+        self.degree = 0;
+        self.moduli.clear();
+        self.plaintext = 0;
+        self.variance = 0;
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static Parameters {
+        static instance: Parameters = Parameters {
+            degree: 0,
+            moduli: ::std::vec::Vec::new(),
+            plaintext: 0,
+            variance: 0,
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for Parameters {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("Parameters").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for Parameters {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Parameters {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+static file_descriptor_proto_data: &'static [u8] = b"\
+    \n\tbgv.proto\x12\tfhers.bgv\"]\n\nCiphertext\x12\x0c\n\x01c\x18\x01\x20\
+    \x03(\x0cR\x01c\x12\x14\n\x05level\x18\x02\x20\x01(\rR\x05level\x12+\n\
+    \x11correction_factor\x18\x03\x20\x01(\x04R\x10correctionFactor\"v\n\nPa\
+    rameters\x12\x16\n\x06degree\x18\x01\x20\x01(\rR\x06degree\x12\x16\n\x06\
+    moduli\x18\x02\x20\x03(\x04R\x06moduli\x12\x1c\n\tplaintext\x18\x03\x20\
+    \x01(\x04R\tplaintext\x12\x1a\n\x08variance\x18\x04\x20\x01(\rR\x08varia\
+    nceb\x06proto3\
+";
+
+/// `FileDescriptorProto` object which was a source for this generated file
+fn file_descriptor_proto() -> &'static ::protobuf::descriptor::FileDescriptorProto {
+    static file_descriptor_proto_lazy: ::protobuf::rt::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::rt::Lazy::new();
+    file_descriptor_proto_lazy.get(|| {
+        ::protobuf::Message::parse_from_bytes(file_descriptor_proto_data).unwrap()
+    })
+}
+
+/// `FileDescriptor` object which allows dynamic access to files
+pub fn file_descriptor() -> &'static ::protobuf::reflect::FileDescriptor {
+    static generated_file_descriptor_lazy: ::protobuf::rt::Lazy<::protobuf::reflect::GeneratedFileDescriptor> = ::protobuf::rt::Lazy::new();
+    static file_descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::FileDescriptor> = ::protobuf::rt::Lazy::new();
+    file_descriptor.get(|| {
+        let generated_file_descriptor = generated_file_descriptor_lazy.get(|| {
+            let mut deps = ::std::vec::Vec::with_capacity(0);
+            let mut messages = ::std::vec::Vec::with_capacity(2);
+            messages.push(Ciphertext::generated_message_descriptor_data());
+            messages.push(Parameters::generated_message_descriptor_data());
+            let mut enums = ::std::vec::Vec::with_capacity(0);
+            ::protobuf::reflect::GeneratedFileDescriptor::new_generated(
+                file_descriptor_proto(),
+                deps,
+                messages,
+                enums,
+            )
+        });
+        ::protobuf::reflect::FileDescriptor::new_generated_2(generated_file_descriptor)
+    })
+}
@@ -0,0 +1,247 @@
+//! Ciphertext type in the CKKS encryption scheme.
+
+use crate::bgv::reduce_to_ctx;
+use crate::ckks::{parameters::CkksParameters, proto::ckks::Ciphertext as CiphertextProto};
+use crate::{Error, Result};
+use fhe_math::rq::{Poly, Representation};
+use fhe_traits::{
+    DeserializeParametrized, DeserializeWithContext, FheCiphertext, FheParametrized, Serialize,
+};
+use protobuf::Message;
+use std::sync::Arc;
+
+/// A ciphertext encrypting a plaintext.
+///
+/// In CKKS, a ciphertext `c` at level `l` satisfies `<c, s> = m + e` modulo
+/// the ciphertext modulus `Q_l`, where `m` encodes the values multiplied by
+/// the scale of the ciphertext, and `e` is a small error which only affects
+/// the least significant bits of the values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ciphertext {
+    /// The parameters of the underlying CKKS encryption scheme.
+    pub(crate) par: Arc<CkksParameters>,
+
+    /// The ciphertext elements.
+    pub(crate) c: Vec<Poly>,
+
+    /// The ciphertext level
+    pub(crate) level: usize,
+
+    /// The scale of the encrypted values.
+    pub(crate) scale: f64,
+}
+
+impl Ciphertext {
+    /// Rescale the ciphertext, i.e., divide it by the last modulus q of its
+    /// context and switch it to the next level. The scale of the ciphertext
+    /// is divided by q; this is typically used after a multiplication to
+    /// bring the scale back to its original size.
+    pub fn rescale_to_next_level(&mut self) -> Result<()> {
+        if self.level == self.par.max_level() {
+            return Err(Error::MathError(fhe_math::Error::NoMoreContext));
+        }
+
+        let rescaler = &self.par.rescalers[self.level];
+        for ci in self.c.iter_mut() {
+            *ci = ci.scale(rescaler)?;
+        }
+        self.scale /= *self.par.ctx[self.level].moduli().last().unwrap() as f64;
+        self.level += 1;
+        Ok(())
+    }
+
+    /// Modulo switch the ciphertext to the next level, without changing its
+    /// scale. This is used to bring ciphertexts to the same level.
+    pub fn mod_switch_to_next_level(&mut self) -> Result<()> {
+        if self.level < self.par.max_level() {
+            let next_ctx = self.par.ctx_at_level(self.level + 1)?;
+            for ci in self.c.iter_mut() {
+                *ci = reduce_to_ctx(ci, next_ctx)?;
+            }
+            self.level += 1
+        }
+        Ok(())
+    }
+
+    /// Modulo switch the ciphertext to the last level, without changing its
+    /// scale.
+    pub fn mod_switch_to_last_level(&mut self) -> Result<()> {
+        while self.level < self.par.max_level() {
+            self.mod_switch_to_next_level()?
+        }
+        Ok(())
+    }
+
+    /// Create a ciphertext from a vector of polynomials and a scale.
+    /// A ciphertext must contain at least two polynomials, and all polynomials
+    /// must be in Ntt representation and with the same context.
+    pub fn new(c: Vec<Poly>, scale: f64, par: &Arc<CkksParameters>) -> Result<Self> {
+        if c.len() < 2 {
+            return Err(Error::TooFewValues(c.len(), 2));
+        }
+
+        let ctx = c[0].ctx();
+        let level = par.level_of_ctx(ctx)?;
+
+        // Check that all polynomials have the expected representation and context.
+        for ci in c.iter() {
+            if ci.representation() != &Representation::Ntt {
+                return Err(Error::MathError(fhe_math::Error::IncorrectRepresentation(
+                    ci.representation().clone(),
+                    Representation::Ntt,
+                )));
+            }
+            if ci.ctx() != ctx {
+                return Err(Error::MathError(fhe_math::Error::InvalidContext));
+            }
+        }
+
+        if !scale.is_finite() || scale <= 0.0 {
+            return Err(Error::InvalidScale);
+        }
+
+        Ok(Self {
+            par: par.clone(),
+            c,
+            level,
+            scale,
+        })
+    }
+
+    /// Get the i-th polynomial of the ciphertext.
+    pub fn get(&self, i: usize) -> Option<&Poly> {
+        self.c.get(i)
+    }
+
+    /// Returns the level of this ciphertext.
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    /// Returns the scale of this ciphertext.
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+}
+
+impl FheCiphertext for Ciphertext {}
+
+impl FheParametrized for Ciphertext {
+    type Parameters = CkksParameters;
+}
+
+impl Serialize for Ciphertext {
+    fn to_bytes(&self) -> Vec<u8> {
+        CiphertextProto::from(self).write_to_bytes().unwrap()
+    }
+}
+
+impl DeserializeParametrized for Ciphertext {
+    fn from_bytes(bytes: &[u8], par: &Arc<CkksParameters>) -> Result<Self> {
+        let ctp =
+            CiphertextProto::parse_from_bytes(bytes).map_err(|_| Error::SerializationError)?;
+        if ctp.level as usize > par.max_level() {
+            return Err(Error::SerializationError);
+        }
+        let ctx = par.ctx_at_level(ctp.level as usize)?;
+        let c = ctp
+            .c
+            .iter()
+            .map(|ci| Poly::from_bytes(ci, ctx).map_err(Error::MathError))
+            .collect::<Result<Vec<Poly>>>()?;
+        Ciphertext::new(c, ctp.scale, par)
+    }
+
+    type Error = Error;
+}
+
+/// Conversions from and to protobuf.
+impl From<&Ciphertext> for CiphertextProto {
+    fn from(ct: &Ciphertext) -> Self {
+        let mut proto = CiphertextProto::new();
+        proto.c = ct.c.iter().map(|ci| ci.to_bytes()).collect();
+        proto.level = ct.level as u32;
+        proto.scale = ct.scale;
+        proto
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ckks::{Ciphertext, CkksParameters, Encoding, Plaintext, SecretKey};
+    use fhe_traits::{
+        DeserializeParametrized, FheDecoder, FheDecrypter, FheEncoder, FheEncrypter, Serialize,
+    };
+    use rand::{thread_rng, Rng};
+    use std::error::Error;
+
+    #[test]
+    fn serialize() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        for params in [
+            CkksParameters::default_arc(1, 16),
+            CkksParameters::default_arc(4, 16),
+        ] {
+            let sk = SecretKey::random(&params, &mut rng);
+            let v = vec![1.0; params.slots()];
+            let pt = Plaintext::try_encode(&v, Encoding::slots(), &params)?;
+            let mut ct: Ciphertext = sk.try_encrypt(&pt, &mut rng)?;
+            assert_eq!(ct, Ciphertext::from_bytes(&ct.to_bytes(), &params)?);
+
+            ct.mod_switch_to_last_level()?;
+            assert_eq!(ct, Ciphertext::from_bytes(&ct.to_bytes(), &params)?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn new() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = CkksParameters::default_arc(4, 16);
+        let sk = SecretKey::random(&params, &mut rng);
+        let v = vec![1.0; params.slots()];
+        let pt = Plaintext::try_encode(&v, Encoding::slots(), &params)?;
+        let mut ct: Ciphertext = sk.try_encrypt(&pt, &mut rng)?;
+        ct.rescale_to_next_level()?;
+
+        let c = vec![ct.get(0).unwrap().clone(), ct.get(1).unwrap().clone()];
+        assert_eq!(ct, Ciphertext::new(c.clone(), ct.scale(), &params)?);
+        assert!(Ciphertext::new(c[..1].to_vec(), 1.0, &params).is_err());
+        assert!(Ciphertext::new(c, 0.0, &params).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn rescale() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = CkksParameters::default_arc(4, 16);
+        for _ in 0..20 {
+            let sk = SecretKey::random(&params, &mut rng);
+            let v = (0..params.slots())
+                .map(|_| rng.gen_range(-10.0..10.0))
+                .collect::<Vec<f64>>();
+
+            // Encode with a large scale, so that the values remain precise
+            // after dividing by all the moduli but the first one.
+            let scale = params.moduli()[1..]
+                .iter()
+                .fold(params.scale(), |acc, qi| acc * (*qi as f64));
+            let pt = Plaintext::try_encode(&v, Encoding::slots().with_scale(scale), &params)?;
+            let mut ct: Ciphertext = sk.try_encrypt(&pt, &mut rng)?;
+
+            while ct.level() < params.max_level() {
+                ct.rescale_to_next_level()?;
+                let pt2 = sk.try_decrypt(&ct)?;
+                let w = Vec::<f64>::try_decode(&pt2, None)?;
+                v.iter()
+                    .zip(&w)
+                    .for_each(|(vi, wi)| assert!((vi - wi).abs() < 1e-6));
+            }
+            assert!((ct.scale() / params.scale() - 1.0).abs() < 1e-9);
+            assert!(ct.rescale_to_next_level().is_err());
+        }
+
+        Ok(())
+    }
+}
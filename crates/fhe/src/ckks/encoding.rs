@@ -0,0 +1,187 @@
+//! The encoding type for CKKS, and the canonical embedding.
+
+use fhe_traits::FhePlaintextEncoding;
+use num_complex::Complex64;
+use std::f64::consts::PI;
+
+/// An encoding for the plaintext.
+///
+/// A CKKS plaintext encodes up to `degree / 2` complex numbers in its slots,
+/// using the canonical embedding. The values are multiplied by a scale before
+/// being rounded to integers, which sets the precision of the encoding.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Encoding {
+    pub(crate) level: usize,
+    pub(crate) scale: Option<f64>,
+}
+
+impl Encoding {
+    /// A slot encoding at level 0, with the default scale of the parameters.
+    pub fn slots() -> Self {
+        Self {
+            level: 0,
+            scale: None,
+        }
+    }
+
+    /// A slot encoding at a specific level, with the default scale of the
+    /// parameters.
+    pub fn slots_at_level(level: usize) -> Self {
+        Self { level, scale: None }
+    }
+
+    /// Use a specific scale instead of the default scale of the parameters.
+    /// This is needed to add a plaintext to a ciphertext which has been
+    /// rescaled, since both must have the same scale.
+    pub fn with_scale(mut self, scale: f64) -> Self {
+        self.scale = Some(scale);
+        self
+    }
+}
+
+impl FhePlaintextEncoding for Encoding {}
+
+/// Special FFT of Cheon, Kim, Kim and Song, evaluating a polynomial of degree
+/// `n` at the roots `ζ^(5^j)` for `0 <= j < n/2`, where `ζ` is a primitive
+/// `2n`-th root of unity. The other roots of `X^n + 1` are the conjugates of
+/// these, so a real polynomial is entirely determined by these `n/2` values.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SpecialFft {
+    slots: usize,
+    m: usize,
+    rot_group: Box<[usize]>,
+    roots: Box<[Complex64]>,
+}
+
+impl SpecialFft {
+    /// Precompute the roots of unity for polynomials of degree `degree`.
+    pub(crate) fn new(degree: usize) -> Self {
+        let slots = degree / 2;
+        let m = 2 * degree;
+
+        let mut rot_group = Vec::with_capacity(slots);
+        let mut five_pow = 1usize;
+        for _ in 0..slots {
+            rot_group.push(five_pow);
+            five_pow = (five_pow * 5) % m;
+        }
+
+        let roots = (0..=m)
+            .map(|k| Complex64::from_polar(1.0, 2.0 * PI * (k as f64) / (m as f64)))
+            .collect::<Vec<_>>();
+
+        Self {
+            slots,
+            m,
+            rot_group: rot_group.into_boxed_slice(),
+            roots: roots.into_boxed_slice(),
+        }
+    }
+
+    /// Evaluate in place the "complexified" polynomial whose coefficients are
+    /// `vals[i] = m_i + m_(i + n/2) * I` at the roots `ζ^(5^j)`.
+    pub(crate) fn forward(&self, vals: &mut [Complex64]) {
+        debug_assert_eq!(vals.len(), self.slots);
+
+        bit_reverse(vals);
+        let mut len = 2;
+        while len <= self.slots {
+            let lenh = len >> 1;
+            let lenq = len << 2;
+            for i in (0..self.slots).step_by(len) {
+                for j in 0..lenh {
+                    let idx = (self.rot_group[j] % lenq) * self.m / lenq;
+                    let u = vals[i + j];
+                    let v = vals[i + j + lenh] * self.roots[idx];
+                    vals[i + j] = u + v;
+                    vals[i + j + lenh] = u - v;
+                }
+            }
+            len <<= 1;
+        }
+    }
+
+    /// Inverse of [`Self::forward`].
+    pub(crate) fn backward(&self, vals: &mut [Complex64]) {
+        debug_assert_eq!(vals.len(), self.slots);
+
+        let mut len = self.slots;
+        while len >= 2 {
+            let lenh = len >> 1;
+            let lenq = len << 2;
+            for i in (0..self.slots).step_by(len) {
+                for j in 0..lenh {
+                    let idx = (lenq - (self.rot_group[j] % lenq)) * self.m / lenq;
+                    let u = vals[i + j] + vals[i + j + lenh];
+                    let v = (vals[i + j] - vals[i + j + lenh]) * self.roots[idx];
+                    vals[i + j] = u;
+                    vals[i + j + lenh] = v;
+                }
+            }
+            len >>= 1;
+        }
+        bit_reverse(vals);
+
+        let slots_inv = 1.0 / (self.slots as f64);
+        vals.iter_mut().for_each(|v| *v *= slots_inv);
+    }
+}
+
+/// Permute a vector whose length is a power of two in bit-reversed order.
+fn bit_reverse(vals: &mut [Complex64]) {
+    let n = vals.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j >= bit {
+            j -= bit;
+            bit >>= 1;
+        }
+        j += bit;
+        if i < j {
+            vals.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SpecialFft;
+    use num_complex::Complex64;
+    use rand::{thread_rng, Rng};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn canonical_embedding() {
+        let mut rng = thread_rng();
+        for degree in [8, 16, 128] {
+            let fft = SpecialFft::new(degree);
+
+            let m = (0..degree)
+                .map(|_| rng.gen_range(-100.0..100.0))
+                .collect::<Vec<f64>>();
+            let mut vals = (0..degree / 2)
+                .map(|i| Complex64::new(m[i], m[i + degree / 2]))
+                .collect::<Vec<_>>();
+            fft.forward(&mut vals);
+
+            // Compare with the evaluation of the polynomial at the roots.
+            let mut five_pow = 1;
+            for v in &vals {
+                let root = Complex64::from_polar(1.0, PI * (five_pow as f64) / (degree as f64));
+                let expected = m
+                    .iter()
+                    .rev()
+                    .fold(Complex64::new(0.0, 0.0), |acc, mi| acc * root + mi);
+                assert!((v - expected).norm() < 1e-9);
+                five_pow = (five_pow * 5) % (2 * degree);
+            }
+
+            fft.backward(&mut vals);
+            for i in 0..degree / 2 {
+                assert!((vals[i].re - m[i]).abs() < 1e-9);
+                assert!((vals[i].im - m[i + degree / 2]).abs() < 1e-9);
+            }
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! Galois keys for the CKKS encryption scheme
+
+use super::key_switching::{key_switch, new_key_switching_key};
+use super::SecretKey;
+use crate::bfv::KeySwitchingKey;
+use crate::ckks::{Ciphertext, CkksParameters};
+use crate::{Error, Result};
+use fhe_math::rq::{traits::TryConvertFrom, Poly, Representation, SubstitutionExponent};
+use fhe_traits::FheParametrized;
+use rand::{CryptoRng, RngCore};
+use std::sync::Arc;
+use zeroize::Zeroizing;
+
+/// Galois key for the CKKS encryption scheme.
+///
+/// A Galois key switches from `s(x^i)` to `s(x)` where `s(x)` is the secret
+/// key. Rotations of the slots and complex conjugation are both Galois
+/// automorphisms.
+#[derive(Debug, PartialEq)]
+pub struct GaloisKey {
+    pub(crate) par: Arc<CkksParameters>,
+
+    /// The substitution exponent, at each level.
+    pub(crate) elements: Box<[SubstitutionExponent]>,
+
+    pub(crate) ksk: KeySwitchingKey,
+}
+
+impl GaloisKey {
+    /// Generate a [`GaloisKey`] from a [`SecretKey`] for the substitution
+    /// `x -> x^exponent`. Returns an error if the exponent is even.
+    pub fn new<R: RngCore + CryptoRng>(
+        sk: &SecretKey,
+        exponent: usize,
+        rng: &mut R,
+    ) -> Result<Self> {
+        let elements = sk
+            .par
+            .ctx
+            .iter()
+            .map(|ctx| SubstitutionExponent::new(ctx, exponent))
+            .collect::<fhe_math::Result<Vec<_>>>()?;
+
+        let s = Zeroizing::new(Poly::try_convert_from(
+            sk.coeffs.as_ref(),
+            &sk.par.ctx[0],
+            false,
+            Representation::PowerBasis,
+        )?);
+        let s_sub = Zeroizing::new(s.substitute(&elements[0])?);
+        let ksk = new_key_switching_key(sk, &s_sub, rng)?;
+
+        Ok(Self {
+            par: sk.par.clone(),
+            elements: elements.into_boxed_slice(),
+            ksk,
+        })
+    }
+
+    /// Generate a [`GaloisKey`] which rotates the slots to the left by
+    /// `steps`; negative values rotate to the right.
+    pub fn new_rotation<R: RngCore + CryptoRng>(
+        sk: &SecretKey,
+        steps: isize,
+        rng: &mut R,
+    ) -> Result<Self> {
+        // The slot `j` corresponds to the root of unity `ζ^(5^j)`, and 5 has
+        // order `degree / 2` modulo `2 * degree`.
+        let slots = sk.par.slots() as isize;
+        let m = 2 * sk.par.degree();
+        let exponent = (0..steps.rem_euclid(slots)).fold(1usize, |exponent, _| (exponent * 5) % m);
+        Self::new(sk, exponent, rng)
+    }
+
+    /// Generate a [`GaloisKey`] which conjugates the slots.
+    pub fn new_conjugation<R: RngCore + CryptoRng>(sk: &SecretKey, rng: &mut R) -> Result<Self> {
+        Self::new(sk, 2 * sk.par.degree() - 1, rng)
+    }
+
+    /// Apply the Galois automorphism to a [`Ciphertext`], and relinearize the
+    /// result using the [`GaloisKey`].
+    pub fn relinearize(&self, ct: &Ciphertext) -> Result<Ciphertext> {
+        if ct.c.len() != 2 {
+            return Err(Error::InvalidCiphertextSize {
+                expected: 2,
+                found: ct.c.len(),
+            });
+        } else if ct.par != self.par {
+            return Err(Error::ParametersMismatch);
+        }
+
+        let element = &self.elements[ct.level];
+        let mut c1 = ct.c[1].substitute(element)?;
+        c1.change_representation(Representation::PowerBasis);
+        let (mut c0, c1) = key_switch(&self.ksk, &self.par, &c1)?;
+        c0 += &ct.c[0].substitute(element)?;
+
+        Ok(Ciphertext {
+            par: ct.par.clone(),
+            c: vec![c0, c1],
+            level: ct.level,
+            scale: ct.scale,
+        })
+    }
+}
+
+impl FheParametrized for GaloisKey {
+    type Parameters = CkksParameters;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GaloisKey;
+    use crate::ckks::{Ciphertext, CkksParameters, Encoding, Plaintext, SecretKey};
+    use fhe_traits::{FheDecoder, FheDecrypter, FheEncoder, FheEncrypter};
+    use num_complex::Complex64;
+    use rand::{thread_rng, Rng};
+    use std::error::Error;
+
+    #[test]
+    fn rotation() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = CkksParameters::default_arc(3, 16);
+        let slots = params.slots();
+        let sk = SecretKey::random(&params, &mut rng);
+        for steps in [1, 3, -1, 8] {
+            let gk = GaloisKey::new_rotation(&sk, steps, &mut rng)?;
+            for level in 0..params.max_level() {
+                let v = (0..slots)
+                    .map(|_| Complex64::new(rng.gen_range(-10.0..10.0), rng.gen_range(-10.0..10.0)))
+                    .collect::<Vec<_>>();
+                let pt = Plaintext::try_encode(&v, Encoding::slots_at_level(level), &params)?;
+                let ct: Ciphertext = sk.try_encrypt(&pt, &mut rng)?;
+
+                let ct2 = gk.relinearize(&ct)?;
+                assert_eq!(ct2.level(), level);
+                let w = Vec::<Complex64>::try_decode(&sk.try_decrypt(&ct2)?, None)?;
+                for (i, wi) in w.iter().enumerate() {
+                    let expected = v[(i as isize + steps).rem_euclid(slots as isize) as usize];
+                    assert!((expected - wi).norm() < 1e-6);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn conjugation() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = CkksParameters::default_arc(3, 16);
+        let sk = SecretKey::random(&params, &mut rng);
+        let gk = GaloisKey::new_conjugation(&sk, &mut rng)?;
+        assert!(GaloisKey::new(&sk, 2, &mut rng).is_err());
+
+        let v = (0..params.slots())
+            .map(|_| Complex64::new(rng.gen_range(-10.0..10.0), rng.gen_range(-10.0..10.0)))
+            .collect::<Vec<_>>();
+        let pt = Plaintext::try_encode(&v, Encoding::slots(), &params)?;
+        let ct: Ciphertext = sk.try_encrypt(&pt, &mut rng)?;
+
+        let w = Vec::<Complex64>::try_decode(&sk.try_decrypt(&gk.relinearize(&ct)?)?, None)?;
+        v.iter()
+            .zip(&w)
+            .for_each(|(vi, wi)| assert!((vi.conj() - wi).norm() < 1e-6));
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+//! Key switching for the CKKS encryption scheme, built on the key switching
+//! keys of the BFV encryption scheme.
+
+use crate::bfv::{KeySwitchingKey, KeySwitchingMode, SecretKey as BfvSecretKey};
+use crate::bgv::reduce_to_ctx;
+use crate::ckks::{CkksParameters, SecretKey};
+use crate::Result;
+use fhe_math::rq::Poly;
+use rand::{CryptoRng, RngCore};
+
+/// Generate a key switching key from the polynomial `from` to the secret key,
+/// for polynomials at the first level.
+///
+/// The noise added by key switching must remain small compared to the scale,
+/// so the key uses hybrid key switching with one digit per ciphertext modulus:
+/// the single 62-bit special prime is at least as wide as each digit. The
+/// parameters account for this special prime when checking their security.
+pub(crate) fn new_key_switching_key<R: RngCore + CryptoRng>(
+    sk: &SecretKey,
+    from: &Poly,
+    rng: &mut R,
+) -> Result<KeySwitchingKey> {
+    let bfv_sk = BfvSecretKey::new(sk.coeffs.to_vec(), &sk.par.key_switching_par);
+    let mode = KeySwitchingMode::Hybrid {
+        dnum: sk.par.moduli().len(),
+    };
+    KeySwitchingKey::new_with_mode(&bfv_sk, from, 0, 0, mode, rng)
+}
+
+/// Key switch a polynomial in PowerBasis representation at any level.
+///
+/// The polynomial is lifted to the first level, where the key switching key
+/// is defined; since the lift is congruent to the polynomial modulo the
+/// ciphertext modulus of its level, reducing the output of the key switching
+/// back to that level yields the expected result.
+pub(crate) fn key_switch(
+    ksk: &KeySwitchingKey,
+    par: &CkksParameters,
+    p: &Poly,
+) -> Result<(Poly, Poly)> {
+    let level = par.level_of_ctx(p.ctx())?;
+    let p_lifted = p.scale(&par.extenders[level])?;
+    let (c0, c1) = ksk.key_switch(&p_lifted)?;
+    Ok((reduce_to_ctx(&c0, p.ctx())?, reduce_to_ctx(&c1, p.ctx())?))
+}
@@ -0,0 +1,10 @@
+mod galois_key;
+mod key_switching;
+mod public_key;
+mod relinearization_key;
+mod secret_key;
+
+pub use galois_key::GaloisKey;
+pub use public_key::PublicKey;
+pub use relinearization_key::RelinearizationKey;
+pub use secret_key::SecretKey;
@@ -0,0 +1,105 @@
+//! Relinearization keys for the CKKS encryption scheme
+
+use super::key_switching::{key_switch, new_key_switching_key};
+use super::SecretKey;
+use crate::bfv::KeySwitchingKey;
+use crate::ckks::{Ciphertext, CkksParameters};
+use crate::{Error, Result};
+use fhe_math::rq::{Poly, Representation};
+use fhe_traits::FheParametrized;
+use rand::{CryptoRng, RngCore};
+use std::sync::Arc;
+use zeroize::Zeroizing;
+
+/// Relinearization key for the CKKS encryption scheme.
+///
+/// The key switches from the square of the secret key to the secret key at
+/// the first level; ciphertexts at lower levels are lifted to the first level
+/// before being relinearized.
+#[derive(Debug, PartialEq)]
+pub struct RelinearizationKey {
+    pub(crate) par: Arc<CkksParameters>,
+    pub(crate) ksk: KeySwitchingKey,
+}
+
+impl RelinearizationKey {
+    /// Generate a [`RelinearizationKey`] from a [`SecretKey`].
+    pub fn new<R: RngCore + CryptoRng>(sk: &SecretKey, rng: &mut R) -> Result<Self> {
+        let s = sk.to_poly(&Poly::zero(&sk.par.ctx[0], Representation::Ntt))?;
+        let mut s2 = Zeroizing::new(s.as_ref() * s.as_ref());
+        s2.change_representation(Representation::PowerBasis);
+        let ksk = new_key_switching_key(sk, &s2, rng)?;
+        Ok(Self {
+            par: sk.par.clone(),
+            ksk,
+        })
+    }
+
+    /// Relinearize an "extended" ciphertext (c0, c1, c2) into a [`Ciphertext`]
+    pub fn relinearizes(&self, ct: &mut Ciphertext) -> Result<()> {
+        if ct.c.len() != 3 {
+            Err(Error::InvalidCiphertextSize {
+                expected: 3,
+                found: ct.c.len(),
+            })
+        } else if ct.par != self.par {
+            Err(Error::ParametersMismatch)
+        } else {
+            let mut c2 = ct.c.pop().unwrap();
+            c2.change_representation(Representation::PowerBasis);
+            let (c0, c1) = key_switch(&self.ksk, &self.par, &c2)?;
+            ct.c[0] += &c0;
+            ct.c[1] += &c1;
+            Ok(())
+        }
+    }
+}
+
+impl FheParametrized for RelinearizationKey {
+    type Parameters = CkksParameters;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RelinearizationKey;
+    use crate::ckks::{Ciphertext, CkksParameters, Encoding, Plaintext, SecretKey};
+    use fhe_traits::{FheDecoder, FheDecrypter, FheEncoder, FheEncrypter};
+    use rand::{thread_rng, Rng};
+    use std::error::Error;
+
+    #[test]
+    fn relinearization() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = CkksParameters::default_arc(4, 16);
+        for _ in 0..10 {
+            let sk = SecretKey::random(&params, &mut rng);
+            let rk = RelinearizationKey::new(&sk, &mut rng)?;
+
+            for level in 0..params.max_level() {
+                let v1 = (0..params.slots())
+                    .map(|_| rng.gen_range(-10.0..10.0))
+                    .collect::<Vec<f64>>();
+                let v2 = (0..params.slots())
+                    .map(|_| rng.gen_range(-10.0..10.0))
+                    .collect::<Vec<f64>>();
+
+                let pt1 = Plaintext::try_encode(&v1, Encoding::slots_at_level(level), &params)?;
+                let pt2 = Plaintext::try_encode(&v2, Encoding::slots_at_level(level), &params)?;
+                let ct1: Ciphertext = sk.try_encrypt(&pt1, &mut rng)?;
+                let ct2: Ciphertext = sk.try_encrypt(&pt2, &mut rng)?;
+
+                let mut ct3 = &ct1 * &ct2;
+                assert!(rk.relinearizes(&mut ct1.clone()).is_err());
+                rk.relinearizes(&mut ct3)?;
+                assert_eq!(ct3.c.len(), 2);
+
+                let pt = sk.try_decrypt(&ct3)?;
+                let w = Vec::<f64>::try_decode(&pt, None)?;
+                for (v1i, v2i, wi) in itertools::izip!(&v1, &v2, &w) {
+                    assert!((v1i * v2i - wi).abs() < 1e-6)
+                }
+            }
+        }
+        Ok(())
+    }
+}
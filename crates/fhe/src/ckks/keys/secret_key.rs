@@ -0,0 +1,206 @@
+//! Secret keys for the CKKS encryption scheme
+
+use crate::ckks::{Ciphertext, CkksParameters, Plaintext};
+use crate::{Error, Result};
+use fhe_math::rq::{traits::TryConvertFrom, Poly, Representation};
+use fhe_traits::{FheDecrypter, FheEncrypter, FheParametrized};
+use fhe_util::sample_vec_cbd;
+use rand::{CryptoRng, RngCore};
+use std::sync::Arc;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+/// Secret key for the CKKS encryption scheme.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SecretKey {
+    pub(crate) par: Arc<CkksParameters>,
+    pub(crate) coeffs: Box<[i64]>,
+}
+
+impl Zeroize for SecretKey {
+    fn zeroize(&mut self) {
+        self.coeffs.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SecretKey {}
+
+impl SecretKey {
+    /// Generate a random [`SecretKey`].
+    pub fn random<R: RngCore + CryptoRng>(par: &Arc<CkksParameters>, rng: &mut R) -> Self {
+        let s_coefficients = sample_vec_cbd(par.degree(), par.variance, rng).unwrap();
+        Self::new(s_coefficients, par)
+    }
+
+    /// Generate a [`SecretKey`] from its coefficients.
+    pub(crate) fn new(coeffs: Vec<i64>, par: &Arc<CkksParameters>) -> Self {
+        Self {
+            par: par.clone(),
+            coeffs: coeffs.into_boxed_slice(),
+        }
+    }
+
+    /// Returns the secret key as a polynomial in Ntt representation at the
+    /// level of the provided polynomial.
+    pub(crate) fn to_poly(&self, p: &Poly) -> Result<Zeroizing<Poly>> {
+        let mut s = Zeroizing::new(Poly::try_convert_from(
+            self.coeffs.as_ref(),
+            p.ctx(),
+            false,
+            Representation::PowerBasis,
+        )?);
+        s.change_representation(Representation::Ntt);
+        Ok(s)
+    }
+
+    /// Encrypt a polynomial in Ntt representation, i.e., output a ciphertext
+    /// `(b, a)` such that `b + a * s = p + e` for a small error `e`.
+    pub(crate) fn encrypt_poly<R: RngCore + CryptoRng>(
+        &self,
+        p: &Poly,
+        scale: f64,
+        rng: &mut R,
+    ) -> Result<Ciphertext> {
+        assert_eq!(p.representation(), &Representation::Ntt);
+
+        let level = self.par.level_of_ctx(p.ctx())?;
+        let s = self.to_poly(p)?;
+
+        let mut a = Poly::random(p.ctx(), Representation::Ntt, rng);
+        let a_s = Zeroizing::new(&a * s.as_ref());
+
+        let mut b = Poly::small(p.ctx(), Representation::Ntt, self.par.variance, rng)
+            .map_err(Error::MathError)?;
+        b -= &a_s;
+        b += p;
+
+        // It is now safe to enable variable time computations.
+        unsafe {
+            a.allow_variable_time_computations();
+            b.allow_variable_time_computations()
+        }
+
+        Ok(Ciphertext {
+            par: self.par.clone(),
+            c: vec![b, a],
+            level,
+            scale,
+        })
+    }
+}
+
+impl FheParametrized for SecretKey {
+    type Parameters = CkksParameters;
+}
+
+impl FheEncrypter<Plaintext, Ciphertext> for SecretKey {
+    type Error = Error;
+
+    fn try_encrypt<R: RngCore + CryptoRng>(
+        &self,
+        pt: &Plaintext,
+        rng: &mut R,
+    ) -> Result<Ciphertext> {
+        assert_eq!(self.par, pt.par);
+        self.encrypt_poly(&pt.poly_ntt, pt.scale, rng)
+    }
+}
+
+impl FheDecrypter<Plaintext, Ciphertext> for SecretKey {
+    type Error = Error;
+
+    fn try_decrypt(&self, ct: &Ciphertext) -> Result<Plaintext> {
+        if self.par != ct.par {
+            Err(Error::ParametersMismatch)
+        } else {
+            let s = self.to_poly(&ct.c[0])?;
+            let mut si = s.clone();
+
+            let mut c = Zeroizing::new(ct.c[0].clone());
+            c.disallow_variable_time_computations();
+
+            for i in 1..ct.c.len() {
+                let mut cis = Zeroizing::new(ct.c[i].clone());
+                cis.disallow_variable_time_computations();
+                *cis.as_mut() *= si.as_ref();
+                *c.as_mut() += &cis;
+                *si.as_mut() *= s.as_ref();
+            }
+
+            Ok(Plaintext {
+                par: self.par.clone(),
+                poly_ntt: c.as_ref().clone(),
+                scale: ct.scale,
+                level: ct.level,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SecretKey;
+    use crate::ckks::{Ciphertext, CkksParameters, Encoding, Plaintext};
+    use fhe_traits::{FheDecoder, FheDecrypter, FheEncoder, FheEncrypter};
+    use num_complex::Complex64;
+    use rand::{thread_rng, Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+    use std::error::Error;
+
+    #[test]
+    fn keygen() {
+        let mut rng = thread_rng();
+        let params = CkksParameters::default_arc(1, 16);
+        let sk = SecretKey::random(&params, &mut rng);
+        assert_eq!(sk.par, params);
+
+        sk.coeffs.iter().for_each(|ci| {
+            // Check that this is a small polynomial
+            assert!((*ci).abs() <= 2 * sk.par.variance as i64)
+        })
+    }
+
+    #[test]
+    fn encrypt_decrypt() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        for params in [
+            CkksParameters::default_arc(1, 16),
+            CkksParameters::default_arc(4, 16),
+        ] {
+            for level in 0..=params.max_level() {
+                for _ in 0..20 {
+                    let sk = SecretKey::random(&params, &mut rng);
+
+                    let v = (0..params.slots())
+                        .map(|_| {
+                            Complex64::new(rng.gen_range(-10.0..10.0), rng.gen_range(-10.0..10.0))
+                        })
+                        .collect::<Vec<_>>();
+                    let pt = Plaintext::try_encode(&v, Encoding::slots_at_level(level), &params)?;
+                    let ct: Ciphertext = sk.try_encrypt(&pt, &mut rng)?;
+                    assert_eq!(ct.level(), level);
+                    let pt2 = sk.try_decrypt(&ct)?;
+
+                    let w = Vec::<Complex64>::try_decode(&pt2, None)?;
+                    v.iter()
+                        .zip(&w)
+                        .for_each(|(vi, wi)| assert!((vi - wi).norm() < 1e-6));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn deterministic_encryption() -> Result<(), Box<dyn Error>> {
+        let params = CkksParameters::default_arc(2, 16);
+        let sk = SecretKey::random(&params, &mut thread_rng());
+        let pt = Plaintext::try_encode(&[1.0, 2.0, 3.0], Encoding::slots(), &params)?;
+
+        let ct1: Ciphertext = sk.try_encrypt(&pt, &mut ChaCha8Rng::from_seed([1u8; 32]))?;
+        let ct2: Ciphertext = sk.try_encrypt(&pt, &mut ChaCha8Rng::from_seed([1u8; 32]))?;
+        assert_eq!(ct1, ct2);
+
+        Ok(())
+    }
+}
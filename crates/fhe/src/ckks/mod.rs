@@ -0,0 +1,19 @@
+#![warn(missing_docs, unused_imports)]
+
+//! The Cheon-Kim-Kim-Song homomorphic encryption scheme for approximate
+//! arithmetic
+
+mod ciphertext;
+mod encoding;
+mod keys;
+mod ops;
+mod parameters;
+mod plaintext;
+#[allow(renamed_and_removed_lints)]
+mod proto;
+
+pub use ciphertext::Ciphertext;
+pub use encoding::Encoding;
+pub use keys::{GaloisKey, PublicKey, RelinearizationKey, SecretKey};
+pub use parameters::{CkksParameters, CkksParametersBuilder};
+pub use plaintext::Plaintext;
@@ -0,0 +1,286 @@
+//! Operations over ciphertexts
+
+use super::{Ciphertext, Plaintext};
+use fhe_math::rq::{Poly, Representation};
+use itertools::{izip, Itertools};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+impl Add<&Ciphertext> for &Ciphertext {
+    type Output = Ciphertext;
+
+    fn add(self, rhs: &Ciphertext) -> Ciphertext {
+        let mut self_clone = self.clone();
+        self_clone += rhs;
+        self_clone
+    }
+}
+
+impl AddAssign<&Ciphertext> for Ciphertext {
+    fn add_assign(&mut self, rhs: &Ciphertext) {
+        assert_eq!(self.par, rhs.par);
+        assert_eq!(self.level, rhs.level);
+        assert_eq!(self.scale, rhs.scale);
+        assert_eq!(self.c.len(), rhs.c.len());
+
+        izip!(&mut self.c, &rhs.c).for_each(|(c1i, c2i)| *c1i += c2i);
+    }
+}
+
+impl Add<&Plaintext> for &Ciphertext {
+    type Output = Ciphertext;
+
+    fn add(self, rhs: &Plaintext) -> Ciphertext {
+        let mut self_clone = self.clone();
+        self_clone += rhs;
+        self_clone
+    }
+}
+
+impl Add<&Ciphertext> for &Plaintext {
+    type Output = Ciphertext;
+
+    fn add(self, rhs: &Ciphertext) -> Ciphertext {
+        rhs + self
+    }
+}
+
+impl AddAssign<&Plaintext> for Ciphertext {
+    fn add_assign(&mut self, rhs: &Plaintext) {
+        assert_eq!(self.par, rhs.par);
+        assert_eq!(self.level, rhs.level);
+        assert_eq!(self.scale, rhs.scale);
+
+        self.c[0] += &rhs.poly_ntt;
+    }
+}
+
+impl Sub<&Ciphertext> for &Ciphertext {
+    type Output = Ciphertext;
+
+    fn sub(self, rhs: &Ciphertext) -> Ciphertext {
+        let mut self_clone = self.clone();
+        self_clone -= rhs;
+        self_clone
+    }
+}
+
+impl SubAssign<&Ciphertext> for Ciphertext {
+    fn sub_assign(&mut self, rhs: &Ciphertext) {
+        assert_eq!(self.par, rhs.par);
+        assert_eq!(self.level, rhs.level);
+        assert_eq!(self.scale, rhs.scale);
+        assert_eq!(self.c.len(), rhs.c.len());
+
+        izip!(&mut self.c, &rhs.c).for_each(|(c1i, c2i)| *c1i -= c2i);
+    }
+}
+
+impl Sub<&Plaintext> for &Ciphertext {
+    type Output = Ciphertext;
+
+    fn sub(self, rhs: &Plaintext) -> Ciphertext {
+        let mut self_clone = self.clone();
+        self_clone -= rhs;
+        self_clone
+    }
+}
+
+impl Sub<&Ciphertext> for &Plaintext {
+    type Output = Ciphertext;
+
+    fn sub(self, rhs: &Ciphertext) -> Ciphertext {
+        -(rhs - self)
+    }
+}
+
+impl SubAssign<&Plaintext> for Ciphertext {
+    fn sub_assign(&mut self, rhs: &Plaintext) {
+        assert_eq!(self.par, rhs.par);
+        assert_eq!(self.level, rhs.level);
+        assert_eq!(self.scale, rhs.scale);
+
+        self.c[0] -= &rhs.poly_ntt;
+    }
+}
+
+impl Neg for &Ciphertext {
+    type Output = Ciphertext;
+
+    fn neg(self) -> Ciphertext {
+        let c = self.c.iter().map(|c1i| -c1i).collect_vec();
+        Ciphertext {
+            par: self.par.clone(),
+            c,
+            level: self.level,
+            scale: self.scale,
+        }
+    }
+}
+
+impl Neg for Ciphertext {
+    type Output = Ciphertext;
+
+    fn neg(mut self) -> Ciphertext {
+        self.c.iter_mut().for_each(|c1i| *c1i = -&*c1i);
+        self
+    }
+}
+
+/// The multiplication by a plaintext multiplies the scales; the output
+/// ciphertext is typically rescaled afterwards.
+impl MulAssign<&Plaintext> for Ciphertext {
+    fn mul_assign(&mut self, rhs: &Plaintext) {
+        assert_eq!(self.par, rhs.par);
+        assert_eq!(self.level, rhs.level);
+
+        self.c.iter_mut().for_each(|ci| *ci *= &rhs.poly_ntt);
+        self.scale *= rhs.scale;
+    }
+}
+
+impl Mul<&Plaintext> for &Ciphertext {
+    type Output = Ciphertext;
+
+    fn mul(self, rhs: &Plaintext) -> Ciphertext {
+        let mut self_clone = self.clone();
+        self_clone *= rhs;
+        self_clone
+    }
+}
+
+/// The tensor product of two ciphertexts, computed directly modulo the
+/// ciphertext modulus; the scale of the output is the product of the scales.
+/// The output ciphertext can be relinearized using a
+/// [`RelinearizationKey`](super::RelinearizationKey), and then rescaled.
+impl Mul<&Ciphertext> for &Ciphertext {
+    type Output = Ciphertext;
+
+    fn mul(self, rhs: &Ciphertext) -> Ciphertext {
+        assert_eq!(self.par, rhs.par);
+        assert_eq!(self.level, rhs.level);
+
+        let ctx = self.c[0].ctx();
+        let mut c = vec![Poly::zero(ctx, Representation::Ntt); self.c.len() + rhs.c.len() - 1];
+        for i in 0..self.c.len() {
+            for j in 0..rhs.c.len() {
+                c[i + j] += &(&self.c[i] * &rhs.c[j])
+            }
+        }
+
+        Ciphertext {
+            par: self.par.clone(),
+            c,
+            level: self.level,
+            scale: self.scale * rhs.scale,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ckks::{
+        Ciphertext, CkksParameters, Encoding, Plaintext, RelinearizationKey, SecretKey,
+    };
+    use fhe_traits::{FheDecoder, FheDecrypter, FheEncoder, FheEncrypter};
+    use itertools::izip;
+    use rand::{thread_rng, Rng};
+    use std::error::Error;
+
+    #[test]
+    fn add_sub_neg() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        for params in [
+            CkksParameters::default_arc(1, 16),
+            CkksParameters::default_arc(4, 16),
+        ] {
+            for _ in 0..20 {
+                let a = (0..params.slots())
+                    .map(|_| rng.gen_range(-10.0..10.0))
+                    .collect::<Vec<f64>>();
+                let b = (0..params.slots())
+                    .map(|_| rng.gen_range(-10.0..10.0))
+                    .collect::<Vec<f64>>();
+                let sum = izip!(&a, &b).map(|(ai, bi)| ai + bi).collect::<Vec<_>>();
+                let diff = izip!(&a, &b).map(|(ai, bi)| ai - bi).collect::<Vec<_>>();
+                let neg = a.iter().map(|ai| -ai).collect::<Vec<_>>();
+
+                let sk = SecretKey::random(&params, &mut rng);
+                let pt_a = Plaintext::try_encode(&a, Encoding::slots(), &params)?;
+                let pt_b = Plaintext::try_encode(&b, Encoding::slots(), &params)?;
+                let ct_a: Ciphertext = sk.try_encrypt(&pt_a, &mut rng)?;
+                let ct_b: Ciphertext = sk.try_encrypt(&pt_b, &mut rng)?;
+
+                for (ct, expected) in [
+                    (&ct_a + &ct_b, &sum),
+                    (&ct_a + &pt_b, &sum),
+                    (&pt_a + &ct_b, &sum),
+                    (&ct_a - &ct_b, &diff),
+                    (&ct_a - &pt_b, &diff),
+                    (&pt_a - &ct_b, &diff),
+                    (-&ct_a, &neg),
+                    (-ct_a.clone(), &neg),
+                ] {
+                    let w = Vec::<f64>::try_decode(&sk.try_decrypt(&ct)?, None)?;
+                    izip!(expected, &w).for_each(|(ei, wi)| assert!((ei - wi).abs() < 1e-6));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn mul() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = CkksParameters::default_arc(4, 16);
+        for _ in 0..20 {
+            let v1 = (0..params.slots())
+                .map(|_| rng.gen_range(-2.0..2.0))
+                .collect::<Vec<f64>>();
+            let v2 = (0..params.slots())
+                .map(|_| rng.gen_range(-2.0..2.0))
+                .collect::<Vec<f64>>();
+            let expected = izip!(&v1, &v2).map(|(a, b)| a * b).collect::<Vec<_>>();
+
+            let sk = SecretKey::random(&params, &mut rng);
+            let rk = RelinearizationKey::new(&sk, &mut rng)?;
+            let pt1 = Plaintext::try_encode(&v1, Encoding::slots(), &params)?;
+            let pt2 = Plaintext::try_encode(&v2, Encoding::slots(), &params)?;
+            let ct1: Ciphertext = sk.try_encrypt(&pt1, &mut rng)?;
+            let ct2: Ciphertext = sk.try_encrypt(&pt2, &mut rng)?;
+
+            // Multiplication by a plaintext, and rescaling.
+            let mut ct = &ct1 * &pt2;
+            assert_eq!(ct.scale(), params.scale() * params.scale());
+            ct.rescale_to_next_level()?;
+            let w = Vec::<f64>::try_decode(&sk.try_decrypt(&ct)?, None)?;
+            izip!(&expected, &w).for_each(|(ei, wi)| assert!((ei - wi).abs() < 1e-6));
+
+            // Multiplication, relinearization and rescaling.
+            let mut ct3 = &ct1 * &ct2;
+            rk.relinearizes(&mut ct3)?;
+            ct3.rescale_to_next_level()?;
+            assert_eq!(ct3.level(), 1);
+            let w = Vec::<f64>::try_decode(&sk.try_decrypt(&ct3)?, None)?;
+            izip!(&expected, &w).for_each(|(ei, wi)| assert!((ei - wi).abs() < 1e-6));
+
+            // Square, relinearize and rescale again.
+            let mut ct4 = &ct3 * &ct3;
+            rk.relinearizes(&mut ct4)?;
+            ct4.rescale_to_next_level()?;
+            let w = Vec::<f64>::try_decode(&sk.try_decrypt(&ct4)?, None)?;
+            izip!(&expected, &w).for_each(|(ei, wi)| assert!((ei * ei - wi).abs() < 1e-5));
+
+            // Add a plaintext encoded at the scale of the rescaled ciphertext.
+            let pt = Plaintext::try_encode(
+                &v1,
+                Encoding::slots_at_level(2).with_scale(ct4.scale()),
+                &params,
+            )?;
+            let w = Vec::<f64>::try_decode(&sk.try_decrypt(&(&ct4 + &pt))?, None)?;
+            izip!(&expected, &v1, &w)
+                .for_each(|(ei, v1i, wi)| assert!((ei * ei + v1i - wi).abs() < 1e-5));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,479 @@
+//! Create parameters for the CKKS encryption scheme
+
+use crate::bfv::{BfvParameters, BfvParametersBuilder, SecurityLevel};
+use crate::ckks::{encoding::SpecialFft, proto::ckks::Parameters};
+use crate::{Error, ParametersError, Result};
+use fhe_math::{
+    rns::ScalingFactor,
+    rq::{scaler::Scaler, Context},
+};
+use fhe_traits::{Deserialize, FheParameters, Serialize};
+use itertools::Itertools;
+use num_bigint::BigUint;
+use num_traits::One;
+use protobuf::Message;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// Parameters for the CKKS encryption scheme.
+#[derive(PartialEq)]
+pub struct CkksParameters {
+    /// Number of coefficients in a polynomial.
+    polynomial_degree: usize,
+
+    /// Vector of coprime moduli q_i for the ciphertext.
+    pub(crate) moduli: Box<[u64]>,
+
+    /// Vector of the sized of the coprime moduli q_i for the ciphertext.
+    moduli_sizes: Box<[usize]>,
+
+    /// Error variance
+    pub(crate) variance: usize,
+
+    /// Default scale of the plaintexts.
+    scale: f64,
+
+    /// Context for the underlying polynomials
+    pub(crate) ctx: Vec<Arc<Context>>,
+
+    /// Scalers dividing a polynomial by the last modulus of its level.
+    pub(crate) rescalers: Box<[Scaler]>,
+
+    /// Scalers lifting a polynomial at each level to the first level.
+    pub(crate) extenders: Box<[Scaler]>,
+
+    /// The canonical embedding.
+    pub(crate) fft: SpecialFft,
+
+    /// BFV parameters with the same degree, ciphertext moduli, contexts,
+    /// variance and minimum security level, so that the CKKS keys can be built
+    /// on the BFV key switching keys. Their plaintext modulus of 2 is never
+    /// used.
+    ///
+    /// The keys are generated at the first level, in hybrid mode with one
+    /// digit per ciphertext modulus and hence a single special prime of 62
+    /// bits, which is counted when checking the security of the parameters.
+    pub(crate) key_switching_par: Arc<BfvParameters>,
+}
+
+impl Debug for CkksParameters {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CkksParameters")
+            .field("polynomial_degree", &self.polynomial_degree)
+            .field("moduli", &self.moduli)
+            .field("scale", &self.scale)
+            .finish()
+    }
+}
+
+impl FheParameters for CkksParameters {}
+
+unsafe impl Send for CkksParameters {}
+
+impl CkksParameters {
+    /// Returns the underlying polynomial degree
+    pub const fn degree(&self) -> usize {
+        self.polynomial_degree
+    }
+
+    /// Returns the number of complex slots of a plaintext.
+    pub const fn slots(&self) -> usize {
+        self.polynomial_degree / 2
+    }
+
+    /// Returns a reference to the ciphertext moduli
+    pub fn moduli(&self) -> &[u64] {
+        &self.moduli
+    }
+
+    /// Returns a reference to the ciphertext moduli
+    pub fn moduli_sizes(&self) -> &[usize] {
+        &self.moduli_sizes
+    }
+
+    /// Returns the default scale of the plaintexts.
+    pub const fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Returns the maximum level allowed by these parameters.
+    pub fn max_level(&self) -> usize {
+        self.moduli.len() - 1
+    }
+
+    /// Returns the context corresponding to the level.
+    pub(crate) fn ctx_at_level(&self, level: usize) -> Result<&Arc<Context>> {
+        self.ctx.get(level).ok_or(Error::InvalidLevel(level))
+    }
+
+    /// Returns the level of a given context
+    pub(crate) fn level_of_ctx(&self, ctx: &Arc<Context>) -> Result<usize> {
+        self.ctx[0].niterations_to(ctx).map_err(Error::MathError)
+    }
+
+    /// Default parameters for tests, with a first ciphertext modulus of 60
+    /// bits followed by `num_moduli - 1` moduli of 40 bits, and a scale of
+    /// 2^40.
+    #[cfg(test)]
+    pub fn default_arc(num_moduli: usize, degree: usize) -> Arc<Self> {
+        if !degree.is_power_of_two() || degree < 8 {
+            panic!("Invalid degree");
+        }
+        let mut moduli_sizes = vec![40usize; num_moduli];
+        moduli_sizes[0] = 60;
+        CkksParametersBuilder::new()
+            .set_degree(degree)
+            .set_moduli_sizes(&moduli_sizes)
+            .set_scale(2f64.powi(40))
+            .allow_insecure()
+            .build_arc()
+            .unwrap()
+    }
+}
+
+/// Builder for parameters for the Ckks encryption scheme.
+#[derive(Debug)]
+pub struct CkksParametersBuilder {
+    degree: usize,
+    variance: usize,
+    scale: f64,
+    ciphertext_moduli: Vec<u64>,
+    ciphertext_moduli_sizes: Vec<usize>,
+    min_security_level: Option<SecurityLevel>,
+}
+
+impl CkksParametersBuilder {
+    /// Creates a new instance of the builder
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            degree: Default::default(),
+            variance: 10,
+            scale: 2f64.powi(40),
+            ciphertext_moduli: Default::default(),
+            ciphertext_moduli_sizes: Default::default(),
+            min_security_level: Some(SecurityLevel::Bits128),
+        }
+    }
+
+    /// Sets the polynomial degree. Returns an error if the degree is not
+    /// a power of two larger or equal to 8.
+    pub fn set_degree(&mut self, degree: usize) -> &mut Self {
+        self.degree = degree;
+        self
+    }
+
+    /// Sets the default scale of the plaintexts, which is 2^40 unless
+    /// specified. Returns an error if the scale is not a finite number larger
+    /// or equal to 1.
+    pub fn set_scale(&mut self, scale: f64) -> &mut Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Sets the sizes of the ciphertext moduli.
+    /// Only one of `set_moduli_sizes` and `set_moduli`
+    /// can be specified.
+    pub fn set_moduli_sizes(&mut self, sizes: &[usize]) -> &mut Self {
+        self.ciphertext_moduli_sizes = sizes.to_owned();
+        self
+    }
+
+    /// Sets the ciphertext moduli to use.
+    /// Only one of `set_moduli_sizes` and `set_moduli`
+    /// can be specified.
+    pub fn set_moduli(&mut self, moduli: &[u64]) -> &mut Self {
+        self.ciphertext_moduli = moduli.to_owned();
+        self
+    }
+
+    /// Sets the error variance. Returns an error if the variance is not between
+    /// one and sixteen.
+    pub fn set_variance(&mut self, variance: usize) -> &mut Self {
+        self.variance = variance;
+        self
+    }
+
+    /// Sets the minimum security level of the parameters, which is 128 bits by
+    /// default. The special prime of the key switching keys is counted in the
+    /// modulus.
+    pub fn set_min_security_level(&mut self, security_level: SecurityLevel) -> &mut Self {
+        self.min_security_level = Some(security_level);
+        self
+    }
+
+    /// Allows parameters with less than 128 bits of security. Such parameters
+    /// are insecure, and must only be used for tests.
+    pub fn allow_insecure(&mut self) -> &mut Self {
+        self.min_security_level = None;
+        self
+    }
+
+    /// Build a new `CkksParameters` inside an `Arc`.
+    pub fn build_arc(&self) -> Result<Arc<CkksParameters>> {
+        self.build().map(Arc::new)
+    }
+
+    /// Build a new `CkksParameters`.
+    pub fn build(&self) -> Result<CkksParameters> {
+        // Check that the degree is a power of 2 (and large enough).
+        if self.degree < 8 || !self.degree.is_power_of_two() {
+            return Err(Error::ParametersError(ParametersError::InvalidDegree(
+                self.degree,
+            )));
+        }
+
+        if !self.scale.is_finite() || self.scale < 1.0 {
+            return Err(Error::ParametersError(ParametersError::InvalidScale));
+        }
+
+        // Check that one of `ciphertext_moduli` and `ciphertext_moduli_sizes` is
+        // specified.
+        if !self.ciphertext_moduli.is_empty() && !self.ciphertext_moduli_sizes.is_empty() {
+            return Err(Error::ParametersError(ParametersError::TooManySpecified));
+        } else if self.ciphertext_moduli.is_empty() && self.ciphertext_moduli_sizes.is_empty() {
+            return Err(Error::ParametersError(ParametersError::TooFewSpecified));
+        }
+
+        // Get or generate the moduli
+        let mut moduli = self.ciphertext_moduli.clone();
+        if !self.ciphertext_moduli_sizes.is_empty() {
+            moduli =
+                BfvParametersBuilder::generate_moduli(&self.ciphertext_moduli_sizes, self.degree)?
+        }
+
+        // Recomputes the moduli sizes
+        let moduli_sizes = moduli
+            .iter()
+            .map(|m| 64 - m.leading_zeros() as usize)
+            .collect_vec();
+
+        // Check the security level. The key switching keys are defined modulo the
+        // product of the ciphertext moduli and of one special prime of 62 bits.
+        if let Some(min_security_level) = self.min_security_level {
+            let security_level =
+                SecurityLevel::estimate(self.degree, moduli_sizes.iter().sum::<usize>() + 62);
+            if security_level.is_none_or(|level| level < min_security_level) {
+                return Err(Error::ParametersError(
+                    ParametersError::InsufficientSecurity(
+                        security_level.map_or(0, |level| level.bits()),
+                        min_security_level.bits(),
+                    ),
+                ));
+            }
+        }
+
+        // The plaintext modulus is irrelevant for key switching.
+        let mut key_switching_builder = BfvParametersBuilder::new();
+        key_switching_builder
+            .set_degree(self.degree)
+            .set_plaintext_modulus(2)
+            .set_moduli(&moduli)
+            .set_variance(self.variance);
+        if let Some(min_security_level) = self.min_security_level {
+            key_switching_builder.set_min_security_level(min_security_level);
+        } else {
+            key_switching_builder.allow_insecure();
+        }
+        let key_switching_par = key_switching_builder.build_arc()?;
+
+        let ctx = key_switching_par.ctx.clone();
+        let rescalers = ctx
+            .iter()
+            .tuple_windows()
+            .map(|(ctx_i, ctx_next)| {
+                let q_last = ctx_i.moduli().last().unwrap();
+                Scaler::new(
+                    ctx_i,
+                    ctx_next,
+                    ScalingFactor::new(&BigUint::one(), &BigUint::from(*q_last)),
+                )
+            })
+            .collect::<fhe_math::Result<Vec<_>>>()?;
+        let extenders = ctx
+            .iter()
+            .map(|ctx_i| Scaler::new(ctx_i, &ctx[0], ScalingFactor::one()))
+            .collect::<fhe_math::Result<Vec<_>>>()?;
+
+        Ok(CkksParameters {
+            polynomial_degree: self.degree,
+            moduli: moduli.into_boxed_slice(),
+            moduli_sizes: moduli_sizes.into_boxed_slice(),
+            variance: self.variance,
+            scale: self.scale,
+            ctx,
+            rescalers: rescalers.into_boxed_slice(),
+            extenders: extenders.into_boxed_slice(),
+            fft: SpecialFft::new(self.degree),
+            key_switching_par,
+        })
+    }
+}
+
+impl Serialize for CkksParameters {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut params = Parameters::new();
+        params.degree = self.polynomial_degree as u32;
+        params.moduli = self.moduli.to_vec();
+        params.variance = self.variance as u32;
+        params.scale = self.scale;
+        params.min_security_level = self
+            .key_switching_par
+            .min_security_level
+            .map_or(0, |level| level.bits() as u32);
+        params.write_to_bytes().unwrap()
+    }
+}
+
+/// The deserialized parameters must provide the minimum security level they
+/// were built with, which is serialized alongside them; parameters built with
+/// [`CkksParametersBuilder::allow_insecure`] are deserialized without a
+/// minimum security level.
+impl Deserialize for CkksParameters {
+    fn try_deserialize(bytes: &[u8]) -> Result<Self> {
+        if let Ok(params) = Parameters::parse_from_bytes(bytes) {
+            let mut builder = CkksParametersBuilder::new();
+            builder
+                .set_degree(params.degree as usize)
+                .set_moduli(&params.moduli)
+                .set_variance(params.variance as usize)
+                .set_scale(params.scale);
+            match params.min_security_level {
+                0 => builder.allow_insecure(),
+                128 => builder.set_min_security_level(SecurityLevel::Bits128),
+                192 => builder.set_min_security_level(SecurityLevel::Bits192),
+                256 => builder.set_min_security_level(SecurityLevel::Bits256),
+                _ => return Err(Error::SerializationError),
+            };
+            builder.build()
+        } else {
+            Err(Error::SerializationError)
+        }
+    }
+    type Error = Error;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CkksParameters, CkksParametersBuilder};
+    use crate::bfv::SecurityLevel;
+    use crate::ckks::proto::ckks::Parameters;
+    use crate::{Error as FheError, ParametersError};
+    use fhe_traits::{Deserialize, Serialize};
+    use protobuf::Message;
+    use std::error::Error;
+    use std::sync::Arc;
+
+    #[test]
+    fn default() {
+        let params = CkksParameters::default_arc(3, 16);
+        assert_eq!(params.moduli.len(), 3);
+        assert_eq!(params.moduli_sizes(), &[60, 40, 40]);
+        assert_eq!(params.degree(), 16);
+        assert_eq!(params.slots(), 8);
+        assert_eq!(params.scale(), 2f64.powi(40));
+        assert_eq!(params.max_level(), 2);
+    }
+
+    #[test]
+    fn invalid_scale() {
+        for scale in [0.5, f64::NAN, f64::INFINITY] {
+            assert!(CkksParametersBuilder::new()
+                .set_degree(16)
+                .set_moduli_sizes(&[60, 40])
+                .set_scale(scale)
+                .allow_insecure()
+                .build()
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn serialize() -> Result<(), Box<dyn Error>> {
+        let params = CkksParametersBuilder::new()
+            .set_degree(16)
+            .set_moduli_sizes(&[60, 40, 40])
+            .set_scale(2f64.powi(30))
+            .set_variance(4)
+            .allow_insecure()
+            .build()?;
+        let bytes = params.to_bytes();
+        assert_eq!(CkksParameters::try_deserialize(&bytes)?, params);
+
+        // The minimum security level is serialized, and checked again.
+        let params = CkksParametersBuilder::new()
+            .set_degree(8192)
+            .set_moduli_sizes(&[60, 40, 40])
+            .set_scale(2f64.powi(30))
+            .build()?;
+        let bytes = params.to_bytes();
+        assert_eq!(CkksParameters::try_deserialize(&bytes)?, params);
+        let mut proto = Parameters::parse_from_bytes(&bytes)?;
+        proto.degree = 16;
+        assert_eq!(
+            CkksParameters::try_deserialize(&proto.write_to_bytes()?),
+            Err(FheError::ParametersError(
+                ParametersError::InsufficientSecurity(0, 128)
+            ))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn key_switching_parameters() {
+        let params = CkksParameters::default_arc(3, 16);
+        let key_switching_par = &params.key_switching_par;
+        assert_eq!(key_switching_par.degree(), params.degree());
+        assert_eq!(key_switching_par.moduli(), params.moduli());
+        assert_eq!(key_switching_par.variance, params.variance);
+        assert_eq!(key_switching_par.min_security_level, None);
+        assert!(key_switching_par
+            .ctx
+            .iter()
+            .zip(params.ctx.iter())
+            .all(|(ctx_bfv, ctx_ckks)| Arc::ptr_eq(ctx_bfv, ctx_ckks)));
+
+        let params = CkksParametersBuilder::new()
+            .set_degree(8192)
+            .set_moduli_sizes(&[60, 40, 40])
+            .build()
+            .unwrap();
+        assert_eq!(
+            params.key_switching_par.min_security_level,
+            Some(SecurityLevel::Bits128)
+        );
+    }
+
+    #[test]
+    fn security_level() -> Result<(), Box<dyn Error>> {
+        // 202 bits with the special prime, below the bound of 218 bits.
+        let mut builder = CkksParametersBuilder::new();
+        builder.set_degree(8192).set_moduli_sizes(&[60, 40, 40]);
+        assert!(builder.build().is_ok());
+        assert_eq!(
+            builder
+                .set_min_security_level(SecurityLevel::Bits192)
+                .build()
+                .unwrap_err(),
+            FheError::ParametersError(ParametersError::InsufficientSecurity(128, 192))
+        );
+
+        // 158 bits of ciphertext modulus are within the bound of 218 bits only
+        // without the special prime.
+        builder
+            .set_min_security_level(SecurityLevel::Bits128)
+            .set_moduli_sizes(&[60, 50, 48]);
+        assert_eq!(
+            builder.build().unwrap_err(),
+            FheError::ParametersError(ParametersError::InsufficientSecurity(0, 128))
+        );
+        assert!(builder.allow_insecure().build().is_ok());
+
+        let params = CkksParameters::default_arc(3, 16);
+        assert_eq!(
+            CkksParameters::try_deserialize(&params.to_bytes())?,
+            *params
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,281 @@
+//! Plaintext type in the CKKS encryption scheme.
+use crate::{
+    ckks::{CkksParameters, Encoding},
+    Error, Result,
+};
+use fhe_math::rq::{traits::TryConvertFrom, Context, Poly, Representation};
+use fhe_traits::{FheDecoder, FheEncoder, FheParametrized, FhePlaintext};
+use num_bigint::BigUint;
+use num_complex::Complex64;
+use num_traits::{FromPrimitive, ToPrimitive};
+use std::sync::Arc;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// A plaintext object, that encodes a vector of complex numbers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Plaintext {
+    /// The parameters of the underlying CKKS encryption scheme.
+    pub(crate) par: Arc<CkksParameters>,
+    /// The encoded polynomial, in Ntt representation.
+    pub(crate) poly_ntt: Poly,
+    /// The scale of the encoded values.
+    pub(crate) scale: f64,
+    /// The level of the plaintext
+    pub(crate) level: usize,
+}
+
+impl FheParametrized for Plaintext {
+    type Parameters = CkksParameters;
+}
+
+impl FhePlaintext for Plaintext {
+    type Encoding = Encoding;
+}
+
+// Zeroizing of plaintexts.
+impl ZeroizeOnDrop for Plaintext {}
+
+impl Zeroize for Plaintext {
+    fn zeroize(&mut self) {
+        self.poly_ntt.zeroize();
+    }
+}
+
+impl Plaintext {
+    /// Returns the level of this plaintext.
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    /// Returns the scale of this plaintext.
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+}
+
+unsafe impl Send for Plaintext {}
+
+/// Round real coefficients to the nearest integers, and convert them into a
+/// polynomial in Ntt representation.
+fn round_to_poly(coefficients: &[f64], ctx: &Arc<Context>) -> Result<Poly> {
+    if coefficients.iter().any(|c| !c.is_finite()) {
+        return Err(Error::NonFiniteValue);
+    }
+
+    let mut p = if coefficients.iter().all(|c| c.abs() < 2f64.powi(62)) {
+        let v = coefficients
+            .iter()
+            .map(|c| c.round() as i64)
+            .collect::<Vec<_>>();
+        Poly::try_convert_from(v.as_ref() as &[i64], ctx, false, Representation::PowerBasis)?
+    } else {
+        let q = ctx.modulus();
+        let v = coefficients
+            .iter()
+            .map(|c| {
+                let c_abs = BigUint::from_f64(c.abs().round()).unwrap() % q;
+                if c.is_sign_negative() {
+                    (q - c_abs) % q
+                } else {
+                    c_abs
+                }
+            })
+            .collect::<Vec<_>>();
+        Poly::try_convert_from(
+            v.as_ref() as &[BigUint],
+            ctx,
+            false,
+            Representation::PowerBasis,
+        )?
+    };
+    p.change_representation(Representation::Ntt);
+    Ok(p)
+}
+
+// Encoding and decoding.
+
+impl<'a, const N: usize, T> FheEncoder<&'a [T; N]> for Plaintext
+where
+    Plaintext: FheEncoder<&'a [T], Error = Error>,
+{
+    type Error = Error;
+    fn try_encode(
+        value: &'a [T; N],
+        encoding: Encoding,
+        par: &Arc<CkksParameters>,
+    ) -> Result<Self> {
+        Plaintext::try_encode(value.as_ref(), encoding, par)
+    }
+}
+
+impl<'a, T> FheEncoder<&'a Vec<T>> for Plaintext
+where
+    Plaintext: FheEncoder<&'a [T], Error = Error>,
+{
+    type Error = Error;
+    fn try_encode(
+        value: &'a Vec<T>,
+        encoding: Encoding,
+        par: &Arc<CkksParameters>,
+    ) -> Result<Self> {
+        Plaintext::try_encode(value.as_ref(), encoding, par)
+    }
+}
+
+impl<'a> FheEncoder<&'a [Complex64]> for Plaintext {
+    type Error = Error;
+    fn try_encode(
+        value: &'a [Complex64],
+        encoding: Encoding,
+        par: &Arc<CkksParameters>,
+    ) -> Result<Self> {
+        if value.len() > par.slots() {
+            return Err(Error::TooManyValues(value.len(), par.slots()));
+        }
+        let ctx = par.ctx_at_level(encoding.level)?;
+        let scale = encoding.scale.unwrap_or(par.scale());
+
+        let mut vals = vec![Complex64::default(); par.slots()];
+        vals[..value.len()].copy_from_slice(value);
+        par.fft.backward(&mut vals);
+
+        let mut coefficients = vec![0f64; par.degree()];
+        let (re, im) = coefficients.split_at_mut(par.slots());
+        for (v, re_i, im_i) in itertools::izip!(&vals, re, im) {
+            *re_i = v.re * scale;
+            *im_i = v.im * scale;
+        }
+
+        Ok(Plaintext {
+            par: par.clone(),
+            poly_ntt: round_to_poly(&coefficients, ctx)?,
+            scale,
+            level: encoding.level,
+        })
+    }
+}
+
+impl<'a> FheEncoder<&'a [f64]> for Plaintext {
+    type Error = Error;
+    fn try_encode(value: &'a [f64], encoding: Encoding, par: &Arc<CkksParameters>) -> Result<Self> {
+        let w = value
+            .iter()
+            .map(|v| Complex64::new(*v, 0.0))
+            .collect::<Vec<_>>();
+        Plaintext::try_encode(w.as_ref() as &[Complex64], encoding, par)
+    }
+}
+
+/// Decoding uses the scale stored in the plaintext, so the encoding does not
+/// need to be specified.
+impl FheDecoder<Plaintext> for Vec<Complex64> {
+    fn try_decode<O>(pt: &Plaintext, _encoding: O) -> Result<Vec<Complex64>>
+    where
+        O: Into<Option<Encoding>>,
+    {
+        let mut p = pt.poly_ntt.clone();
+        p.change_representation(Representation::PowerBasis);
+
+        // Lift the centered coefficients to real numbers.
+        let q = p.ctx().modulus().clone();
+        let q_half = &q >> 1;
+        let coefficients = Vec::<BigUint>::from(&p)
+            .iter()
+            .map(|c| {
+                let c = if c > &q_half {
+                    -(&q - c).to_f64().unwrap()
+                } else {
+                    c.to_f64().unwrap()
+                };
+                c / pt.scale
+            })
+            .collect::<Vec<_>>();
+        p.zeroize();
+
+        let slots = pt.par.slots();
+        let mut vals = (0..slots)
+            .map(|i| Complex64::new(coefficients[i], coefficients[i + slots]))
+            .collect::<Vec<_>>();
+        pt.par.fft.forward(&mut vals);
+        Ok(vals)
+    }
+
+    type Error = Error;
+}
+
+impl FheDecoder<Plaintext> for Vec<f64> {
+    fn try_decode<O>(pt: &Plaintext, encoding: O) -> Result<Vec<f64>>
+    where
+        O: Into<Option<Encoding>>,
+    {
+        let v = Vec::<Complex64>::try_decode(pt, encoding)?;
+        Ok(v.iter().map(|vi| vi.re).collect())
+    }
+
+    type Error = Error;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Plaintext;
+    use crate::ckks::{CkksParameters, Encoding};
+    use fhe_traits::{FheDecoder, FheEncoder};
+    use num_complex::Complex64;
+    use rand::{thread_rng, Rng};
+    use std::error::Error;
+
+    #[test]
+    fn try_encode() -> Result<(), Box<dyn Error>> {
+        let params = CkksParameters::default_arc(2, 16);
+        let a = vec![1.5f64; params.slots()];
+
+        let plaintext = Plaintext::try_encode(&a, Encoding::slots(), &params);
+        assert!(plaintext.is_ok());
+        assert_eq!(plaintext?.scale(), params.scale());
+
+        let plaintext = Plaintext::try_encode(&a, Encoding::slots_at_level(1), &params)?;
+        assert_eq!(plaintext.level(), 1);
+
+        let plaintext = Plaintext::try_encode(&[1.0, 2.0], Encoding::slots_at_level(2), &params);
+        assert!(plaintext.is_err());
+
+        let plaintext =
+            Plaintext::try_encode(&vec![1.0; params.slots() + 1], Encoding::slots(), &params);
+        assert!(plaintext.is_err());
+
+        let plaintext = Plaintext::try_encode(&[f64::NAN], Encoding::slots(), &params);
+        assert!(plaintext.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn encode_decode() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = CkksParameters::default_arc(3, 16);
+        for _ in 0..20 {
+            let a = (0..params.slots())
+                .map(|_| Complex64::new(rng.gen_range(-10.0..10.0), rng.gen_range(-10.0..10.0)))
+                .collect::<Vec<_>>();
+            let pt = Plaintext::try_encode(&a, Encoding::slots(), &params)?;
+            let b = Vec::<Complex64>::try_decode(&pt, Encoding::slots())?;
+            a.iter()
+                .zip(&b)
+                .for_each(|(ai, bi)| assert!((ai - bi).norm() < 1e-9));
+
+            let a = (0..params.slots())
+                .map(|_| rng.gen_range(-10.0..10.0))
+                .collect::<Vec<f64>>();
+            for scale in [2f64.powi(20), 2f64.powi(70)] {
+                let pt = Plaintext::try_encode(&a, Encoding::slots().with_scale(scale), &params)?;
+                assert_eq!(pt.scale(), scale);
+                let b = Vec::<f64>::try_decode(&pt, None)?;
+                a.iter()
+                    .zip(&b)
+                    .for_each(|(ai, bi)| assert!((ai - bi).abs() < 1e-4));
+            }
+        }
+
+        Ok(())
+    }
+}
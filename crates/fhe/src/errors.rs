@@ -23,9 +23,10 @@ pub enum Error {
     #[error("Too few values provided: {0} is below limit {1}")]
     TooFewValues(usize, usize),
 
-    /// Indicates that an input is invalid.
-    #[error("{0}")]
-    UnspecifiedInput(String),
+    /// Indicates that no encoding was specified, neither by the plaintext nor
+    /// by the caller.
+    #[error("No encoding specified")]
+    MissingEncoding,
 
     /// Indicates a mismatch in the encodings.
     #[error("Encoding mismatch: found {0}, expected {1}")]
@@ -35,14 +36,134 @@ pub enum Error {
     #[error("Does not support {0} encoding")]
     EncodingNotSupported(String),
 
+    /// Indicates that authenticated data could not be decrypted, e.g., because
+    /// of an incorrect key or tampered data.
+    #[error("Authentication failed")]
+    AuthenticationFailed,
+
+    /// Indicates that the key derivation parameters are invalid or too large.
+    #[error("Invalid key derivation parameters")]
+    InvalidKdfParameters,
+
+    /// Indicates that the kind of wrapping key does not match the envelope.
+    #[error("Wrapping key mismatch")]
+    WrappingKeyMismatch,
+
+    /// Indicates that reading or writing serialized data failed.
+    #[error("I/O error: {0}")]
+    Io(std::io::ErrorKind),
+
+    /// Indicates that the parameters of the operands differ.
+    #[error("Parameters mismatch")]
+    ParametersMismatch,
+
+    /// Indicates that an operand is not at the expected level.
+    #[error("Level mismatch: found {found}, expected {expected}")]
+    LevelMismatch {
+        /// The expected level.
+        expected: usize,
+        /// The level of the operand.
+        found: usize,
+    },
+
+    /// Indicates that a level is invalid for the parameters.
+    #[error("Invalid level: {0}")]
+    InvalidLevel(usize),
+
+    /// Indicates that a ciphertext does not have the expected number of parts.
+    #[error("Invalid ciphertext size: {found}, expected {expected}")]
+    InvalidCiphertextSize {
+        /// The expected number of parts.
+        expected: usize,
+        /// The number of parts of the ciphertext.
+        found: usize,
+    },
+
+    /// Indicates that the key for the Galois element is missing.
+    #[error("Missing Galois key for element {0}")]
+    MissingGaloisKey(usize),
+
+    /// Indicates that the relinearization key for a level is missing.
+    #[error("Missing relinearization key at level {0}")]
+    MissingRelinearizationKey(usize),
+
+    /// Indicates that the parameters do not have enough moduli for an
+    /// operation.
+    #[error("Not enough moduli")]
+    NotEnoughModuli,
+
+    /// Indicates that the estimated noise budget required by a computation
+    /// exceeds the noise budget of the ciphertext, in bits.
+    #[error("Insufficient noise budget: {0} bits required, {1} bits available")]
+    InsufficientNoiseBudget(usize, usize),
+
+    /// Indicates that several relinearization keys are for the same level.
+    #[error("Duplicate relinearization key at level {0}")]
+    DuplicateRelinearizationKey(usize),
+
+    /// Indicates that several evaluation keys are for the same level.
+    #[error("Duplicate evaluation key at level {0}")]
+    DuplicateEvaluationKey(usize),
+
+    /// Indicates that a column rotation index is invalid.
+    #[error("Invalid column index: {0}")]
+    InvalidColumnIndex(usize),
+
+    /// Indicates that the number of digits of hybrid key switching is invalid.
+    #[error("Invalid number of digits: {dnum}, expected an integer between 1 and {max}")]
+    InvalidDnum {
+        /// The number of digits.
+        dnum: usize,
+        /// The largest number of digits.
+        max: usize,
+    },
+
+    /// Indicates that the special moduli are not distinct, or not distinct
+    /// from the ciphertext moduli.
+    #[error("The special moduli must be distinct and differ from the ciphertext moduli")]
+    InvalidSpecialModuli,
+
+    /// Indicates that a matrix does not have the expected size.
+    #[error("Invalid matrix size: {found}, expected {expected}")]
+    InvalidMatrixSize {
+        /// The expected number of rows and columns.
+        expected: usize,
+        /// The number of rows or columns of the matrix.
+        found: usize,
+    },
+
+    /// Indicates that the baby step of a linear transform is invalid.
+    #[error("Invalid baby step: {0}")]
+    InvalidBabyStep(usize),
+
+    /// Indicates that an exponent is invalid.
+    #[error("Invalid exponent: {0}")]
+    InvalidExponent(usize),
+
+    /// Indicates that a node does not belong to the circuit.
+    #[error("Invalid node: {0}")]
+    InvalidNode(usize),
+
+    /// Indicates that a value is not invertible modulo the plaintext modulus.
+    #[error("{0} is not invertible modulo the plaintext modulus")]
+    NonInvertible(u64),
+
+    /// Indicates that a scale is not a positive number.
+    #[error("Invalid scale")]
+    InvalidScale,
+
+    /// Indicates that a value to encode is not finite.
+    #[error("Non-finite value")]
+    NonFiniteValue,
+
+    /// Indicates that a smudging noise of this number of bits is too large
+    /// for the ciphertext modulus.
+    #[error("Smudging noise too large: {0} bits")]
+    SmudgingTooLarge(usize),
+
     /// Indicates a parameter error.
     #[error("{0}")]
     ParametersError(ParametersError),
-
-    /// Indicates a default error
-    /// TODO: To delete eventually
-    #[error("{0}")]
-    DefaultError(String),
 }
 
 impl From<fhe_math::Error> for Error {
@@ -66,17 +187,46 @@ pub enum ParametersError {
     #[error("Not enough primes of size {0} for polynomials of degree {1}")]
     NotEnoughPrimes(usize, usize),
 
-    /// Indicates that the plaintext is invalid.
-    #[error("{0}")]
-    InvalidPlaintext(String),
+    /// Indicates that the plaintext modulus is invalid.
+    #[error("Invalid plaintext modulus: {0}")]
+    InvalidPlaintext(u64),
 
-    /// Indicates that too many parameters were specified.
-    #[error("{0}")]
-    TooManySpecified(String),
+    /// Indicates that the plaintext modulus is not smaller than the first
+    /// ciphertext modulus.
+    #[error("Plaintext modulus too large: {0} should be smaller than {1}")]
+    PlaintextTooLarge(u64, u64),
 
-    /// Indicates that too few parameters were specified.
-    #[error("{0}")]
-    TooFewSpecified(String),
+    /// Indicates that the plaintext modulus size is invalid.
+    #[error("Invalid plaintext modulus size: {0}, expected an integer between {1} and {2}")]
+    InvalidPlaintextSize(usize, usize, usize),
+
+    /// Indicates that the scale is not a finite number larger or equal to 1.
+    #[error("Invalid scale: expected a finite number larger or equal to 1")]
+    InvalidScale,
+
+    /// Indicates that the error variance is invalid.
+    #[error("Invalid variance: {0}, expected an integer between {1} and {2}")]
+    InvalidVariance(usize, usize, usize),
+
+    /// Indicates that the estimated security of the parameters is below the
+    /// required security, in bits.
+    #[error("Insufficient security: {0} bits estimated, at least {1} bits required")]
+    InsufficientSecurity(usize, usize),
+
+    /// Indicates that the ciphertext modulus required by a computation exceeds
+    /// the largest modulus allowed by the security level, in bits.
+    #[error("Ciphertext modulus too large: {0} bits required, at most {1} bits allowed")]
+    ModulusTooLarge(usize, usize),
+
+    /// Indicates that both the ciphertext moduli and their sizes were
+    /// specified.
+    #[error("Only one of the ciphertext moduli and their sizes can be specified")]
+    TooManySpecified,
+
+    /// Indicates that neither the ciphertext moduli nor their sizes were
+    /// specified.
+    #[error("One of the ciphertext moduli and their sizes must be specified")]
+    TooFewSpecified,
 }
 
 #[cfg(test)]
@@ -98,10 +248,7 @@ mod tests {
             Error::TooFewValues(10, 17).to_string(),
             "Too few values provided: 10 is below limit 17"
         );
-        assert_eq!(
-            Error::UnspecifiedInput("test string".to_string()).to_string(),
-            "test string"
-        );
+        assert_eq!(Error::MissingEncoding.to_string(), "No encoding specified");
         assert_eq!(
             Error::EncodingMismatch("enc1".to_string(), "enc2".to_string()).to_string(),
             "Encoding mismatch: found enc1, expected enc2"
@@ -110,6 +257,97 @@ mod tests {
             Error::EncodingNotSupported("test".to_string()).to_string(),
             "Does not support test encoding"
         );
+        assert_eq!(
+            Error::AuthenticationFailed.to_string(),
+            "Authentication failed"
+        );
+        assert_eq!(
+            Error::InvalidKdfParameters.to_string(),
+            "Invalid key derivation parameters"
+        );
+        assert_eq!(
+            Error::WrappingKeyMismatch.to_string(),
+            "Wrapping key mismatch"
+        );
+        assert_eq!(
+            Error::Io(std::io::ErrorKind::NotFound).to_string(),
+            "I/O error: entity not found"
+        );
+        assert_eq!(Error::ParametersMismatch.to_string(), "Parameters mismatch");
+        assert_eq!(
+            Error::LevelMismatch {
+                expected: 0,
+                found: 1
+            }
+            .to_string(),
+            "Level mismatch: found 1, expected 0"
+        );
+        assert_eq!(Error::InvalidLevel(3).to_string(), "Invalid level: 3");
+        assert_eq!(
+            Error::InvalidCiphertextSize {
+                expected: 2,
+                found: 3
+            }
+            .to_string(),
+            "Invalid ciphertext size: 3, expected 2"
+        );
+        assert_eq!(
+            Error::MissingGaloisKey(3).to_string(),
+            "Missing Galois key for element 3"
+        );
+        assert_eq!(
+            Error::MissingRelinearizationKey(1).to_string(),
+            "Missing relinearization key at level 1"
+        );
+        assert_eq!(Error::NotEnoughModuli.to_string(), "Not enough moduli");
+        assert_eq!(
+            Error::InsufficientNoiseBudget(200, 124).to_string(),
+            "Insufficient noise budget: 200 bits required, 124 bits available"
+        );
+        assert_eq!(
+            Error::DuplicateRelinearizationKey(1).to_string(),
+            "Duplicate relinearization key at level 1"
+        );
+        assert_eq!(
+            Error::DuplicateEvaluationKey(0).to_string(),
+            "Duplicate evaluation key at level 0"
+        );
+        assert_eq!(
+            Error::InvalidColumnIndex(9).to_string(),
+            "Invalid column index: 9"
+        );
+        assert_eq!(
+            Error::InvalidDnum { dnum: 4, max: 3 }.to_string(),
+            "Invalid number of digits: 4, expected an integer between 1 and 3"
+        );
+        assert_eq!(
+            Error::InvalidSpecialModuli.to_string(),
+            "The special moduli must be distinct and differ from the ciphertext moduli"
+        );
+        assert_eq!(
+            Error::InvalidMatrixSize {
+                expected: 16,
+                found: 15
+            }
+            .to_string(),
+            "Invalid matrix size: 15, expected 16"
+        );
+        assert_eq!(
+            Error::InvalidBabyStep(0).to_string(),
+            "Invalid baby step: 0"
+        );
+        assert_eq!(Error::InvalidExponent(0).to_string(), "Invalid exponent: 0");
+        assert_eq!(Error::InvalidNode(3).to_string(), "Invalid node: 3");
+        assert_eq!(
+            Error::NonInvertible(6).to_string(),
+            "6 is not invertible modulo the plaintext modulus"
+        );
+        assert_eq!(Error::InvalidScale.to_string(), "Invalid scale");
+        assert_eq!(Error::NonFiniteValue.to_string(), "Non-finite value");
+        assert_eq!(
+            Error::SmudgingTooLarge(80).to_string(),
+            "Smudging noise too large: 80 bits"
+        );
         assert_eq!(
             Error::ParametersError(ParametersError::InvalidDegree(10)).to_string(),
             ParametersError::InvalidDegree(10).to_string()
@@ -131,16 +369,40 @@ mod tests {
             "Not enough primes of size 1 for polynomials of degree 2"
         );
         assert_eq!(
-            ParametersError::InvalidPlaintext("test".to_string()).to_string(),
-            "test"
+            ParametersError::InvalidPlaintext(1).to_string(),
+            "Invalid plaintext modulus: 1"
+        );
+        assert_eq!(
+            ParametersError::PlaintextTooLarge(1153, 1153).to_string(),
+            "Plaintext modulus too large: 1153 should be smaller than 1153"
+        );
+        assert_eq!(
+            ParametersError::InvalidPlaintextSize(8, 10, 62).to_string(),
+            "Invalid plaintext modulus size: 8, expected an integer between 10 and 62"
+        );
+        assert_eq!(
+            ParametersError::InvalidScale.to_string(),
+            "Invalid scale: expected a finite number larger or equal to 1"
+        );
+        assert_eq!(
+            ParametersError::InvalidVariance(0, 1, 16).to_string(),
+            "Invalid variance: 0, expected an integer between 1 and 16"
+        );
+        assert_eq!(
+            ParametersError::InsufficientSecurity(0, 128).to_string(),
+            "Insufficient security: 0 bits estimated, at least 128 bits required"
+        );
+        assert_eq!(
+            ParametersError::ModulusTooLarge(900, 881).to_string(),
+            "Ciphertext modulus too large: 900 bits required, at most 881 bits allowed"
         );
         assert_eq!(
-            ParametersError::TooManySpecified("test".to_string()).to_string(),
-            "test"
+            ParametersError::TooManySpecified.to_string(),
+            "Only one of the ciphertext moduli and their sizes can be specified"
         );
         assert_eq!(
-            ParametersError::TooFewSpecified("test".to_string()).to_string(),
-            "test"
+            ParametersError::TooFewSpecified.to_string(),
+            "One of the ciphertext moduli and their sizes must be specified"
         );
     }
 }
@@ -6,6 +6,9 @@
 mod errors;
 
 pub mod bfv;
+pub mod bgv;
+pub mod ckks;
+pub mod mbfv;
 pub use errors::{Error, ParametersError, Result};
 
 // Test the source code included in the README.
@@ -0,0 +1,15 @@
+//! Aggregation of the shares of the multiparty protocols.
+
+use crate::Result;
+
+/// Aggregate the shares produced by the parties in a multiparty protocol.
+///
+/// The shares of all the parties must be aggregated, since the joint secret
+/// key is the sum of all the secret key shares.
+pub trait Aggregate<S>: Sized {
+    /// Aggregate the shares into the output of the protocol. Returns an error
+    /// if there are no shares, or if the shares are incompatible.
+    fn from_shares<T>(iter: T) -> Result<Self>
+    where
+        T: IntoIterator<Item = S>;
+}
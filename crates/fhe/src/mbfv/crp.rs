@@ -0,0 +1,98 @@
+//! Common random polynomials for the multiparty protocols.
+
+use crate::bfv::BfvParameters;
+use crate::Result;
+use fhe_math::rq::{Poly, Representation};
+use rand::{CryptoRng, Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::sync::Arc;
+
+/// A polynomial sampled uniformly at random and known to all the parties,
+/// from which the parties derive the uniform parts of the joint keys.
+///
+/// The parties typically agree on a seed, and generate the same common random
+/// polynomials from it using [`CommonRandomPoly::new_from_seed`] or
+/// [`CommonRandomPoly::new_vec_from_seed`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CommonRandomPoly {
+    pub(crate) poly: Poly,
+}
+
+impl CommonRandomPoly {
+    /// Generate a [`CommonRandomPoly`] at the first level.
+    pub fn new<R: RngCore + CryptoRng>(par: &Arc<BfvParameters>, rng: &mut R) -> Result<Self> {
+        let mut seed = <ChaCha8Rng as SeedableRng>::Seed::default();
+        rng.fill(&mut seed);
+        Self::new_from_seed(par, seed)
+    }
+
+    /// Generate `n` [`CommonRandomPoly`] at the first level.
+    pub fn new_vec<R: RngCore + CryptoRng>(
+        n: usize,
+        par: &Arc<BfvParameters>,
+        rng: &mut R,
+    ) -> Result<Vec<Self>> {
+        let mut seed = <ChaCha8Rng as SeedableRng>::Seed::default();
+        rng.fill(&mut seed);
+        Self::new_vec_from_seed(n, par, seed)
+    }
+
+    /// Generate deterministically a [`CommonRandomPoly`] at the first level
+    /// from a seed shared by the parties.
+    pub fn new_from_seed(
+        par: &Arc<BfvParameters>,
+        seed: <ChaCha8Rng as SeedableRng>::Seed,
+    ) -> Result<Self> {
+        let ctx = par.ctx_at_level(0)?;
+        let mut poly = Poly::random_from_seed(ctx, Representation::Ntt, seed);
+        unsafe { poly.allow_variable_time_computations() }
+        Ok(Self { poly })
+    }
+
+    /// Generate deterministically `n` [`CommonRandomPoly`] at the first level
+    /// from a seed shared by the parties.
+    pub fn new_vec_from_seed(
+        n: usize,
+        par: &Arc<BfvParameters>,
+        seed: <ChaCha8Rng as SeedableRng>::Seed,
+    ) -> Result<Vec<Self>> {
+        let mut rng = ChaCha8Rng::from_seed(seed);
+        (0..n)
+            .map(|_| {
+                let mut seed_i = <ChaCha8Rng as SeedableRng>::Seed::default();
+                rng.fill(&mut seed_i);
+                Self::new_from_seed(par, seed_i)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CommonRandomPoly;
+    use crate::bfv::BfvParameters;
+    use rand::thread_rng;
+    use std::error::Error;
+
+    #[test]
+    fn from_seed() -> Result<(), Box<dyn Error>> {
+        let params = BfvParameters::default_arc(3, 16);
+        let crp = CommonRandomPoly::new_from_seed(&params, [1u8; 32])?;
+        assert_eq!(crp, CommonRandomPoly::new_from_seed(&params, [1u8; 32])?);
+        assert_ne!(crp, CommonRandomPoly::new_from_seed(&params, [2u8; 32])?);
+        assert_ne!(crp, CommonRandomPoly::new(&params, &mut thread_rng())?);
+
+        let crps = CommonRandomPoly::new_vec_from_seed(3, &params, [1u8; 32])?;
+        assert_eq!(crps.len(), 3);
+        assert_eq!(
+            crps,
+            CommonRandomPoly::new_vec_from_seed(3, &params, [1u8; 32])?
+        );
+        assert_ne!(crps[0], crps[1]);
+        assert_eq!(
+            CommonRandomPoly::new_vec(2, &params, &mut thread_rng())?.len(),
+            2
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,170 @@
+//! Collective decryption of a ciphertext.
+
+use super::Aggregate;
+use crate::bfv::{
+    check_smudging_bits, smudging_noise, BfvParameters, Ciphertext, Plaintext, SecretKey,
+};
+use crate::{Error, Result};
+use fhe_math::rq::{traits::TryConvertFrom, Poly, Representation};
+use rand::{CryptoRng, RngCore};
+use std::sync::Arc;
+use zeroize::Zeroizing;
+
+/// A party's share of the decryption of a ciphertext `(c0, c1)`.
+///
+/// The share of the party holding the secret key share `s_i` is
+/// `c1 * s_i + e_i`, where `e_i` is a smudging noise; adding `c0` and the
+/// shares of all the parties yields `c0 + c1 * s` up to a larger noise, from
+/// which the plaintext is recovered. The smudging noise statistically hides
+/// the noise of the ciphertext, which could otherwise leak information about
+/// the secret key shares.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DecryptionShare {
+    pub(crate) par: Arc<BfvParameters>,
+    pub(crate) ct: Arc<Ciphertext>,
+    pub(crate) h_share: Poly,
+}
+
+impl DecryptionShare {
+    /// Generate the decryption share of a ciphertext with two parts, using a
+    /// smudging noise with coefficients uniform in `[-2^smudging_bits,
+    /// 2^smudging_bits]`.
+    ///
+    /// The smudging noise should exceed the noise of the ciphertext by a
+    /// statistical security margin, while the sum of the smudging noises of all
+    /// the parties must remain below `q / (2 * t)`, where `q` is the ciphertext
+    /// modulus at the level of the ciphertext and `t` the plaintext modulus.
+    /// Returns an error if a single smudging noise already exceeds this bound.
+    pub fn new<R: RngCore + CryptoRng>(
+        sk_share: &SecretKey,
+        ct: &Arc<Ciphertext>,
+        smudging_bits: usize,
+        rng: &mut R,
+    ) -> Result<Self> {
+        if sk_share.par != ct.par {
+            return Err(Error::ParametersMismatch);
+        }
+        if ct.c.len() != 2 {
+            return Err(Error::InvalidCiphertextSize {
+                expected: 2,
+                found: ct.c.len(),
+            });
+        }
+
+        let ctx = ct.c[0].ctx();
+        check_smudging_bits(&sk_share.par, ctx, smudging_bits)?;
+
+        let mut s = Zeroizing::new(Poly::try_convert_from(
+            sk_share.coeffs.as_ref(),
+            ctx,
+            false,
+            Representation::PowerBasis,
+        )?);
+        s.change_representation(Representation::Ntt);
+
+        let mut h_share = ct.c[1].clone();
+        h_share.disallow_variable_time_computations();
+        h_share *= s.as_ref();
+        h_share += &smudging_noise(ctx, sk_share.par.degree(), smudging_bits, rng)?;
+
+        // It is now safe to enable variable time computations.
+        unsafe { h_share.allow_variable_time_computations() }
+
+        Ok(Self {
+            par: sk_share.par.clone(),
+            ct: ct.clone(),
+            h_share,
+        })
+    }
+}
+
+impl Aggregate<DecryptionShare> for Plaintext {
+    fn from_shares<T>(iter: T) -> Result<Self>
+    where
+        T: IntoIterator<Item = DecryptionShare>,
+    {
+        let mut shares = iter.into_iter();
+        let share = shares.next().ok_or(Error::TooFewValues(0, 1))?;
+        let mut c = Zeroizing::new(share.ct.c[0].clone());
+        *c.as_mut() += &share.h_share;
+        for sh in shares {
+            if sh.par != share.par || sh.ct != share.ct {
+                return Err(Error::ParametersMismatch);
+            }
+            *c.as_mut() += &sh.h_share;
+        }
+        Plaintext::from_decrypted_poly(c.as_ref(), &share.par)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bfv::{BfvParameters, Ciphertext, Encoding, Plaintext, PublicKey, SecretKey};
+    use crate::mbfv::{Aggregate, CommonRandomPoly, DecryptionShare, PublicKeyShare};
+    use fhe_traits::{FheDecoder, FheEncoder, FheEncrypter};
+    use rand::thread_rng;
+    use std::{error::Error, sync::Arc};
+
+    const NUM_PARTIES: usize = 5;
+
+    #[test]
+    fn collective_decryption() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        for params in [
+            BfvParameters::default_arc(1, 16),
+            BfvParameters::default_arc(4, 16),
+        ] {
+            for level in 0..=params.max_level() {
+                let crp = CommonRandomPoly::new(&params, &mut rng)?;
+                let sk_shares = (0..NUM_PARTIES)
+                    .map(|_| SecretKey::random(&params, &mut rng))
+                    .collect::<Vec<_>>();
+                let pk = PublicKey::from_shares(
+                    sk_shares
+                        .iter()
+                        .map(|sk_i| PublicKeyShare::new(sk_i, crp.clone(), &mut rng))
+                        .collect::<crate::Result<Vec<_>>>()?,
+                )?;
+
+                let v = params.plaintext.random_vec(params.degree(), &mut rng);
+                let pt = Plaintext::try_encode(&v, Encoding::simd_at_level(level), &params)?;
+                let ct: Arc<Ciphertext> = Arc::new(pk.try_encrypt(&pt, &mut rng)?);
+
+                let shares = sk_shares
+                    .iter()
+                    .map(|sk_i| DecryptionShare::new(sk_i, &ct, 30, &mut rng))
+                    .collect::<crate::Result<Vec<_>>>()?;
+                let pt2 = Plaintext::from_shares(shares.clone())?;
+                assert_eq!(pt2.level(), level);
+                assert_eq!(
+                    Vec::<u64>::try_decode(&pt2, Encoding::simd_at_level(level))?,
+                    v
+                );
+
+                // All the parties must take part in the decryption.
+                let pt3 = Plaintext::from_shares(shares[1..].to_vec())?;
+                assert_ne!(
+                    Vec::<u64>::try_decode(&pt3, Encoding::simd_at_level(level))?,
+                    v
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn smudging_too_large() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BfvParameters::default_arc(1, 16);
+        let sk = SecretKey::random(&params, &mut rng);
+        let pt = Plaintext::try_encode(&[1u64, 2, 3], Encoding::poly(), &params)?;
+        let ct: Arc<Ciphertext> = Arc::new(sk.try_encrypt(&pt, &mut rng)?);
+        assert!(DecryptionShare::new(&sk, &ct, 30, &mut rng).is_ok());
+        assert!(matches!(
+            DecryptionShare::new(&sk, &ct, 55, &mut rng),
+            Err(crate::Error::SmudgingTooLarge(55))
+        ));
+        assert!(Plaintext::from_shares(Vec::<DecryptionShare>::new()).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+#![warn(missing_docs, unused_imports)]
+
+//! The multiparty Brakerski-Fan-Vercauteren homomorphic encryption scheme.
+//!
+//! Each party holds a share of the secret key, which is a regular
+//! [`SecretKey`](crate::bfv::SecretKey) generated independently; the joint
+//! secret key is the sum of the shares and is never reconstructed. The
+//! parties run the protocols of this module to generate a joint
+//! [`PublicKey`](crate::bfv::PublicKey) and
+//! [`RelinearizationKey`](crate::bfv::RelinearizationKey), to decrypt
+//! ciphertexts collectively, and to switch ciphertexts to the public key of
+//! another recipient. The outputs of the protocols are regular BFV
+//! objects, so the usual homomorphic operations apply.
+
+mod aggregate;
+mod crp;
+mod decryption;
+mod public_key_gen;
+mod public_key_switch;
+mod relin_key_gen;
+
+pub use aggregate::Aggregate;
+pub use crp::CommonRandomPoly;
+pub use decryption::DecryptionShare;
+pub use public_key_gen::PublicKeyShare;
+pub use public_key_switch::PublicKeySwitchShare;
+pub use relin_key_gen::{RelinKeyGenerator, RelinKeyShareRound1, RelinKeyShareRound2};
@@ -0,0 +1,151 @@
+//! Collective generation of a public key.
+
+use super::{Aggregate, CommonRandomPoly};
+use crate::bfv::{BfvParameters, Ciphertext, PublicKey, SecretKey};
+use crate::{Error, Result};
+use fhe_math::rq::{traits::TryConvertFrom, Poly, Representation};
+use rand::{CryptoRng, RngCore};
+use std::sync::Arc;
+use zeroize::Zeroizing;
+
+/// A party's share of the joint public key.
+///
+/// Given a common random polynomial `a`, the share of the party holding the
+/// secret key share `s_i` is `-a * s_i + e_i`; the sum of the shares and `a`
+/// form a public key for the joint secret key `s = s_1 + ... + s_n`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PublicKeyShare {
+    pub(crate) par: Arc<BfvParameters>,
+    pub(crate) crp: CommonRandomPoly,
+    pub(crate) p0_share: Poly,
+}
+
+impl PublicKeyShare {
+    /// Generate the share of the joint public key for a secret key share and
+    /// a common random polynomial.
+    pub fn new<R: RngCore + CryptoRng>(
+        sk_share: &SecretKey,
+        crp: CommonRandomPoly,
+        rng: &mut R,
+    ) -> Result<Self> {
+        let ctx = sk_share.par.ctx_at_level(0)?;
+        if crp.poly.ctx() != ctx {
+            return Err(Error::MathError(fhe_math::Error::InvalidContext));
+        }
+
+        let mut s = Zeroizing::new(Poly::try_convert_from(
+            sk_share.coeffs.as_ref(),
+            ctx,
+            false,
+            Representation::PowerBasis,
+        )?);
+        s.change_representation(Representation::Ntt);
+
+        let mut a = crp.poly.clone();
+        a.disallow_variable_time_computations();
+        let a_s = Zeroizing::new(&a * s.as_ref());
+
+        let mut p0_share = Poly::small(ctx, Representation::Ntt, sk_share.par.variance, rng)?;
+        p0_share -= &a_s;
+        Ok(Self {
+            par: sk_share.par.clone(),
+            crp,
+            p0_share,
+        })
+    }
+}
+
+impl Aggregate<PublicKeyShare> for PublicKey {
+    fn from_shares<T>(iter: T) -> Result<Self>
+    where
+        T: IntoIterator<Item = PublicKeyShare>,
+    {
+        let mut shares = iter.into_iter();
+        let share = shares.next().ok_or(Error::TooFewValues(0, 1))?;
+        let mut p0 = share.p0_share;
+        for sh in shares {
+            if sh.par != share.par || sh.crp != share.crp {
+                return Err(Error::ParametersMismatch);
+            }
+            p0 += &sh.p0_share;
+        }
+
+        let mut p1 = share.crp.poly;
+        // The polynomials of a public key should not allow for variable time
+        // computation.
+        p0.disallow_variable_time_computations();
+        p1.disallow_variable_time_computations();
+        Ok(PublicKey {
+            par: share.par.clone(),
+            c: Ciphertext {
+                par: share.par,
+                seed: None,
+                c: vec![p0, p1],
+                level: 0,
+                noise: None,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bfv::{BfvParameters, Ciphertext, Encoding, Plaintext, PublicKey, SecretKey};
+    use crate::mbfv::{Aggregate, CommonRandomPoly, PublicKeyShare};
+    use fhe_traits::{FheDecoder, FheDecrypter, FheEncoder, FheEncrypter};
+    use rand::thread_rng;
+    use std::error::Error;
+
+    const NUM_PARTIES: usize = 5;
+
+    #[test]
+    fn encrypt_decrypt() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        for params in [
+            BfvParameters::default_arc(1, 16),
+            BfvParameters::default_arc(4, 16),
+        ] {
+            for _ in 0..10 {
+                let crp = CommonRandomPoly::new(&params, &mut rng)?;
+                let sk_shares = (0..NUM_PARTIES)
+                    .map(|_| SecretKey::random(&params, &mut rng))
+                    .collect::<Vec<_>>();
+                let pk = PublicKey::from_shares(
+                    sk_shares
+                        .iter()
+                        .map(|sk_i| PublicKeyShare::new(sk_i, crp.clone(), &mut rng))
+                        .collect::<crate::Result<Vec<_>>>()?,
+                )?;
+
+                // The joint secret key is the sum of the shares.
+                let mut coeffs = vec![0i64; params.degree()];
+                sk_shares.iter().for_each(|sk_i| {
+                    coeffs
+                        .iter_mut()
+                        .zip(sk_i.coeffs.iter())
+                        .for_each(|(c, si)| *c += si)
+                });
+                let sk = SecretKey::new(coeffs, &params);
+
+                let v = params.plaintext.random_vec(params.degree(), &mut rng);
+                let pt = Plaintext::try_encode(&v, Encoding::poly(), &params)?;
+                let ct: Ciphertext = pk.try_encrypt(&pt, &mut rng)?;
+                let pt2 = sk.try_decrypt(&ct)?;
+                assert_eq!(Vec::<u64>::try_decode(&pt2, Encoding::poly())?, v);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn aggregate_errors() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BfvParameters::default_arc(2, 16);
+        let sk = SecretKey::random(&params, &mut rng);
+        let share1 = PublicKeyShare::new(&sk, CommonRandomPoly::new(&params, &mut rng)?, &mut rng)?;
+        let share2 = PublicKeyShare::new(&sk, CommonRandomPoly::new(&params, &mut rng)?, &mut rng)?;
+        assert!(PublicKey::from_shares(Vec::<PublicKeyShare>::new()).is_err());
+        assert!(PublicKey::from_shares([share1, share2]).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,231 @@
+//! Collective switching of a ciphertext to the public key of a recipient.
+
+use super::Aggregate;
+use crate::bfv::{
+    check_smudging_bits, smudging_noise, BfvParameters, Ciphertext, PublicKey, SecretKey,
+};
+use crate::{Error, Result};
+use fhe_math::rq::{traits::TryConvertFrom, Poly, Representation};
+use rand::{CryptoRng, RngCore};
+use std::sync::Arc;
+use zeroize::Zeroizing;
+
+/// A party's share of the switching of a ciphertext `(c0, c1)` under the
+/// joint secret key to a ciphertext under the public key `(p0, p1)` of a
+/// recipient.
+///
+/// The share of the party holding the secret key share `s_i` is
+/// `(c1 * s_i + u_i * p0 + e0_i, u_i * p1 + e1_i)`, where `u_i` is an
+/// ephemeral secret and `e0_i` a smudging noise. Adding `c0` to the sum of the
+/// first parts of the shares yields, together with the sum of the second
+/// parts, a ciphertext which decrypts under the secret key of the recipient,
+/// without the plaintext ever being revealed to the parties.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PublicKeySwitchShare {
+    pub(crate) par: Arc<BfvParameters>,
+    pub(crate) ct: Arc<Ciphertext>,
+    pub(crate) pk: PublicKey,
+    pub(crate) h0_share: Poly,
+    pub(crate) h1_share: Poly,
+}
+
+impl PublicKeySwitchShare {
+    /// Generate the share of the switching of a ciphertext with two parts to
+    /// the public key `pk`, using a smudging noise with coefficients uniform
+    /// in `[-2^smudging_bits, 2^smudging_bits]`.
+    ///
+    /// The smudging noise is subject to the same constraints as for a
+    /// [`DecryptionShare`](super::DecryptionShare).
+    pub fn new<R: RngCore + CryptoRng>(
+        sk_share: &SecretKey,
+        ct: &Arc<Ciphertext>,
+        pk: &PublicKey,
+        smudging_bits: usize,
+        rng: &mut R,
+    ) -> Result<Self> {
+        if sk_share.par != ct.par || pk.par != ct.par {
+            return Err(Error::ParametersMismatch);
+        }
+        if ct.c.len() != 2 {
+            return Err(Error::InvalidCiphertextSize {
+                expected: 2,
+                found: ct.c.len(),
+            });
+        }
+
+        let ctx = ct.c[0].ctx();
+        check_smudging_bits(&sk_share.par, ctx, smudging_bits)?;
+
+        // The public key is an encryption of zero, so it remains valid after
+        // switching to the level of the ciphertext.
+        let mut pk_ct = pk.c.clone();
+        while pk_ct.level != ct.level {
+            pk_ct.mod_switch_to_next_level()?;
+        }
+
+        let mut s = Zeroizing::new(Poly::try_convert_from(
+            sk_share.coeffs.as_ref(),
+            ctx,
+            false,
+            Representation::PowerBasis,
+        )?);
+        s.change_representation(Representation::Ntt);
+        let u = Zeroizing::new(Poly::small(
+            ctx,
+            Representation::Ntt,
+            sk_share.par.variance,
+            rng,
+        )?);
+
+        let mut h0_share = ct.c[1].clone();
+        h0_share.disallow_variable_time_computations();
+        h0_share *= s.as_ref();
+        h0_share += &(u.as_ref() * &pk_ct.c[0]);
+        h0_share += &smudging_noise(ctx, sk_share.par.degree(), smudging_bits, rng)?;
+
+        let mut h1_share = Poly::small(ctx, Representation::Ntt, sk_share.par.variance, rng)?;
+        h1_share += &(u.as_ref() * &pk_ct.c[1]);
+
+        // It is now safe to enable variable time computations.
+        unsafe {
+            h0_share.allow_variable_time_computations();
+            h1_share.allow_variable_time_computations();
+        }
+
+        Ok(Self {
+            par: sk_share.par.clone(),
+            ct: ct.clone(),
+            pk: pk.clone(),
+            h0_share,
+            h1_share,
+        })
+    }
+}
+
+impl Aggregate<PublicKeySwitchShare> for Ciphertext {
+    fn from_shares<T>(iter: T) -> Result<Self>
+    where
+        T: IntoIterator<Item = PublicKeySwitchShare>,
+    {
+        let mut shares = iter.into_iter();
+        let share = shares.next().ok_or(Error::TooFewValues(0, 1))?;
+        let mut h0 = share.ct.c[0].clone();
+        h0 += &share.h0_share;
+        let mut h1 = share.h1_share;
+        for sh in shares {
+            if sh.par != share.par || sh.ct != share.ct || sh.pk != share.pk {
+                return Err(Error::ParametersMismatch);
+            }
+            h0 += &sh.h0_share;
+            h1 += &sh.h1_share;
+        }
+
+        Ok(Ciphertext {
+            par: share.par,
+            seed: None,
+            c: vec![h0, h1],
+            level: share.ct.level,
+            noise: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bfv::{BfvParameters, Ciphertext, Encoding, Plaintext, PublicKey, SecretKey};
+    use crate::mbfv::{
+        Aggregate, CommonRandomPoly, DecryptionShare, PublicKeyShare, PublicKeySwitchShare,
+    };
+    use fhe_traits::{FheDecoder, FheDecrypter, FheEncoder, FheEncrypter};
+    use rand::thread_rng;
+    use std::{error::Error, sync::Arc};
+
+    const NUM_PARTIES: usize = 5;
+
+    #[test]
+    fn switch_to_recipient() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        for params in [
+            BfvParameters::default_arc(1, 16),
+            BfvParameters::default_arc(4, 16),
+        ] {
+            for level in 0..=params.max_level() {
+                let crp = CommonRandomPoly::new(&params, &mut rng)?;
+                let sk_shares = (0..NUM_PARTIES)
+                    .map(|_| SecretKey::random(&params, &mut rng))
+                    .collect::<Vec<_>>();
+                let pk = PublicKey::from_shares(
+                    sk_shares
+                        .iter()
+                        .map(|sk_i| PublicKeyShare::new(sk_i, crp.clone(), &mut rng))
+                        .collect::<crate::Result<Vec<_>>>()?,
+                )?;
+
+                let sk_recipient = SecretKey::random(&params, &mut rng);
+                let pk_recipient = PublicKey::new(&sk_recipient, &mut rng);
+
+                let v = params.plaintext.random_vec(params.degree(), &mut rng);
+                let pt = Plaintext::try_encode(&v, Encoding::simd_at_level(level), &params)?;
+                let ct: Arc<Ciphertext> = Arc::new(pk.try_encrypt(&pt, &mut rng)?);
+
+                let ct_switched = Arc::new(Ciphertext::from_shares(
+                    sk_shares
+                        .iter()
+                        .map(|sk_i| {
+                            PublicKeySwitchShare::new(sk_i, &ct, &pk_recipient, 30, &mut rng)
+                        })
+                        .collect::<crate::Result<Vec<_>>>()?,
+                )?);
+                assert_eq!(ct_switched.level, level);
+
+                // The recipient decrypts the switched ciphertext.
+                let pt2 = sk_recipient.try_decrypt(&ct_switched)?;
+                assert_eq!(
+                    Vec::<u64>::try_decode(&pt2, Encoding::simd_at_level(level))?,
+                    v
+                );
+
+                // Neither another key, nor the parties, can decrypt it.
+                let sk_other = SecretKey::random(&params, &mut rng);
+                let pt3 = sk_other.try_decrypt(&ct_switched)?;
+                assert_ne!(
+                    Vec::<u64>::try_decode(&pt3, Encoding::simd_at_level(level))?,
+                    v
+                );
+                let pt4 = Plaintext::from_shares(
+                    sk_shares
+                        .iter()
+                        .map(|sk_i| DecryptionShare::new(sk_i, &ct_switched, 30, &mut rng))
+                        .collect::<crate::Result<Vec<_>>>()?,
+                )?;
+                assert_ne!(
+                    Vec::<u64>::try_decode(&pt4, Encoding::simd_at_level(level))?,
+                    v
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn incompatible_shares() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BfvParameters::default_arc(2, 16);
+        let sk = SecretKey::random(&params, &mut rng);
+        let pt = Plaintext::try_encode(&[1u64, 2, 3], Encoding::poly(), &params)?;
+        let ct: Arc<Ciphertext> = Arc::new(sk.try_encrypt(&pt, &mut rng)?);
+        let pk1 = PublicKey::new(&SecretKey::random(&params, &mut rng), &mut rng);
+        let pk2 = PublicKey::new(&SecretKey::random(&params, &mut rng), &mut rng);
+
+        let share1 = PublicKeySwitchShare::new(&sk, &ct, &pk1, 30, &mut rng)?;
+        let share2 = PublicKeySwitchShare::new(&sk, &ct, &pk2, 30, &mut rng)?;
+        assert!(Ciphertext::from_shares([share1, share2]).is_err());
+        assert!(Ciphertext::from_shares(Vec::<PublicKeySwitchShare>::new()).is_err());
+        assert!(PublicKeySwitchShare::new(&sk, &ct, &pk1, 63, &mut rng).is_ok());
+        assert!(matches!(
+            PublicKeySwitchShare::new(&sk, &ct, &pk1, 120, &mut rng),
+            Err(crate::Error::SmudgingTooLarge(120))
+        ));
+        Ok(())
+    }
+}
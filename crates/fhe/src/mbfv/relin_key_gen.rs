@@ -0,0 +1,341 @@
+//! Collective generation of a relinearization key.
+
+use super::{Aggregate, CommonRandomPoly};
+use crate::bfv::{BfvParameters, KeySwitchingKey, RelinearizationKey, SecretKey};
+use crate::{Error, Result};
+use fhe_math::{
+    rns::RnsContext,
+    rq::{traits::TryConvertFrom, Poly, Representation},
+};
+use fhe_util::sample_vec_cbd;
+use itertools::izip;
+use rand::{CryptoRng, RngCore};
+use std::sync::Arc;
+use zeroize::Zeroizing;
+
+/// Generator of a party's shares of the joint relinearization key.
+///
+/// The relinearization key is generated in two rounds. Given common random
+/// polynomials `a_j`, one per ciphertext modulus, and an ephemeral secret
+/// `u_i`, the party holding the secret key share `s_i` first shares
+/// `(-u_i * a_j + s_i * g_j + e0_ij, s_i * a_j + e1_ij)`, where `g_j` is the
+/// gadget vector. Once the round 1 shares are aggregated into `(h0_j, h1_j)`,
+/// the party shares `(s_i * h0_j + e2_ij, (u_i - s_i) * h1_j + e3_ij)`. The sum
+/// of the round 2 shares and `h1_j` form a key switching key from `s^2` to the
+/// joint secret key `s`.
+#[derive(Debug)]
+pub struct RelinKeyGenerator<'a> {
+    sk_share: &'a SecretKey,
+    crp: &'a [CommonRandomPoly],
+    u: Zeroizing<Poly>,
+}
+
+/// A party's share of the first round of the relinearization key generation.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RelinKeyShareRound1 {
+    pub(crate) par: Arc<BfvParameters>,
+    pub(crate) h0: Box<[Poly]>,
+    pub(crate) h1: Box<[Poly]>,
+}
+
+/// A party's share of the second round of the relinearization key
+/// generation.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RelinKeyShareRound2 {
+    pub(crate) par: Arc<BfvParameters>,
+    pub(crate) h0: Box<[Poly]>,
+    pub(crate) h1: Box<[Poly]>,
+
+    /// The aggregated `h1` of the first round, which is the uniform part of
+    /// the relinearization key.
+    pub(crate) r1_h1: Box<[Poly]>,
+}
+
+impl<'a> RelinKeyGenerator<'a> {
+    /// Create a generator for a secret key share and one common random
+    /// polynomial per ciphertext modulus. Returns an error if the parameters
+    /// have a single ciphertext modulus, since they do not support key
+    /// switching.
+    pub fn new<R: RngCore + CryptoRng>(
+        sk_share: &'a SecretKey,
+        crp: &'a [CommonRandomPoly],
+        rng: &mut R,
+    ) -> Result<Self> {
+        let ctx = sk_share.par.ctx_at_level(0)?;
+        if ctx.moduli().len() == 1 {
+            return Err(Error::NotEnoughModuli);
+        }
+        if crp.len() < ctx.moduli().len() {
+            return Err(Error::TooFewValues(crp.len(), ctx.moduli().len()));
+        } else if crp.len() > ctx.moduli().len() {
+            return Err(Error::TooManyValues(crp.len(), ctx.moduli().len()));
+        }
+        if crp.iter().any(|crp_j| crp_j.poly.ctx() != ctx) {
+            return Err(Error::MathError(fhe_math::Error::InvalidContext));
+        }
+
+        let u_coefficients = Zeroizing::new(
+            sample_vec_cbd(sk_share.par.degree(), sk_share.par.variance, rng).map_err(|_| {
+                Error::MathError(fhe_math::Error::InvalidVariance(sk_share.par.variance))
+            })?,
+        );
+        let mut u = Zeroizing::new(Poly::try_convert_from(
+            u_coefficients.as_ref() as &[i64],
+            ctx,
+            false,
+            Representation::PowerBasis,
+        )?);
+        u.change_representation(Representation::Ntt);
+
+        Ok(Self { sk_share, crp, u })
+    }
+
+    /// Returns the secret key share as a polynomial in Ntt representation.
+    fn s(&self) -> Result<Zeroizing<Poly>> {
+        let mut s = Zeroizing::new(Poly::try_convert_from(
+            self.sk_share.coeffs.as_ref(),
+            self.u.ctx(),
+            false,
+            Representation::PowerBasis,
+        )?);
+        s.change_representation(Representation::Ntt);
+        Ok(s)
+    }
+
+    /// Sample a small error polynomial in Ntt representation.
+    fn e<R: RngCore + CryptoRng>(&self, rng: &mut R) -> Result<Poly> {
+        Ok(Poly::small(
+            self.u.ctx(),
+            Representation::Ntt,
+            self.sk_share.par.variance,
+            rng,
+        )?)
+    }
+
+    /// Generate the share of the first round.
+    pub fn round_1<R: RngCore + CryptoRng>(&self, rng: &mut R) -> Result<RelinKeyShareRound1> {
+        let s = self.s()?;
+        let rns = RnsContext::new(self.u.ctx().moduli())?;
+
+        let mut h0 = Vec::with_capacity(self.crp.len());
+        let mut h1 = Vec::with_capacity(self.crp.len());
+        for (j, crp_j) in self.crp.iter().enumerate() {
+            let mut a = crp_j.poly.clone();
+            a.disallow_variable_time_computations();
+
+            let mut h0_j = self.e(rng)?;
+            h0_j -= &(&a * self.u.as_ref());
+            h0_j += &(rns.get_garner(j).unwrap() * s.as_ref());
+
+            let mut h1_j = self.e(rng)?;
+            h1_j += &(&a * s.as_ref());
+
+            // It is now safe to enable variable time computations.
+            unsafe {
+                h0_j.allow_variable_time_computations();
+                h1_j.allow_variable_time_computations();
+            }
+            h0.push(h0_j);
+            h1.push(h1_j);
+        }
+
+        Ok(RelinKeyShareRound1 {
+            par: self.sk_share.par.clone(),
+            h0: h0.into_boxed_slice(),
+            h1: h1.into_boxed_slice(),
+        })
+    }
+
+    /// Generate the share of the second round from the aggregation of the
+    /// shares of the first round.
+    pub fn round_2<R: RngCore + CryptoRng>(
+        &self,
+        r1: &RelinKeyShareRound1,
+        rng: &mut R,
+    ) -> Result<RelinKeyShareRound2> {
+        if r1.par != self.sk_share.par || r1.h0.len() != self.crp.len() {
+            return Err(Error::ParametersMismatch);
+        }
+
+        let s = self.s()?;
+        let u_minus_s = Zeroizing::new(self.u.as_ref() - s.as_ref());
+
+        let mut h0 = Vec::with_capacity(self.crp.len());
+        let mut h1 = Vec::with_capacity(self.crp.len());
+        for (r1_h0_j, r1_h1_j) in izip!(r1.h0.iter(), r1.h1.iter()) {
+            let mut h0_j = r1_h0_j.clone();
+            h0_j.disallow_variable_time_computations();
+            h0_j *= s.as_ref();
+            h0_j += &self.e(rng)?;
+
+            let mut h1_j = r1_h1_j.clone();
+            h1_j.disallow_variable_time_computations();
+            h1_j *= u_minus_s.as_ref();
+            h1_j += &self.e(rng)?;
+
+            // It is now safe to enable variable time computations.
+            unsafe {
+                h0_j.allow_variable_time_computations();
+                h1_j.allow_variable_time_computations();
+            }
+            h0.push(h0_j);
+            h1.push(h1_j);
+        }
+
+        Ok(RelinKeyShareRound2 {
+            par: self.sk_share.par.clone(),
+            h0: h0.into_boxed_slice(),
+            h1: h1.into_boxed_slice(),
+            r1_h1: r1.h1.clone(),
+        })
+    }
+}
+
+impl Aggregate<RelinKeyShareRound1> for RelinKeyShareRound1 {
+    fn from_shares<T>(iter: T) -> Result<Self>
+    where
+        T: IntoIterator<Item = RelinKeyShareRound1>,
+    {
+        let mut shares = iter.into_iter();
+        let mut agg = shares.next().ok_or(Error::TooFewValues(0, 1))?;
+        for sh in shares {
+            if sh.par != agg.par || sh.h0.len() != agg.h0.len() {
+                return Err(Error::ParametersMismatch);
+            }
+            izip!(agg.h0.iter_mut(), sh.h0.iter()).for_each(|(a, b)| *a += b);
+            izip!(agg.h1.iter_mut(), sh.h1.iter()).for_each(|(a, b)| *a += b);
+        }
+        Ok(agg)
+    }
+}
+
+impl Aggregate<RelinKeyShareRound2> for RelinearizationKey {
+    fn from_shares<T>(iter: T) -> Result<Self>
+    where
+        T: IntoIterator<Item = RelinKeyShareRound2>,
+    {
+        let mut shares = iter.into_iter();
+        let share = shares.next().ok_or(Error::TooFewValues(0, 1))?;
+        let mut c0 = share.h0.to_vec();
+        izip!(c0.iter_mut(), share.h1.iter()).for_each(|(a, b)| *a += b);
+        for sh in shares {
+            if sh.par != share.par || sh.r1_h1 != share.r1_h1 {
+                return Err(Error::ParametersMismatch);
+            }
+            izip!(c0.iter_mut(), sh.h0.iter(), sh.h1.iter()).for_each(|(a, b, c)| {
+                *a += b;
+                *a += c;
+            });
+        }
+
+        let mut c1 = share.r1_h1.to_vec();
+        c0.iter_mut()
+            .chain(c1.iter_mut())
+            .for_each(|p| p.change_representation(Representation::NttShoup));
+
+        let ctx = share.par.ctx_at_level(0)?;
+        Ok(RelinearizationKey {
+            ksk: KeySwitchingKey {
+                par: share.par.clone(),
+                seed: None,
+                c0: c0.into_boxed_slice(),
+                c1: c1.into_boxed_slice(),
+                ciphertext_level: 0,
+                ctx_ciphertext: ctx.clone(),
+                ksk_level: 0,
+                ctx_ksk: ctx.clone(),
+                hybrid: None,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bfv::{
+        BfvParameters, Ciphertext, Encoding, Plaintext, PublicKey, RelinearizationKey, SecretKey,
+    };
+    use crate::mbfv::{
+        Aggregate, CommonRandomPoly, DecryptionShare, PublicKeyShare, RelinKeyGenerator,
+        RelinKeyShareRound1,
+    };
+    use fhe_traits::{FheDecoder, FheEncoder, FheEncrypter};
+    use rand::thread_rng;
+    use std::{error::Error, sync::Arc};
+
+    const NUM_PARTIES: usize = 5;
+
+    #[test]
+    fn relinearization() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BfvParameters::default_arc(3, 16);
+        for _ in 0..5 {
+            let sk_shares = (0..NUM_PARTIES)
+                .map(|_| SecretKey::random(&params, &mut rng))
+                .collect::<Vec<_>>();
+
+            let crp = CommonRandomPoly::new(&params, &mut rng)?;
+            let pk = PublicKey::from_shares(
+                sk_shares
+                    .iter()
+                    .map(|sk_i| PublicKeyShare::new(sk_i, crp.clone(), &mut rng))
+                    .collect::<crate::Result<Vec<_>>>()?,
+            )?;
+
+            let crps = CommonRandomPoly::new_vec(params.moduli().len(), &params, &mut rng)?;
+            let generators = sk_shares
+                .iter()
+                .map(|sk_i| RelinKeyGenerator::new(sk_i, &crps, &mut rng))
+                .collect::<crate::Result<Vec<_>>>()?;
+            let r1 = RelinKeyShareRound1::from_shares(
+                generators
+                    .iter()
+                    .map(|g| g.round_1(&mut rng))
+                    .collect::<crate::Result<Vec<_>>>()?,
+            )?;
+            let rk = RelinearizationKey::from_shares(
+                generators
+                    .iter()
+                    .map(|g| g.round_2(&r1, &mut rng))
+                    .collect::<crate::Result<Vec<_>>>()?,
+            )?;
+
+            let v1 = params.plaintext.random_vec(params.degree(), &mut rng);
+            let v2 = params.plaintext.random_vec(params.degree(), &mut rng);
+            let mut expected = v1.clone();
+            params.plaintext.mul_vec(&mut expected, &v2);
+
+            let pt1 = Plaintext::try_encode(&v1, Encoding::simd(), &params)?;
+            let pt2 = Plaintext::try_encode(&v2, Encoding::simd(), &params)?;
+            let ct1: Ciphertext = pk.try_encrypt(&pt1, &mut rng)?;
+            let ct2: Ciphertext = pk.try_encrypt(&pt2, &mut rng)?;
+            let mut ct3 = &ct1 * &ct2;
+            rk.relinearizes(&mut ct3)?;
+            let ct3 = Arc::new(ct3);
+
+            let pt = Plaintext::from_shares(
+                sk_shares
+                    .iter()
+                    .map(|sk_i| DecryptionShare::new(sk_i, &ct3, 30, &mut rng))
+                    .collect::<crate::Result<Vec<_>>>()?,
+            )?;
+            assert_eq!(Vec::<u64>::try_decode(&pt, Encoding::simd())?, expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn generator_errors() -> Result<(), Box<dyn Error>> {
+        let mut rng = thread_rng();
+        let params = BfvParameters::default_arc(1, 16);
+        let sk = SecretKey::random(&params, &mut rng);
+        let crps = CommonRandomPoly::new_vec(1, &params, &mut rng)?;
+        assert!(RelinKeyGenerator::new(&sk, &crps, &mut rng).is_err());
+
+        let params = BfvParameters::default_arc(3, 16);
+        let sk = SecretKey::random(&params, &mut rng);
+        let crps = CommonRandomPoly::new_vec(2, &params, &mut rng)?;
+        assert!(RelinKeyGenerator::new(&sk, &crps, &mut rng).is_err());
+        Ok(())
+    }
+}